@@ -127,6 +127,7 @@ CREATE TABLE public.device_command (
     value_from integer DEFAULT 0,
     value_to integer DEFAULT 0,
     delay integer DEFAULT 0,
+    frequency integer,
     description text DEFAULT ''::text NOT NULL,
     reload boolean DEFAULT false NOT NULL,
     enable boolean DEFAULT false NOT NULL,
@@ -193,6 +194,36 @@ ALTER TABLE public.log ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
 );
 
 
+--
+-- Name: notification; Type: TABLE; Schema: public; Owner: postgres
+--
+
+CREATE TABLE public.notification (
+    id integer NOT NULL,
+    user_id integer DEFAULT 0 NOT NULL,
+    device_id integer DEFAULT 0 NOT NULL,
+    kind character varying(16) DEFAULT 'alarm'::character varying NOT NULL,
+    description text NOT NULL,
+    enable boolean DEFAULT true NOT NULL
+);
+
+
+ALTER TABLE public.notification OWNER TO postgres;
+
+--
+-- Name: notification_id_seq; Type: SEQUENCE; Schema: public; Owner: postgres
+--
+
+ALTER TABLE public.notification ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
+    SEQUENCE NAME public.notification_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
 --
 -- Name: port; Type: TABLE; Schema: public; Owner: postgres
 --
@@ -227,6 +258,38 @@ ALTER TABLE public.port ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
 );
 
 
+--
+-- Name: telegram_chat; Type: TABLE; Schema: public; Owner: postgres
+--
+
+CREATE TABLE public.telegram_chat (
+    id integer NOT NULL,
+    user_id integer DEFAULT 0 NOT NULL,
+    chat_id text NOT NULL,
+    username text NOT NULL,
+    date text NOT NULL,
+    sender_id text DEFAULT ''::text NOT NULL,
+    link_code text DEFAULT ''::text NOT NULL,
+    link_expires text DEFAULT ''::text NOT NULL
+);
+
+
+ALTER TABLE public.telegram_chat OWNER TO postgres;
+
+--
+-- Name: telegram_chat_id_seq; Type: SEQUENCE; Schema: public; Owner: postgres
+--
+
+ALTER TABLE public.telegram_chat ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
+    SEQUENCE NAME public.telegram_chat_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
 --
 -- Name: timer; Type: TABLE; Schema: public; Owner: postgres
 --
@@ -381,6 +444,127 @@ ALTER TABLE public."user" ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
 );
 
 
+--
+-- Name: user_role; Type: TABLE; Schema: public; Owner: postgres
+--
+
+CREATE TABLE public.user_role (
+    id integer NOT NULL,
+    user_id integer DEFAULT 0 NOT NULL,
+    role text DEFAULT 'Viewer'::text NOT NULL,
+    all_zones boolean DEFAULT false NOT NULL
+);
+
+
+ALTER TABLE public.user_role OWNER TO postgres;
+
+--
+-- Name: user_role_id_seq; Type: SEQUENCE; Schema: public; Owner: postgres
+--
+
+ALTER TABLE public.user_role ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
+    SEQUENCE NAME public.user_role_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
+--
+-- Name: user_zone; Type: TABLE; Schema: public; Owner: postgres
+--
+
+CREATE TABLE public.user_zone (
+    id integer NOT NULL,
+    user_id integer DEFAULT 0 NOT NULL,
+    zone_id integer DEFAULT 0 NOT NULL
+);
+
+
+ALTER TABLE public.user_zone OWNER TO postgres;
+
+--
+-- Name: user_zone_id_seq; Type: SEQUENCE; Schema: public; Owner: postgres
+--
+
+ALTER TABLE public.user_zone ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
+    SEQUENCE NAME public.user_zone_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
+--
+-- Name: webhook; Type: TABLE; Schema: public; Owner: postgres
+--
+
+CREATE TABLE public.webhook (
+    id integer NOT NULL,
+    user_id integer DEFAULT 0 NOT NULL,
+    name text NOT NULL,
+    url text NOT NULL,
+    secret text NOT NULL,
+    events text NOT NULL,
+    description text NOT NULL,
+    enable boolean DEFAULT true NOT NULL
+);
+
+
+ALTER TABLE public.webhook OWNER TO postgres;
+
+--
+-- Name: webhook_id_seq; Type: SEQUENCE; Schema: public; Owner: postgres
+--
+
+ALTER TABLE public.webhook ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
+    SEQUENCE NAME public.webhook_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
+--
+-- Name: webhook_delivery; Type: TABLE; Schema: public; Owner: postgres
+--
+
+CREATE TABLE public.webhook_delivery (
+    id integer NOT NULL,
+    webhook_id integer DEFAULT 0 NOT NULL,
+    event text NOT NULL,
+    payload text NOT NULL,
+    status character varying(16) DEFAULT 'pending'::character varying NOT NULL,
+    attempts integer DEFAULT 0 NOT NULL,
+    response_code integer DEFAULT 0 NOT NULL,
+    error text NOT NULL,
+    date text NOT NULL,
+    updated text NOT NULL
+);
+
+
+ALTER TABLE public.webhook_delivery OWNER TO postgres;
+
+--
+-- Name: webhook_delivery_id_seq; Type: SEQUENCE; Schema: public; Owner: postgres
+--
+
+ALTER TABLE public.webhook_delivery ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (
+    SEQUENCE NAME public.webhook_delivery_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
 --
 -- Name: zone; Type: TABLE; Schema: public; Owner: postgres
 --
@@ -466,6 +650,7 @@ CREATE TABLE public.zone_command_if (
     zone_command_id integer DEFAULT 0 NOT NULL,
     device_id integer DEFAULT 0 NOT NULL,
     command_id integer DEFAULT 0 NOT NULL,
+    type text DEFAULT 'None'::text NOT NULL,
     description text DEFAULT ''::text NOT NULL,
     enable boolean DEFAULT false NOT NULL
 );
@@ -557,6 +742,14 @@ ALTER TABLE ONLY public.log
     ADD CONSTRAINT log_pkey PRIMARY KEY (id);
 
 
+--
+-- Name: notification notification_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.notification
+    ADD CONSTRAINT notification_pkey PRIMARY KEY (id);
+
+
 --
 -- Name: port port_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
 --
@@ -573,6 +766,22 @@ ALTER TABLE ONLY public.port
     ADD CONSTRAINT port_unique UNIQUE (user_id, name);
 
 
+--
+-- Name: telegram_chat telegram_chat_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.telegram_chat
+    ADD CONSTRAINT telegram_chat_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: telegram_chat telegram_chat_unique; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.telegram_chat
+    ADD CONSTRAINT telegram_chat_unique UNIQUE (user_id);
+
+
 --
 -- Name: timer_device timer_device_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
 --
@@ -653,6 +862,54 @@ ALTER TABLE ONLY public."user"
     ADD CONSTRAINT user_unique UNIQUE (name);
 
 
+--
+-- Name: user_role user_role_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.user_role
+    ADD CONSTRAINT user_role_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: user_role user_role_unique; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.user_role
+    ADD CONSTRAINT user_role_unique UNIQUE (user_id);
+
+
+--
+-- Name: user_zone user_zone_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.user_zone
+    ADD CONSTRAINT user_zone_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: user_zone user_zone_unique; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.user_zone
+    ADD CONSTRAINT user_zone_unique UNIQUE (user_id, zone_id);
+
+
+--
+-- Name: webhook webhook_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.webhook
+    ADD CONSTRAINT webhook_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: webhook_delivery webhook_delivery_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.webhook_delivery
+    ADD CONSTRAINT webhook_delivery_pkey PRIMARY KEY (id);
+
+
 --
 -- Name: zone_command_action zone_command_action_pkey; Type: CONSTRAINT; Schema: public; Owner: postgres
 --
@@ -717,6 +974,157 @@ ALTER TABLE ONLY public.zone
     ADD CONSTRAINT zone_unique UNIQUE (user_id, name);
 
 
+--
+-- Name: webhook_delivery_webhook_id; Type: INDEX; Schema: public; Owner: postgres
+--
+
+CREATE INDEX webhook_delivery_webhook_id ON public.webhook_delivery USING btree (webhook_id);
+
+
+--
+-- Name: device device_zone_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.device
+    ADD CONSTRAINT device_zone_id_fkey FOREIGN KEY (zone_id) REFERENCES public.zone(id);
+
+
+--
+-- Name: device device_port_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.device
+    ADD CONSTRAINT device_port_id_fkey FOREIGN KEY (port_id) REFERENCES public.port(id);
+
+
+--
+-- Name: device_command device_command_device_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.device_command
+    ADD CONSTRAINT device_command_device_id_fkey FOREIGN KEY (device_id) REFERENCES public.device(id);
+
+
+--
+-- Name: timer_device timer_device_timer_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_device
+    ADD CONSTRAINT timer_device_timer_id_fkey FOREIGN KEY (timer_id) REFERENCES public.timer(id);
+
+
+--
+-- Name: timer_device timer_device_device_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_device
+    ADD CONSTRAINT timer_device_device_id_fkey FOREIGN KEY (device_id) REFERENCES public.device(id);
+
+
+--
+-- Name: timer_device timer_device_command_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_device
+    ADD CONSTRAINT timer_device_command_id_fkey FOREIGN KEY (command_id) REFERENCES public.device_command(id);
+
+
+--
+-- Name: timer_item timer_item_timer_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_item
+    ADD CONSTRAINT timer_item_timer_id_fkey FOREIGN KEY (timer_id) REFERENCES public.timer(id);
+
+
+--
+-- Name: timer_limit timer_limit_device_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_limit
+    ADD CONSTRAINT timer_limit_device_id_fkey FOREIGN KEY (device_id) REFERENCES public.device(id);
+
+
+--
+-- Name: timer_limit timer_limit_command_from_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_limit
+    ADD CONSTRAINT timer_limit_command_from_id_fkey FOREIGN KEY (command_from_id) REFERENCES public.device_command(id);
+
+
+--
+-- Name: timer_limit timer_limit_command_to_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.timer_limit
+    ADD CONSTRAINT timer_limit_command_to_id_fkey FOREIGN KEY (command_to_id) REFERENCES public.device_command(id);
+
+
+--
+-- Name: webhook_delivery webhook_delivery_webhook_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.webhook_delivery
+    ADD CONSTRAINT webhook_delivery_webhook_id_fkey FOREIGN KEY (webhook_id) REFERENCES public.webhook(id);
+
+
+--
+-- Name: zone_command zone_command_zone_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command
+    ADD CONSTRAINT zone_command_zone_id_fkey FOREIGN KEY (zone_id) REFERENCES public.zone(id);
+
+
+--
+-- Name: zone_command_action zone_command_action_zone_command_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command_action
+    ADD CONSTRAINT zone_command_action_zone_command_id_fkey FOREIGN KEY (zone_command_id) REFERENCES public.zone_command(id);
+
+
+--
+-- Name: zone_command_action zone_command_action_device_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command_action
+    ADD CONSTRAINT zone_command_action_device_id_fkey FOREIGN KEY (device_id) REFERENCES public.device(id);
+
+
+--
+-- Name: zone_command_action zone_command_action_command_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command_action
+    ADD CONSTRAINT zone_command_action_command_id_fkey FOREIGN KEY (command_id) REFERENCES public.device_command(id);
+
+
+--
+-- Name: zone_command_if zone_command_if_zone_command_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command_if
+    ADD CONSTRAINT zone_command_if_zone_command_id_fkey FOREIGN KEY (zone_command_id) REFERENCES public.zone_command(id);
+
+
+--
+-- Name: zone_command_if zone_command_if_device_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command_if
+    ADD CONSTRAINT zone_command_if_device_id_fkey FOREIGN KEY (device_id) REFERENCES public.device(id);
+
+
+--
+-- Name: zone_command_if zone_command_if_command_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: postgres
+--
+
+ALTER TABLE ONLY public.zone_command_if
+    ADD CONSTRAINT zone_command_if_command_id_fkey FOREIGN KEY (command_id) REFERENCES public.device_command(id);
+
+
 --
 -- PostgreSQL database dump complete
 --
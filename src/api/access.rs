@@ -0,0 +1,426 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/access.rs
+
+//--------------------------------------------------------------------------------- Description
+// Role and zone checks used by the services: Admin does everything, Operator executes, switches and
+// manages what is in its granted zones (every zone only with all_zones), Viewer only reads. A new
+// zone, port or timer is in no zone yet, so creating one takes every zone.
+// Tenant scope: port, zone, timer, webhook and notification belong to a user_id, their children follow the parent;
+// other users' rows are reported as not found, Admin sees every tenant
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, Iterable, PaginatorTrait, PrimaryKeyToColumn, QueryFilter, QuerySelect, QueryTrait};
+use sea_orm::sea_query::{Expr, SelectStatement};
+use crate::api::error::ApiError;
+use crate::logics::general::Roles;
+use crate::orm::logics::user_role::UserRoleORM;
+use crate::orm::logics::user_zone::UserZoneORM;
+use crate::orm::models::device::{Column as DeviceColumn, Entity as DeviceEntity};
+use crate::orm::models::device_command::{Column as DeviceCommandColumn, Entity as DeviceCommandEntity};
+use crate::orm::models::port::{Column as PortColumn, Entity as PortEntity};
+use crate::orm::models::timer::{Column as TimerColumn, Entity as TimerEntity};
+use crate::orm::models::timer_device::{Column as TimerDeviceColumn, Entity as TimerDeviceEntity};
+use crate::orm::models::timer_item::{Column as TimerItemColumn, Entity as TimerItemEntity};
+use crate::orm::models::timer_limit::{Column as TimerLimitColumn, Entity as TimerLimitEntity};
+use crate::orm::models::zone::{Column as ZoneColumn, Entity as ZoneEntity};
+use crate::orm::models::zone_command::{Column as ZoneCommandColumn, Entity as ZoneCommandEntity};
+use crate::orm::models::zone_command_action::{Column as ZoneCommandActionColumn, Entity as ZoneCommandActionEntity};
+use crate::orm::models::zone_command_if::{Column as ZoneCommandIfColumn, Entity as ZoneCommandIfEntity};
+use crate::orm::models::webhook::{Column as WebhookColumn, Entity as WebhookEntity};
+use crate::orm::models::webhook_delivery::{Column as WebhookDeliveryColumn, Entity as WebhookDeliveryEntity};
+use crate::orm::models::notification::{Column as NotificationColumn, Entity as NotificationEntity};
+use crate::orm::models::user::Model as UserModel;
+
+//--------------------------------------------------------------------------------- Access
+/// Role and zone grants of the authenticated user, put in the request extensions by the auth middleware
+#[derive(Debug, Clone)]
+pub struct Access
+{
+    pub user_id: i32,
+    pub role: Roles,
+    pub zones: Vec<i32>,
+    /// Every zone is granted, zones is then ignored
+    pub all_zones: bool,
+}
+
+impl Access
+{
+    //------------------------- Unrestricted
+    /// Used when authentication is turned off
+    pub fn unrestricted() -> Self
+    {
+        Self { user_id: 0, role: Roles::Admin, zones: Vec::new(), all_zones: true }
+    }
+
+    //------------------------- Load
+    /// A user without a user_role row is a Viewer without zones
+    pub async fn load(db: &DatabaseConnection, user: &UserModel) -> Result<Self, ApiError>
+    {
+        let (role, all_zones) = match UserRoleORM::new(false, false).item_by_user(db, user.id).await.into_result()
+        {
+            Ok(role) => (Roles::from_name(&role.role).unwrap_or(Roles::Viewer), role.all_zones),
+            Err(ApiError::NotFound(_)) => (Roles::Viewer, false),
+            Err(e) => return Err(e),
+        };
+        let zones = UserZoneORM::new(false, false).items_by_user(db, user.id).await.into_result()?
+            .into_iter().map(|grant| grant.zone_id).collect();
+
+        Ok(Self { user_id: user.id, role, zones, all_zones })
+    }
+
+    //------------------------- Tenant Scope
+    /// Condition limiting a query to the user's rows, empty for admin
+    pub fn scope<E: Owned>(&self) -> Condition
+    {
+        match self.is_admin()
+        {
+            true => Condition::all(),
+            false => E::owned_by(self.user_id),
+        }
+    }
+
+    /// scope() by table name, for the delete cascade that walks relations rather than entities;
+    /// a table without an owner is out of scope for everyone but admin
+    pub fn scope_of(&self, table: &str) -> Condition
+    {
+        match table
+        {
+            "port" => self.scope::<PortEntity>(),
+            "zone" => self.scope::<ZoneEntity>(),
+            "timer" => self.scope::<TimerEntity>(),
+            "device" => self.scope::<DeviceEntity>(),
+            "device_command" => self.scope::<DeviceCommandEntity>(),
+            "zone_command" => self.scope::<ZoneCommandEntity>(),
+            "zone_command_action" => self.scope::<ZoneCommandActionEntity>(),
+            "zone_command_if" => self.scope::<ZoneCommandIfEntity>(),
+            "timer_device" => self.scope::<TimerDeviceEntity>(),
+            "timer_item" => self.scope::<TimerItemEntity>(),
+            "timer_limit" => self.scope::<TimerLimitEntity>(),
+            "webhook" => self.scope::<WebhookEntity>(),
+            "webhook_delivery" => self.scope::<WebhookDeliveryEntity>(),
+            "notification" => self.scope::<NotificationEntity>(),
+            _ if self.is_admin() => Condition::all(),
+            _ => Condition::all().add(Expr::value(false)),
+        }
+    }
+
+    /// The row must exist within the user's scope
+    pub async fn require_owned<E: Owned>(&self, db: &DatabaseConnection, id: i32) -> Result<(), ApiError>
+    where
+        E::Model: Sync,
+    {
+        let found = E::find().filter(id_column::<E>().eq(id)).filter(self.scope::<E>()).count(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+        match found > 0
+        {
+            true => Ok(()),
+            false => Err(ApiError::NotFound(format!("{} {} not found", E::default().table_name(), id))),
+        }
+    }
+
+    /// Owner to store on a new or updated port, zone or timer; only admin may pick another user
+    pub fn owner(&self, user_id: i32) -> i32
+    {
+        match self.is_admin()
+        {
+            true => user_id,
+            false => self.user_id,
+        }
+    }
+
+    //------------------------- Checks
+    pub fn is_admin(&self) -> bool
+    {
+        self.role == Roles::Admin
+    }
+
+    /// Admin, or a user granted every zone
+    pub fn has_all_zones(&self) -> bool
+    {
+        self.is_admin() || self.all_zones
+    }
+
+    /// At least this role
+    pub fn require(&self, role: Roles) -> Result<(), ApiError>
+    {
+        match self.role >= role
+        {
+            true => Ok(()),
+            false => Err(ApiError::Forbidden(format!("{} role required", role.name()))),
+        }
+    }
+
+    /// The user itself or an admin
+    pub fn require_self(&self, user_id: i32) -> Result<(), ApiError>
+    {
+        match self.user_id == user_id
+        {
+            true => Ok(()),
+            false => self.require(Roles::Admin),
+        }
+    }
+
+    /// At least this role, and the zone must be granted unless the user is admin or has all zones
+    pub fn require_zone(&self, role: Roles, zone_id: i32) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        match self.has_all_zones() || self.zones.contains(&zone_id)
+        {
+            true => Ok(()),
+            false => Err(ApiError::Forbidden(format!("No grant for zone {}", zone_id))),
+        }
+    }
+
+    /// At least this role with every zone granted, for rows that are in no zone yet
+    pub fn require_all_zones(&self, role: Roles) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        match self.has_all_zones()
+        {
+            true => Ok(()),
+            false => Err(ApiError::Forbidden("Every zone must be granted".to_string())),
+        }
+    }
+
+    /// Zone check through the device's zone
+    pub async fn require_device(&self, db: &DatabaseConnection, role: Roles, device_id: i32) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        if self.has_all_zones() { return Ok(()); }
+
+        let device = DeviceEntity::find_by_id(device_id).one(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?
+            .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+        self.require_zone(role, device.zone_id)
+    }
+
+    /// Zone check through the command's device
+    pub async fn require_device_command(&self, db: &DatabaseConnection, role: Roles, command_id: i32) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        if self.has_all_zones() { return Ok(()); }
+
+        let command = DeviceCommandEntity::find_by_id(command_id).one(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?
+            .ok_or_else(|| ApiError::NotFound("Device command not found".to_string()))?;
+        self.require_device(db, role, command.device_id).await
+    }
+
+    /// Zone check through the zone command's zone
+    pub async fn require_zone_command(&self, db: &DatabaseConnection, role: Roles, zone_command_id: i32) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        if self.has_all_zones() { return Ok(()); }
+
+        let zone_command = ZoneCommandEntity::find_by_id(zone_command_id).one(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?
+            .ok_or_else(|| ApiError::NotFound("Zone command not found".to_string()))?;
+        self.require_zone(role, zone_command.zone_id)
+    }
+
+    /// Zone check on every device wired to the port
+    pub async fn require_port(&self, db: &DatabaseConnection, role: Roles, port_id: i32) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        if self.has_all_zones() { return Ok(()); }
+
+        let zone_ids: Vec<i32> = DeviceEntity::find().select_only().column(DeviceColumn::ZoneId)
+            .filter(DeviceColumn::PortId.eq(port_id))
+            .into_tuple().all(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+
+        if zone_ids.is_empty()
+        {
+            return Err(ApiError::Forbidden(format!("Port {} drives no device in a granted zone", port_id)));
+        }
+        zone_ids.into_iter().try_for_each(|zone_id| self.require_zone(role, zone_id))
+    }
+
+    /// Zone check on every device the timer drives
+    pub async fn require_timer(&self, db: &DatabaseConnection, role: Roles, timer_id: i32) -> Result<(), ApiError>
+    {
+        self.require(role)?;
+        if self.has_all_zones() { return Ok(()); }
+
+        let device_ids: Vec<i32> = TimerDeviceEntity::find().select_only().column(TimerDeviceColumn::DeviceId)
+            .filter(TimerDeviceColumn::TimerId.eq(timer_id))
+            .into_tuple().all(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+        let zone_ids: Vec<i32> = DeviceEntity::find().select_only().column(DeviceColumn::ZoneId)
+            .filter(DeviceColumn::Id.is_in(device_ids))
+            .into_tuple().all(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+
+        if zone_ids.is_empty()
+        {
+            return Err(ApiError::Forbidden(format!("Timer {} drives no device in a granted zone", timer_id)));
+        }
+        zone_ids.into_iter().try_for_each(|zone_id| self.require_zone(role, zone_id))
+    }
+}
+
+//--------------------------------------------------------------------------------- Owned
+/// Entities that belong to a tenant, directly through user_id or through their parent
+pub trait Owned: EntityTrait
+{
+    fn owned_by(user_id: i32) -> Condition;
+}
+
+/// Ids of the user's rows, as a subquery for the children
+fn owned_ids<E: Owned>(user_id: i32) -> SelectStatement
+{
+    E::find().select_only().column(id_column::<E>()).filter(E::owned_by(user_id)).into_query()
+}
+
+fn id_column<E: EntityTrait>() -> E::Column
+{
+    E::PrimaryKey::iter().next().expect("entity without primary key").into_column()
+}
+
+impl Owned for PortEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(PortColumn::UserId.eq(user_id)) }
+}
+
+impl Owned for ZoneEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(ZoneColumn::UserId.eq(user_id)) }
+}
+
+impl Owned for TimerEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(TimerColumn::UserId.eq(user_id)) }
+}
+
+impl Owned for DeviceEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(DeviceColumn::ZoneId.in_subquery(owned_ids::<ZoneEntity>(user_id))) }
+}
+
+impl Owned for DeviceCommandEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(DeviceCommandColumn::DeviceId.in_subquery(owned_ids::<DeviceEntity>(user_id))) }
+}
+
+impl Owned for ZoneCommandEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(ZoneCommandColumn::ZoneId.in_subquery(owned_ids::<ZoneEntity>(user_id))) }
+}
+
+impl Owned for ZoneCommandActionEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(ZoneCommandActionColumn::ZoneCommandId.in_subquery(owned_ids::<ZoneCommandEntity>(user_id))) }
+}
+
+impl Owned for ZoneCommandIfEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(ZoneCommandIfColumn::ZoneCommandId.in_subquery(owned_ids::<ZoneCommandEntity>(user_id))) }
+}
+
+impl Owned for TimerDeviceEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(TimerDeviceColumn::TimerId.in_subquery(owned_ids::<TimerEntity>(user_id))) }
+}
+
+impl Owned for TimerItemEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(TimerItemColumn::TimerId.in_subquery(owned_ids::<TimerEntity>(user_id))) }
+}
+
+impl Owned for TimerLimitEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(TimerLimitColumn::DeviceId.in_subquery(owned_ids::<DeviceEntity>(user_id))) }
+}
+
+impl Owned for WebhookEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(WebhookColumn::UserId.eq(user_id)) }
+}
+
+impl Owned for WebhookDeliveryEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(WebhookDeliveryColumn::WebhookId.in_subquery(owned_ids::<WebhookEntity>(user_id))) }
+}
+
+impl Owned for NotificationEntity
+{
+    fn owned_by(user_id: i32) -> Condition { Condition::all().add(NotificationColumn::UserId.eq(user_id)) }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use sea_orm::Database;
+    use super::*;
+
+    fn access(user_id: i32, role: Roles, zones: &[i32]) -> Access
+    {
+        Access { user_id, role, zones: zones.to_vec(), all_zones: false }
+    }
+
+    async fn samples() -> DatabaseConnection
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        db
+    }
+
+    #[test]
+    fn role_self_and_zone_checks()
+    {
+        let operator = access(2, Roles::Operator, &[1]);
+        assert!(operator.require(Roles::Viewer).is_ok() && operator.require(Roles::Operator).is_ok());
+        assert!(matches!(operator.require(Roles::Admin), Err(ApiError::Forbidden(_))));
+
+        assert!(operator.require_self(2).is_ok());
+        assert!(matches!(operator.require_self(3), Err(ApiError::Forbidden(_))));
+        assert!(Access::unrestricted().require_self(3).is_ok());
+
+        assert!(operator.require_zone(Roles::Operator, 1).is_ok());
+        assert!(matches!(operator.require_zone(Roles::Operator, 2), Err(ApiError::Forbidden(_))));
+        assert!(Access { all_zones: true, ..operator.clone() }.require_zone(Roles::Operator, 2).is_ok());
+        assert!(matches!(access(2, Roles::Viewer, &[1]).require_zone(Roles::Operator, 1), Err(ApiError::Forbidden(_))), "a grant does not raise the role");
+    }
+
+    #[tokio::test]
+    async fn zone_grants_follow_devices_ports_and_timers()
+    {
+        let db = samples().await;
+
+        // Device 1 is in zone 1 on port 20 alone; port 19 and timer 1 also drive devices of other zones
+        let operator = access(1, Roles::Operator, &[1]);
+        let device = DeviceEntity::find().filter(DeviceColumn::ZoneId.eq(2)).one(&db).await.unwrap().unwrap();
+        assert!(operator.require_device(&db, Roles::Operator, 1).await.is_ok());
+        assert!(matches!(operator.require_device(&db, Roles::Operator, device.id).await, Err(ApiError::Forbidden(_))));
+        assert!(operator.require_port(&db, Roles::Operator, 20).await.is_ok());
+        assert!(matches!(operator.require_port(&db, Roles::Operator, 19).await, Err(ApiError::Forbidden(_))));
+        assert!(matches!(operator.require_timer(&db, Roles::Operator, 1).await, Err(ApiError::Forbidden(_))));
+        let every_zone: Vec<i32> = ZoneEntity::find().select_only().column(ZoneColumn::Id).into_tuple().all(&db).await.unwrap();
+        assert!(access(1, Roles::Operator, &every_zone).require_timer(&db, Roles::Operator, 1).await.is_ok());
+        assert!(matches!(access(1, Roles::Viewer, &[1]).require_device(&db, Roles::Operator, 1).await, Err(ApiError::Forbidden(_))));
+    }
+    #[tokio::test]
+    async fn tenant_scope_hides_other_owners_rows()
+    {
+        let db = samples().await;
+
+        // The samples belong to user 1
+        let owner = access(1, Roles::Viewer, &[]);
+        let stranger = access(2, Roles::Viewer, &[]);
+        assert!(owner.require_owned::<ZoneEntity>(&db, 1).await.is_ok());
+        assert!(owner.require_owned::<DeviceEntity>(&db, 1).await.is_ok());
+        assert!(matches!(stranger.require_owned::<ZoneEntity>(&db, 1).await, Err(ApiError::NotFound(_))));
+        assert!(matches!(stranger.require_owned::<DeviceEntity>(&db, 1).await, Err(ApiError::NotFound(_))), "a child follows its parent's owner");
+        assert!(Access::unrestricted().require_owned::<DeviceEntity>(&db, 1).await.is_ok());
+        assert_eq!((stranger.owner(1), Access::unrestricted().owner(2)), (2, 2), "only admin picks the owner");
+
+        let zones = |access: Access, table: &'static str| { let db = db.clone(); async move { ZoneEntity::find().filter(access.scope_of(table)).count(&db).await.unwrap() } };
+        let total = ZoneEntity::find().count(&db).await.unwrap();
+        assert_eq!(zones(owner.clone(), "zone").await, total);
+        assert_eq!(zones(stranger, "zone").await, 0);
+        assert_eq!(zones(owner, "user").await, 0, "a table without an owner is out of scope");
+        assert_eq!(zones(Access::unrestricted(), "user").await, total);
+    }
+}
@@ -0,0 +1,219 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/auth.rs
+
+//--------------------------------------------------------------------------------- Description
+// Password hashing (argon2) and signed access tokens (HS256) for the API
+
+//--------------------------------------------------------------------------------- Import
+use std::sync::{Arc, LazyLock};
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::{rand_core::{OsRng, RngCore}, SaltString};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::ToSchema;
+use crate::api::error::ApiError;
+use crate::orm::models::user::Model as UserModel;
+
+//--------------------------------------------------------------------------------- Password
+/// Hash a password with argon2 and a random salt; whatever the client sends is hashed, even a
+/// value that already looks like a PHC string (legacy plain text rows are hashed by a migration)
+pub fn hash_password(password: &str) -> Result<String, ApiError>
+{
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| ApiError::Internal(format!("Failed to hash password: {}", e)))
+}
+
+/// Check a plain text password against the stored hash
+pub fn verify_password(password: &str, stored: &str) -> bool
+{
+    PasswordHash::new(stored).is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+/// Hash of a random password, checked against when a login names an unknown user
+pub fn dummy_hash() -> &'static str
+{
+    static HASH: LazyLock<String> = LazyLock::new(|| hash_password(&generate_key()).unwrap_or_default());
+    &HASH
+}
+
+//--------------------------------------------------------------------------------- Key
+/// Random API key for X-API-Key, 32 bytes in URL-safe base64
+pub fn generate_key() -> String
+{
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    URL_SAFE_NO_PAD.encode(key)
+}
+
+/// A user with its API key; the key is only returned when it is created or rotated
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "User with its API key, returned only when the key is created or rotated")]
+pub struct UserKey
+{
+    #[serde(flatten)]
+    pub user: UserModel,
+    #[schema(example = "Jx3m0w6...")]
+    pub key: String,
+}
+
+impl From<UserModel> for UserKey
+{
+    fn from(user: UserModel) -> Self
+    {
+        let key = user.key.clone();
+        Self { user, key }
+    }
+}
+
+//--------------------------------------------------------------------------------- Token
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims
+{
+    pub sub: i32,
+    pub name: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Signed access token returned by login")]
+pub struct Token
+{
+    #[schema(example = "eyJ0eXAiOiJKV1QiLCJhbGciOiJIUzI1NiJ9...")]
+    pub token: String,
+    #[schema(example = "Bearer")]
+    pub token_type: String,
+    #[schema(example = 1)]
+    pub user_id: i32,
+    #[schema(example = "2025-09-01T12:00:00+00:00")]
+    pub expires: String,
+}
+
+//--------------------------------------------------------------------------------- Auth
+/// Sample values from .env files and docs; anyone could sign tokens with them
+const PLACEHOLDER_SECRETS: [&str; 6] = ["change_me", "changeme", "change-me", "secret", "your_secret", "your-secret-key"];
+
+#[derive(Clone)]
+pub struct Auth
+{
+    pub enabled: bool,
+    pub ttl: i64,
+    secret: Arc<Vec<u8>>,
+}
+
+impl Auth
+{
+    //------------------------- New
+    /// AUTH_ENABLED (default true), AUTH_SECRET and AUTH_TOKEN_TTL (seconds, default 86400);
+    /// fails on a placeholder secret so a sample .env cannot go live
+    pub fn new() -> Result<Self, String>
+    {
+        let enabled = std::env::var("AUTH_ENABLED").map(|value| value != "false").unwrap_or(true);
+        let ttl = std::env::var("AUTH_TOKEN_TTL").ok().and_then(|value| value.parse::<i64>().ok()).filter(|ttl| *ttl > 0).unwrap_or(86400);
+        let secret = match std::env::var("AUTH_SECRET")
+        {
+            Ok(secret) if PLACEHOLDER_SECRETS.contains(&secret.trim().to_lowercase().as_str()) =>
+            {
+                return Err(format!("AUTH_SECRET is the placeholder '{}', set a random value or leave it empty", secret));
+            }
+            Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+            _ =>
+            {
+                warn!("AUTH_SECRET is not set, tokens are signed with a random secret and expire on restart");
+                let mut secret = vec![0u8; 32];
+                OsRng.fill_bytes(&mut secret);
+                secret
+            }
+        };
+        if !enabled
+        {
+            warn!("AUTH_ENABLED=false, every route is open");
+        }
+
+        Ok(Self { enabled, ttl, secret: Arc::new(secret) })
+    }
+
+    //------------------------- Issue
+    /// Sign a token for the user
+    pub fn issue(&self, user: &UserModel) -> Result<Token, ApiError>
+    {
+        let now = Utc::now();
+        let expires = now + chrono::Duration::seconds(self.ttl);
+        let claims = Claims { sub: user.id, name: user.username.clone(), iat: now.timestamp(), exp: expires.timestamp() };
+        let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(&self.secret))
+            .map_err(|e| ApiError::Internal(format!("Failed to sign token: {}", e)))?;
+
+        Ok(Token { token, token_type: "Bearer".to_string(), user_id: user.id, expires: expires.to_rfc3339() })
+    }
+
+    //------------------------- Verify
+    /// Check signature and expiry, returns the claims
+    pub fn verify(&self, token: &str) -> Result<Claims, ApiError>
+    {
+        decode::<Claims>(token, &DecodingKey::from_secret(&self.secret), &Validation::default())
+            .map(|data| data.claims)
+            .map_err(|e| ApiError::Unauthorized(format!("Invalid token: {}", e)))
+    }
+}
+
+//--------------------------------------------------------------------------------- Current User
+/// The authenticated user, put in the request extensions by the auth middleware
+#[derive(Debug, Clone)]
+pub struct CurrentUser(pub UserModel);
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn hashes_plain_text()
+    {
+        let hash = hash_password("123456").unwrap();
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_password("123456", &hash));
+        assert!(!verify_password("654321", &hash));
+    }
+
+    #[test]
+    fn a_client_supplied_hash_is_hashed_again()
+    {
+        let hash = hash_password("123456").unwrap();
+        let stored = hash_password(&hash).unwrap();
+        assert_ne!(stored, hash);
+        assert!(!verify_password("123456", &stored), "the precomputed hash does not become the password");
+        assert!(verify_password(&hash, &stored));
+    }
+
+    #[test]
+    fn plain_text_is_never_a_match()
+    {
+        assert!(!verify_password("123456", "123456"));
+        assert!(!verify_password("", ""));
+    }
+
+    #[test]
+    fn the_dummy_hash_is_a_real_hash_that_matches_nothing()
+    {
+        assert!(dummy_hash().starts_with("$argon2"));
+        assert!(PasswordHash::new(dummy_hash()).is_ok());
+        assert!(!verify_password("", dummy_hash()));
+        assert!(!verify_password("123456", dummy_hash()));
+    }
+
+    #[test]
+    fn keys_are_random()
+    {
+        let (first, second) = (generate_key(), generate_key());
+        assert_eq!(first.len(), 43);
+        assert_ne!(first, second);
+    }
+}
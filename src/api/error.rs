@@ -0,0 +1,56 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/error.rs
+
+//--------------------------------------------------------------------------------- Description
+// HTTP side of the typed errors: their status codes, and the response body in the ModelOutput shape
+
+//--------------------------------------------------------------------------------- Import
+use axum::{http::StatusCode, response::{IntoResponse, Response}};
+use crate::api::extract::Json;
+use crate::logics::general::ModelOutput;
+pub use crate::logics::error::ApiError;
+
+//--------------------------------------------------------------------------------- Status
+impl ApiError
+{
+    //------------------------- Status Code
+    pub fn status_code(&self) -> StatusCode
+    {
+        match self
+        {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Database(_) | ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError
+{
+    fn into_response(self) -> Response
+    {
+        (self.status_code(), Json(ModelOutput::<()>::fail(self))).into_response()
+    }
+}
+
+//--------------------------------------------------------------------------------- Output
+impl<T> ModelOutput<T>
+{
+    /// Handler result: the output as JSON on success, its typed error otherwise
+    pub fn into_json(self) -> Result<Json<ModelOutput<T>>, ApiError>
+    {
+        match self.status
+        {
+            true => Ok(Json(self)),
+            false => Err(self.fault.unwrap_or_else(|| ApiError::Internal(self.error.unwrap_or(self.message)))),
+        }
+    }
+    /// Handler result for the create endpoints: 201 Created with the new row
+    pub fn into_created(self) -> Result<(StatusCode, Json<ModelOutput<T>>), ApiError>
+    {
+        self.into_json().map(|json| (StatusCode::CREATED, json))
+    }
+}
@@ -0,0 +1,77 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/extract.rs
+
+//--------------------------------------------------------------------------------- Description
+// Json, Path and Query as axum's, with their rejections turned into ApiError::Validation so a
+// malformed body, id or query string gets the ModelOutput body like every other error
+
+//--------------------------------------------------------------------------------- Import
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::api::error::ApiError;
+
+//--------------------------------------------------------------------------------- Json
+/// JSON request body, and JSON response
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection>
+    {
+        axum::Json::<T>::from_request(request, state).await
+            .map(|axum::Json(value)| Json(value))
+            .map_err(|rejection| ApiError::Validation(rejection.body_text()))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T>
+{
+    fn into_response(self) -> Response
+    {
+        axum::Json(self.0).into_response()
+    }
+}
+
+//--------------------------------------------------------------------------------- Path
+pub struct Path<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection>
+    {
+        axum::extract::Path::<T>::from_request_parts(parts, state).await
+            .map(|axum::extract::Path(value)| Path(value))
+            .map_err(|rejection| ApiError::Validation(rejection.body_text()))
+    }
+}
+
+//--------------------------------------------------------------------------------- Query
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection>
+    {
+        axum::extract::Query::<T>::from_request_parts(parts, state).await
+            .map(|axum::extract::Query(value)| Query(value))
+            .map_err(|rejection| ApiError::Validation(rejection.body_text()))
+    }
+}
@@ -0,0 +1,146 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/graphql/loader.rs
+
+//--------------------------------------------------------------------------------- Description
+// Batches the relation fields: the `commands` of every device on one level of a query load in a
+// single `device_id IN (...)` select instead of one select per device. One loader per request,
+// so rows are cached for that request only and stay within the caller's tenant scope
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use async_graphql::dataloader::Loader;
+use sea_orm::{ColumnTrait, DatabaseConnection, Iden, Iterable, ModelTrait, QueryFilter};
+use sea_orm::sea_query::ValueType;
+use crate::api::access::{Access, Owned};
+use crate::api::error::ApiError;
+use super::graphql_error;
+
+//--------------------------------------------------------------------------------- Key
+/// The rows of E whose `column` holds `id`: "id" for a parent, the foreign key for children
+pub struct Related<E>
+{
+    pub column: &'static str,
+    pub id: i32,
+    entity: PhantomData<fn() -> E>,
+}
+
+impl<E> Related<E>
+{
+    pub fn new(column: &'static str, id: i32) -> Self
+    {
+        Self { column, id, entity: PhantomData }
+    }
+}
+
+impl<E> Clone for Related<E>
+{
+    fn clone(&self) -> Self
+    {
+        Self::new(self.column, self.id)
+    }
+}
+
+impl<E> PartialEq for Related<E>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.column == other.column && self.id == other.id
+    }
+}
+
+impl<E> Eq for Related<E> {}
+
+impl<E> Hash for Related<E>
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.column.hash(state);
+        self.id.hash(state);
+    }
+}
+
+//--------------------------------------------------------------------------------- Loader
+pub struct RelationLoader
+{
+    db: DatabaseConnection,
+    access: Access,
+}
+
+impl RelationLoader
+{
+    pub fn new(db: DatabaseConnection, access: Access) -> Self
+    {
+        Self { db, access }
+    }
+}
+
+impl<E: Owned + 'static> Loader<Related<E>> for RelationLoader
+where
+    E::Model: Sync,
+{
+    type Value = Vec<E::Model>;
+    type Error = async_graphql::Error;
+
+    /// One select per column asked for, the rows grouped back by the value of that column
+    async fn load(&self, keys: &[Related<E>]) -> Result<HashMap<Related<E>, Self::Value>, Self::Error>
+    {
+        let mut columns: HashMap<&'static str, Vec<i32>> = HashMap::new();
+        for key in keys
+        {
+            columns.entry(key.column).or_default().push(key.id);
+        }
+
+        let mut found: HashMap<Related<E>, Self::Value> = HashMap::new();
+        for (name, ids) in columns
+        {
+            let column = E::Column::iter().find(|column| column.to_string() == name)
+                .ok_or_else(|| async_graphql::Error::new(format!("Unknown relation column {}", name)))?;
+            let rows = E::find().filter(column.is_in(ids)).filter(self.access.scope::<E>()).all(&self.db).await
+                .map_err(|e| graphql_error(ApiError::from_db(e.to_string(), &e)))?;
+            for row in rows
+            {
+                // A null foreign key matches no key
+                if let Ok(id) = <i32 as ValueType>::try_from(row.get(column))
+                {
+                    found.entry(Related::new(name, id)).or_default().push(row);
+                }
+            }
+        }
+        Ok(found)
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use async_graphql::dataloader::{DataLoader, Loader};
+    use sea_orm::{ActiveModelTrait, Database, Set};
+    use crate::api::access::Access;
+    use crate::logics::general::Roles;
+    use crate::orm::models::{device, zone};
+    use super::{Related, RelationLoader};
+
+    #[tokio::test]
+    async fn batches_and_scopes_the_related_rows()
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        let other = zone::ActiveModel { user_id: Set(2), name: Set("Other".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        let other = other.insert(&db).await.unwrap().id;
+
+        let access = Access { user_id: 1, role: Roles::Viewer, zones: Vec::new(), all_zones: true };
+        let loader = RelationLoader::new(db.clone(), access);
+        let zones = loader.load(&[Related::<zone::Entity>::new("id", 1), Related::<zone::Entity>::new("id", other)]).await.unwrap();
+        assert_eq!(zones.keys().map(|key| key.id).collect::<Vec<_>>(), vec![1], "the other tenant's zone is left out");
+
+        let loader = DataLoader::new(loader, tokio::spawn);
+        let devices = loader.load_many([1, other].map(|id| Related::<device::Entity>::new("zone_id", id))).await.unwrap();
+        let pool = devices.get(&Related::new("zone_id", 1)).expect("zone 1 has devices");
+        assert!(pool.iter().all(|device| device.zone_id == 1));
+        assert!(!devices.contains_key(&Related::new("zone_id", other)), "a zone without devices has no entry");
+    }
+}
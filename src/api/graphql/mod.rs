@@ -0,0 +1,128 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/graphql/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// GraphQL endpoint over the same services as the REST routes: the auth middleware has already
+// put the caller's Access in the request, so roles and tenant scope apply the same way
+
+//--------------------------------------------------------------------------------- Import
+pub mod query;
+pub mod mutation;
+pub mod relation;
+pub mod loader;
+
+use std::collections::HashMap;
+use async_graphql::{EmptySubscription, ErrorExtensions, InputObject, OutputType, Schema, SimpleObject};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
+use axum::{extract::State, response::Html, Extension};
+use crate::api::extract::Json;
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::logics::general::ModelOutput;
+use crate::orm::models::{config, device, device_command, log, port, timer, timer_device, timer_item, timer_limit, user, zone, zone_command, zone_command_action, zone_command_if};
+use crate::AppState;
+use self::loader::RelationLoader;
+use self::mutation::Mutation;
+use self::query::Query;
+
+//--------------------------------------------------------------------------------- Schema
+pub type ApiSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// Nested relations make deep queries cheap to write, the depth limit keeps them cheap to run
+const MAX_DEPTH: usize = 8;
+
+pub fn schema() -> ApiSchema
+{
+    Schema::build(Query, Mutation, EmptySubscription).limit_depth(MAX_DEPTH).finish()
+}
+
+//--------------------------------------------------------------------------------- Handler
+/// POST /graphql, behind the auth middleware like every other route; the relation loader lives for one request
+pub async fn graphql_handler(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let loader = DataLoader::new(RelationLoader::new(state.db.clone(), access.clone()), tokio::spawn);
+    let request = request.data(state.clone()).data(access).data(loader);
+    Json(state.graphql.execute(request).await)
+}
+
+/// GET /graphql/playground, the page only; its queries go to POST /graphql with the user's credentials
+pub async fn playground() -> Html<String> {
+    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+}
+
+//--------------------------------------------------------------------------------- Page
+#[derive(InputObject, Default)]
+pub struct PageInput
+{
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+    /// Column to sort by (default: id)
+    pub sort: Option<String>,
+    /// asc or desc (default: asc)
+    pub order: Option<String>,
+}
+
+/// One page of rows and the row count before paging
+#[derive(SimpleObject)]
+#[graphql(concrete(name = "ConfigPage", params(config::Model)))]
+#[graphql(concrete(name = "UserPage", params(user::Model)))]
+#[graphql(concrete(name = "LogPage", params(log::Model)))]
+#[graphql(concrete(name = "PortPage", params(port::Model)))]
+#[graphql(concrete(name = "ZonePage", params(zone::Model)))]
+#[graphql(concrete(name = "DevicePage", params(device::Model)))]
+#[graphql(concrete(name = "DeviceCommandPage", params(device_command::Model)))]
+#[graphql(concrete(name = "TimerPage", params(timer::Model)))]
+#[graphql(concrete(name = "TimerDevicePage", params(timer_device::Model)))]
+#[graphql(concrete(name = "TimerItemPage", params(timer_item::Model)))]
+#[graphql(concrete(name = "TimerLimitPage", params(timer_limit::Model)))]
+#[graphql(concrete(name = "ZoneCommandPage", params(zone_command::Model)))]
+#[graphql(concrete(name = "ZoneCommandActionPage", params(zone_command_action::Model)))]
+#[graphql(concrete(name = "ZoneCommandIfPage", params(zone_command_if::Model)))]
+pub struct Page<T: OutputType>
+{
+    pub items: Vec<T>,
+    pub total: u64,
+}
+
+/// Filters and paging in the form the services read from the REST query string
+fn filters(filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> HashMap<String, String>
+{
+    let mut filters = filter.unwrap_or_default();
+    let page = page.unwrap_or_default();
+    if let Some(limit) = page.limit { filters.insert("limit".to_string(), limit.to_string()); }
+    if let Some(offset) = page.offset { filters.insert("offset".to_string(), offset.to_string()); }
+    if let Some(sort) = page.sort { filters.insert("sort".to_string(), sort); }
+    if let Some(order) = page.order { filters.insert("order".to_string(), order); }
+    filters
+}
+
+//--------------------------------------------------------------------------------- Errors
+/// Service errors keep their HTTP status in the "code" extension
+fn graphql_error(error: ApiError) -> async_graphql::Error
+{
+    let code = error.status_code().as_u16();
+    async_graphql::Error::new(error.to_string()).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+fn into_data<T>(output: ModelOutput<T>) -> async_graphql::Result<T>
+{
+    output.into_result().map_err(graphql_error)
+}
+
+fn into_page<T: OutputType>(output: ModelOutput<Vec<T>>) -> async_graphql::Result<Page<T>>
+{
+    let total = output.total;
+    let items = into_data(output)?;
+    Ok(Page { total: total.unwrap_or(items.len() as u64), items })
+}
+
+//--------------------------------------------------------------------------------- Context
+/// App state and the caller's access, put in the request data by the handler
+fn request<'a>(ctx: &async_graphql::Context<'a>) -> async_graphql::Result<(&'a AppState, &'a Access)>
+{
+    Ok((ctx.data::<AppState>()?, ctx.data::<Access>()?))
+}
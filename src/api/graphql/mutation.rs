@@ -0,0 +1,36 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/graphql/mutation.rs
+
+//--------------------------------------------------------------------------------- Description
+// Mutations for running commands, with the same role and zone checks as the REST routes
+
+//--------------------------------------------------------------------------------- Import
+use async_graphql::{Context, Object, Result};
+use crate::api::services::device::DeviceService;
+use crate::api::services::zone_command::ZoneCommandService;
+use crate::orm::models::device::Model as DeviceModel;
+use crate::orm::models::zone_command::Model as ZoneCommandModel;
+use super::{into_data, request};
+
+//--------------------------------------------------------------------------------- Mutation
+pub struct Mutation;
+
+#[Object]
+impl Mutation
+{
+    //------------------------- Execute Device
+    /// Run a device command, given by id or name (e.g. on, off), like POST /device/execute
+    async fn execute_device(&self, ctx: &Context<'_>, device_id: i32, command: String) -> Result<DeviceModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(DeviceService::new().execute(&state.executor, access, device_id, &command).await)
+    }
+
+    //------------------------- Run Zone Command
+    /// Manual run of every enabled action of a zone command, its conditions are not checked; like POST /zone_command/run
+    async fn run_zone_command(&self, ctx: &Context<'_>, id: i32) -> Result<ZoneCommandModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(ZoneCommandService::new().run(&state.db, access, &state.rules, id).await)
+    }
+}
@@ -0,0 +1,228 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/graphql/query.rs
+
+//--------------------------------------------------------------------------------- Description
+// Read queries: a paged list and a single item per entity, through the REST services.
+// filter takes the same column filters as the REST query string, e.g. {name: "Pool", enable: "true"}
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use async_graphql::{Context, Object, Result};
+use crate::api::services::config::ConfigService;
+use crate::api::services::user::UserService;
+use crate::api::services::log::LogService;
+use crate::api::services::port::PortService;
+use crate::api::services::zone::ZoneService;
+use crate::api::services::device::DeviceService;
+use crate::api::services::device_command::DeviceCommandService;
+use crate::api::services::timer::TimerService;
+use crate::api::services::timer_device::TimerDeviceService;
+use crate::api::services::timer_item::TimerItemService;
+use crate::api::services::timer_limit::TimerLimitService;
+use crate::api::services::zone_command::ZoneCommandService;
+use crate::api::services::zone_command_action::ZoneCommandActionService;
+use crate::api::services::zone_command_if::ZoneCommandIfService;
+use crate::orm::models::config::Model as ConfigModel;
+use crate::orm::models::user::Model as UserModel;
+use crate::orm::models::log::Model as LogModel;
+use crate::orm::models::port::Model as PortModel;
+use crate::orm::models::zone::Model as ZoneModel;
+use crate::orm::models::device::Model as DeviceModel;
+use crate::orm::models::device_command::Model as DeviceCommandModel;
+use crate::orm::models::timer::Model as TimerModel;
+use crate::orm::models::timer_device::Model as TimerDeviceModel;
+use crate::orm::models::timer_item::Model as TimerItemModel;
+use crate::orm::models::timer_limit::Model as TimerLimitModel;
+use crate::orm::models::zone_command::Model as ZoneCommandModel;
+use crate::orm::models::zone_command_action::Model as ZoneCommandActionModel;
+use crate::orm::models::zone_command_if::Model as ZoneCommandIfModel;
+use super::{filters, into_data, into_page, request, Page, PageInput};
+
+//--------------------------------------------------------------------------------- Query
+pub struct Query;
+
+#[Object]
+impl Query
+{
+    //------------------------- Config
+    async fn configs(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<ConfigModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(ConfigService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn config(&self, ctx: &Context<'_>, id: i32) -> Result<ConfigModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(ConfigService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- User
+    async fn users(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<UserModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(UserService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn user(&self, ctx: &Context<'_>, id: i32) -> Result<UserModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(UserService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- Log
+    async fn logs(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<LogModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(LogService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn log(&self, ctx: &Context<'_>, id: i32) -> Result<LogModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(LogService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- Port
+    async fn ports(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<PortModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(PortService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn port(&self, ctx: &Context<'_>, id: i32) -> Result<PortModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(PortService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- Zone
+    async fn zones(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<ZoneModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(ZoneService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn zone(&self, ctx: &Context<'_>, id: i32) -> Result<ZoneModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(ZoneService::new().item(&state.db, access, id, None).await).map(|graph| graph.zone)
+    }
+
+    //------------------------- Device
+    async fn devices(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<DeviceModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(DeviceService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn device(&self, ctx: &Context<'_>, id: i32) -> Result<DeviceModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(DeviceService::new().item(&state.db, access, id, None).await).map(|graph| graph.device)
+    }
+
+    //------------------------- DeviceCommand
+    async fn device_commands(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<DeviceCommandModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(DeviceCommandService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn device_command(&self, ctx: &Context<'_>, id: i32) -> Result<DeviceCommandModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(DeviceCommandService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- Timer
+    async fn timers(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<TimerModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(TimerService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn timer(&self, ctx: &Context<'_>, id: i32) -> Result<TimerModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(TimerService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- TimerDevice
+    async fn timer_devices(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<TimerDeviceModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(TimerDeviceService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn timer_device(&self, ctx: &Context<'_>, id: i32) -> Result<TimerDeviceModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(TimerDeviceService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- TimerItem
+    async fn timer_items(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<TimerItemModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(TimerItemService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn timer_item(&self, ctx: &Context<'_>, id: i32) -> Result<TimerItemModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(TimerItemService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- TimerLimit
+    async fn timer_limits(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<TimerLimitModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(TimerLimitService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn timer_limit(&self, ctx: &Context<'_>, id: i32) -> Result<TimerLimitModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(TimerLimitService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- ZoneCommand
+    async fn zone_commands(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<ZoneCommandModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(ZoneCommandService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn zone_command(&self, ctx: &Context<'_>, id: i32) -> Result<ZoneCommandModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(ZoneCommandService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- ZoneCommandAction
+    async fn zone_command_actions(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<ZoneCommandActionModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(ZoneCommandActionService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn zone_command_action(&self, ctx: &Context<'_>, id: i32) -> Result<ZoneCommandActionModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(ZoneCommandActionService::new().item(&state.db, access, id).await)
+    }
+
+    //------------------------- ZoneCommandIf
+    async fn zone_command_ifs(&self, ctx: &Context<'_>, filter: Option<HashMap<String, String>>, page: Option<PageInput>) -> Result<Page<ZoneCommandIfModel>>
+    {
+        let (state, access) = request(ctx)?;
+        into_page(ZoneCommandIfService::new().items(&state.db, access, filters(filter, page)).await)
+    }
+
+    async fn zone_command_if(&self, ctx: &Context<'_>, id: i32) -> Result<ZoneCommandIfModel>
+    {
+        let (state, access) = request(ctx)?;
+        into_data(ZoneCommandIfService::new().item(&state.db, access, id).await)
+    }
+}
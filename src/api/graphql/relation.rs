@@ -0,0 +1,246 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/graphql/relation.rs
+
+//--------------------------------------------------------------------------------- Description
+// Relation fields on the entities, loaded through the request's RelationLoader so sibling rows
+// share one select; related rows are limited to the caller's tenant scope like everything else
+
+//--------------------------------------------------------------------------------- Import
+use async_graphql::{ComplexObject, Context, Result};
+use async_graphql::dataloader::DataLoader;
+use crate::api::access::Owned;
+use crate::orm::models::{device, device_command, port, timer, timer_device, timer_item, timer_limit, zone, zone_command, zone_command_action, zone_command_if};
+use super::loader::{Related, RelationLoader};
+
+//--------------------------------------------------------------------------------- Helpers
+/// Children of E whose foreign key `column` points at `id`, batched with the siblings' lookups
+async fn many<E: Owned + 'static>(ctx: &Context<'_>, column: &'static str, id: i32) -> Result<Vec<E::Model>>
+where
+    E::Model: Sync,
+{
+    let loader = ctx.data::<DataLoader<RelationLoader>>()?;
+    Ok(loader.load_one(Related::<E>::new(column, id)).await?.unwrap_or_default())
+}
+
+/// The E row with this id, when it is in the caller's scope
+async fn one<E: Owned + 'static>(ctx: &Context<'_>, id: i32) -> Result<Option<E::Model>>
+where
+    E::Model: Sync,
+{
+    Ok(many::<E>(ctx, "id", id).await?.into_iter().next())
+}
+
+//--------------------------------------------------------------------------------- Port
+#[ComplexObject]
+impl port::Model
+{
+    async fn devices(&self, ctx: &Context<'_>) -> Result<Vec<device::Model>>
+    {
+        many::<device::Entity>(ctx, "port_id", self.id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Zone
+#[ComplexObject]
+impl zone::Model
+{
+    async fn devices(&self, ctx: &Context<'_>) -> Result<Vec<device::Model>>
+    {
+        many::<device::Entity>(ctx, "zone_id", self.id).await
+    }
+
+    async fn zone_commands(&self, ctx: &Context<'_>) -> Result<Vec<zone_command::Model>>
+    {
+        many::<zone_command::Entity>(ctx, "zone_id", self.id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Device
+#[ComplexObject]
+impl device::Model
+{
+    async fn zone(&self, ctx: &Context<'_>) -> Result<Option<zone::Model>>
+    {
+        one::<zone::Entity>(ctx, self.zone_id).await
+    }
+
+    async fn port(&self, ctx: &Context<'_>) -> Result<Option<port::Model>>
+    {
+        one::<port::Entity>(ctx, self.port_id).await
+    }
+
+    async fn power(&self, ctx: &Context<'_>) -> Result<Option<port::Model>>
+    {
+        one::<port::Entity>(ctx, self.power_id).await
+    }
+
+    /// The command the device is in
+    async fn command(&self, ctx: &Context<'_>) -> Result<Option<device_command::Model>>
+    {
+        one::<device_command::Entity>(ctx, self.command_id).await
+    }
+
+    async fn commands(&self, ctx: &Context<'_>) -> Result<Vec<device_command::Model>>
+    {
+        many::<device_command::Entity>(ctx, "device_id", self.id).await
+    }
+
+    async fn timer_devices(&self, ctx: &Context<'_>) -> Result<Vec<timer_device::Model>>
+    {
+        many::<timer_device::Entity>(ctx, "device_id", self.id).await
+    }
+
+    async fn timer_limits(&self, ctx: &Context<'_>) -> Result<Vec<timer_limit::Model>>
+    {
+        many::<timer_limit::Entity>(ctx, "device_id", self.id).await
+    }
+
+    async fn zone_command_ifs(&self, ctx: &Context<'_>) -> Result<Vec<zone_command_if::Model>>
+    {
+        many::<zone_command_if::Entity>(ctx, "device_id", self.id).await
+    }
+
+    async fn zone_command_actions(&self, ctx: &Context<'_>) -> Result<Vec<zone_command_action::Model>>
+    {
+        many::<zone_command_action::Entity>(ctx, "device_id", self.id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Device Command
+#[ComplexObject]
+impl device_command::Model
+{
+    async fn device(&self, ctx: &Context<'_>) -> Result<Option<device::Model>>
+    {
+        one::<device::Entity>(ctx, self.device_id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Zone Command
+#[ComplexObject]
+impl zone_command::Model
+{
+    async fn zone(&self, ctx: &Context<'_>) -> Result<Option<zone::Model>>
+    {
+        one::<zone::Entity>(ctx, self.zone_id).await
+    }
+
+    async fn ifs(&self, ctx: &Context<'_>) -> Result<Vec<zone_command_if::Model>>
+    {
+        many::<zone_command_if::Entity>(ctx, "zone_command_id", self.id).await
+    }
+
+    async fn actions(&self, ctx: &Context<'_>) -> Result<Vec<zone_command_action::Model>>
+    {
+        many::<zone_command_action::Entity>(ctx, "zone_command_id", self.id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Zone Command If
+#[ComplexObject]
+impl zone_command_if::Model
+{
+    async fn zone_command(&self, ctx: &Context<'_>) -> Result<Option<zone_command::Model>>
+    {
+        one::<zone_command::Entity>(ctx, self.zone_command_id).await
+    }
+
+    async fn device(&self, ctx: &Context<'_>) -> Result<Option<device::Model>>
+    {
+        one::<device::Entity>(ctx, self.device_id).await
+    }
+
+    async fn command(&self, ctx: &Context<'_>) -> Result<Option<device_command::Model>>
+    {
+        one::<device_command::Entity>(ctx, self.command_id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Zone Command Action
+#[ComplexObject]
+impl zone_command_action::Model
+{
+    async fn zone_command(&self, ctx: &Context<'_>) -> Result<Option<zone_command::Model>>
+    {
+        one::<zone_command::Entity>(ctx, self.zone_command_id).await
+    }
+
+    async fn device(&self, ctx: &Context<'_>) -> Result<Option<device::Model>>
+    {
+        one::<device::Entity>(ctx, self.device_id).await
+    }
+
+    async fn command(&self, ctx: &Context<'_>) -> Result<Option<device_command::Model>>
+    {
+        match self.command_id
+        {
+            Some(command_id) => one::<device_command::Entity>(ctx, command_id).await,
+            None => Ok(None),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Timer
+#[ComplexObject]
+impl timer::Model
+{
+    async fn items(&self, ctx: &Context<'_>) -> Result<Vec<timer_item::Model>>
+    {
+        many::<timer_item::Entity>(ctx, "timer_id", self.id).await
+    }
+
+    async fn devices(&self, ctx: &Context<'_>) -> Result<Vec<timer_device::Model>>
+    {
+        many::<timer_device::Entity>(ctx, "timer_id", self.id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Timer Device
+#[ComplexObject]
+impl timer_device::Model
+{
+    async fn timer(&self, ctx: &Context<'_>) -> Result<Option<timer::Model>>
+    {
+        one::<timer::Entity>(ctx, self.timer_id).await
+    }
+
+    async fn device(&self, ctx: &Context<'_>) -> Result<Option<device::Model>>
+    {
+        one::<device::Entity>(ctx, self.device_id).await
+    }
+
+    async fn command(&self, ctx: &Context<'_>) -> Result<Option<device_command::Model>>
+    {
+        one::<device_command::Entity>(ctx, self.command_id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Timer Item
+#[ComplexObject]
+impl timer_item::Model
+{
+    async fn timer(&self, ctx: &Context<'_>) -> Result<Option<timer::Model>>
+    {
+        one::<timer::Entity>(ctx, self.timer_id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Timer Limit
+#[ComplexObject]
+impl timer_limit::Model
+{
+    async fn device(&self, ctx: &Context<'_>) -> Result<Option<device::Model>>
+    {
+        one::<device::Entity>(ctx, self.device_id).await
+    }
+
+    async fn command_from(&self, ctx: &Context<'_>) -> Result<Option<device_command::Model>>
+    {
+        one::<device_command::Entity>(ctx, self.command_from_id).await
+    }
+
+    async fn command_to(&self, ctx: &Context<'_>) -> Result<Option<device_command::Model>>
+    {
+        one::<device_command::Entity>(ctx, self.command_to_id).await
+    }
+}
@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use utoipa::ToSchema;
 use std::collections::HashMap;
 use crate::{orm::models::config::Model as ConfigModel, logics::general::ModelOutput, AppState};
 use crate::api::services::config::ConfigService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -106,19 +109,23 @@ pub struct UpdateConfigRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of configs to return"),
         ("offset" = Option<i32>, Query, description = "Number of configs to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of configurations retrieved successfully", body = Vec<ConfigModel>),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn list_configs(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<ConfigModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<ConfigModel>>>, ApiError> {
     let service = ConfigService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetConfig
@@ -130,16 +137,18 @@ pub async fn list_configs(
     responses(
         (status = 200, description = "Configuration retrieved successfully", body = ConfigModel),
         (status = 404, description = "Configuration not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ConfigModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ConfigModel>>, ApiError> {
     let service = ConfigService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableConfig
@@ -154,16 +163,18 @@ pub async fn get_config(
     responses(
         (status = 200, description = "Config enabled successfully", body = ConfigModel),
         (status = 404, description = "Config not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn enable_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ConfigModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ConfigModel>>, ApiError> {
     let service = ConfigService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableConfig
@@ -178,16 +189,18 @@ pub async fn enable_config(
     responses(
         (status = 200, description = "Config disabled successfully", body = ConfigModel),
         (status = 404, description = "Config not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn disable_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ConfigModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ConfigModel>>, ApiError> {
     let service = ConfigService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateConfig
@@ -203,14 +216,16 @@ pub async fn disable_config(
         (status = 200, description = "Configuration updated successfully", body = ConfigModel),
         (status = 400, description = "Invalid request payload"),
         (status = 404, description = "Configuration not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateConfigRequest>,
-) -> Result<Json<ModelOutput<ConfigModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ConfigModel>>, ApiError> {
     let service = ConfigService::new();
     
     let config_model = ConfigModel {
@@ -249,8 +264,8 @@ pub async fn update_config(
         verbose: payload.verbose.unwrap_or(false),
     };
     
-    let result = service.update(&state.db, config_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, config_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateConfig
@@ -262,13 +277,15 @@ pub async fn update_config(
     responses(
         (status = 201, description = "Configuration created successfully", body = ConfigModel),
         (status = 400, description = "Invalid request payload"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateConfigRequest>,
-) -> Result<Json<ModelOutput<ConfigModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<ConfigModel>>), ApiError> {
     let service = ConfigService::new();
     let config_model = ConfigModel {
         id: 0, // Will be auto-generated
@@ -306,8 +323,8 @@ pub async fn create_config(
         verbose: payload.verbose,
     };
     
-    let result = service.add(&state.db, config_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, config_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteConfig
@@ -321,16 +338,18 @@ pub async fn create_config(
     responses(
         (status = 200, description = "Configuration deleted successfully"),
         (status = 404, description = "Configuration not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = ConfigService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusConfig
@@ -345,14 +364,16 @@ pub async fn delete_config(
     responses(
         (status = 200, description = "Config status toggled successfully", body = ConfigModel),
         (status = 404, description = "Config not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn status_config(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ConfigModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ConfigModel>>, ApiError> {
     let service = ConfigService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
@@ -6,15 +6,19 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::device::Model as DeviceModel, logics::general::ModelOutput, AppState};
 use crate::api::services::device::DeviceService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+use crate::orm::logics::graph::DeviceGraph;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -80,6 +84,8 @@ pub struct UpdateDeviceRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of devices to return"),
         ("offset" = Option<i32>, Query, description = "Number of devices to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of devices retrieved successfully", body = Vec<DeviceModel>),
@@ -88,11 +94,12 @@ pub struct UpdateDeviceRequest {
 )]
 pub async fn list_devices(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<DeviceModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<DeviceModel>>>, ApiError> {
     let service = DeviceService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetDevice
@@ -102,21 +109,25 @@ pub async fn list_devices(
     tag = "🔧 Device",
 
     params(
-        ("id" = i32, Path, description = "Device ID")
+        ("id" = i32, Path, description = "Device ID"),
+        ("expand" = Option<String>, Query, description = "Comma separated parts to include: commands, rules, timers"),
     ),
     responses(
-        (status = 200, description = "Device retrieved successfully", body = DeviceModel),
+        (status = 200, description = "Device retrieved successfully", body = DeviceGraph),
         (status = 404, description = "Device not found"),
+        (status = 422, description = "Invalid expand value"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceModel>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<DeviceGraph>>, ApiError> {
     let service = DeviceService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id, params.get("expand").map(String::as_str)).await;
+    result.into_json()
 }
 
 //------------------------- EnableDevice
@@ -136,11 +147,12 @@ pub async fn get_device(
 )]
 pub async fn enable_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceModel>>, ApiError> {
     let service = DeviceService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableDevice
@@ -160,11 +172,12 @@ pub async fn enable_device(
 )]
 pub async fn disable_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceModel>>, ApiError> {
     let service = DeviceService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateDevice
@@ -181,14 +194,17 @@ pub async fn disable_device(
         (status = 200, description = "Device updated successfully", body = DeviceModel),
         (status = 404, description = "Device not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateDeviceRequest>,
-) -> Result<Json<ModelOutput<DeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceModel>>, ApiError> {
     let service = DeviceService::new();
     
     let device_model = DeviceModel {
@@ -206,8 +222,8 @@ pub async fn update_device(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, device_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, &state.executor.events, device_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateDevice
@@ -220,13 +236,16 @@ pub async fn update_device(
     responses(
         (status = 201, description = "Device created successfully", body = DeviceModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateDeviceRequest>,
-) -> Result<Json<ModelOutput<DeviceModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<DeviceModel>>), ApiError> {
     let service = DeviceService::new();
     let device_model = DeviceModel {
         id: 0, // Will be auto-generated
@@ -243,8 +262,8 @@ pub async fn create_device(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, device_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, device_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteDevice
@@ -254,21 +273,25 @@ pub async fn create_device(
     tag = "🔧 Device",
 
     params(
-        ("id" = i32, Path, description = "Device ID to delete")
+        ("id" = i32, Path, description = "Device ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete or reset the rows that reference it (default: false)"),
     ),
     responses(
         (status = 200, description = "Device deleted successfully"),
         (status = 404, description = "Device not found"),
+        (status = 409, description = "Still referenced by other rows"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = DeviceService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
 }
 
 //------------------------- StatusDevice
@@ -288,9 +311,37 @@ pub async fn delete_device(
 )]
 pub async fn status_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceModel>>, ApiError> {
+    let service = DeviceService::new();
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- ExecuteDevice
+#[utoipa::path(
+    post,
+    path = "/device/execute/{device_id}/{command}",
+    tag = "🔧 Device",
+
+    params(
+        ("device_id" = i32, Path, description = "Device ID to execute the command on"),
+        ("command" = String, Path, description = "Device command ID or name (e.g. on, off)")
+    ),
+    responses(
+        (status = 200, description = "Device command executed successfully", body = DeviceModel),
+        (status = 404, description = "Device or command not found"),
+        (status = 422, description = "Device or command disabled, or command not valid for the device"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn execute_device(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path((device_id, command)): Path<(i32, String)>,
+) -> Result<Json<ModelOutput<DeviceModel>>, ApiError> {
     let service = DeviceService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.execute(&state.executor, &access, device_id, &command).await;
+    result.into_json()
 }
@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::device_command::Model as DeviceCommandModel, logics::general::ModelOutput, AppState};
 use crate::api::services::device_command::DeviceCommandService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -30,6 +33,8 @@ pub struct CreateDeviceCommandRequest {
     pub value_to: Option<i32>,
     #[schema(example = 1000)]
     pub delay: Option<i32>,
+    #[schema(example = 1000)]
+    pub frequency: Option<i32>,
     #[schema(example = "Command to turn on LED")]
     pub description: String,
     #[schema(example = false)]
@@ -53,6 +58,8 @@ pub struct UpdateDeviceCommandRequest {
     pub value_to: Option<i32>,
     #[schema(example = 1000)]
     pub delay: Option<i32>,
+    #[schema(example = 1000)]
+    pub frequency: Option<i32>,
     #[schema(example = "Command to turn on LED")]
     pub description: Option<String>,
     #[schema(example = false)]
@@ -72,6 +79,8 @@ pub struct UpdateDeviceCommandRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of device commands to return"),
         ("offset" = Option<i32>, Query, description = "Number of device commands to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of device commands retrieved successfully", body = Vec<DeviceCommandModel>),
@@ -80,11 +89,12 @@ pub struct UpdateDeviceCommandRequest {
 )]
 pub async fn list_device_commands(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<DeviceCommandModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<DeviceCommandModel>>>, ApiError> {
     let service = DeviceCommandService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- Get
@@ -104,11 +114,12 @@ pub async fn list_device_commands(
 )]
 pub async fn get_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceCommandModel>>, ApiError> {
     let service = DeviceCommandService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Enable
@@ -128,11 +139,12 @@ pub async fn get_device_command(
 )]
 pub async fn enable_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceCommandModel>>, ApiError> {
     let service = DeviceCommandService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Disable
@@ -152,11 +164,12 @@ pub async fn enable_device_command(
 )]
 pub async fn disable_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceCommandModel>>, ApiError> {
     let service = DeviceCommandService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Update
@@ -173,14 +186,17 @@ pub async fn disable_device_command(
         (status = 200, description = "Device command updated successfully", body = DeviceCommandModel),
         (status = 404, description = "Device command not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateDeviceCommandRequest>,
-) -> Result<Json<ModelOutput<DeviceCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceCommandModel>>, ApiError> {
     let service = DeviceCommandService::new();
     
     let device_command_model = DeviceCommandModel {
@@ -190,14 +206,15 @@ pub async fn update_device_command(
         value_from: payload.value_from,
         value_to: payload.value_to,
         delay: payload.delay,
+        frequency: payload.frequency,
         description: payload.description.unwrap_or_default(),
         reload: payload.reload.unwrap_or(false),
         enable: payload.enable.unwrap_or(true),
         r#type: payload.r#type.unwrap_or_default(),
     };
     
-    let result = service.update(&state.db, device_command_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, device_command_model).await;
+    result.into_json()
 }
 
 //------------------------- Create
@@ -210,13 +227,16 @@ pub async fn update_device_command(
     responses(
         (status = 201, description = "Device command created successfully", body = DeviceCommandModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateDeviceCommandRequest>,
-) -> Result<Json<ModelOutput<DeviceCommandModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<DeviceCommandModel>>), ApiError> {
     let service = DeviceCommandService::new();
     let device_command_model = DeviceCommandModel {
         id: 0, // Will be auto-generated
@@ -225,14 +245,15 @@ pub async fn create_device_command(
         value_from: payload.value_from,
         value_to: payload.value_to,
         delay: payload.delay,
+        frequency: payload.frequency,
         description: payload.description,
         reload: payload.reload,
         enable: payload.enable,
         r#type: payload.r#type,
     };
     
-    let result = service.add(&state.db, device_command_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, device_command_model).await;
+    result.into_created()
 }
 
 //------------------------- Delete
@@ -241,21 +262,25 @@ pub async fn create_device_command(
     path = "/device_command/delete/{id}",
     tag = "📡 Device Command",
     params(
-        ("id" = i32, Path, description = "Device command ID to delete")
+        ("id" = i32, Path, description = "Device command ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete or reset the rows that reference it (default: false)"),
     ),
     responses(
         (status = 200, description = "Device command deleted successfully"),
         (status = 404, description = "Device command not found"),
+        (status = 409, description = "Still referenced by other rows"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = DeviceCommandService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
 }
 
 //------------------------- Status
@@ -275,9 +300,10 @@ pub async fn delete_device_command(
 )]
 pub async fn status_device_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<DeviceCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<DeviceCommandModel>>, ApiError> {
     let service = DeviceCommandService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
\ No newline at end of file
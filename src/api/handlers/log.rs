@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::log::Model as LogModel, logics::general::ModelOutput, AppState};
 use crate::api::services::log::LogService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -53,19 +56,23 @@ pub struct UpdateLogRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of logs to return"),
         ("offset" = Option<i32>, Query, description = "Number of logs to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of logs retrieved successfully", body = Vec<LogModel>),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn list_logs(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<LogModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<LogModel>>>, ApiError> {
     let service = LogService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetLog
@@ -80,16 +87,18 @@ pub async fn list_logs(
     responses(
         (status = 200, description = "Log retrieved successfully", body = LogModel),
         (status = 404, description = "Log not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<LogModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<LogModel>>, ApiError> {
     let service = LogService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableLog
@@ -104,16 +113,18 @@ pub async fn get_log(
     responses(
         (status = 200, description = "Log enabled successfully", body = LogModel),
         (status = 404, description = "Log not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn enable_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<LogModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<LogModel>>, ApiError> {
     let service = LogService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableLog
@@ -128,16 +139,18 @@ pub async fn enable_log(
     responses(
         (status = 200, description = "Log disabled successfully", body = LogModel),
         (status = 404, description = "Log not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn disable_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<LogModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<LogModel>>, ApiError> {
     let service = LogService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateLog
@@ -154,14 +167,18 @@ pub async fn disable_log(
         (status = 200, description = "Log updated successfully", body = LogModel),
         (status = 404, description = "Log not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateLogRequest>,
-) -> Result<Json<ModelOutput<LogModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<LogModel>>, ApiError> {
     let service = LogService::new();
     
     let log_model = LogModel {
@@ -172,8 +189,8 @@ pub async fn update_log(
         data: payload.data.unwrap_or_default(),
     };
     
-    let result = service.update(&state.db, log_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, log_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateLog
@@ -186,13 +203,17 @@ pub async fn update_log(
     responses(
         (status = 201, description = "Log created successfully", body = LogModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateLogRequest>,
-) -> Result<Json<ModelOutput<LogModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<LogModel>>), ApiError> {
     let service = LogService::new();
     let log_model = LogModel {
         id: 0, // Will be auto-generated
@@ -202,8 +223,8 @@ pub async fn create_log(
         data: payload.data,
     };
     
-    let result = service.add(&state.db, log_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, &state.executor.events, log_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteLog
@@ -218,16 +239,18 @@ pub async fn create_log(
     responses(
         (status = 200, description = "Log deleted successfully"),
         (status = 404, description = "Log not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = LogService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusLog
@@ -242,14 +265,16 @@ pub async fn delete_log(
     responses(
         (status = 200, description = "Log status toggled successfully", body = LogModel),
         (status = 404, description = "Log not found"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn status_log(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<LogModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<LogModel>>, ApiError> {
     let service = LogService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
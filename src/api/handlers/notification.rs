@@ -0,0 +1,278 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/handlers/notification.rs
+
+//--------------------------------------------------------------------------------- Description
+// Axum handlers for Notification CRUD operations
+
+//--------------------------------------------------------------------------------- Import
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Extension,
+};
+use crate::api::extract::{Json, Path, Query};
+use serde::Deserialize;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use crate::{orm::models::notification::Model as NotificationModel, logics::general::ModelOutput, AppState};
+use crate::api::services::notification::NotificationService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+
+//--------------------------------------------------------------------------------- Request DTOs
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for creating a new notification")]
+pub struct CreateNotificationRequest {
+    #[schema(example = 1)]
+    pub user_id: i32,
+    #[schema(example = 8)]
+    pub device_id: i32,
+    #[schema(example = "fuse")]
+    pub kind: String,
+    #[schema(example = "Tell me when the erjet fuse trips")]
+    pub description: String,
+    #[schema(example = true)]
+    pub enable: bool,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for updating an existing notification")]
+pub struct UpdateNotificationRequest {
+    #[schema(example = 1)]
+    pub user_id: Option<i32>,
+    #[schema(example = 8)]
+    pub device_id: Option<i32>,
+    #[schema(example = "fuse")]
+    pub kind: Option<String>,
+    #[schema(example = "Tell me when the erjet fuse trips")]
+    pub description: Option<String>,
+    #[schema(example = true)]
+    pub enable: Option<bool>,
+}
+
+//--------------------------------------------------------------------------------- Handlers
+//------------------------- ListNotifications
+#[utoipa::path(
+    get,
+    path = "/notification/items",
+    tag = "🔔 Notification",
+
+    params(
+        ("limit" = Option<i32>, Query, description = "Maximum number of notifications to return"),
+        ("offset" = Option<i32>, Query, description = "Number of notifications to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
+    ),
+    responses(
+        (status = 200, description = "List of notifications retrieved successfully", body = Vec<NotificationModel>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_notifications(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<Vec<NotificationModel>>>, ApiError> {
+    let service = NotificationService::new();
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
+}
+
+//------------------------- GetNotification
+#[utoipa::path(
+    get,
+    path = "/notification/item/{id}",
+    tag = "🔔 Notification",
+
+    params(
+        ("id" = i32, Path, description = "Notification ID")
+    ),
+    responses(
+        (status = 200, description = "Notification retrieved successfully", body = NotificationModel),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<NotificationModel>>, ApiError> {
+    let service = NotificationService::new();
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- EnableNotification
+#[utoipa::path(
+    get,
+    path = "/notification/enable/{id}",
+    tag = "🔔 Notification",
+
+    params(
+        ("id" = i32, Path, description = "Notification ID to enable")
+    ),
+    responses(
+        (status = 200, description = "Notification enabled successfully", body = NotificationModel),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn enable_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<NotificationModel>>, ApiError> {
+    let service = NotificationService::new();
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- DisableNotification
+#[utoipa::path(
+    get,
+    path = "/notification/disable/{id}",
+    tag = "🔔 Notification",
+
+    params(
+        ("id" = i32, Path, description = "Notification ID to disable")
+    ),
+    responses(
+        (status = 200, description = "Notification disabled successfully", body = NotificationModel),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn disable_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<NotificationModel>>, ApiError> {
+    let service = NotificationService::new();
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- UpdateNotification
+#[utoipa::path(
+    put,
+    path = "/notification/update/{id}",
+    tag = "🔔 Notification",
+
+    params(
+        ("id" = i32, Path, description = "Notification ID to update")
+    ),
+    request_body = UpdateNotificationRequest,
+    responses(
+        (status = 200, description = "Notification updated successfully", body = NotificationModel),
+        (status = 404, description = "Notification not found"),
+        (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn update_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateNotificationRequest>,
+) -> Result<Json<ModelOutput<NotificationModel>>, ApiError> {
+    let service = NotificationService::new();
+    
+    let notification_model = NotificationModel {
+        id,
+        user_id: payload.user_id.unwrap_or_default(),
+        device_id: payload.device_id.unwrap_or_default(),
+        kind: payload.kind.unwrap_or_default(),
+        description: payload.description.unwrap_or_default(),
+        enable: payload.enable.unwrap_or(true),
+    };
+    
+    let result = service.update(&state.db, &access, notification_model).await;
+    result.into_json()
+}
+
+//------------------------- CreateNotification
+#[utoipa::path(
+    post,
+    path = "/notification/add",
+    tag = "🔔 Notification",
+
+    request_body = CreateNotificationRequest,
+    responses(
+        (status = 201, description = "Notification created successfully", body = NotificationModel),
+        (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn create_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Json(payload): Json<CreateNotificationRequest>,
+) -> Result<(StatusCode, Json<ModelOutput<NotificationModel>>), ApiError> {
+    let service = NotificationService::new();
+    let notification_model = NotificationModel {
+        id: 0, // Will be auto-generated
+        user_id: payload.user_id,
+        device_id: payload.device_id,
+        kind: payload.kind,
+        description: payload.description,
+        enable: payload.enable,
+    };
+    
+    let result = service.add(&state.db, &access, notification_model).await;
+    result.into_created()
+}
+
+//------------------------- DeleteNotification
+#[utoipa::path(
+    delete,
+    path = "/notification/delete/{id}",
+    tag = "🔔 Notification",
+
+    params(
+        ("id" = i32, Path, description = "Notification ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Notification deleted successfully"),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
+    let service = NotificationService::new();
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- StatusNotification
+#[utoipa::path(
+    get,
+    path = "/notification/status/{id}",
+    tag = "🔔 Notification",
+
+    params(
+        ("id" = i32, Path, description = "Notification ID to toggle status")
+    ),
+    responses(
+        (status = 200, description = "Notification status toggled successfully", body = NotificationModel),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn status_notification(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<NotificationModel>>, ApiError> {
+    let service = NotificationService::new();
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
+}
@@ -6,15 +6,19 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::port::Model as PortModel, logics::general::ModelOutput, AppState};
 use crate::api::services::port::PortService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+use crate::hardware::Drivers;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -54,6 +58,8 @@ pub struct UpdatePortRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of ports to return"),
         ("offset" = Option<i32>, Query, description = "Number of ports to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of ports retrieved successfully", body = Vec<PortModel>),
@@ -62,11 +68,12 @@ pub struct UpdatePortRequest {
 )]
 pub async fn list_ports(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<PortModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<PortModel>>>, ApiError> {
     let service = PortService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- Get
@@ -86,11 +93,12 @@ pub async fn list_ports(
 )]
 pub async fn get_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<PortModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
     let service = PortService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Enable
@@ -110,11 +118,12 @@ pub async fn get_port(
 )]
 pub async fn enable_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<PortModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
     let service = PortService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Disable
@@ -133,11 +142,12 @@ pub async fn enable_port(
 )]
 pub async fn disable_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<PortModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
     let service = PortService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Update
@@ -153,14 +163,17 @@ pub async fn disable_port(
         (status = 200, description = "Port updated successfully", body = PortModel),
         (status = 404, description = "Port not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdatePortRequest>,
-) -> Result<Json<ModelOutput<PortModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
     let service = PortService::new();
     
     let port_model = PortModel {
@@ -176,8 +189,8 @@ pub async fn update_port(
         r#type: payload.r#type.unwrap_or_default(),
     };
     
-    let result = service.update(&state.db, port_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, port_model).await;
+    result.into_json()
 }
 
 //------------------------- Create
@@ -189,13 +202,16 @@ pub async fn update_port(
     responses(
         (status = 201, description = "Port created successfully", body = PortModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreatePortRequest>,
-) -> Result<Json<ModelOutput<PortModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<PortModel>>), ApiError> {
     let service = PortService::new();
     let port_model = PortModel {
         id: 0, // Will be auto-generated
@@ -210,8 +226,8 @@ pub async fn create_port(
         r#type: payload.r#type,
     };
     
-    let result = service.add(&state.db, port_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, port_model).await;
+    result.into_created()
 }
 
 //------------------------- Delete
@@ -221,21 +237,25 @@ pub async fn create_port(
     tag = "🔌 Port",
 
     params(
-        ("id" = i32, Path, description = "Port ID to delete")
+        ("id" = i32, Path, description = "Port ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete or reset the rows that reference it (default: false)"),
     ),
     responses(
         (status = 200, description = "Port deleted successfully"),
         (status = 404, description = "Port not found"),
+        (status = 409, description = "Still referenced by other rows"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = PortService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
 }
 
 //------------------------- Status
@@ -255,9 +275,84 @@ pub async fn delete_port(
 )]
 pub async fn status_port(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
+    let service = PortService::new();
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- Read
+#[utoipa::path(
+    get,
+    path = "/port/read/{id}",
+    tag = "🔌 Port",
+
+    params(
+        ("id" = i32, Path, description = "Port ID to read from hardware")
+    ),
+    responses(
+        (status = 200, description = "Port level read successfully", body = PortModel),
+        (status = 404, description = "Port not found"),
+        (status = 422, description = "Port disabled, unsupported or invalid value"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn read_port(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<PortModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
     let service = PortService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.read(&state.db, &access, &state.executor, id).await;
+    result.into_json()
+}
+
+//------------------------- Write
+#[utoipa::path(
+    get,
+    path = "/port/write/{id}/{value}",
+    tag = "🔌 Port",
+
+    params(
+        ("id" = i32, Path, description = "Port ID to write to hardware"),
+        ("value" = i32, Path, description = "Level to drive the port to"),
+        ("address" = Option<String>, Query, description = "Device address on an I2C, SPI or UART port"),
+        ("frequency" = Option<i32>, Query, description = "PWM frequency in Hz"),
+    ),
+    responses(
+        (status = 200, description = "Port level written successfully", body = PortModel),
+        (status = 404, description = "Port not found"),
+        (status = 422, description = "Port disabled, unsupported or invalid value"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn write_port(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path((id, value)): Path<(i32, i32)>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<PortModel>>, ApiError> {
+    let service = PortService::new();
+    let result = service.write(&state.db, &access, &state.executor, id, value, params).await;
+    result.into_json()
+}
+
+//------------------------- Drivers
+#[utoipa::path(
+    get,
+    path = "/port/drivers",
+    tag = "🔌 Port",
+
+    responses(
+        (status = 200, description = "Hardware backend in use for each kind of port", body = Drivers),
+        (status = 401, description = "Missing or invalid credentials")
+    )
+)]
+pub async fn port_drivers(
+    State(state): State<AppState>,
+) -> Json<ModelOutput<Drivers>> {
+    let drivers = state.executor.buses.drivers(state.pins.as_ref());
+    Json(ModelOutput::success(drivers, "Hardware drivers retrieved successfully".to_string()))
 }
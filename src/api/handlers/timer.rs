@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::timer::Model as TimerModel, logics::general::ModelOutput, AppState};
 use crate::api::services::timer::TimerService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -45,6 +48,8 @@ pub struct UpdateTimerRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of timers to return"),
         ("offset" = Option<i32>, Query, description = "Number of timers to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of timers retrieved successfully", body = Vec<TimerModel>),
@@ -53,11 +58,12 @@ pub struct UpdateTimerRequest {
 )]
 pub async fn list_timers(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<TimerModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<TimerModel>>>, ApiError> {
     let service = TimerService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetTimer
@@ -77,11 +83,12 @@ pub async fn list_timers(
 )]
 pub async fn get_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerModel>>, ApiError> {
     let service = TimerService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableTimer
@@ -101,11 +108,12 @@ pub async fn get_timer(
 )]
 pub async fn enable_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerModel>>, ApiError> {
     let service = TimerService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -124,11 +132,12 @@ pub async fn enable_timer(
 )]
 pub async fn disable_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerModel>>, ApiError> {
     let service = TimerService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateTimer
@@ -145,14 +154,17 @@ pub async fn disable_timer(
         (status = 200, description = "Timer updated successfully", body = TimerModel),
         (status = 404, description = "Timer not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateTimerRequest>,
-) -> Result<Json<ModelOutput<TimerModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerModel>>, ApiError> {
     let service = TimerService::new();
     
     let timer_model = TimerModel {
@@ -163,8 +175,8 @@ pub async fn update_timer(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, timer_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, timer_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateTimer
@@ -177,13 +189,16 @@ pub async fn update_timer(
     responses(
         (status = 201, description = "Timer created successfully", body = TimerModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateTimerRequest>,
-) -> Result<Json<ModelOutput<TimerModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<TimerModel>>), ApiError> {
     let service = TimerService::new();
     let timer_model = TimerModel {
         id: 0, // Will be auto-generated
@@ -193,8 +208,8 @@ pub async fn create_timer(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, timer_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, timer_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteTimer
@@ -204,21 +219,25 @@ pub async fn create_timer(
     tag = "⏰ Timer",
 
     params(
-        ("id" = i32, Path, description = "Timer ID to delete")
+        ("id" = i32, Path, description = "Timer ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete or reset the rows that reference it (default: false)"),
     ),
     responses(
         (status = 200, description = "Timer deleted successfully"),
         (status = 404, description = "Timer not found"),
+        (status = 409, description = "Still referenced by other rows"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = TimerService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
 }
 
 //------------------------- StatusTimer
@@ -238,9 +257,10 @@ pub async fn delete_timer(
 )]
 pub async fn status_timer(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerModel>>, ApiError> {
     let service = TimerService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
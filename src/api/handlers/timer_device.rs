@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::timer_device::Model as TimerDeviceModel, logics::general::ModelOutput, AppState};
 use crate::api::services::timer_device::TimerDeviceService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -56,6 +59,8 @@ pub struct UpdateTimerDeviceRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of timer devices to return"),
         ("offset" = Option<i32>, Query, description = "Number of timer devices to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of timer devices retrieved successfully", body = Vec<TimerDeviceModel>),
@@ -64,11 +69,12 @@ pub struct UpdateTimerDeviceRequest {
 )]
 pub async fn list_timer_devices(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<TimerDeviceModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<TimerDeviceModel>>>, ApiError> {
     let service = TimerDeviceService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetTimerDevice
@@ -88,11 +94,12 @@ pub async fn list_timer_devices(
 )]
 pub async fn get_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerDeviceModel>>, ApiError> {
     let service = TimerDeviceService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableTimerDevice
@@ -112,11 +119,12 @@ pub async fn get_timer_device(
 )]
 pub async fn enable_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerDeviceModel>>, ApiError> {
     let service = TimerDeviceService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableTimerDevice
@@ -136,11 +144,12 @@ pub async fn enable_timer_device(
 )]
 pub async fn disable_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerDeviceModel>>, ApiError> {
     let service = TimerDeviceService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateTimerDevice
@@ -157,14 +166,17 @@ pub async fn disable_timer_device(
         (status = 200, description = "Timer device updated successfully", body = TimerDeviceModel),
         (status = 404, description = "Timer device not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateTimerDeviceRequest>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerDeviceModel>>, ApiError> {
     let service = TimerDeviceService::new();
     
     let timer_device_model = TimerDeviceModel {
@@ -176,8 +188,8 @@ pub async fn update_timer_device(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, timer_device_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, timer_device_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateTimerDevice
@@ -190,13 +202,16 @@ pub async fn update_timer_device(
     responses(
         (status = 201, description = "Timer device created successfully", body = TimerDeviceModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateTimerDeviceRequest>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<TimerDeviceModel>>), ApiError> {
     let service = TimerDeviceService::new();
     let timer_device_model = TimerDeviceModel {
         id: 0, // Will be auto-generated
@@ -207,8 +222,8 @@ pub async fn create_timer_device(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, timer_device_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, timer_device_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteTimerDevice
@@ -228,11 +243,12 @@ pub async fn create_timer_device(
 )]
 pub async fn delete_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = TimerDeviceService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusTimerDevice
@@ -252,11 +268,12 @@ pub async fn delete_timer_device(
 )]
 pub async fn status_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerDeviceModel>>, ApiError> {
     let service = TimerDeviceService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- Import
@@ -286,8 +303,9 @@ pub struct ImportTimerDeviceQuery {
 )]
 pub async fn import_timer_device(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(q): Query<ImportTimerDeviceQuery>,
-) -> Result<Json<ModelOutput<TimerDeviceModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerDeviceModel>>, ApiError> {
     let service = TimerDeviceService::new();
 
     // Build filters to check if mapping already exists
@@ -296,7 +314,7 @@ pub async fn import_timer_device(
     filters.insert("device_id".to_string(), q.device_id.to_string());
 
 
-    let existing = service.items(&state.db, filters).await;
+    let existing = service.items(&state.db, &access, filters).await;
 
     if existing.status 
     {
@@ -314,8 +332,8 @@ pub async fn import_timer_device(
                     description: current.description,
                     enable: q.enable,
                 };
-                let result = service.update(&state.db, updated).await;
-                return Ok(Json(result));
+                let result = service.update(&state.db, &access, updated).await;
+                return result.into_json();
             }
         }
     }
@@ -329,6 +347,6 @@ pub async fn import_timer_device(
         description: "".to_string(),
         enable: q.enable,
     };
-    let result = service.add(&state.db, to_create).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, to_create).await;
+    result.into_json()
 }
@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::timer_item::Model as TimerItemModel, logics::general::ModelOutput, AppState};
 use crate::api::services::timer_item::TimerItemService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -61,6 +64,8 @@ pub struct UpdateTimerItemRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of timer items to return"),
         ("offset" = Option<i32>, Query, description = "Number of timer items to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of timer items retrieved successfully", body = Vec<TimerItemModel>),
@@ -69,11 +74,12 @@ pub struct UpdateTimerItemRequest {
 )]
 pub async fn list_timer_items(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<TimerItemModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<TimerItemModel>>>, ApiError> {
     let service = TimerItemService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetTimerItem
@@ -93,11 +99,12 @@ pub async fn list_timer_items(
 )]
 pub async fn get_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerItemModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerItemModel>>, ApiError> {
     let service = TimerItemService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableTimerItem
@@ -117,11 +124,12 @@ pub async fn get_timer_item(
 )]
 pub async fn enable_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerItemModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerItemModel>>, ApiError> {
     let service = TimerItemService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableTimerItem
@@ -141,11 +149,12 @@ pub async fn enable_timer_item(
 )]
 pub async fn disable_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerItemModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerItemModel>>, ApiError> {
     let service = TimerItemService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateTimerItem
@@ -162,14 +171,17 @@ pub async fn disable_timer_item(
         (status = 200, description = "Timer item updated successfully", body = TimerItemModel),
         (status = 404, description = "Timer item not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateTimerItemRequest>,
-) -> Result<Json<ModelOutput<TimerItemModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerItemModel>>, ApiError> {
     let service = TimerItemService::new();
     
     let timer_item_model = TimerItemModel {
@@ -182,8 +194,8 @@ pub async fn update_timer_item(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, timer_item_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, timer_item_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateTimerItem
@@ -196,13 +208,16 @@ pub async fn update_timer_item(
     responses(
         (status = 201, description = "Timer item created successfully", body = TimerItemModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateTimerItemRequest>,
-) -> Result<Json<ModelOutput<TimerItemModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<TimerItemModel>>), ApiError> {
     let service = TimerItemService::new();
     let timer_item_model = TimerItemModel {
         id: 0, // Will be auto-generated
@@ -214,8 +229,8 @@ pub async fn create_timer_item(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, timer_item_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, timer_item_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteTimerItem
@@ -235,11 +250,12 @@ pub async fn create_timer_item(
 )]
 pub async fn delete_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = TimerItemService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusTimerItem
@@ -259,9 +275,10 @@ pub async fn delete_timer_item(
 )]
 pub async fn status_timer_item(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerItemModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerItemModel>>, ApiError> {
     let service = TimerItemService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
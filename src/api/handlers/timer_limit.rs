@@ -6,15 +6,19 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::timer_limit::Model as TimerLimitModel, logics::general::ModelOutput, AppState};
 use crate::api::services::timer_limit::TimerLimitService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+use crate::engine::limit::ActiveTimerLimit;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -61,6 +65,8 @@ pub struct UpdateTimerLimitRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of timer limits to return"),
         ("offset" = Option<i32>, Query, description = "Number of timer limits to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of timer limits retrieved successfully", body = Vec<TimerLimitModel>),
@@ -69,11 +75,12 @@ pub struct UpdateTimerLimitRequest {
 )]
 pub async fn list_timer_limits(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<TimerLimitModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<TimerLimitModel>>>, ApiError> {
     let service = TimerLimitService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetTimerLimit
@@ -93,11 +100,12 @@ pub async fn list_timer_limits(
 )]
 pub async fn get_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerLimitModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerLimitModel>>, ApiError> {
     let service = TimerLimitService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableTimerLimit
@@ -117,11 +125,12 @@ pub async fn get_timer_limit(
 )]
 pub async fn enable_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerLimitModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerLimitModel>>, ApiError> {
     let service = TimerLimitService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, &state.limits, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableTimerLimit
@@ -141,11 +150,12 @@ pub async fn enable_timer_limit(
 )]
 pub async fn disable_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerLimitModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerLimitModel>>, ApiError> {
     let service = TimerLimitService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, &state.limits, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateTimerLimit
@@ -162,14 +172,17 @@ pub async fn disable_timer_limit(
         (status = 200, description = "Timer limit updated successfully", body = TimerLimitModel),
         (status = 404, description = "Timer limit not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateTimerLimitRequest>,
-) -> Result<Json<ModelOutput<TimerLimitModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerLimitModel>>, ApiError> {
     let service = TimerLimitService::new();
     
     let timer_limit_model = TimerLimitModel {
@@ -182,8 +195,8 @@ pub async fn update_timer_limit(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, timer_limit_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, &state.limits, timer_limit_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateTimerLimit
@@ -196,13 +209,16 @@ pub async fn update_timer_limit(
     responses(
         (status = 201, description = "Timer limit created successfully", body = TimerLimitModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateTimerLimitRequest>,
-) -> Result<Json<ModelOutput<TimerLimitModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<TimerLimitModel>>), ApiError> {
     let service = TimerLimitService::new();
     let timer_limit_model = TimerLimitModel {
         id: 0, // Will be auto-generated
@@ -214,8 +230,8 @@ pub async fn create_timer_limit(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, timer_limit_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, &state.limits, timer_limit_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteTimerLimit
@@ -235,11 +251,12 @@ pub async fn create_timer_limit(
 )]
 pub async fn delete_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = TimerLimitService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, &state.limits, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusTimerLimit
@@ -259,9 +276,30 @@ pub async fn delete_timer_limit(
 )]
 pub async fn status_timer_limit(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<TimerLimitModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<TimerLimitModel>>, ApiError> {
     let service = TimerLimitService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, &state.limits, id).await;
+    result.into_json()
+}
+
+//------------------------- ActiveTimerLimits
+#[utoipa::path(
+    get,
+    path = "/timer_limit/active",
+    tag = "⏰⚡ Timer Limit",
+
+    responses(
+        (status = 200, description = "Running timer limit countdowns with remaining time", body = Vec<ActiveTimerLimit>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn active_timer_limits(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+) -> Result<Json<ModelOutput<Vec<ActiveTimerLimit>>>, ApiError> {
+    let service = TimerLimitService::new();
+    let result = service.active(&state.db, &access, &state.limits).await;
+    result.into_json()
 }
@@ -6,15 +6,21 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::user::Model as UserModel, logics::general::ModelOutput, AppState};
+use crate::orm::models::{user_role::Model as UserRoleModel, user_zone::Model as UserZoneModel};
 use crate::api::services::user::UserService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+use crate::api::auth::{Token, UserKey};
+use crate::telegram::TelegramLink;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -26,8 +32,6 @@ pub struct CreateUserRequest {
     pub username: String,
     #[schema(example = "secure_password")]
     pub password: String,
-    #[schema(example = "api_key_123")]
-    pub key: String,
     #[schema(example = "john@example.com")]
     pub email: String,
     #[schema(example = "+1234567890")]
@@ -45,10 +49,9 @@ pub struct UpdateUserRequest {
     pub name: Option<String>,
     #[schema(example = "johndoe")]
     pub username: Option<String>,
+    /// Leave empty to keep the current password
     #[schema(example = "secure_password")]
     pub password: Option<String>,
-    #[schema(example = "api_key_123")]
-    pub key: Option<String>,
     #[schema(example = "john@example.com")]
     pub email: Option<String>,
     #[schema(example = "+1234567890")]
@@ -59,7 +62,55 @@ pub struct UpdateUserRequest {
     pub enable: Option<bool>,
 }
 
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for logging in")]
+pub struct LoginRequest {
+    #[schema(example = "johndoe")]
+    pub username: String,
+    #[schema(example = "secure_password")]
+    pub password: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for setting a user's role")]
+pub struct UpdateUserRoleRequest {
+    #[schema(example = "Operator")]
+    pub role: String,
+    /// Grant every zone; left as it was when omitted, false for a user without a role yet
+    #[schema(example = false)]
+    pub all_zones: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for granting a zone to a user")]
+pub struct GrantUserZoneRequest {
+    #[schema(example = 1)]
+    pub zone_id: i32,
+}
+
 //--------------------------------------------------------------------------------- Handlers
+//------------------------- LoginUser
+#[utoipa::path(
+    post,
+    path = "/user/login",
+    tag = "👥 User",
+    request_body = LoginRequest,
+    security(()),
+    responses(
+        (status = 200, description = "Login successful, returns a signed token", body = Token),
+        (status = 401, description = "Invalid username or password"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn login_user(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<ModelOutput<Token>>, ApiError> {
+    let service = UserService::new();
+    let result = service.login(&state.db, &state.auth, &payload.username, &payload.password).await;
+    result.into_json()
+}
+
 //------------------------- ListUsers
 #[utoipa::path(
     get,
@@ -68,6 +119,8 @@ pub struct UpdateUserRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of users to return"),
         ("offset" = Option<i32>, Query, description = "Number of users to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of users retrieved successfully", body = Vec<UserModel>),
@@ -76,11 +129,12 @@ pub struct UpdateUserRequest {
 )]
 pub async fn list_users(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<UserModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<UserModel>>>, ApiError> {
     let service = UserService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetUser
@@ -99,11 +153,12 @@ pub async fn list_users(
 )]
 pub async fn get_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<UserModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<UserModel>>, ApiError> {
     let service = UserService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableUser
@@ -122,11 +177,12 @@ pub async fn get_user(
 )]
 pub async fn enable_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<UserModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<UserModel>>, ApiError> {
     let service = UserService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableUser
@@ -145,11 +201,12 @@ pub async fn enable_user(
 )]
 pub async fn disable_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<UserModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<UserModel>>, ApiError> {
     let service = UserService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateUser
@@ -165,14 +222,17 @@ pub async fn disable_user(
         (status = 200, description = "User updated successfully", body = UserModel),
         (status = 404, description = "User not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateUserRequest>,
-) -> Result<Json<ModelOutput<UserModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<UserModel>>, ApiError> {
     let service = UserService::new();
     
     let user_model = UserModel {
@@ -180,15 +240,15 @@ pub async fn update_user(
         name: payload.name.unwrap_or_default(),
         username: payload.username.unwrap_or_default(),
         password: payload.password.unwrap_or_default(),
-        key: payload.key.unwrap_or_default(),
+        key: String::new(),
         email: payload.email.unwrap_or_default(),
         phone: payload.phone.unwrap_or_default(),
         tg_id: payload.tg_id.unwrap_or_default(),
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, user_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, user_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateUser
@@ -198,30 +258,83 @@ pub async fn update_user(
     tag = "👥 User",
     request_body = CreateUserRequest,
     responses(
-        (status = 201, description = "User created successfully", body = UserModel),
+        (status = 201, description = "User created successfully, with its key", body = UserKey),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateUserRequest>,
-) -> Result<Json<ModelOutput<UserModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<UserKey>>), ApiError> {
     let service = UserService::new();
     let user_model = UserModel {
         id: 0, // Will be auto-generated
         name: payload.name,
         username: payload.username,
         password: payload.password,
-        key: payload.key,
+        key: String::new(), // Generated by the service
         email: payload.email,
         phone: payload.phone,
         tg_id: payload.tg_id,
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, user_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, user_model).await;
+    result.into_created()
+}
+
+//------------------------- RotateUserKey
+#[utoipa::path(
+    post,
+    path = "/user/key/{id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID whose key is replaced")
+    ),
+    responses(
+        (status = 200, description = "User key rotated successfully, with the new key", body = UserKey),
+        (status = 403, description = "Not allowed for this role"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn rotate_user_key(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<UserKey>>, ApiError> {
+    let service = UserService::new();
+    let result = service.rotate_key(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- TelegramLinkUser
+#[utoipa::path(
+    post,
+    path = "/user/telegram/{id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID to link a Telegram account to")
+    ),
+    responses(
+        (status = 200, description = "Telegram link code created successfully", body = TelegramLink),
+        (status = 403, description = "Not allowed for this role"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn telegram_link_user(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<TelegramLink>>, ApiError> {
+    let service = UserService::new();
+    let result = service.telegram_link(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DeleteUser
@@ -240,11 +353,12 @@ pub async fn create_user(
 )]
 pub async fn delete_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = UserService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusUser
@@ -264,9 +378,141 @@ pub async fn delete_user(
 )]
 pub async fn status_user(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<UserModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<UserModel>>, ApiError> {
+    let service = UserService::new();
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- GetUserRole
+#[utoipa::path(
+    get,
+    path = "/user/role/{id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User role retrieved successfully", body = UserRoleModel),
+        (status = 403, description = "Not allowed for this role"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_user_role(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<UserRoleModel>>, ApiError> {
+    let service = UserService::new();
+    let result = service.role(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- UpdateUserRole
+#[utoipa::path(
+    put,
+    path = "/user/role/{id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    request_body = UpdateUserRoleRequest,
+    responses(
+        (status = 200, description = "User role updated successfully", body = UserRoleModel),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User not found"),
+        (status = 422, description = "Invalid role"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn update_user_role(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateUserRoleRequest>,
+) -> Result<Json<ModelOutput<UserRoleModel>>, ApiError> {
+    let service = UserService::new();
+    let result = service.set_role(&state.db, &access, id, &payload.role, payload.all_zones).await;
+    result.into_json()
+}
+
+//------------------------- ListUserZones
+#[utoipa::path(
+    get,
+    path = "/user/zones/{id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Zone grants retrieved successfully", body = Vec<UserZoneModel>),
+        (status = 403, description = "Not allowed for this role"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_user_zones(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<Vec<UserZoneModel>>>, ApiError> {
+    let service = UserService::new();
+    let result = service.zones(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- GrantUserZone
+#[utoipa::path(
+    post,
+    path = "/user/zones/{id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    request_body = GrantUserZoneRequest,
+    responses(
+        (status = 200, description = "Zone granted successfully", body = UserZoneModel),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User or zone not found"),
+        (status = 409, description = "Zone already granted"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn grant_user_zone(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+    Json(payload): Json<GrantUserZoneRequest>,
+) -> Result<Json<ModelOutput<UserZoneModel>>, ApiError> {
+    let service = UserService::new();
+    let result = service.grant_zone(&state.db, &access, id, payload.zone_id).await;
+    result.into_json()
+}
+
+//------------------------- RevokeUserZone
+#[utoipa::path(
+    delete,
+    path = "/user/zones/{id}/{zone_id}",
+    tag = "👥 User",
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        ("zone_id" = i32, Path, description = "Zone ID to revoke")
+    ),
+    responses(
+        (status = 200, description = "Zone grant deleted successfully"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Zone grant not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn revoke_user_zone(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path((id, zone_id)): Path<(i32, i32)>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = UserService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.revoke_zone(&state.db, &access, id, zone_id).await;
+    result.into_json()
 }
@@ -0,0 +1,293 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/handlers/webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// Axum handlers for Webhook CRUD operations
+
+//--------------------------------------------------------------------------------- Import
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Extension,
+};
+use crate::api::extract::{Json, Path, Query};
+use serde::Deserialize;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use crate::{orm::models::webhook::Model as WebhookModel, logics::general::ModelOutput, AppState};
+use crate::api::services::webhook::WebhookService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+
+//--------------------------------------------------------------------------------- Request DTOs
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for creating a new webhook")]
+pub struct CreateWebhookRequest {
+    #[schema(example = 1)]
+    pub user_id: i32,
+    #[schema(example = "Home server")]
+    pub name: String,
+    #[schema(example = "https://example.com/hooks/iot")]
+    pub url: String,
+    #[schema(example = "change-me")]
+    pub secret: String,
+    #[schema(example = "device_changed,rule_fired,limit_reached,log_error")]
+    pub events: String,
+    #[schema(example = "Device and rule events for the home server")]
+    pub description: String,
+    #[schema(example = true)]
+    pub enable: bool,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[schema(description = "Request payload for updating an existing webhook, an empty or missing secret keeps the current one")]
+pub struct UpdateWebhookRequest {
+    #[schema(example = 1)]
+    pub user_id: Option<i32>,
+    #[schema(example = "Home server")]
+    pub name: Option<String>,
+    #[schema(example = "https://example.com/hooks/iot")]
+    pub url: Option<String>,
+    #[schema(example = "change-me")]
+    pub secret: Option<String>,
+    #[schema(example = "*")]
+    pub events: Option<String>,
+    #[schema(example = "Device and rule events for the home server")]
+    pub description: Option<String>,
+    #[schema(example = true)]
+    pub enable: Option<bool>,
+}
+
+//--------------------------------------------------------------------------------- Handlers
+//------------------------- ListWebhooks
+#[utoipa::path(
+    get,
+    path = "/webhook/items",
+    tag = "🪝 Webhook",
+
+    params(
+        ("limit" = Option<i32>, Query, description = "Maximum number of webhooks to return"),
+        ("offset" = Option<i32>, Query, description = "Number of webhooks to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
+    ),
+    responses(
+        (status = 200, description = "List of webhooks retrieved successfully", body = Vec<WebhookModel>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<Vec<WebhookModel>>>, ApiError> {
+    let service = WebhookService::new();
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
+}
+
+//------------------------- GetWebhook
+#[utoipa::path(
+    get,
+    path = "/webhook/item/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook retrieved successfully", body = WebhookModel),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<WebhookModel>>, ApiError> {
+    let service = WebhookService::new();
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- EnableWebhook
+#[utoipa::path(
+    get,
+    path = "/webhook/enable/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook ID to enable")
+    ),
+    responses(
+        (status = 200, description = "Webhook enabled successfully", body = WebhookModel),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn enable_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<WebhookModel>>, ApiError> {
+    let service = WebhookService::new();
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- DisableWebhook
+#[utoipa::path(
+    get,
+    path = "/webhook/disable/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook ID to disable")
+    ),
+    responses(
+        (status = 200, description = "Webhook disabled successfully", body = WebhookModel),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn disable_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<WebhookModel>>, ApiError> {
+    let service = WebhookService::new();
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- UpdateWebhook
+#[utoipa::path(
+    put,
+    path = "/webhook/update/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook ID to update")
+    ),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated successfully", body = WebhookModel),
+        (status = 404, description = "Webhook not found"),
+        (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<ModelOutput<WebhookModel>>, ApiError> {
+    let service = WebhookService::new();
+    
+    let webhook_model = WebhookModel {
+        id,
+        user_id: payload.user_id.unwrap_or_default(),
+        name: payload.name.unwrap_or_default(),
+        url: payload.url.unwrap_or_default(),
+        secret: payload.secret.unwrap_or_default(),
+        events: payload.events.unwrap_or_default(),
+        description: payload.description.unwrap_or_default(),
+        enable: payload.enable.unwrap_or(true),
+    };
+    
+    let result = service.update(&state.db, &access, webhook_model).await;
+    result.into_json()
+}
+
+//------------------------- CreateWebhook
+#[utoipa::path(
+    post,
+    path = "/webhook/add",
+    tag = "🪝 Webhook",
+
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created successfully", body = WebhookModel),
+        (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<ModelOutput<WebhookModel>>), ApiError> {
+    let service = WebhookService::new();
+    let webhook_model = WebhookModel {
+        id: 0, // Will be auto-generated
+        user_id: payload.user_id,
+        name: payload.name,
+        url: payload.url,
+        secret: payload.secret,
+        events: payload.events,
+        description: payload.description,
+        enable: payload.enable,
+    };
+    
+    let result = service.add(&state.db, &access, webhook_model).await;
+    result.into_created()
+}
+
+//------------------------- DeleteWebhook
+#[utoipa::path(
+    delete,
+    path = "/webhook/delete/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete its deliveries (default: false)"),
+    ),
+    responses(
+        (status = 200, description = "Webhook deleted successfully"),
+        (status = 404, description = "Webhook not found"),
+        (status = 409, description = "Still referenced by other rows"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
+    let service = WebhookService::new();
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
+}
+
+//------------------------- StatusWebhook
+#[utoipa::path(
+    get,
+    path = "/webhook/status/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook ID to toggle status")
+    ),
+    responses(
+        (status = 200, description = "Webhook status toggled successfully", body = WebhookModel),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn status_webhook(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<WebhookModel>>, ApiError> {
+    let service = WebhookService::new();
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
+}
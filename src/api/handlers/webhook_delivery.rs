@@ -0,0 +1,124 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/handlers/webhook_delivery.rs
+
+//--------------------------------------------------------------------------------- Description
+// Axum handlers for the webhook delivery log: list, inspect, delete and replay
+
+//--------------------------------------------------------------------------------- Import
+use axum::{
+    extract::State,
+    Extension,
+};
+use crate::api::extract::{Json, Path, Query};
+use std::collections::HashMap;
+use crate::{orm::models::webhook_delivery::Model as WebhookDeliveryModel, logics::general::ModelOutput, AppState};
+use crate::api::services::webhook_delivery::WebhookDeliveryService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+
+//--------------------------------------------------------------------------------- Handlers
+//------------------------- ListWebhookDeliveries
+#[utoipa::path(
+    get,
+    path = "/webhook_delivery/items",
+    tag = "🪝 Webhook",
+
+    params(
+        ("webhook_id" = Option<i32>, Query, description = "Deliveries of this webhook"),
+        ("status" = Option<String>, Query, description = "pending, delivered or failed"),
+        ("event" = Option<String>, Query, description = "Event name, such as device_changed"),
+        ("limit" = Option<i32>, Query, description = "Maximum number of deliveries to return"),
+        ("offset" = Option<i32>, Query, description = "Number of deliveries to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
+    ),
+    responses(
+        (status = 200, description = "List of webhook deliveries retrieved successfully", body = Vec<WebhookDeliveryModel>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<Vec<WebhookDeliveryModel>>>, ApiError> {
+    let service = WebhookDeliveryService::new();
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
+}
+
+//------------------------- GetWebhookDelivery
+#[utoipa::path(
+    get,
+    path = "/webhook_delivery/item/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery retrieved successfully", body = WebhookDeliveryModel),
+        (status = 404, description = "Webhook delivery not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_webhook_delivery(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<WebhookDeliveryModel>>, ApiError> {
+    let service = WebhookDeliveryService::new();
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- ReplayWebhookDelivery
+#[utoipa::path(
+    post,
+    path = "/webhook_delivery/replay/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook delivery ID to send again")
+    ),
+    responses(
+        (status = 200, description = "Delivery queued again with its original payload", body = WebhookDeliveryModel),
+        (status = 404, description = "Webhook delivery not found"),
+        (status = 409, description = "Delivery is still being sent"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn replay_webhook_delivery(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<WebhookDeliveryModel>>, ApiError> {
+    let service = WebhookDeliveryService::new();
+    let result = service.replay(&state.db, &access, &state.webhooks, id).await;
+    result.into_json()
+}
+
+//------------------------- DeleteWebhookDelivery
+#[utoipa::path(
+    delete,
+    path = "/webhook_delivery/delete/{id}",
+    tag = "🪝 Webhook",
+
+    params(
+        ("id" = i32, Path, description = "Webhook delivery ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery deleted successfully"),
+        (status = 404, description = "Webhook delivery not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_webhook_delivery(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
+    let service = WebhookDeliveryService::new();
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
+}
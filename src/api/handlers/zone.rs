@@ -6,15 +6,19 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::zone::Model as ZoneModel, logics::general::ModelOutput, AppState};
 use crate::api::services::zone::ZoneService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
+use crate::orm::logics::graph::ZoneGraph;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -53,6 +57,8 @@ pub struct UpdateZoneRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of zones to return"),
         ("offset" = Option<i32>, Query, description = "Number of zones to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of zones retrieved successfully", body = Vec<ZoneModel>),
@@ -61,11 +67,12 @@ pub struct UpdateZoneRequest {
 )]
 pub async fn list_zones(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<ZoneModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<ZoneModel>>>, ApiError> {
     let service = ZoneService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetZone
@@ -74,21 +81,25 @@ pub async fn list_zones(
     path = "/zone/item/{id}",
     tag = "🏠 Zone",
     params(
-        ("id" = i32, Path, description = "Zone ID")
+        ("id" = i32, Path, description = "Zone ID"),
+        ("expand" = Option<String>, Query, description = "Comma separated parts to include: devices, commands, rules, timers"),
     ),
     responses(
-        (status = 200, description = "Zone retrieved successfully", body = ZoneModel),
+        (status = 200, description = "Zone retrieved successfully", body = ZoneGraph),
         (status = 404, description = "Zone not found"),
+        (status = 422, description = "Invalid expand value"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneModel>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<ZoneGraph>>, ApiError> {
     let service = ZoneService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id, params.get("expand").map(String::as_str)).await;
+    result.into_json()
 }
 
 //------------------------- EnableZone
@@ -108,11 +119,12 @@ pub async fn get_zone(
 )]
 pub async fn enable_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneModel>>, ApiError> {
     let service = ZoneService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableZone
@@ -132,11 +144,12 @@ pub async fn enable_zone(
 )]
 pub async fn disable_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneModel>>, ApiError> {
     let service = ZoneService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusZone
@@ -155,11 +168,12 @@ pub async fn disable_zone(
 )]
 pub async fn status_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneModel>>, ApiError> {
     let service = ZoneService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateZone
@@ -175,14 +189,17 @@ pub async fn status_zone(
         (status = 200, description = "Zone updated successfully", body = ZoneModel),
         (status = 404, description = "Zone not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateZoneRequest>,
-) -> Result<Json<ModelOutput<ZoneModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneModel>>, ApiError> {
     let service = ZoneService::new();
     
     let zone_model = ZoneModel {
@@ -193,8 +210,8 @@ pub async fn update_zone(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, zone_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, zone_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateZone
@@ -207,13 +224,16 @@ pub async fn update_zone(
     responses(
         (status = 201, description = "Zone created successfully", body = ZoneModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateZoneRequest>,
-) -> Result<Json<ModelOutput<ZoneModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<ZoneModel>>), ApiError> {
     let service = ZoneService::new();
     let zone_model = ZoneModel {
         id: 0, // Will be auto-generated
@@ -223,8 +243,8 @@ pub async fn create_zone(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, zone_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, zone_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteZone
@@ -234,19 +254,23 @@ pub async fn create_zone(
     tag = "🏠 Zone",
 
     params(
-        ("id" = i32, Path, description = "Zone ID to delete")
+        ("id" = i32, Path, description = "Zone ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete or reset the rows that reference it (default: false)"),
     ),
     responses(
         (status = 200, description = "Zone deleted successfully"),
         (status = 404, description = "Zone not found"),
+        (status = 409, description = "Still referenced by other rows"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_zone(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = ZoneService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
 }
@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::zone_command::Model as ZoneCommandModel, logics::general::ModelOutput, AppState};
 use crate::api::services::zone_command::ZoneCommandService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -53,6 +56,8 @@ pub struct UpdateZoneCommandRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of zone commands to return"),
         ("offset" = Option<i32>, Query, description = "Number of zone commands to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of zone commands retrieved successfully", body = Vec<ZoneCommandModel>),
@@ -61,11 +66,12 @@ pub struct UpdateZoneCommandRequest {
 )]
 pub async fn list_zone_commands(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<ZoneCommandModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<ZoneCommandModel>>>, ApiError> {
     let service = ZoneCommandService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetZoneCommand
@@ -85,11 +91,12 @@ pub async fn list_zone_commands(
 )]
 pub async fn get_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandModel>>, ApiError> {
     let service = ZoneCommandService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableZoneCommand
@@ -109,11 +116,12 @@ pub async fn get_zone_command(
 )]
 pub async fn enable_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandModel>>, ApiError> {
     let service = ZoneCommandService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableZoneCommand
@@ -133,11 +141,12 @@ pub async fn enable_zone_command(
 )]
 pub async fn disable_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandModel>>, ApiError> {
     let service = ZoneCommandService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateZoneCommand
@@ -154,14 +163,17 @@ pub async fn disable_zone_command(
         (status = 200, description = "Zone command updated successfully", body = ZoneCommandModel),
         (status = 404, description = "Zone command not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateZoneCommandRequest>,
-) -> Result<Json<ModelOutput<ZoneCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandModel>>, ApiError> {
     let service = ZoneCommandService::new();
     
     let zone_command_model = ZoneCommandModel {
@@ -172,8 +184,8 @@ pub async fn update_zone_command(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, zone_command_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, zone_command_model).await;
+    result.into_json()
 }
 
 //------------------------- CreateZoneCommand
@@ -186,13 +198,16 @@ pub async fn update_zone_command(
     responses(
         (status = 201, description = "Zone command created successfully", body = ZoneCommandModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateZoneCommandRequest>,
-) -> Result<Json<ModelOutput<ZoneCommandModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<ZoneCommandModel>>), ApiError> {
     let service = ZoneCommandService::new();
     let zone_command_model = ZoneCommandModel {
         id: 0, // Will be auto-generated
@@ -202,8 +217,8 @@ pub async fn create_zone_command(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, zone_command_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, zone_command_model).await;
+    result.into_created()
 }
 
 //------------------------- DeleteZoneCommand
@@ -213,21 +228,25 @@ pub async fn create_zone_command(
     tag = "🎯 Zone Command",
 
     params(
-        ("id" = i32, Path, description = "Zone command ID to delete")
+        ("id" = i32, Path, description = "Zone command ID to delete"),
+        ("cascade" = Option<bool>, Query, description = "Also delete or reset the rows that reference it (default: false)"),
     ),
     responses(
         (status = 200, description = "Zone command deleted successfully"),
         (status = 404, description = "Zone command not found"),
+        (status = 409, description = "Still referenced by other rows"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = ZoneCommandService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id, params.get("cascade").is_some_and(|value| value == "true")).await;
+    result.into_json()
 }
 
 //------------------------- StatusZoneCommand
@@ -247,9 +266,36 @@ pub async fn delete_zone_command(
 )]
 pub async fn status_zone_command(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Path(id): Path<i32>,
+) -> Result<Json<ModelOutput<ZoneCommandModel>>, ApiError> {
+    let service = ZoneCommandService::new();
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
+}
+
+//------------------------- RunZoneCommand
+#[utoipa::path(
+    post,
+    path = "/zone_command/run/{id}",
+    tag = "🏠🔧 Zone Command",
+
+    params(
+        ("id" = i32, Path, description = "Zone Command ID to run")
+    ),
+    responses(
+        (status = 200, description = "Zone Command actions executed successfully", body = ZoneCommandModel),
+        (status = 404, description = "Zone Command not found"),
+        (status = 422, description = "An action device or command is disabled"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn run_zone_command(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandModel>>, ApiError> {
     let service = ZoneCommandService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.run(&state.db, &access, &state.rules, id).await;
+    result.into_json()
 }
@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::zone_command_action::Model as ZoneCommandActionModel, logics::general::ModelOutput, AppState};
 use crate::api::services::zone_command_action::ZoneCommandActionService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -61,6 +64,8 @@ pub struct UpdateZoneCommandActionRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of zone command actions to return"),
         ("offset" = Option<i32>, Query, description = "Number of zone command actions to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of zone command actions retrieved successfully", body = Vec<ZoneCommandActionModel>),
@@ -69,11 +74,12 @@ pub struct UpdateZoneCommandActionRequest {
 )]
 pub async fn list_zone_command_actions(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<ZoneCommandActionModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<ZoneCommandActionModel>>>, ApiError> {
     let service = ZoneCommandActionService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 //------------------------- GetZoneCommandAction
@@ -93,11 +99,12 @@ pub async fn list_zone_command_actions(
 )]
 pub async fn get_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, ApiError> {
     let service = ZoneCommandActionService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- EnableZoneCommandAction
@@ -117,11 +124,12 @@ pub async fn get_zone_command_action(
 )]
 pub async fn enable_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, ApiError> {
     let service = ZoneCommandActionService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- DisableZoneCommandAction
@@ -141,11 +149,12 @@ pub async fn enable_zone_command_action(
 )]
 pub async fn disable_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, ApiError> {
     let service = ZoneCommandActionService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- UpdateZoneCommandAction
@@ -162,14 +171,17 @@ pub async fn disable_zone_command_action(
         (status = 200, description = "Zone command action updated successfully", body = ZoneCommandActionModel),
         (status = 404, description = "Zone command action not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateZoneCommandActionRequest>,
-) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, ApiError> {
     let service = ZoneCommandActionService::new();
     
     let zone_command_action_model = ZoneCommandActionModel {
@@ -182,8 +194,8 @@ pub async fn update_zone_command_action(
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, zone_command_action_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, zone_command_action_model).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -195,13 +207,16 @@ pub async fn update_zone_command_action(
     responses(
         (status = 201, description = "Zone command action created successfully", body = ZoneCommandActionModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateZoneCommandActionRequest>,
-) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<ZoneCommandActionModel>>), ApiError> {
     let service = ZoneCommandActionService::new();
     let zone_command_action_model = ZoneCommandActionModel {
         id: 0, // Will be auto-generated
@@ -213,8 +228,8 @@ pub async fn create_zone_command_action(
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, zone_command_action_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, zone_command_action_model).await;
+    result.into_created()
 }
 
 #[utoipa::path(
@@ -233,11 +248,12 @@ pub async fn create_zone_command_action(
 )]
 pub async fn delete_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = ZoneCommandActionService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusZoneCommandAction
@@ -257,9 +273,10 @@ pub async fn delete_zone_command_action(
 )]
 pub async fn status_zone_command_action(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandActionModel>>, ApiError> {
     let service = ZoneCommandActionService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
@@ -6,15 +6,18 @@
 
 //--------------------------------------------------------------------------------- Import
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
-    Json,
+    Extension,
 };
+use crate::api::extract::{Json, Path, Query};
 use serde::Deserialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use crate::{orm::models::zone_command_if::Model as ZoneCommandIfModel, logics::general::ModelOutput, AppState};
 use crate::api::services::zone_command_if::ZoneCommandIfService;
+use crate::api::error::ApiError;
+use crate::api::access::Access;
 
 //--------------------------------------------------------------------------------- Request DTOs
 #[derive(Deserialize, ToSchema)]
@@ -28,6 +31,8 @@ pub struct CreateZoneCommandIfRequest {
     pub device_id: i32,
     #[schema(example = 1)]
     pub command_id: i32,
+    #[schema(example = "Equal")]
+    pub r#type: String,
     #[schema(example = "Check temperature condition")]
     pub description: String,
     #[schema(example = true)]
@@ -45,6 +50,8 @@ pub struct UpdateZoneCommandIfRequest {
     pub device_id: Option<i32>,
     #[schema(example = 1)]
     pub command_id: Option<i32>,
+    #[schema(example = "Equal")]
+    pub r#type: Option<String>,
     #[schema(example = "Check temperature condition")]
     pub description: Option<String>,
     #[schema(example = true)]
@@ -60,6 +67,8 @@ pub struct UpdateZoneCommandIfRequest {
     params(
         ("limit" = Option<i32>, Query, description = "Maximum number of zone command conditions to return"),
         ("offset" = Option<i32>, Query, description = "Number of zone command conditions to skip"),
+        ("sort" = Option<String>, Query, description = "Column to sort by (default: id)"),
+        ("order" = Option<String>, Query, description = "Sort order: asc or desc (default: asc)"),
     ),
     responses(
         (status = 200, description = "List of zone command conditions retrieved successfully", body = Vec<ZoneCommandIfModel>),
@@ -68,11 +77,12 @@ pub struct UpdateZoneCommandIfRequest {
 )]
 pub async fn list_zone_command_ifs(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<ModelOutput<Vec<ZoneCommandIfModel>>>, StatusCode> {
+) -> Result<Json<ModelOutput<Vec<ZoneCommandIfModel>>>, ApiError> {
     let service = ZoneCommandIfService::new();
-    let result = service.items(&state.db, params).await;
-    Ok(Json(result))
+    let result = service.items(&state.db, &access, params).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -91,11 +101,12 @@ pub async fn list_zone_command_ifs(
 )]
 pub async fn get_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, ApiError> {
     let service = ZoneCommandIfService::new();
-    let result = service.item(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.item(&state.db, &access, id).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -114,11 +125,12 @@ pub async fn get_zone_command_if(
 )]
 pub async fn enable_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, ApiError> {
     let service = ZoneCommandIfService::new();
-    let result = service.enable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.enable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -137,11 +149,12 @@ pub async fn enable_zone_command_if(
 )]
 pub async fn disable_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, ApiError> {
     let service = ZoneCommandIfService::new();
-    let result = service.disable(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.disable(&state.db, &access, id).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -157,14 +170,17 @@ pub async fn disable_zone_command_if(
         (status = 200, description = "Zone command condition updated successfully", body = ZoneCommandIfModel),
         (status = 404, description = "Zone command condition not found"),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
     Json(payload): Json<UpdateZoneCommandIfRequest>,
-) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, ApiError> {
     let service = ZoneCommandIfService::new();
     
     let zone_command_if_model = ZoneCommandIfModel {
@@ -173,12 +189,13 @@ pub async fn update_zone_command_if(
         zone_command_id: payload.zone_command_id.unwrap_or_default(),
         device_id: payload.device_id.unwrap_or_default(),
         command_id: payload.command_id.unwrap_or_default(),
+        r#type: payload.r#type.unwrap_or_default(),
         description: payload.description.unwrap_or_default(),
         enable: payload.enable.unwrap_or(true),
     };
     
-    let result = service.update(&state.db, zone_command_if_model).await;
-    Ok(Json(result))
+    let result = service.update(&state.db, &access, zone_command_if_model).await;
+    result.into_json()
 }
 
 #[utoipa::path(
@@ -190,13 +207,16 @@ pub async fn update_zone_command_if(
     responses(
         (status = 201, description = "Zone command condition created successfully", body = ZoneCommandIfModel),
         (status = 400, description = "Invalid request data"),
+        (status = 409, description = "Conflicts with an existing record"),
+        (status = 422, description = "Validation failed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Json(payload): Json<CreateZoneCommandIfRequest>,
-) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, StatusCode> {
+) -> Result<(StatusCode, Json<ModelOutput<ZoneCommandIfModel>>), ApiError> {
     let service = ZoneCommandIfService::new();
     let zone_command_if_model = ZoneCommandIfModel {
         id: 0, // Will be auto-generated
@@ -204,12 +224,13 @@ pub async fn create_zone_command_if(
         zone_command_id: payload.zone_command_id,
         device_id: payload.device_id,
         command_id: payload.command_id,
+        r#type: payload.r#type,
         description: payload.description,
         enable: payload.enable,
     };
     
-    let result = service.add(&state.db, zone_command_if_model).await;
-    Ok(Json(result))
+    let result = service.add(&state.db, &access, zone_command_if_model).await;
+    result.into_created()
 }
 
 #[utoipa::path(
@@ -228,11 +249,12 @@ pub async fn create_zone_command_if(
 )]
 pub async fn delete_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<String>>, StatusCode> {
+) -> Result<Json<ModelOutput<String>>, ApiError> {
     let service = ZoneCommandIfService::new();
-    let result = service.delete(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.delete(&state.db, &access, id).await;
+    result.into_json()
 }
 
 //------------------------- StatusZoneCommandIf
@@ -252,9 +274,10 @@ pub async fn delete_zone_command_if(
 )]
 pub async fn status_zone_command_if(
     State(state): State<AppState>,
+    Extension(access): Extension<Access>,
     Path(id): Path<i32>,
-) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, StatusCode> {
+) -> Result<Json<ModelOutput<ZoneCommandIfModel>>, ApiError> {
     let service = ZoneCommandIfService::new();
-    let result = service.status(&state.db, id).await;
-    Ok(Json(result))
+    let result = service.status(&state.db, &access, id).await;
+    result.into_json()
 }
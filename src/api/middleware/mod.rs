@@ -5,8 +5,23 @@
 // Middleware configuration
 
 //--------------------------------------------------------------------------------- Import
-use axum::{http::Request, middleware::Next, response::Response};
+use axum::{extract::State, http::{header, Request}, middleware::Next, response::Response};
 use std::time::Instant;
+use crate::api::access::Access;
+use crate::api::auth::CurrentUser;
+use crate::api::error::ApiError;
+use crate::orm::logics::user::UserORM;
+use crate::AppState;
+
+//--------------------------------------------------------------------------------- Constants
+/// Header carrying a user's `key` as an API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Routes that may carry credentials in the query string (?token= or ?key=), browsers cannot set headers on a WebSocket
+const QUERY_CREDENTIAL_PATHS: [&str; 1] = ["/ws"];
+
+/// Routes reachable without credentials
+const PUBLIC_PATHS: [&str; 4] = ["/user/login", "/doc", "/api-doc", "/graphql/playground"];
 
 //--------------------------------------------------------------------------------- Middleware
 pub async fn logging_middleware(
@@ -32,3 +47,74 @@ pub async fn logging_middleware(
     
     response
 }
+
+//------------------------- Auth
+/// Require a bearer token from /user/login or a user's key in the X-API-Key header,
+/// then attach the user's role and zone grants for the services
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request<axum::body::Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !state.auth.enabled {
+        request.extensions_mut().insert(Access::unrestricted());
+        return Ok(next.run(request).await);
+    }
+
+    let path = request.uri().path();
+    if PUBLIC_PATHS.iter().any(|public| path == *public || path.starts_with(&format!("{}/", public))) {
+        return Ok(next.run(request).await);
+    }
+
+    let headers = request.headers();
+    let query = match QUERY_CREDENTIAL_PATHS.contains(&path) {
+        true => request.uri().query().unwrap_or_default(),
+        false => "",
+    };
+    let bearer = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+        .or_else(|| query_value(query, "token"));
+    let api_key = headers.get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|key| key.trim().to_string())
+        .or_else(|| query_value(query, "key"))
+        .filter(|key| !key.is_empty());
+
+    let orm = UserORM::new(false, false);
+    let user = match (bearer, api_key) {
+        (Some(token), _) => {
+            let claims = state.auth.verify(&token)?;
+            let user = orm.item(&state.db, claims.sub).await.into_result().map_err(|e| unauthorized(e, "User of this token no longer exists"))?;
+            if !user.enable {
+                return Err(ApiError::Unauthorized("User of this token is disabled".to_string()));
+            }
+            user
+        }
+        (None, Some(key)) => orm.item_by_key(&state.db, &key).await.into_result().map_err(|e| unauthorized(e, "Invalid API key"))?,
+        (None, None) => return Err(ApiError::Unauthorized("Missing bearer token or X-API-Key header".to_string())),
+    };
+
+    let access = Access::load(&state.db, &user).await?;
+    request.extensions_mut().insert(access);
+    request.extensions_mut().insert(CurrentUser(user));
+    Ok(next.run(request).await)
+}
+
+/// Raw value of a query string parameter; tokens and keys need no decoding
+fn query_value(query: &str, name: &str) -> Option<String> {
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Missing users are an authentication failure, database errors stay as they are
+fn unauthorized(error: ApiError, message: &str) -> ApiError {
+    match error {
+        ApiError::NotFound(_) => ApiError::Unauthorized(message.to_string()),
+        error => error,
+    }
+}
@@ -1,4 +1,10 @@
+pub mod access;
+pub mod auth;
+pub mod error;
+pub mod extract;
+pub mod graphql;
 pub mod handlers;
 pub mod middleware;
 pub mod routes;
 pub mod services;
+pub mod socket;
@@ -8,7 +8,7 @@
 use axum::routing::{get, post, put, delete};
 use axum::Router;
 use crate::AppState;
-use crate::api::handlers::device::{list_devices, get_device, create_device, update_device, delete_device, disable_device, enable_device, status_device};
+use crate::api::handlers::device::{list_devices, get_device, create_device, update_device, delete_device, disable_device, enable_device, status_device, execute_device};
 
 //--------------------------------------------------------------------------------- Router
 pub fn router() -> Router<AppState> 
@@ -19,6 +19,7 @@ pub fn router() -> Router<AppState>
         .route("/enable/{id}", get(enable_device))
         .route("/disable/{id}", get(disable_device))
         .route("/status/{id}", get(status_device))
+        .route("/execute/{device_id}/{command}", post(execute_device))
         .route("/update/{id}", put(update_device))
         .route("/add", post(create_device))
         .route("/delete/{id}", delete(delete_device))       
@@ -0,0 +1,25 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/routes/notification.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is route for notification
+
+//--------------------------------------------------------------------------------- Import
+use axum::routing::{get, post, put, delete};
+use axum::Router;
+use crate::AppState;
+use crate::api::handlers::notification::{list_notifications, get_notification, create_notification, update_notification, delete_notification, disable_notification, enable_notification, status_notification};
+
+//--------------------------------------------------------------------------------- Router
+pub fn router() -> Router<AppState> 
+{
+    Router::new()
+        .route("/items", get(list_notifications))
+        .route("/item/{id}", get(get_notification))
+        .route("/enable/{id}", get(enable_notification))
+        .route("/disable/{id}", get(disable_notification))
+        .route("/status/{id}", get(status_notification))
+        .route("/update/{id}", put(update_notification))
+        .route("/add", post(create_notification))
+        .route("/delete/{id}", delete(delete_notification))
+}
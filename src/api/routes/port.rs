@@ -8,7 +8,7 @@
 use axum::routing::{get, post, put, delete};
 use axum::Router;
 use crate::AppState;
-use crate::api::handlers::port::{list_ports, get_port, create_port, update_port, delete_port, disable_port, enable_port, status_port};
+use crate::api::handlers::port::{list_ports, get_port, create_port, update_port, delete_port, disable_port, enable_port, status_port, read_port, write_port, port_drivers};
 
 //--------------------------------------------------------------------------------- Router
 pub fn router() -> Router<AppState> 
@@ -19,6 +19,9 @@ pub fn router() -> Router<AppState>
         .route("/enable/{id}", get(enable_port))
         .route("/disable/{id}", get(disable_port))
         .route("/status/{id}", get(status_port))
+        .route("/read/{id}", get(read_port))
+        .route("/write/{id}/{value}", get(write_port))
+        .route("/drivers", get(port_drivers))
         .route("/update/{id}", put(update_port))
         .route("/add", post(create_port))
         .route("/delete/{id}", delete(delete_port))       
@@ -8,7 +8,7 @@
 use axum::routing::{get, post, put, delete};
 use axum::Router;
 use crate::AppState;
-use crate::api::handlers::timer_limit::{list_timer_limits, get_timer_limit, create_timer_limit, update_timer_limit, delete_timer_limit, disable_timer_limit, enable_timer_limit, status_timer_limit};
+use crate::api::handlers::timer_limit::{list_timer_limits, get_timer_limit, create_timer_limit, update_timer_limit, delete_timer_limit, disable_timer_limit, enable_timer_limit, status_timer_limit, active_timer_limits};
 
 //--------------------------------------------------------------------------------- Router
 pub fn router() -> Router<AppState> 
@@ -19,6 +19,7 @@ pub fn router() -> Router<AppState>
         .route("/enable/{id}", get(enable_timer_limit))
         .route("/disable/{id}", get(disable_timer_limit))
         .route("/status/{id}", get(status_timer_limit))
+        .route("/active", get(active_timer_limits))
         .route("/update/{id}", put(update_timer_limit))
         .route("/add", post(create_timer_limit))
         .route("/delete/{id}", delete(delete_timer_limit))       
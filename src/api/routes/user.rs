@@ -8,12 +8,13 @@
 use axum::routing::{get, post, put, delete};
 use axum::Router;
 use crate::AppState;
-use crate::api::handlers::user::{list_users, get_user, create_user, update_user, delete_user, disable_user, enable_user, status_user};
+use crate::api::handlers::user::{login_user, list_users, get_user, create_user, update_user, rotate_user_key, telegram_link_user, delete_user, disable_user, enable_user, status_user, get_user_role, update_user_role, list_user_zones, grant_user_zone, revoke_user_zone};
 
 //--------------------------------------------------------------------------------- Router
 pub fn router() -> Router<AppState> 
 {
     Router::new()
+        .route("/login", post(login_user))
         .route("/items", get(list_users))
         .route("/item/{id}", get(get_user))
         .route("/enable/{id}", get(enable_user))
@@ -21,5 +22,10 @@ pub fn router() -> Router<AppState>
         .route("/status/{id}", get(status_user))
         .route("/update/{id}", put(update_user))
         .route("/add", post(create_user))
-        .route("/delete/{id}", delete(delete_user))       
+        .route("/key/{id}", post(rotate_user_key))
+        .route("/telegram/{id}", post(telegram_link_user))
+        .route("/delete/{id}", delete(delete_user))
+        .route("/role/{id}", get(get_user_role).put(update_user_role))
+        .route("/zones/{id}", get(list_user_zones).post(grant_user_zone))
+        .route("/zones/{id}/{zone_id}", delete(revoke_user_zone))       
 }
\ No newline at end of file
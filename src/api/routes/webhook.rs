@@ -0,0 +1,25 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/routes/webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is route for webhook
+
+//--------------------------------------------------------------------------------- Import
+use axum::routing::{get, post, put, delete};
+use axum::Router;
+use crate::AppState;
+use crate::api::handlers::webhook::{list_webhooks, get_webhook, create_webhook, update_webhook, delete_webhook, disable_webhook, enable_webhook, status_webhook};
+
+//--------------------------------------------------------------------------------- Router
+pub fn router() -> Router<AppState> 
+{
+    Router::new()
+        .route("/items", get(list_webhooks))
+        .route("/item/{id}", get(get_webhook))
+        .route("/enable/{id}", get(enable_webhook))
+        .route("/disable/{id}", get(disable_webhook))
+        .route("/status/{id}", get(status_webhook))
+        .route("/update/{id}", put(update_webhook))
+        .route("/add", post(create_webhook))
+        .route("/delete/{id}", delete(delete_webhook))
+}
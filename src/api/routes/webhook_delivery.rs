@@ -0,0 +1,21 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/routes/webhook_delivery.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is route for webhook_delivery
+
+//--------------------------------------------------------------------------------- Import
+use axum::routing::{get, post, delete};
+use axum::Router;
+use crate::AppState;
+use crate::api::handlers::webhook_delivery::{list_webhook_deliveries, get_webhook_delivery, replay_webhook_delivery, delete_webhook_delivery};
+
+//--------------------------------------------------------------------------------- Router
+pub fn router() -> Router<AppState> 
+{
+    Router::new()
+        .route("/items", get(list_webhook_deliveries))
+        .route("/item/{id}", get(get_webhook_delivery))
+        .route("/replay/{id}", post(replay_webhook_delivery))
+        .route("/delete/{id}", delete(delete_webhook_delivery))
+}
@@ -8,7 +8,8 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::config::{Model as ConfigModel, ActiveModel as ConfigActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
 use crate::orm::logics::config::ConfigORM;
 
 //--------------------------------------------------------------------------------- Service
@@ -29,32 +30,38 @@ impl ConfigService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<ConfigModel>> 
+    /// Configs hold the API, nginx, git and wifi keys, so only admins read or change them
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<ConfigModel>> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.items(db, filters).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ConfigModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ConfigModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, _db: &DatabaseConnection, _id: i32) -> ModelOutput<ConfigModel> 
+    pub async fn enable(&self, _db: &DatabaseConnection, access: &Access, _id: i32) -> ModelOutput<ConfigModel> 
     {
-        ModelOutput::error("Config does not support enable/disable functionality".to_string())
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        ModelOutput::invalid("Config does not support enable/disable functionality".to_string())
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, _db: &DatabaseConnection, _id: i32) -> ModelOutput<ConfigModel> 
+    pub async fn disable(&self, _db: &DatabaseConnection, access: &Access, _id: i32) -> ModelOutput<ConfigModel> 
     {
-        ModelOutput::error("Config does not support enable/disable functionality".to_string())
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        ModelOutput::invalid("Config does not support enable/disable functionality".to_string())
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: ConfigModel) -> ModelOutput<ConfigModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: ConfigModel) -> ModelOutput<ConfigModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         let active_config = ConfigActiveModel 
         {
             id: Set(item.id),
@@ -96,8 +103,9 @@ impl ConfigService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: ConfigModel) -> ModelOutput<ConfigModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: ConfigModel) -> ModelOutput<ConfigModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         let active_config = ConfigActiveModel 
         {
             id: Default::default(),
@@ -139,14 +147,16 @@ impl ConfigService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ConfigModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ConfigModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
 }
@@ -5,16 +5,29 @@
 // This is service for device
 
 //--------------------------------------------------------------------------------- Import
-use sea_orm::{DatabaseConnection, Set};
+use sea_orm::{DatabaseConnection, EntityTrait, Set};
 use std::collections::HashMap;
 use crate::orm::models::device::{Model as DeviceModel, ActiveModel as DeviceActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::port::Entity as PortEntity;
+use crate::orm::models::zone::Entity as ZoneEntity;
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::device_command::Entity as DeviceCommandEntity;
 use crate::orm::logics::device::DeviceORM;
+use crate::orm::logics::graph::{Expand, GraphORM, DeviceGraph};
+use crate::orm::models::timer::Entity as TimerEntity;
+use crate::orm::models::zone_command::Entity as ZoneCommandEntity;
+use crate::orm::logics::integrity::{self, Parent};
+use crate::engine::executor::Executor;
+use crate::engine::events::{Event, EventBus};
 
 //--------------------------------------------------------------------------------- Service
 pub struct DeviceService 
 {
     pub logic: DeviceORM,
+    pub graph: GraphORM,
 }
 
 impl DeviceService 
@@ -25,36 +38,58 @@ impl DeviceService
         Self 
         {
             logic: DeviceORM::new(true, true),
+            graph: GraphORM::new(true, true),
         }
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<DeviceModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<DeviceModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<DeviceEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<DeviceModel> 
+    /// expand: comma separated devices, commands, rules, timers
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32, expand: Option<&str>) -> ModelOutput<DeviceGraph> 
     {
-        self.logic.item(db, id).await
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, id).await { return ModelOutput::fail(e); }
+        let expand = match Expand::parse(expand) { Ok(expand) => expand, Err(e) => return ModelOutput::fail(e) };
+
+        let output = self.logic.item(db, id).await;
+        if expand.is_empty() { return output.map(DeviceGraph::plain); }
+        match output.into_result()
+        {
+            Ok(item) => self.graph.device(db, item, expand, access.scope::<ZoneCommandEntity>(), access.scope::<TimerEntity>()).await,
+            Err(e) => ModelOutput::fail(e),
+        }
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<DeviceModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<DeviceModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<DeviceModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<DeviceModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: DeviceModel) -> ModelOutput<DeviceModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, events: &EventBus, item: DeviceModel) -> ModelOutput<DeviceModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, item.zone_id).await { return ModelOutput::fail(e); }
+        if item.port_id != 0 && let Err(e) = access.require_owned::<PortEntity>(db, item.port_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = links(db, access, item.id, &item).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, item.zone_id) { return ModelOutput::fail(e); }
+        let previous = self.logic.item(db, item.id).await.data;
         let active_device = DeviceActiveModel 
         {
             id: Set(item.id),
@@ -71,12 +106,24 @@ impl DeviceService
             enable: Set(item.enable),
         };
 
-        self.logic.update(db, active_device).await
+        let result = self.logic.update(db, active_device).await;
+
+        // Let the rule engine know when a device state was changed from outside the executor
+        if let (Some(previous), Some(device)) = (previous, result.data.as_ref()) && (previous.value != device.value || previous.command_id != device.command_id)
+        {
+            events.publish(Event::DeviceChanged { device_id: device.id, zone_id: device.zone_id, command_id: device.command_id, previous: previous.value, value: device.value });
+        }
+
+        result
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: DeviceModel) -> ModelOutput<DeviceModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: DeviceModel) -> ModelOutput<DeviceModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, item.zone_id).await { return ModelOutput::fail(e); }
+        if item.port_id != 0 && let Err(e) = access.require_owned::<PortEntity>(db, item.port_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = links(db, access, 0, &item).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, item.zone_id) { return ModelOutput::fail(e); }
         let active_device = DeviceActiveModel 
         {
             id: Default::default(),
@@ -97,14 +144,43 @@ impl DeviceService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::Device, id, cascade, &|table| access.scope_of(table)).await { return output; }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<DeviceModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<DeviceModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Execute
+    pub async fn execute(&self, executor: &Executor, access: &Access, device_id: i32, command: &str) -> ModelOutput<DeviceModel> 
+    {
+        if let Err(e) = access.require_owned::<DeviceEntity>(&executor.db, device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(&executor.db, Roles::Operator, device_id).await { return ModelOutput::fail(e); }
+        executor.execute(device_id, command).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Links
+/// The executor drives the power port and runs the current command, so a non-zero power_id must be
+/// a port of the caller's and a non-zero command_id a command of this device (none yet on add)
+async fn links(db: &DatabaseConnection, access: &Access, device_id: i32, item: &DeviceModel) -> Result<(), ApiError>
+{
+    if item.power_id != 0 { access.require_owned::<PortEntity>(db, item.power_id).await?; }
+    if item.command_id == 0 { return Ok(()); }
+
+    let command = DeviceCommandEntity::find_by_id(item.command_id).one(db).await.map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+    match command
+    {
+        Some(command) if device_id != 0 && command.device_id == device_id => Ok(()),
+        _ => Err(ApiError::Validation(format!("Command {} is not a command of this device", item.command_id))),
+    }
 }
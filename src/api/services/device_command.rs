@@ -8,8 +8,12 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::device_command::{Model as DeviceCommandModel, ActiveModel as DeviceCommandActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::device_command::Entity as DeviceCommandEntity;
 use crate::orm::logics::device_command::DeviceCommandORM;
+use crate::orm::logics::integrity::{self, Parent};
 
 //--------------------------------------------------------------------------------- Service
 pub struct DeviceCommandService 
@@ -29,32 +33,37 @@ impl DeviceCommandService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<DeviceCommandModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<DeviceCommandModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<DeviceCommandEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<DeviceCommandModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<DeviceCommandModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceCommandEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, _db: &DatabaseConnection, _id: i32) -> ModelOutput<DeviceCommandModel> 
+    pub async fn enable(&self, _db: &DatabaseConnection, _access: &Access, _id: i32) -> ModelOutput<DeviceCommandModel> 
     {
-        ModelOutput::error("DeviceCommand does not support enable/disable functionality".to_string())
+        ModelOutput::invalid("DeviceCommand does not support enable/disable functionality".to_string())
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, _db: &DatabaseConnection, _id: i32) -> ModelOutput<DeviceCommandModel> 
+    pub async fn disable(&self, _db: &DatabaseConnection, _access: &Access, _id: i32) -> ModelOutput<DeviceCommandModel> 
     {
-        ModelOutput::error("DeviceCommand does not support enable/disable functionality".to_string())
+        ModelOutput::invalid("DeviceCommand does not support enable/disable functionality".to_string())
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: DeviceCommandModel) -> ModelOutput<DeviceCommandModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: DeviceCommandModel) -> ModelOutput<DeviceCommandModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceCommandEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device_command(db, Roles::Operator, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_device_command = DeviceCommandActiveModel 
         {
             id: Set(item.id),
@@ -63,6 +72,7 @@ impl DeviceCommandService
             value_from: Set(item.value_from),
             value_to: Set(item.value_to),
             delay: Set(item.delay),
+            frequency: Set(item.frequency),
             description: Set(item.description),
             reload: Set(item.reload),
             enable: Set(item.enable),
@@ -73,8 +83,10 @@ impl DeviceCommandService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: DeviceCommandModel) -> ModelOutput<DeviceCommandModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: DeviceCommandModel) -> ModelOutput<DeviceCommandModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_device_command = DeviceCommandActiveModel 
         {
             id: Default::default(),
@@ -83,6 +95,7 @@ impl DeviceCommandService
             value_from: Set(item.value_from),
             value_to: Set(item.value_to),
             delay: Set(item.delay),
+            frequency: Set(item.frequency),
             description: Set(item.description),
             reload: Set(item.reload),
             enable: Set(item.enable),
@@ -93,14 +106,19 @@ impl DeviceCommandService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require_owned::<DeviceCommandEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device_command(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::DeviceCommand, id, cascade, &|table| access.scope_of(table)).await { return output; }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<DeviceCommandModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<DeviceCommandModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceCommandEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device_command(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
 }
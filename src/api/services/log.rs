@@ -8,8 +8,10 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::log::{Model as LogModel, ActiveModel as LogActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
 use crate::orm::logics::log::LogORM;
+use crate::engine::events::{Event, EventBus};
 
 //--------------------------------------------------------------------------------- Service
 pub struct LogService 
@@ -29,32 +31,38 @@ impl LogService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<LogModel>> 
+    /// Logs span every tenant and zone, so only admins read or change them
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<LogModel>> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.items(db, filters).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<LogModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<LogModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, _db: &DatabaseConnection, _id: i32) -> ModelOutput<LogModel> 
+    pub async fn enable(&self, _db: &DatabaseConnection, access: &Access, _id: i32) -> ModelOutput<LogModel> 
     {
-        ModelOutput::error("Log does not support enable/disable functionality".to_string())
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        ModelOutput::invalid("Log does not support enable/disable functionality".to_string())
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, _db: &DatabaseConnection, _id: i32) -> ModelOutput<LogModel> 
+    pub async fn disable(&self, _db: &DatabaseConnection, access: &Access, _id: i32) -> ModelOutput<LogModel> 
     {
-        ModelOutput::error("Log does not support enable/disable functionality".to_string())
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        ModelOutput::invalid("Log does not support enable/disable functionality".to_string())
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: LogModel) -> ModelOutput<LogModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: LogModel) -> ModelOutput<LogModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         let active_log = LogActiveModel 
         {
             id: Set(item.id),
@@ -68,8 +76,9 @@ impl LogService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: LogModel) -> ModelOutput<LogModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, events: &EventBus, item: LogModel) -> ModelOutput<LogModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         let active_log = LogActiveModel 
         {
             id: Default::default(),
@@ -79,18 +88,25 @@ impl LogService
             data: Set(item.data),
         };
 
-        self.logic.add(db, active_log).await
+        let output = self.logic.add(db, active_log).await;
+        if let Some(log) = &output.data
+        {
+            events.publish(Event::LogAdded { log_id: log.id, date: log.date.clone(), name: log.name.clone(), status: log.status, data: log.data.clone() });
+        }
+        output
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<LogModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<LogModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
 }
@@ -12,3 +12,6 @@ pub mod timer_limit;
 pub mod zone_command;
 pub mod zone_command_action;
 pub mod zone_command_if;
+pub mod webhook;
+pub mod webhook_delivery;
+pub mod notification;
@@ -0,0 +1,132 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/services/notification.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is service for notification
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::{DatabaseConnection, Set};
+use std::collections::HashMap;
+use crate::orm::models::notification::{Model as NotificationModel, ActiveModel as NotificationActiveModel};
+use crate::logics::general::ModelOutput;
+use crate::api::access::Access;
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::notification::Entity as NotificationEntity;
+use crate::orm::logics::notification::NotificationORM;
+
+//--------------------------------------------------------------------------------- Service
+pub struct NotificationService 
+{
+    pub logic: NotificationORM,
+}
+
+impl Default for NotificationService
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl NotificationService 
+{
+    //------------------------- New
+    pub fn new() -> Self 
+    {
+        Self 
+        {
+            logic: NotificationORM::new(true, true),
+        }
+    }
+
+    //------------------------- Items
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<NotificationModel>> 
+    {
+        self.logic.items_in(db, filters, access.scope::<NotificationEntity>()).await
+    }
+
+    //------------------------- Item
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<NotificationModel> 
+    {
+        if let Err(e) = access.require_owned::<NotificationEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.item(db, id).await
+    }
+
+    //------------------------- Enable
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<NotificationModel> 
+    {
+        if let Err(e) = access.require_owned::<NotificationEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.enable(db, id).await
+    }
+
+    //------------------------- Disable
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<NotificationModel> 
+    {
+        if let Err(e) = access.require_owned::<NotificationEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.disable(db, id).await
+    }
+
+    //------------------------- Update
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, mut item: NotificationModel) -> ModelOutput<NotificationModel> 
+    {
+        if let Err(e) = access.require_owned::<NotificationEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        item.user_id = access.owner(item.user_id);
+        if let Some(output) = self.validate(db, access, &mut item).await { return output; }
+        let active_notification = NotificationActiveModel 
+        {
+            id: Set(item.id),
+            user_id: Set(item.user_id),
+            device_id: Set(item.device_id),
+            kind: Set(item.kind),
+            description: Set(item.description),
+            enable: Set(item.enable),
+        };
+
+        self.logic.update(db, active_notification).await
+    }
+
+    //------------------------- Add
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, mut item: NotificationModel) -> ModelOutput<NotificationModel> 
+    {
+        item.user_id = access.owner(item.user_id);
+        if let Some(output) = self.validate(db, access, &mut item).await { return output; }
+        let active_notification = NotificationActiveModel 
+        {
+            id: Default::default(),
+            user_id: Set(item.user_id),
+            device_id: Set(item.device_id),
+            kind: Set(item.kind),
+            description: Set(item.description),
+            enable: Set(item.enable),
+        };
+
+        self.logic.add(db, active_notification).await
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
+    {
+        if let Err(e) = access.require_owned::<NotificationEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.delete(db, id).await
+    }
+
+    //------------------------- Status
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<NotificationModel> 
+    {
+        if let Err(e) = access.require_owned::<NotificationEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.status(db, id).await
+    }
+
+    //------------------------- Validate
+    /// Known kind, and a device of the user's or 0 for every device they can see
+    async fn validate<T>(&self, db: &DatabaseConnection, access: &Access, item: &mut NotificationModel) -> Option<ModelOutput<T>>
+    {
+        item.kind = item.kind.trim().to_lowercase();
+        if !["alarm", "fuse", "limit"].contains(&item.kind.as_str())
+        {
+            return Some(ModelOutput::invalid(format!("Unknown notification kind '{}', expected alarm, fuse or limit", item.kind)));
+        }
+        if item.device_id == 0 { return None; }
+        access.require_owned::<DeviceEntity>(db, item.device_id).await.err().map(ModelOutput::fail)
+    }
+}
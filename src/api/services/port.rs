@@ -8,8 +8,12 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::port::{Model as PortModel, ActiveModel as PortActiveModel};
-use crate::logics::general::{ModelOutput, PortTypes, PortProtocols};
+use crate::logics::general::{ModelOutput, PortTypes, PortProtocols, Roles};
+use crate::api::access::Access;
+use crate::orm::models::port::Entity as PortEntity;
 use crate::orm::logics::port::PortORM;
+use crate::orm::logics::integrity::{self, Parent};
+use crate::engine::executor::Executor;
 
 //--------------------------------------------------------------------------------- Service
 pub struct PortService 
@@ -29,35 +33,43 @@ impl PortService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<PortModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<PortModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<PortEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<PortModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<PortModel> 
     {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<PortModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<PortModel> 
     {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_port(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<PortModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<PortModel> 
     {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_port(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: PortModel) -> ModelOutput<PortModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, mut item: PortModel) -> ModelOutput<PortModel> 
     {
+        if let Err(e) = access.require_owned::<PortEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_port(db, Roles::Operator, item.id).await { return ModelOutput::fail(e); }
+        item.user_id = access.owner(item.user_id);
         // Validate port type if provided
         if !item.r#type.is_empty() && !PortTypes::is_valid_type(&item.r#type) {
-            return ModelOutput::error(format!(
+            return ModelOutput::invalid(format!(
                 "Invalid port type '{}'. Valid types are: {}",
                 item.r#type,
                 PortTypes::valid_types().join(", ")
@@ -66,7 +78,7 @@ impl PortService
 
         // Validate port protocol if provided
         if !item.protocol.is_empty() && !PortProtocols::is_valid_protocol(&item.protocol) {
-            return ModelOutput::error(format!(
+            return ModelOutput::invalid(format!(
                 "Invalid port protocol '{}'. Valid protocols are: {}",
                 item.protocol,
                 PortProtocols::valid_protocols().join(", ")
@@ -91,11 +103,13 @@ impl PortService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: PortModel) -> ModelOutput<PortModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, mut item: PortModel) -> ModelOutput<PortModel> 
     {
+        if let Err(e) = access.require_all_zones(Roles::Operator) { return ModelOutput::fail(e); }
+        item.user_id = access.owner(item.user_id);
         // Validate port type if provided
         if !item.r#type.is_empty() && !PortTypes::is_valid_type(&item.r#type) {
-            return ModelOutput::error(format!(
+            return ModelOutput::invalid(format!(
                 "Invalid port type '{}'. Valid types are: {}",
                 item.r#type,
                 PortTypes::valid_types().join(", ")
@@ -104,7 +118,7 @@ impl PortService
 
         // Validate port protocol if provided
         if !item.protocol.is_empty() && !PortProtocols::is_valid_protocol(&item.protocol) {
-            return ModelOutput::error(format!(
+            return ModelOutput::invalid(format!(
                 "Invalid port protocol '{}'. Valid protocols are: {}",
                 item.protocol,
                 PortProtocols::valid_protocols().join(", ")
@@ -129,14 +143,61 @@ impl PortService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_port(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::Port, id, cascade, &|table| access.scope_of(table)).await { return output; }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<PortModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<PortModel> 
     {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_port(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Read
+    /// Viewers may read, so the level read is returned but not saved
+    pub async fn read(&self, db: &DatabaseConnection, access: &Access, executor: &Executor, id: i32) -> ModelOutput<PortModel> 
+    {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
+        let found = self.logic.item(db, id).await;
+        let Some(mut port) = found.data else { return found; };
+
+        match executor.sense(&port).await 
+        {
+            Ok(value) => 
+            {
+                port.value = value;
+                ModelOutput::success(port, format!("Port read successfully (value: {})", value))
+            }
+            Err(e) => ModelOutput::fail(e),
+        }
+    }
+
+    //------------------------- Write
+    /// Same path as a device command: PWM and bus ports too, address and frequency as for a device
+    pub async fn write(&self, db: &DatabaseConnection, access: &Access, executor: &Executor, id: i32, value: i32, params: HashMap<String, String>) -> ModelOutput<PortModel> 
+    {
+        if let Err(e) = access.require_owned::<PortEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_port(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
+        let frequency = match params.get("frequency").map(|f| f.parse::<i32>())
+        {
+            None => None,
+            Some(Ok(frequency)) => Some(frequency),
+            Some(Err(_)) => return ModelOutput::invalid("frequency must be a number".to_string()),
+        };
+        let address = params.get("address").map(String::as_str).unwrap_or_default();
+        let found = self.logic.item(db, id).await;
+        let Some(port) = found.data else { return found; };
+
+        match executor.drive(&port, address, value, frequency).await 
+        {
+            Ok(port) => ModelOutput::success(port, format!("Port written successfully (value: {})", value)),
+            Err(e) => ModelOutput::fail(e),
+        }
+    }
 }
@@ -8,8 +8,11 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::timer::{Model as TimerModel, ActiveModel as TimerActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::orm::models::timer::Entity as TimerEntity;
 use crate::orm::logics::timer::TimerORM;
+use crate::orm::logics::integrity::{self, Parent};
 
 //--------------------------------------------------------------------------------- Service
 pub struct TimerService 
@@ -29,32 +32,40 @@ impl TimerService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<TimerEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: TimerModel) -> ModelOutput<TimerModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, mut item: TimerModel) -> ModelOutput<TimerModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        item.user_id = access.owner(item.user_id);
+        if let Err(e) = access.require_timer(db, Roles::Operator, item.id).await { return ModelOutput::fail(e); }
         let active_timer = TimerActiveModel 
         {
             id: Set(item.id),
@@ -68,8 +79,10 @@ impl TimerService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: TimerModel) -> ModelOutput<TimerModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, mut item: TimerModel) -> ModelOutput<TimerModel> 
     {
+        item.user_id = access.owner(item.user_id);
+        if let Err(e) = access.require_all_zones(Roles::Operator) { return ModelOutput::fail(e); }
         let active_timer = TimerActiveModel 
         {
             id: Default::default(),
@@ -83,14 +96,19 @@ impl TimerService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::Timer, id, cascade, &|table| access.scope_of(table)).await { return output; }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
 }
@@ -8,7 +8,12 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::timer_device::{Model as TimerDeviceModel, ActiveModel as TimerDeviceActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::timer::Entity as TimerEntity;
+use crate::orm::models::timer_device::Entity as TimerDeviceEntity;
 use crate::orm::logics::timer_device::TimerDeviceORM;
 
 //--------------------------------------------------------------------------------- Service
@@ -29,32 +34,40 @@ impl TimerDeviceService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerDeviceModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerDeviceModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<TimerDeviceEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerDeviceModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerDeviceModel> 
     {
+        if let Err(e) = access.require_owned::<TimerDeviceEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerDeviceModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerDeviceModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerDeviceModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerDeviceModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: TimerDeviceModel) -> ModelOutput<TimerDeviceModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: TimerDeviceModel) -> ModelOutput<TimerDeviceModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, item.timer_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = self.operate(db, access, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, item.timer_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_timer_device = TimerDeviceActiveModel 
         {
             id: Set(item.id),
@@ -69,8 +82,12 @@ impl TimerDeviceService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: TimerDeviceModel) -> ModelOutput<TimerDeviceModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: TimerDeviceModel) -> ModelOutput<TimerDeviceModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, item.timer_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, item.timer_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_timer_device = TimerDeviceActiveModel 
         {
             id: Default::default(),
@@ -85,14 +102,25 @@ impl TimerDeviceService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerDeviceModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerDeviceModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Operate
+    /// Operators may switch the timer devices of timers in their granted zones
+    async fn operate(&self, db: &DatabaseConnection, access: &Access, id: i32) -> Result<(), ApiError>
+    {
+        access.require_owned::<TimerDeviceEntity>(db, id).await?;
+        let item = self.logic.item(db, id).await.into_result()?;
+        access.require_timer(db, Roles::Operator, item.timer_id).await
+    }
 }
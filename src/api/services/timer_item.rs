@@ -8,7 +8,11 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::timer_item::{Model as TimerItemModel, ActiveModel as TimerItemActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::timer::Entity as TimerEntity;
+use crate::orm::models::timer_item::Entity as TimerItemEntity;
 use crate::orm::logics::timer_item::TimerItemORM;
 
 //--------------------------------------------------------------------------------- Service
@@ -29,32 +33,38 @@ impl TimerItemService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerItemModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerItemModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<TimerItemEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerItemModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerItemModel> 
     {
+        if let Err(e) = access.require_owned::<TimerItemEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerItemModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerItemModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerItemModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerItemModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: TimerItemModel) -> ModelOutput<TimerItemModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: TimerItemModel) -> ModelOutput<TimerItemModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, item.timer_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = self.operate(db, access, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, item.timer_id).await { return ModelOutput::fail(e); }
         let active_timer_item = TimerItemActiveModel 
         {
             id: Set(item.id),
@@ -70,8 +80,10 @@ impl TimerItemService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: TimerItemModel) -> ModelOutput<TimerItemModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: TimerItemModel) -> ModelOutput<TimerItemModel> 
     {
+        if let Err(e) = access.require_owned::<TimerEntity>(db, item.timer_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_timer(db, Roles::Operator, item.timer_id).await { return ModelOutput::fail(e); }
         let active_timer_item = TimerItemActiveModel 
         {
             id: Default::default(),
@@ -87,14 +99,25 @@ impl TimerItemService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerItemModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerItemModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Operate
+    /// Operators may switch the timer items of timers in their granted zones
+    async fn operate(&self, db: &DatabaseConnection, access: &Access, id: i32) -> Result<(), ApiError>
+    {
+        access.require_owned::<TimerItemEntity>(db, id).await?;
+        let item = self.logic.item(db, id).await.into_result()?;
+        access.require_timer(db, Roles::Operator, item.timer_id).await
+    }
 }
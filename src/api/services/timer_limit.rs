@@ -5,11 +5,16 @@
 // This is service for timer_limit
 
 //--------------------------------------------------------------------------------- Import
-use sea_orm::{DatabaseConnection, Set};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set};
 use std::collections::HashMap;
 use crate::orm::models::timer_limit::{Model as TimerLimitModel, ActiveModel as TimerLimitActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::device::{Column as DeviceColumn, Entity as DeviceEntity};
+use crate::orm::models::timer_limit::Entity as TimerLimitEntity;
 use crate::orm::logics::timer_limit::TimerLimitORM;
+use crate::engine::limit::{ActiveTimerLimit, LimitWatcher};
 
 //--------------------------------------------------------------------------------- Service
 pub struct TimerLimitService 
@@ -29,32 +34,42 @@ impl TimerLimitService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerLimitModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerLimitModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<TimerLimitEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerLimitModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TimerLimitModel> 
     {
+        if let Err(e) = access.require_owned::<TimerLimitEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerLimitModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher, id: i32) -> ModelOutput<TimerLimitModel> 
     {
-        self.logic.enable(db, id).await
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
+        let output = self.logic.enable(db, id).await;
+        if output.status { limits.refresh(id).await; }
+        output
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerLimitModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher, id: i32) -> ModelOutput<TimerLimitModel> 
     {
-        self.logic.disable(db, id).await
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
+        let output = self.logic.disable(db, id).await;
+        if output.status { limits.refresh(id).await; }
+        output
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: TimerLimitModel) -> ModelOutput<TimerLimitModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher, item: TimerLimitModel) -> ModelOutput<TimerLimitModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = self.operate(db, access, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_timer_limit = TimerLimitActiveModel 
         {
             id: Set(item.id),
@@ -66,12 +81,16 @@ impl TimerLimitService
             enable: Set(item.enable),
         };
 
-        self.logic.update(db, active_timer_limit).await
+        let output = self.logic.update(db, active_timer_limit).await;
+        if let Some(limit) = &output.data { limits.refresh(limit.id).await; }
+        output
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: TimerLimitModel) -> ModelOutput<TimerLimitModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher, item: TimerLimitModel) -> ModelOutput<TimerLimitModel> 
     {
+        if let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_timer_limit = TimerLimitActiveModel 
         {
             id: Default::default(),
@@ -83,18 +102,53 @@ impl TimerLimitService
             enable: Set(item.enable),
         };
 
-        self.logic.add(db, active_timer_limit).await
+        let output = self.logic.add(db, active_timer_limit).await;
+        if let Some(limit) = &output.data { limits.refresh(limit.id).await; }
+        output
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher, id: i32) -> ModelOutput<String> 
     {
-        self.logic.delete(db, id).await
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
+        let output = self.logic.delete(db, id).await;
+        if output.status { limits.refresh(id).await; }
+        output
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<TimerLimitModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher, id: i32) -> ModelOutput<TimerLimitModel> 
     {
-        self.logic.status(db, id).await
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
+        let output = self.logic.status(db, id).await;
+        if output.status { limits.refresh(id).await; }
+        output
+    }
+
+    //------------------------- Active
+    /// Countdowns on the user's devices
+    pub async fn active(&self, db: &DatabaseConnection, access: &Access, limits: &LimitWatcher) -> ModelOutput<Vec<ActiveTimerLimit>> 
+    {
+        let mut items = limits.active();
+        if !access.is_admin()
+        {
+            let devices: Vec<i32> = match DeviceEntity::find().select_only().column(DeviceColumn::Id).filter(access.scope::<DeviceEntity>()).into_tuple().all(db).await
+            {
+                Ok(devices) => devices,
+                Err(e) => return ModelOutput::db_error(format!("Database error in TimerLimitService::active: {}", e), &e),
+            };
+            items.retain(|item| devices.contains(&item.device_id));
+        }
+        let message = format!("Active timer limits retrieved successfully (found {} items)", items.len());
+        ModelOutput::success(items, message)
+    }
+
+    //------------------------- Operate
+    /// Operators may switch the limits of devices in their granted zones
+    async fn operate(&self, db: &DatabaseConnection, access: &Access, id: i32) -> Result<(), ApiError>
+    {
+        access.require_owned::<TimerLimitEntity>(db, id).await?;
+        let item = self.logic.item(db, id).await.into_result()?;
+        access.require_device(db, Roles::Operator, item.device_id).await
     }
 }
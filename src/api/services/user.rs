@@ -5,11 +5,21 @@
 // This is service for user - Rust equivalent of Python service layer
 
 //--------------------------------------------------------------------------------- Import
-use sea_orm::{DatabaseConnection, Set};
+use sea_orm::{ActiveValue::NotSet, DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::user::{Model as UserModel, ActiveModel as UserActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
 use crate::orm::logics::user::UserORM;
+use crate::orm::logics::user_role::UserRoleORM;
+use crate::orm::logics::user_zone::UserZoneORM;
+use crate::orm::logics::zone::ZoneORM;
+use crate::orm::logics::telegram_chat::TelegramChatORM;
+use crate::telegram::{self, TelegramLink};
+use crate::orm::models::user_role::Model as UserRoleModel;
+use crate::orm::models::user_zone::Model as UserZoneModel;
+use crate::api::auth::{self, Auth, Token, UserKey};
+use crate::api::error::ApiError;
 
 //--------------------------------------------------------------------------------- Service
 pub struct UserService 
@@ -29,39 +39,59 @@ impl UserService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<UserModel>> 
+    /// Non-admins only see themselves
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, mut filters: HashMap<String, String>) -> ModelOutput<Vec<UserModel>> 
     {
+        if !access.is_admin()
+        {
+            filters.insert("id".to_string(), access.user_id.to_string());
+        }
         self.logic.items(db, filters).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<UserModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<UserModel> 
     {
+        if let Err(e) = access.require_self(id) { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<UserModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<UserModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<UserModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<UserModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: UserModel) -> ModelOutput<UserModel> 
+    /// An empty password keeps the stored one, a new one is hashed; the key only changes through rotate_key
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: UserModel) -> ModelOutput<UserModel> 
     {
+        if let Err(e) = access.require_self(item.id) { return ModelOutput::fail(e); }
+        let password = match item.password.is_empty()
+        {
+            true => NotSet,
+            false => match auth::hash_password(&item.password)
+            {
+                Ok(hash) => Set(hash),
+                Err(e) => return ModelOutput::fail(e),
+            },
+        };
+
         let active_user = UserActiveModel 
         {
             id: Set(item.id),
             name: Set(item.name),
             username: Set(item.username),
-            password: Set(item.password),
-            key: Set(item.key),
+            password,
+            key: NotSet,
             email: Set(item.email),
             phone: Set(item.phone),
             tg_id: Set(item.tg_id),
@@ -72,27 +102,149 @@ impl UserService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: UserModel) -> ModelOutput<UserModel> 
+    /// The key is always generated here, a key sent by the client is ignored; it is in the response only this once
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: UserModel) -> ModelOutput<UserKey> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        if item.password.is_empty()
+        {
+            return ModelOutput::invalid("Password is required".to_string());
+        }
+        let password = match auth::hash_password(&item.password)
+        {
+            Ok(hash) => hash,
+            Err(e) => return ModelOutput::fail(e),
+        };
+
         let active_user = UserActiveModel 
         {
             id: Default::default(),
             name: Set(item.name),
             username: Set(item.username),
-            password: Set(item.password),
-            key: Set(item.key),
+            password: Set(password),
+            key: Set(auth::generate_key()),
             email: Set(item.email),
             phone: Set(item.phone),
             tg_id: Set(item.tg_id),
             enable: Set(item.enable),
         };
 
-        self.logic.add(db, active_user).await
+        self.logic.add(db, active_user).await.map(UserKey::from)
+    }
+
+    //------------------------- Rotate Key
+    /// Replace the key with a random one and return it; the old key stops working at once
+    pub async fn rotate_key(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<UserKey> 
+    {
+        if let Err(e) = access.require_self(id) { return ModelOutput::fail(e); }
+        if let Err(e) = self.logic.item(db, id).await.into_result() { return ModelOutput::fail(e); }
+
+        let active_user = UserActiveModel { id: Set(id), key: Set(auth::generate_key()), ..Default::default() };
+        let mut output = self.logic.update(db, active_user).await.map(UserKey::from);
+        if output.status { output.message = "User key rotated successfully".to_string(); }
+        output
+    }
+
+    //------------------------- Telegram Link
+    /// New one-time code for the bot's /link; a code issued before for the user stops working
+    pub async fn telegram_link(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<TelegramLink> 
+    {
+        if let Err(e) = access.require_self(id) { return ModelOutput::fail(e); }
+        if let Err(e) = self.logic.item(db, id).await.into_result() { return ModelOutput::fail(e); }
+
+        let code = telegram::link_code();
+        let expires = (chrono::Local::now() + telegram::LINK_TTL).format("%Y-%m-%d %H:%M:%S").to_string();
+        TelegramChatORM::new(true, true).set_code(db, id, &code, &expires).await
+            .map(|_| TelegramLink { command: format!("/link {}", code), code, expires })
+    }
+
+    //------------------------- Login
+    /// Verify the password and sign a token; an unknown username still costs one argon2 check,
+    /// so the response time does not tell which usernames exist
+    pub async fn login(&self, db: &DatabaseConnection, auth: &Auth, username: &str, password: &str) -> ModelOutput<Token> 
+    {
+        let denied = || ModelOutput::fail(ApiError::Unauthorized("Invalid username or password".to_string()));
+
+        let user = match self.logic.item_by_username(db, username).await.into_result()
+        {
+            Ok(user) => user,
+            Err(ApiError::NotFound(_)) =>
+            {
+                auth::verify_password(password, auth::dummy_hash());
+                return denied();
+            }
+            Err(e) => return ModelOutput::fail(e),
+        };
+
+        if !auth::verify_password(password, &user.password)
+        {
+            return denied();
+        }
+
+        match auth.issue(&user)
+        {
+            Ok(token) => ModelOutput::success(token, "Login successful".to_string()),
+            Err(e) => ModelOutput::fail(e),
+        }
+    }
+
+    //------------------------- Role
+    /// A user without a stored role is a Viewer
+    pub async fn role(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<UserRoleModel> 
+    {
+        if let Err(e) = access.require_self(id) { return ModelOutput::fail(e); }
+        if let Err(e) = self.logic.item(db, id).await.into_result() { return ModelOutput::fail(e); }
+
+        match UserRoleORM::new(true, true).item_by_user(db, id).await.into_result()
+        {
+            Ok(role) => ModelOutput::success(role, "User role retrieved successfully".to_string()),
+            Err(ApiError::NotFound(_)) => ModelOutput::success(UserRoleModel { id: 0, user_id: id, role: Roles::Viewer.name().to_string(), all_zones: false }, "User role retrieved successfully".to_string()),
+            Err(e) => ModelOutput::fail(e),
+        }
+    }
+
+    //------------------------- Set Role
+    pub async fn set_role(&self, db: &DatabaseConnection, access: &Access, id: i32, role: &str, all_zones: Option<bool>) -> ModelOutput<UserRoleModel> 
+    {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        let role = match Roles::from_name(role)
+        {
+            Some(role) => role,
+            None => return ModelOutput::invalid(format!("Invalid role '{}'. Valid roles are: {}", role, Roles::valid_roles().join(", "))),
+        };
+        if let Err(e) = self.logic.item(db, id).await.into_result() { return ModelOutput::fail(e); }
+
+        UserRoleORM::new(true, true).set(db, id, role.name(), all_zones).await
+    }
+
+    //------------------------- Zones
+    pub async fn zones(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<Vec<UserZoneModel>> 
+    {
+        if let Err(e) = access.require_self(id) { return ModelOutput::fail(e); }
+        UserZoneORM::new(true, true).items_by_user(db, id).await
+    }
+
+    //------------------------- Grant Zone
+    pub async fn grant_zone(&self, db: &DatabaseConnection, access: &Access, id: i32, zone_id: i32) -> ModelOutput<UserZoneModel> 
+    {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        if let Err(e) = self.logic.item(db, id).await.into_result() { return ModelOutput::fail(e); }
+        if let Err(e) = ZoneORM::new(false, false).item(db, zone_id).await.into_result() { return ModelOutput::fail(e); }
+
+        UserZoneORM::new(true, true).add(db, id, zone_id).await
+    }
+
+    //------------------------- Revoke Zone
+    pub async fn revoke_zone(&self, db: &DatabaseConnection, access: &Access, id: i32, zone_id: i32) -> ModelOutput<String> 
+    {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
+        UserZoneORM::new(true, true).delete(db, id, zone_id).await
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
@@ -103,8 +255,9 @@ impl UserService
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<UserModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<UserModel> 
     {
+        if let Err(e) = access.require(Roles::Admin) { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
 }
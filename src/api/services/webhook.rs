@@ -0,0 +1,144 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/services/webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is service for webhook
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::{DatabaseConnection, Set};
+use std::collections::HashMap;
+use crate::orm::models::webhook::{Model as WebhookModel, ActiveModel as WebhookActiveModel};
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::orm::models::webhook::Entity as WebhookEntity;
+use crate::orm::logics::webhook::WebhookORM;
+use crate::orm::logics::integrity::{self, Parent};
+use crate::engine::webhook;
+
+//--------------------------------------------------------------------------------- Service
+pub struct WebhookService 
+{
+    pub logic: WebhookORM,
+}
+
+impl Default for WebhookService
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl WebhookService 
+{
+    //------------------------- New
+    pub fn new() -> Self 
+    {
+        Self 
+        {
+            logic: WebhookORM::new(true, true),
+        }
+    }
+
+    //------------------------- Items
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<WebhookModel>> 
+    {
+        self.logic.items_in(db, filters, access.scope::<WebhookEntity>()).await
+    }
+
+    //------------------------- Item
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<WebhookModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.item(db, id).await
+    }
+
+    //------------------------- Enable
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<WebhookModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        self.logic.enable(db, id).await
+    }
+
+    //------------------------- Disable
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<WebhookModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        self.logic.disable(db, id).await
+    }
+
+    //------------------------- Update
+    /// An empty secret keeps the current one
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, mut item: WebhookModel) -> ModelOutput<WebhookModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        item.user_id = access.owner(item.user_id);
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        if let Some(output) = validate(&item) { return output; }
+        let active_webhook = WebhookActiveModel 
+        {
+            id: Set(item.id),
+            user_id: Set(item.user_id),
+            name: Set(item.name),
+            url: Set(item.url),
+            secret: if item.secret.is_empty() { Default::default() } else { Set(item.secret) },
+            events: Set(item.events),
+            description: Set(item.description),
+            enable: Set(item.enable),
+        };
+
+        self.logic.update(db, active_webhook).await
+    }
+
+    //------------------------- Add
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, mut item: WebhookModel) -> ModelOutput<WebhookModel> 
+    {
+        item.user_id = access.owner(item.user_id);
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        if let Some(output) = validate(&item) { return output; }
+        if item.secret.trim().is_empty() { return ModelOutput::invalid("Webhook secret is required, it signs every delivery".to_string()); }
+        let active_webhook = WebhookActiveModel 
+        {
+            id: Default::default(),
+            user_id: Set(item.user_id),
+            name: Set(item.name),
+            url: Set(item.url),
+            secret: Set(item.secret),
+            events: Set(item.events),
+            description: Set(item.description),
+            enable: Set(item.enable),
+        };
+
+        self.logic.add(db, active_webhook).await
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
+    {
+        if let Err(e) = access.require_owned::<WebhookEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::Webhook, id, cascade, &|table| access.scope_of(table)).await { return output; }
+        self.logic.delete(db, id).await
+    }
+
+    //------------------------- Status
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<WebhookModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        self.logic.status(db, id).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Validate
+/// Webhooks are posted over HTTP(S) only, to public destinations unless WEBHOOK_ALLOW_PRIVATE=true
+fn validate<T>(item: &WebhookModel) -> Option<ModelOutput<T>>
+{
+    match webhook::check_url(&item.url, webhook::private_allowed())
+    {
+        Ok(_) => None,
+        Err(e) => Some(ModelOutput::invalid(format!("Webhook url '{}': {}", item.url, e))),
+    }
+}
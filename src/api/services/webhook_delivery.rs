@@ -0,0 +1,71 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/services/webhook_delivery.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is service for webhook_delivery
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use crate::orm::models::webhook_delivery::Model as WebhookDeliveryModel;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::orm::models::webhook_delivery::Entity as WebhookDeliveryEntity;
+use crate::orm::logics::webhook_delivery::WebhookDeliveryORM;
+use crate::engine::webhook::WebhookDispatcher;
+
+//--------------------------------------------------------------------------------- Service
+pub struct WebhookDeliveryService 
+{
+    pub logic: WebhookDeliveryORM,
+}
+
+impl Default for WebhookDeliveryService
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl WebhookDeliveryService 
+{
+    //------------------------- New
+    pub fn new() -> Self 
+    {
+        Self 
+        {
+            logic: WebhookDeliveryORM::new(true, true),
+        }
+    }
+
+    //------------------------- Items
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<WebhookDeliveryModel>> 
+    {
+        self.logic.items_in(db, filters, access.scope::<WebhookDeliveryEntity>()).await
+    }
+
+    //------------------------- Item
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<WebhookDeliveryModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookDeliveryEntity>(db, id).await { return ModelOutput::fail(e); }
+        self.logic.item(db, id).await
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
+    {
+        if let Err(e) = access.require_owned::<WebhookDeliveryEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        self.logic.delete(db, id).await
+    }
+
+    //------------------------- Replay
+    /// Send the stored payload again; the row goes back to pending until the new attempts end
+    pub async fn replay(&self, db: &DatabaseConnection, access: &Access, webhooks: &WebhookDispatcher, id: i32) -> ModelOutput<WebhookDeliveryModel> 
+    {
+        if let Err(e) = access.require_owned::<WebhookDeliveryEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require(Roles::Operator) { return ModelOutput::fail(e); }
+        webhooks.replay(id).await
+    }
+}
@@ -8,13 +8,19 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::zone::{Model as ZoneModel, ActiveModel as ZoneActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::orm::models::zone::Entity as ZoneEntity;
 use crate::orm::logics::zone::ZoneORM;
+use crate::orm::logics::graph::{Expand, GraphORM, ZoneGraph};
+use crate::orm::models::timer::Entity as TimerEntity;
+use crate::orm::logics::integrity::{self, Parent};
 
 //--------------------------------------------------------------------------------- Service
 pub struct ZoneService 
 {
     pub logic: ZoneORM,
+    pub graph: GraphORM,
 }
 
 impl ZoneService 
@@ -25,36 +31,54 @@ impl ZoneService
         Self 
         {
             logic: ZoneORM::new(true, true),
+            graph: GraphORM::new(true, true),
         }
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<ZoneEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneModel> 
+    /// expand: comma separated devices, commands, rules, timers
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32, expand: Option<&str>) -> ModelOutput<ZoneGraph> 
     {
-        self.logic.item(db, id).await
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, id).await { return ModelOutput::fail(e); }
+        let expand = match Expand::parse(expand) { Ok(expand) => expand, Err(e) => return ModelOutput::fail(e) };
+
+        let output = self.logic.item(db, id).await;
+        if expand.is_empty() { return output.map(ZoneGraph::plain); }
+        match output.into_result()
+        {
+            Ok(item) => self.graph.zone(db, item, expand, access.scope::<TimerEntity>()).await,
+            Err(e) => ModelOutput::fail(e),
+        }
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, id) { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, id) { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: ZoneModel) -> ModelOutput<ZoneModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, mut item: ZoneModel) -> ModelOutput<ZoneModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, item.id).await { return ModelOutput::fail(e); }
+        item.user_id = access.owner(item.user_id);
+        if let Err(e) = access.require_zone(Roles::Operator, item.id) { return ModelOutput::fail(e); }
         let active_zone = ZoneActiveModel 
         {
             id: Set(item.id),
@@ -68,8 +92,10 @@ impl ZoneService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: ZoneModel) -> ModelOutput<ZoneModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, mut item: ZoneModel) -> ModelOutput<ZoneModel> 
     {
+        item.user_id = access.owner(item.user_id);
+        if let Err(e) = access.require_all_zones(Roles::Operator) { return ModelOutput::fail(e); }
         let active_zone = ZoneActiveModel 
         {
             id: Default::default(),
@@ -83,14 +109,19 @@ impl ZoneService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, id) { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::Zone, id, cascade, &|table| access.scope_of(table)).await { return output; }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, id) { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
 }
@@ -8,8 +8,14 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::zone_command::{Model as ZoneCommandModel, ActiveModel as ZoneCommandActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::zone::Entity as ZoneEntity;
+use crate::orm::models::zone_command::Entity as ZoneCommandEntity;
 use crate::orm::logics::zone_command::ZoneCommandORM;
+use crate::orm::logics::integrity::{self, Parent};
+use crate::engine::rule::RuleEngine;
 
 //--------------------------------------------------------------------------------- Service
 pub struct ZoneCommandService 
@@ -29,32 +35,38 @@ impl ZoneCommandService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneCommandModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneCommandModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<ZoneCommandEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: ZoneCommandModel) -> ModelOutput<ZoneCommandModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: ZoneCommandModel) -> ModelOutput<ZoneCommandModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, item.zone_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = self.operate(db, access, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, item.zone_id) { return ModelOutput::fail(e); }
         let active_zone_command = ZoneCommandActiveModel 
         {
             id: Set(item.id),
@@ -68,8 +80,10 @@ impl ZoneCommandService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: ZoneCommandModel) -> ModelOutput<ZoneCommandModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: ZoneCommandModel) -> ModelOutput<ZoneCommandModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneEntity>(db, item.zone_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone(Roles::Operator, item.zone_id) { return ModelOutput::fail(e); }
         let active_zone_command = ZoneCommandActiveModel 
         {
             id: Default::default(),
@@ -83,14 +97,40 @@ impl ZoneCommandService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32, cascade: bool) -> ModelOutput<String> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
+        if let Some(output) = integrity::before_delete(db, Parent::ZoneCommand, id, cascade, &|table| access.scope_of(table)).await { return output; }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Run
+    /// Operators may run the commands of their granted zones
+    pub async fn run(&self, db: &DatabaseConnection, access: &Access, rules: &RuleEngine, id: i32) -> ModelOutput<ZoneCommandModel> 
+    {
+        if let Err(e) = access.require_owned::<ZoneCommandEntity>(db, id).await { return ModelOutput::fail(e); }
+        let zone_command = match self.logic.item(db, id).await.into_result()
+        {
+            Ok(zone_command) => zone_command,
+            Err(e) => return ModelOutput::fail(e),
+        };
+        if let Err(e) = access.require_zone(Roles::Operator, zone_command.zone_id) { return ModelOutput::fail(e); }
+        rules.run(id).await
+    }
+
+    //------------------------- Operate
+    /// Operators may switch the zone commands in their granted zones
+    async fn operate(&self, db: &DatabaseConnection, access: &Access, id: i32) -> Result<(), ApiError>
+    {
+        access.require_owned::<ZoneCommandEntity>(db, id).await?;
+        let item = self.logic.item(db, id).await.into_result()?;
+        access.require_zone(Roles::Operator, item.zone_id)
+    }
 }
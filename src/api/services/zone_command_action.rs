@@ -8,7 +8,12 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::zone_command_action::{Model as ZoneCommandActionModel, ActiveModel as ZoneCommandActionActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::zone_command::Entity as ZoneCommandEntity;
+use crate::orm::models::zone_command_action::Entity as ZoneCommandActionEntity;
 use crate::orm::logics::zone_command_action::ZoneCommandActionORM;
 
 //--------------------------------------------------------------------------------- Service
@@ -29,32 +34,40 @@ impl ZoneCommandActionService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneCommandActionModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneCommandActionModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<ZoneCommandActionEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandActionModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandActionModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandActionEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandActionModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandActionModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandActionModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandActionModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: ZoneCommandActionModel) -> ModelOutput<ZoneCommandActionModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: ZoneCommandActionModel) -> ModelOutput<ZoneCommandActionModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandEntity>(db, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = self.operate(db, access, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone_command(db, Roles::Operator, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_zone_command_action = ZoneCommandActionActiveModel 
         {
             id: Set(item.id),
@@ -70,8 +83,12 @@ impl ZoneCommandActionService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: ZoneCommandActionModel) -> ModelOutput<ZoneCommandActionModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: ZoneCommandActionModel) -> ModelOutput<ZoneCommandActionModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandEntity>(db, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone_command(db, Roles::Operator, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
         let active_zone_command_action = ZoneCommandActionActiveModel 
         {
             id: Default::default(),
@@ -87,14 +104,25 @@ impl ZoneCommandActionService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandActionModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandActionModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Operate
+    /// Operators may switch the actions of zone commands in their granted zones
+    async fn operate(&self, db: &DatabaseConnection, access: &Access, id: i32) -> Result<(), ApiError>
+    {
+        access.require_owned::<ZoneCommandActionEntity>(db, id).await?;
+        let item = self.logic.item(db, id).await.into_result()?;
+        access.require_zone_command(db, Roles::Operator, item.zone_command_id).await
+    }
 }
@@ -8,7 +8,12 @@
 use sea_orm::{DatabaseConnection, Set};
 use std::collections::HashMap;
 use crate::orm::models::zone_command_if::{Model as ZoneCommandIfModel, ActiveModel as ZoneCommandIfActiveModel};
-use crate::logics::general::ModelOutput;
+use crate::logics::general::{ModelOutput, IfTypes, Roles};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::zone_command::Entity as ZoneCommandEntity;
+use crate::orm::models::zone_command_if::Entity as ZoneCommandIfEntity;
 use crate::orm::logics::zone_command_if::ZoneCommandIfORM;
 
 //--------------------------------------------------------------------------------- Service
@@ -29,32 +34,49 @@ impl ZoneCommandIfService
     }
 
     //------------------------- Items
-    pub async fn items(&self, db: &DatabaseConnection, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneCommandIfModel>> 
+    pub async fn items(&self, db: &DatabaseConnection, access: &Access, filters: HashMap<String, String>) -> ModelOutput<Vec<ZoneCommandIfModel>> 
     {
-        self.logic.items(db, filters).await
+        self.logic.items_in(db, filters, access.scope::<ZoneCommandIfEntity>()).await
     }
 
     //------------------------- Item
-    pub async fn item(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandIfModel> 
+    pub async fn item(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandIfModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandIfEntity>(db, id).await { return ModelOutput::fail(e); }
         self.logic.item(db, id).await
     }
 
     //------------------------- Enable
-    pub async fn enable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandIfModel> 
+    pub async fn enable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandIfModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.enable(db, id).await
     }
 
     //------------------------- Disable
-    pub async fn disable(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandIfModel> 
+    pub async fn disable(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandIfModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.disable(db, id).await
     }
 
     //------------------------- Update
-    pub async fn update(&self, db: &DatabaseConnection, item: ZoneCommandIfModel) -> ModelOutput<ZoneCommandIfModel> 
+    pub async fn update(&self, db: &DatabaseConnection, access: &Access, item: ZoneCommandIfModel) -> ModelOutput<ZoneCommandIfModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandEntity>(db, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = self.operate(db, access, item.id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone_command(db, Roles::Operator, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
+        // Validate condition type if provided
+        if !item.r#type.is_empty() && !IfTypes::is_valid_type(&item.r#type) {
+            return ModelOutput::invalid(format!(
+                "Invalid condition type '{}'. Valid types are: {}",
+                item.r#type,
+                IfTypes::valid_types().join(", ")
+            ));
+        }
+
         let active_zone_command_if = ZoneCommandIfActiveModel 
         {
             id: Set(item.id),
@@ -62,6 +84,7 @@ impl ZoneCommandIfService
             zone_command_id: Set(item.zone_command_id),
             device_id: Set(item.device_id),
             command_id: Set(item.command_id),
+            r#type: Set(item.r#type),
             description: Set(item.description),
             enable: Set(item.enable),
         };
@@ -70,8 +93,21 @@ impl ZoneCommandIfService
     }
 
     //------------------------- Add
-    pub async fn add(&self, db: &DatabaseConnection, item: ZoneCommandIfModel) -> ModelOutput<ZoneCommandIfModel> 
+    pub async fn add(&self, db: &DatabaseConnection, access: &Access, item: ZoneCommandIfModel) -> ModelOutput<ZoneCommandIfModel> 
     {
+        if let Err(e) = access.require_owned::<ZoneCommandEntity>(db, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_owned::<DeviceEntity>(db, item.device_id).await { return ModelOutput::fail(e); }
+        if let Err(e) = access.require_zone_command(db, Roles::Operator, item.zone_command_id).await { return ModelOutput::fail(e); }
+        if item.device_id != 0 && let Err(e) = access.require_device(db, Roles::Operator, item.device_id).await { return ModelOutput::fail(e); }
+        // Validate condition type if provided
+        if !item.r#type.is_empty() && !IfTypes::is_valid_type(&item.r#type) {
+            return ModelOutput::invalid(format!(
+                "Invalid condition type '{}'. Valid types are: {}",
+                item.r#type,
+                IfTypes::valid_types().join(", ")
+            ));
+        }
+
         let active_zone_command_if = ZoneCommandIfActiveModel 
         {
             id: Default::default(),
@@ -79,6 +115,7 @@ impl ZoneCommandIfService
             zone_command_id: Set(item.zone_command_id),
             device_id: Set(item.device_id),
             command_id: Set(item.command_id),
+            r#type: Set(item.r#type),
             description: Set(item.description),
             enable: Set(item.enable),
         };
@@ -87,14 +124,25 @@ impl ZoneCommandIfService
     }
 
     //------------------------- Delete
-    pub async fn delete(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<String> 
+    pub async fn delete(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<String> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.delete(db, id).await
     }
 
     //------------------------- Status
-    pub async fn status(&self, db: &DatabaseConnection, id: i32) -> ModelOutput<ZoneCommandIfModel> 
+    pub async fn status(&self, db: &DatabaseConnection, access: &Access, id: i32) -> ModelOutput<ZoneCommandIfModel> 
     {
+        if let Err(e) = self.operate(db, access, id).await { return ModelOutput::fail(e); }
         self.logic.status(db, id).await
     }
+
+    //------------------------- Operate
+    /// Operators may switch the conditions of zone commands in their granted zones
+    async fn operate(&self, db: &DatabaseConnection, access: &Access, id: i32) -> Result<(), ApiError>
+    {
+        access.require_owned::<ZoneCommandIfEntity>(db, id).await?;
+        let item = self.logic.item(db, id).await.into_result()?;
+        access.require_zone_command(db, Roles::Operator, item.zone_command_id).await
+    }
 }
@@ -0,0 +1,324 @@
+//--------------------------------------------------------------------------------- Location
+// src/api/socket/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// GET /ws: live engine events (device changes, command executions, rule firings, timer transitions,
+// timer limit cutoffs, new log entries) as JSON text messages. Subscribe with ?zones=1,2&devices=3 or by sending
+// {"action": "subscribe", "zones": [1]} / {"action": "unsubscribe", "devices": [3]};
+// no subscription means every event. Users only receive events of their own zones; events without a
+// zone (log entries, timers that drive no device) only go to admins. Framing, pings and the
+// handshake are axum's (tokio-tungstenite)
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::{BTreeSet, HashMap};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+use crate::api::access::Access;
+use crate::api::error::ApiError;
+use crate::api::extract::Query;
+use crate::engine::events::Event;
+use crate::orm::models::zone::{Column as ZoneColumn, Entity as ZoneEntity};
+use crate::AppState;
+
+//--------------------------------------------------------------------------------- Constants
+/// Largest message a client may send, subscriptions are a few bytes
+const MAX_MESSAGE: usize = 64 * 1024;
+
+//--------------------------------------------------------------------------------- Messages
+/// Sent by the client
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientRequest
+{
+    Subscribe
+    {
+        #[serde(default)]
+        zones: Vec<i32>,
+        #[serde(default)]
+        devices: Vec<i32>,
+    },
+    Unsubscribe
+    {
+        #[serde(default)]
+        zones: Vec<i32>,
+        #[serde(default)]
+        devices: Vec<i32>,
+    },
+}
+
+/// Sent by the server besides the bus events, tagged the same way
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Reply
+{
+    Subscribed { zones: Vec<i32>, devices: Vec<i32> },
+    Lagged { skipped: u64 },
+    Error { message: String },
+}
+
+//--------------------------------------------------------------------------------- Subscription
+#[derive(Debug, Clone, Default)]
+struct Subscription
+{
+    zones: BTreeSet<i32>,
+    devices: BTreeSet<i32>,
+}
+
+impl Subscription
+{
+    /// Everything when nothing is subscribed, otherwise events of the subscribed zones or devices
+    fn matches(&self, event: &Event) -> bool
+    {
+        if self.zones.is_empty() && self.devices.is_empty() { return true; }
+        event.zone_ids().iter().any(|zone_id| self.zones.contains(zone_id))
+            || event.device_ids().iter().any(|device_id| self.devices.contains(device_id))
+    }
+
+    fn reply(&self) -> Reply
+    {
+        Reply::Subscribed { zones: self.zones.iter().copied().collect(), devices: self.devices.iter().copied().collect() }
+    }
+}
+
+/// Comma separated ids from the query string
+fn parse_ids(params: &HashMap<String, String>, name: &str) -> Result<BTreeSet<i32>, ApiError>
+{
+    params.get(name).map(String::as_str).unwrap_or_default()
+        .split(',').map(str::trim).filter(|id| !id.is_empty())
+        .map(|id| id.parse::<i32>().map_err(|_| ApiError::Validation(format!("Invalid {} id '{}'", name, id))))
+        .collect()
+}
+
+//--------------------------------------------------------------------------------- Handler
+pub async fn ws_handler(
+    State(state): State<AppState>,
+    Extension(access): Extension<Access>,
+    Query(params): Query<HashMap<String, String>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let subscription = match (parse_ids(&params, "zones"), parse_ids(&params, "devices")) {
+        (Ok(zones), Ok(devices)) => Subscription { zones, devices },
+        (Err(e), _) | (_, Err(e)) => return e.into_response(),
+    };
+    let owned = match owned_zones(&state.db, &access).await {
+        Ok(owned) => owned,
+        Err(e) => return e.into_response(),
+    };
+
+    let session = Session { events: state.executor.events.subscribe(), subscription, owned };
+    upgrade.max_message_size(MAX_MESSAGE)
+        .on_failed_upgrade(|e| warn!("Socket::ws_handler - Upgrade failed: {}", e))
+        .on_upgrade(move |socket| session.run(socket))
+}
+
+/// Zones whose events the user may see, None for every zone
+async fn owned_zones(db: &DatabaseConnection, access: &Access) -> Result<Option<BTreeSet<i32>>, ApiError>
+{
+    if access.is_admin() { return Ok(None); }
+
+    let ids: Vec<i32> = ZoneEntity::find().select_only().column(ZoneColumn::Id).filter(access.scope::<ZoneEntity>())
+        .into_tuple().all(db).await
+        .map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+    Ok(Some(ids.into_iter().collect()))
+}
+
+//--------------------------------------------------------------------------------- Session
+struct Session
+{
+    events: broadcast::Receiver<Event>,
+    subscription: Subscription,
+    owned: Option<BTreeSet<i32>>,
+}
+
+impl Session
+{
+    //------------------------- Run
+    /// Pings are answered by the socket itself
+    async fn run(mut self, mut socket: WebSocket)
+    {
+        let mut open = send(&mut socket, &self.subscription.reply()).await;
+        while open
+        {
+            open = tokio::select!
+            {
+                received = self.events.recv() => match received
+                {
+                    Ok(event) if self.wants(&event) => send(&mut socket, &event).await,
+                    Ok(_) => true,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => send(&mut socket, &Reply::Lagged { skipped }).await,
+                    Err(broadcast::error::RecvError::Closed) => false,
+                },
+                message = socket.recv() => match message
+                {
+                    Some(Ok(Message::Text(text))) =>
+                    {
+                        let reply = self.on_request(&text);
+                        send(&mut socket, &reply).await
+                    }
+                    Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Binary(_))) => true,
+                    Some(Ok(Message::Close(_))) | None => false,
+                    Some(Err(e)) =>
+                    {
+                        debug!("Socket::run - Closing on a bad frame: {}", e);
+                        false
+                    }
+                },
+            };
+        }
+
+        let _ = socket.send(Message::Close(None)).await;
+    }
+
+    //------------------------- Wants
+    /// Within the user's zones and the subscription; events without a zone, such as log entries, only for admins
+    fn wants(&self, event: &Event) -> bool
+    {
+        let visible = match &self.owned
+        {
+            Some(owned) => event.zone_ids().iter().any(|zone_id| owned.contains(zone_id)),
+            None => true,
+        };
+        visible && self.subscription.matches(event)
+    }
+
+    //------------------------- On Request
+    fn on_request(&mut self, text: &str) -> Reply
+    {
+        match serde_json::from_str::<ClientRequest>(text)
+        {
+            Ok(ClientRequest::Subscribe { zones, devices }) =>
+            {
+                self.subscription.zones.extend(zones);
+                self.subscription.devices.extend(devices);
+                self.subscription.reply()
+            }
+            Ok(ClientRequest::Unsubscribe { zones, devices }) =>
+            {
+                self.subscription.zones.retain(|zone_id| !zones.contains(zone_id));
+                self.subscription.devices.retain(|device_id| !devices.contains(device_id));
+                self.subscription.reply()
+            }
+            Err(e) => Reply::Error { message: format!("Invalid request: {}", e) },
+        }
+    }
+}
+
+/// Send a JSON text message, false once the connection is gone
+async fn send<T: Serialize>(socket: &mut WebSocket, value: &T) -> bool
+{
+    match serde_json::to_string(value)
+    {
+        Ok(text) => socket.send(Message::Text(text.into())).await.is_ok(),
+        Err(e) =>
+        {
+            warn!("Socket::send - Failed to serialize: {}", e);
+            true
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message as ClientMessage;
+    use crate::engine::events::EventBus;
+
+    fn changed(device_id: i32, zone_id: i32) -> Event
+    {
+        Event::DeviceChanged { device_id, zone_id, command_id: 1, previous: 0, value: 1 }
+    }
+
+    fn log() -> Event
+    {
+        Event::LogAdded { log_id: 1, date: String::new(), name: "Device Execute".to_string(), status: true, data: String::new() }
+    }
+
+    #[test]
+    fn subscription_matches_zones_or_devices()
+    {
+        assert!(Subscription::default().matches(&changed(3, 1)));
+        let subscription = Subscription { zones: BTreeSet::from([2]), devices: BTreeSet::from([3]) };
+        assert!(subscription.matches(&changed(3, 1)));
+        assert!(subscription.matches(&changed(4, 2)));
+        assert!(!subscription.matches(&changed(4, 1)));
+        assert!(!subscription.matches(&log()));
+    }
+
+    #[test]
+    fn zone_less_events_are_for_admins_only()
+    {
+        let bus = EventBus::new(4);
+        let admin = Session { events: bus.subscribe(), subscription: Subscription::default(), owned: None };
+        let user = Session { events: bus.subscribe(), subscription: Subscription::default(), owned: Some(BTreeSet::from([1])) };
+
+        assert!(admin.wants(&log()));
+        assert!(!user.wants(&log()));
+        assert!(user.wants(&changed(3, 1)));
+        assert!(!user.wants(&changed(3, 2)));
+        assert!(!user.wants(&Event::TimerChanged { timer_id: 1, name: "Idle".to_string(), open: true, device_ids: Vec::new(), zone_ids: Vec::new() }));
+    }
+
+    #[test]
+    fn parses_ids()
+    {
+        let params = HashMap::from([("zones".to_string(), "1, 2,,3".to_string()), ("devices".to_string(), "x".to_string())]);
+        assert_eq!(parse_ids(&params, "zones").unwrap(), BTreeSet::from([1, 2, 3]));
+        assert!(parse_ids(&params, "devices").is_err());
+        assert!(parse_ids(&params, "missing").unwrap().is_empty());
+    }
+
+    /// Next text message as JSON
+    async fn next<S>(client: &mut S) -> serde_json::Value
+    where
+        S: StreamExt<Item = Result<ClientMessage, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        loop
+        {
+            if let ClientMessage::Text(text) = client.next().await.unwrap().unwrap() { return serde_json::from_str(&text).unwrap(); }
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_the_events_of_the_subscription()
+    {
+        let bus = EventBus::new(16);
+        let session_bus = bus.clone();
+        let app = Router::new().route("/ws", get(move |upgrade: WebSocketUpgrade|
+        {
+            let session = Session { events: session_bus.subscribe(), subscription: Subscription::default(), owned: Some(BTreeSet::from([1, 2])) };
+            async move { upgrade.on_upgrade(move |socket| session.run(socket)) }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", address)).await.unwrap();
+        assert_eq!(next(&mut client).await["event"], "subscribed");
+
+        // Wait for the subscription reply before publishing, the session is subscribed to the bus by then
+        client.send(ClientMessage::Text(r#"{"action": "subscribe", "zones": [2]}"#.into())).await.unwrap();
+        let reply = next(&mut client).await;
+        assert_eq!(reply["zones"], serde_json::json!([2]));
+
+        bus.publish(log());
+        bus.publish(changed(3, 1));
+        bus.publish(changed(4, 2));
+        let event = next(&mut client).await;
+        assert_eq!(event["event"], "device_changed");
+        assert_eq!(event["device_id"], 4);
+
+        client.send(ClientMessage::Text("{}".into())).await.unwrap();
+        assert_eq!(next(&mut client).await["event"], "error");
+    }
+}
@@ -2,10 +2,12 @@
 // src/args.rs
 
 //--------------------------------------------------------------------------------- Description
-// This file contains argument handling logic for the application
+// This file contains argument handling logic for the application; a failing option returns the
+// error, so the process exits with a non-zero code
 
 //--------------------------------------------------------------------------------- Import
 use crate::logics;
+use crate::orm;
 use sea_orm::DatabaseConnection;
 
 //--------------------------------------------------------------------------------- Argument Handler
@@ -17,19 +19,20 @@ pub async fn handle_arguments(db: &DatabaseConnection) -> Result<bool, Box<dyn s
     {
         match args[1].as_str() 
         {
+            "--migrate" => 
+            {
+                println!("🔧 Applying database migrations...");
+                let applied = orm::migrations::migrate(db).await.map_err(|e| format!("❌ Error applying migrations: {}", e))?;
+                println!("✅ Database is up to date ({} migrations applied)", applied);
+                return Ok(true); // Return true to indicate we should exit
+            }
             "--add-users" => 
             {
                 println!("🔧 Adding sample users...");
-                if let Err(e) = logics::user::add_sample_users(db).await 
-                {
-                    eprintln!("❌ Error adding sample users: {}", e);
-                }
+                logics::user::add_sample_users(db).await.map_err(|e| format!("❌ Error adding sample users: {}", e))?;
             
                 println!("\n📋 Listing all users:");
-                if let Err(e) = logics::user::list_all_users(db).await 
-                {
-                    eprintln!("❌ Error listing users: {}", e);
-                }
+                logics::user::list_all_users(db).await.map_err(|e| format!("❌ Error listing users: {}", e))?;
                 
                 println!("\n✅ Sample users added successfully!");
                 return Ok(true); // Return true to indicate we should exit
@@ -37,11 +40,7 @@ pub async fn handle_arguments(db: &DatabaseConnection) -> Result<bool, Box<dyn s
             "--add-samples" => 
             {
                 let admin = logics::admin::Admin::new();
-                if let Err(e) = admin.add_samples(db).await {
-                    eprintln!("❌ Error adding samples: {}", e);
-                }
-
-                
+                admin.add_samples(db).await.map_err(|e| format!("❌ Error adding samples: {}", e))?;
                 return Ok(true); // Return true to indicate we should exit
             }
             "--help" | "-h" => 
@@ -50,6 +49,7 @@ pub async fn handle_arguments(db: &DatabaseConnection) -> Result<bool, Box<dyn s
                 println!("Usage: {} [OPTIONS]", args[0]);
                 println!();
                 println!("Options:");
+                println!("  --migrate      Apply pending database migrations");
                 println!("  --add-users    Add sample users to the database");
                 println!("  --add-samples  Add sample data via admin module");
                 println!("  --help, -h     Show this help message");
@@ -59,9 +59,7 @@ pub async fn handle_arguments(db: &DatabaseConnection) -> Result<bool, Box<dyn s
             }
             _ => 
             {
-                eprintln!("❌ Unknown argument: {}", args[1]);
-                eprintln!("Use --help for available options.");
-                return Ok(true); // Return true to indicate we should exit
+                return Err(format!("❌ Unknown argument: {}, use --help for available options", args[1]).into());
             }
         }
     }
@@ -5,7 +5,8 @@
 // OpenAPI documentation configuration for the Raspberry Pi IoT Core API
 
 //--------------------------------------------------------------------------------- Import
-use utoipa::OpenApi;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 
 //--------------------------------------------------------------------------------- OpenAPI Documentation
 #[derive(OpenApi)]
@@ -23,6 +24,7 @@ use utoipa::OpenApi;
         crate::api::handlers::config::create_config,
         crate::api::handlers::config::delete_config,
         // User 
+        crate::api::handlers::user::login_user,
         crate::api::handlers::user::list_users,
         crate::api::handlers::user::get_user,
         crate::api::handlers::user::status_user,
@@ -30,7 +32,14 @@ use utoipa::OpenApi;
         crate::api::handlers::user::disable_user,
         crate::api::handlers::user::update_user,
         crate::api::handlers::user::create_user,
+        crate::api::handlers::user::rotate_user_key,
+        crate::api::handlers::user::telegram_link_user,
         crate::api::handlers::user::delete_user,
+        crate::api::handlers::user::get_user_role,
+        crate::api::handlers::user::update_user_role,
+        crate::api::handlers::user::list_user_zones,
+        crate::api::handlers::user::grant_user_zone,
+        crate::api::handlers::user::revoke_user_zone,
         // Port 
         crate::api::handlers::port::list_ports,
         crate::api::handlers::port::get_port,
@@ -40,6 +49,9 @@ use utoipa::OpenApi;
         crate::api::handlers::port::update_port,
         crate::api::handlers::port::create_port,
         crate::api::handlers::port::delete_port,
+        crate::api::handlers::port::read_port,
+        crate::api::handlers::port::write_port,
+        crate::api::handlers::port::port_drivers,
         // Device 
         crate::api::handlers::device::list_devices,
         crate::api::handlers::device::get_device,
@@ -49,6 +61,7 @@ use utoipa::OpenApi;
         crate::api::handlers::device::update_device,
         crate::api::handlers::device::create_device,
         crate::api::handlers::device::delete_device,
+        crate::api::handlers::device::execute_device,
         // Zone 
         crate::api::handlers::zone::list_zones,
         crate::api::handlers::zone::get_zone,
@@ -113,6 +126,7 @@ use utoipa::OpenApi;
         crate::api::handlers::timer_limit::update_timer_limit,
         crate::api::handlers::timer_limit::create_timer_limit,
         crate::api::handlers::timer_limit::delete_timer_limit,
+        crate::api::handlers::timer_limit::active_timer_limits,
         // Zone Command 
         crate::api::handlers::zone_command::list_zone_commands,
         crate::api::handlers::zone_command::get_zone_command,
@@ -122,6 +136,7 @@ use utoipa::OpenApi;
         crate::api::handlers::zone_command::update_zone_command,
         crate::api::handlers::zone_command::create_zone_command,
         crate::api::handlers::zone_command::delete_zone_command,
+        crate::api::handlers::zone_command::run_zone_command,
         // Zone Command Action 
         crate::api::handlers::zone_command_action::list_zone_command_actions,
         crate::api::handlers::zone_command_action::get_zone_command_action,
@@ -140,11 +155,35 @@ use utoipa::OpenApi;
         crate::api::handlers::zone_command_if::update_zone_command_if,
         crate::api::handlers::zone_command_if::create_zone_command_if,
         crate::api::handlers::zone_command_if::delete_zone_command_if,
+        // Webhook 
+        crate::api::handlers::webhook::list_webhooks,
+        crate::api::handlers::webhook::get_webhook,
+        crate::api::handlers::webhook::status_webhook,
+        crate::api::handlers::webhook::enable_webhook,
+        crate::api::handlers::webhook::disable_webhook,
+        crate::api::handlers::webhook::update_webhook,
+        crate::api::handlers::webhook::create_webhook,
+        crate::api::handlers::webhook::delete_webhook,
+        crate::api::handlers::webhook_delivery::list_webhook_deliveries,
+        crate::api::handlers::webhook_delivery::get_webhook_delivery,
+        crate::api::handlers::webhook_delivery::replay_webhook_delivery,
+        crate::api::handlers::webhook_delivery::delete_webhook_delivery,
+        // Notification 
+        crate::api::handlers::notification::list_notifications,
+        crate::api::handlers::notification::get_notification,
+        crate::api::handlers::notification::status_notification,
+        crate::api::handlers::notification::enable_notification,
+        crate::api::handlers::notification::disable_notification,
+        crate::api::handlers::notification::update_notification,
+        crate::api::handlers::notification::create_notification,
+        crate::api::handlers::notification::delete_notification,
     ),
     components(
         schemas(
             crate::orm::models::config::Model,
             crate::orm::models::user::Model,
+            crate::orm::models::user_role::Model,
+            crate::orm::models::user_zone::Model,
             crate::orm::models::device::Model,
             crate::orm::models::zone::Model,
             crate::orm::models::device_command::Model,
@@ -157,8 +196,21 @@ use utoipa::OpenApi;
             crate::orm::models::zone_command::Model,
             crate::orm::models::zone_command_action::Model,
             crate::orm::models::zone_command_if::Model,
+            crate::orm::models::webhook::Model,
+            crate::orm::models::webhook_delivery::Model,
+            crate::orm::models::notification::Model,
+            crate::engine::limit::ActiveTimerLimit,
+            crate::api::auth::Token,
+            crate::api::auth::UserKey,
+            crate::hardware::Drivers,
+            crate::telegram::TelegramLink,
+            crate::orm::logics::graph::ZoneGraph,
+            crate::orm::logics::graph::DeviceGraph,
+            crate::orm::logics::graph::RuleGraph,
+            crate::orm::logics::graph::TimerGraph,
         )
     ),
+    modifiers(&SecurityAddon),
     tags(
             (name = "⚙️ Config"),
             (name = "🔌 Port"),
@@ -173,8 +225,27 @@ use utoipa::OpenApi;
             (name = "🎯 Zone Command"),
             (name = "⚡ Zone Command Action"),
             (name = "🔀 Zone Command Condition"),
+            (name = "🪝 Webhook"),
+            (name = "🔔 Notification"),
             (name = "👥 User")
     ),
 )]
 pub struct ApiDoc;
 
+//--------------------------------------------------------------------------------- Security
+/// Bearer token from /user/login or a user's key in X-API-Key, required on every route unless marked otherwise
+struct SecurityAddon;
+
+impl Modify for SecurityAddon
+{
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi)
+    {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme("bearer", SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()));
+        components.add_security_scheme("api_key", SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))));
+        openapi.security = Some(vec![
+            SecurityRequirement::new("bearer", Vec::<String>::new()),
+            SecurityRequirement::new("api_key", Vec::<String>::new()),
+        ]);
+    }
+}
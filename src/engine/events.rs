@@ -0,0 +1,79 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/events.rs
+
+//--------------------------------------------------------------------------------- Description
+// Internal broadcast bus that the engine publishes state changes and input readings to; the rule
+// engine and limit watcher react to it, the /ws endpoint streams it to clients and the webhooks post it out
+
+//--------------------------------------------------------------------------------- Import
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+//--------------------------------------------------------------------------------- Event
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event
+{
+    DeviceChanged { device_id: i32, zone_id: i32, command_id: i32, previous: i32, value: i32 },
+    CommandExecuted { device_id: i32, zone_id: i32, command_id: i32, command: String, value: i32 },
+    DeviceRead { device_id: i32, zone_id: i32, command_id: i32, command: Option<String>, value: i32 },
+    RuleFired { zone_command_id: i32, zone_id: i32, name: String, manual: bool, actions: usize },
+    TimerChanged { timer_id: i32, name: String, open: bool, device_ids: Vec<i32>, zone_ids: Vec<i32> },
+    LimitReached { timer_limit_id: i32, device_id: i32, zone_id: i32, command_to_id: i32, status: bool },
+    LogAdded { log_id: i32, date: String, name: String, status: bool, data: String },
+}
+
+impl Event
+{
+    /// Zones the event is about, empty for system-wide events such as log entries
+    pub fn zone_ids(&self) -> Vec<i32>
+    {
+        match self
+        {
+            Event::DeviceChanged { zone_id, .. } | Event::CommandExecuted { zone_id, .. } | Event::DeviceRead { zone_id, .. } | Event::RuleFired { zone_id, .. } | Event::LimitReached { zone_id, .. } => vec![*zone_id],
+            Event::TimerChanged { zone_ids, .. } => zone_ids.clone(),
+            Event::LogAdded { .. } => Vec::new(),
+        }
+    }
+
+    /// Devices the event is about
+    pub fn device_ids(&self) -> Vec<i32>
+    {
+        match self
+        {
+            Event::DeviceChanged { device_id, .. } | Event::CommandExecuted { device_id, .. } | Event::DeviceRead { device_id, .. } | Event::LimitReached { device_id, .. } => vec![*device_id],
+            Event::TimerChanged { device_ids, .. } => device_ids.clone(),
+            Event::RuleFired { .. } | Event::LogAdded { .. } => Vec::new(),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Bus
+#[derive(Clone)]
+pub struct EventBus
+{
+    sender: broadcast::Sender<Event>,
+}
+
+impl EventBus
+{
+    //------------------------- New
+    pub fn new(capacity: usize) -> Self
+    {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    //------------------------- Publish
+    pub fn publish(&self, event: Event)
+    {
+        // No subscribers is not an error: the event is simply dropped
+        let _ = self.sender.send(event);
+    }
+
+    //------------------------- Subscribe
+    pub fn subscribe(&self) -> broadcast::Receiver<Event>
+    {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,271 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/executor.rs
+
+//--------------------------------------------------------------------------------- Description
+// Device command execution: resolve device, port and command, drive the hardware and record the outcome
+
+//--------------------------------------------------------------------------------- Import
+use std::sync::Arc;
+use std::time::Duration;
+use sea_orm::{DatabaseConnection, Set};
+use tracing::{debug, error, info};
+use crate::logics::error::ApiError;
+use crate::hardware::{self, gpio::PinDriver, Buses};
+use crate::logics::general::ModelOutput;
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, port::PortORM};
+use crate::orm::models::device::{ActiveModel as DeviceActiveModel, Model as DeviceModel};
+use crate::orm::models::device_command::Model as DeviceCommandModel;
+use crate::orm::models::port::{ActiveModel as PortActiveModel, Model as PortModel};
+use super::events::{Event, EventBus};
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct Executor
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub db: DatabaseConnection,
+    pub pins: Arc<dyn PinDriver>,
+    pub buses: Buses,
+    pub events: EventBus,
+}
+
+impl Executor
+{
+    //------------------------- New
+    pub fn new(db: DatabaseConnection, pins: Arc<dyn PinDriver>, buses: Buses, events: EventBus) -> Self
+    {
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "Executor".to_string(),
+            db,
+            pins,
+            buses,
+            events,
+        }
+    }
+
+    //------------------------- Execute
+    /// Run a device command, given by id or by name, against the device's port
+    pub async fn execute(&self, device_id: i32, command: &str) -> ModelOutput<DeviceModel>
+    {
+        let this_method = "execute";
+        if self.verbose { debug!("{}::{} - Executing command '{}' on device {}", self.this_class, this_method, command, device_id); }
+
+        match self.run(device_id, command).await
+        {
+            Ok((device, command)) =>
+            {
+                let message = format!("Device '{}' ({}) executed command '{}' ({}), value: {}", device.name, device.id, command.name, command.id, device.value);
+                if self.verbose { info!("{}::{} - {}", self.this_class, this_method, message); }
+                self.events.publish(Event::CommandExecuted { device_id: device.id, zone_id: device.zone_id, command_id: command.id, command: command.name.clone(), value: device.value });
+                super::record(self, "Device Execute", true, message.clone()).await;
+                ModelOutput::success(device, message)
+            }
+            Err(e) =>
+            {
+                let error_msg = format!("Device {} command '{}' failed: {}", device_id, command, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                super::record(self, "Device Execute", false, error_msg.clone()).await;
+                ModelOutput::fail(e.with_message(error_msg))
+            }
+        }
+    }
+
+    //------------------------- Run
+    async fn run(&self, device_id: i32, command: &str) -> Result<(DeviceModel, DeviceCommandModel), ApiError>
+    {
+        let device = DeviceORM::new(self.verbose, self.log).item(&self.db, device_id).await.into_result()?;
+        if !device.enable { return Err(ApiError::Validation(format!("Device '{}' is disabled", device.name))); }
+
+        let command = self.resolve_command(&device, command).await?;
+        if !command.enable { return Err(ApiError::Validation(format!("Command '{}' is disabled", command.name))); }
+
+        let port = PortORM::new(self.verbose, self.log).item(&self.db, device.port_id).await.into_result()?;
+
+        // Drive value_from, wait for the delay (ms), then settle on value_to
+        let value_from = command.value_from.unwrap_or(0);
+        let value_to = command.value_to.unwrap_or(value_from);
+        let delay = command.delay.unwrap_or(0);
+
+        self.drive(&port, &device.address, value_from, command.frequency).await?;
+        if delay > 0 { tokio::time::sleep(Duration::from_millis(delay as u64)).await; }
+        if value_to != value_from { self.drive(&port, &device.address, value_to, command.frequency).await?; }
+
+        let previous = device.value;
+        let changed = device.command_id != command.id;
+        let mut active_device: DeviceActiveModel = device.into();
+        active_device.value = Set(value_to);
+        active_device.command_id = Set(command.id);
+        // date: since when the current command is active, timer limits count from it after a restart
+        if changed { active_device.date = Set(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()); }
+        let device = DeviceORM::new(self.verbose, self.log).update(&self.db, active_device).await.into_result()?;
+
+        self.events.publish(Event::DeviceChanged { device_id: device.id, zone_id: device.zone_id, command_id: device.command_id, previous, value: device.value });
+        Ok((device, command))
+    }
+
+    //------------------------- Resolve Command
+    async fn resolve_command(&self, device: &DeviceModel, command: &str) -> Result<DeviceCommandModel, ApiError>
+    {
+        let orm = DeviceCommandORM::new(self.verbose, self.log);
+
+        match command.parse::<i32>()
+        {
+            Ok(command_id) =>
+            {
+                let command = orm.item(&self.db, command_id).await.into_result()?;
+                if command.device_id != device.id { return Err(ApiError::Validation(format!("Command {} does not belong to device '{}'", command_id, device.name))); }
+                Ok(command)
+            }
+            Err(_) => orm.item_by_name(&self.db, device.id, command).await.into_result(),
+        }
+    }
+
+    //------------------------- Drive
+    /// Off the async runtime: a bus write waits for the device to answer
+    pub async fn drive(&self, port: &PortModel, address: &str, value: i32, frequency: Option<i32>) -> Result<PortModel, ApiError>
+    {
+        let (pins, buses, target, address) = (self.pins.clone(), self.buses.clone(), port.clone(), address.to_string());
+        tokio::task::spawn_blocking(move || hardware::port::write_device(pins.as_ref(), &buses, &target, &address, value, frequency)).await
+            .map_err(|e| ApiError::Internal(e.to_string()))??;
+
+        let mut active_port: PortActiveModel = port.clone().into();
+        active_port.value = Set(value);
+        PortORM::new(self.verbose, self.log).update(&self.db, active_port).await.into_result()
+    }
+
+    //------------------------- Sense
+    /// Off the async runtime like drive, the level is only reported, never saved
+    pub async fn sense(&self, port: &PortModel) -> Result<i32, ApiError>
+    {
+        let (pins, target) = (self.pins.clone(), port.clone());
+        let value = tokio::task::spawn_blocking(move || hardware::port::read(pins.as_ref(), &target)).await
+            .map_err(|e| ApiError::Internal(e.to_string()))??;
+        Ok(value)
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Mutex;
+    use std::time::Instant;
+    use sea_orm::{ActiveModelTrait, Database, EntityTrait, QueryOrder};
+    use axum::http::StatusCode;
+    use crate::hardware::HardwareError;
+    use crate::hardware::gpio::MockPinDriver;
+    use crate::orm::models::{device, device_command, log, port};
+    use super::*;
+
+    /// Mock pins that also remember every write, in order
+    #[derive(Default)]
+    struct RecordingPins
+    {
+        pins: MockPinDriver,
+        writes: Mutex<Vec<(u32, i32)>>,
+    }
+
+    impl PinDriver for RecordingPins
+    {
+        fn name(&self) -> &'static str { "recording" }
+
+        fn write(&self, line: u32, value: i32) -> Result<(), HardwareError>
+        {
+            self.writes.lock().unwrap().push((line, value));
+            self.pins.write(line, value)
+        }
+
+        fn read(&self, line: u32) -> Result<i32, HardwareError> { self.pins.read(line) }
+
+        fn output_level(&self, line: u32) -> Result<Option<i32>, HardwareError> { self.pins.output_level(line) }
+    }
+
+    struct Fixture
+    {
+        executor: Executor,
+        pins: Arc<RecordingPins>,
+        device: DeviceModel,
+    }
+
+    /// Samples plus a device of zone 1 on its own GPIO OUT line 5, still on no command (command_id 0)
+    async fn fixture() -> Fixture
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        let port = port::ActiveModel { user_id: Set(1), name: Set("T-Out".to_string()), pin: Set(0), port: Set(5), value: Set(0), description: Set(String::new()), enable: Set(true), protocol: Set("GPIO".to_string()), r#type: Set("OUT".to_string()), ..Default::default() };
+        let port = port.insert(&db).await.unwrap();
+        let device = device::ActiveModel { zone_id: Set(1), port_id: Set(port.id), power_id: Set(0), command_id: Set(0), value: Set(0), tune: Set(0), date: Set("2024-01-01 00:00:00".to_string()), address: Set(String::new()), name: Set("T-Pump".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        let device = device.insert(&db).await.unwrap();
+
+        let pins = Arc::new(RecordingPins::default());
+        let driver: Arc<dyn PinDriver> = pins.clone();
+        Fixture { executor: Executor::new(db, driver.clone(), Buses::mock(driver), EventBus::new(64)), pins, device }
+    }
+
+    async fn command(fixture: &Fixture, name: &str, value_from: i32, value_to: i32, delay: i32, enable: bool) -> DeviceCommandModel
+    {
+        let command = device_command::ActiveModel { device_id: Set(fixture.device.id), name: Set(name.to_string()), value_from: Set(Some(value_from)), value_to: Set(Some(value_to)), delay: Set(Some(delay)), frequency: Set(None), description: Set(String::new()), reload: Set(false), enable: Set(enable), r#type: Set("NONE".to_string()), ..Default::default() };
+        command.insert(&fixture.executor.db).await.unwrap()
+    }
+
+    fn status(output: ModelOutput<DeviceModel>) -> StatusCode
+    {
+        output.into_result().err().map(|e| e.status_code()).unwrap_or(StatusCode::OK)
+    }
+
+    #[tokio::test]
+    async fn pulse_writes_from_then_to_and_records_the_change()
+    {
+        let fixture = fixture().await;
+        let pulse = command(&fixture, "pulse", 1, 0, 50, true).await;
+        let mut events = fixture.executor.events.subscribe();
+
+        let started = Instant::now();
+        let device = fixture.executor.execute(fixture.device.id, "pulse").await.into_result().unwrap();
+        assert!(started.elapsed().as_millis() >= 50, "the delay separates the two writes");
+        assert_eq!(*fixture.pins.writes.lock().unwrap(), vec![(5, 1), (5, 0)]);
+
+        assert_eq!((device.value, device.command_id), (0, pulse.id));
+        assert_ne!(device.date, fixture.device.date, "the date moves with the command");
+        let log = log::Entity::find().order_by_desc(log::Column::Id).one(&fixture.executor.db).await.unwrap().unwrap();
+        assert!(log.status && log.name == "Device Execute" && log.data.contains("'pulse'"), "{}", log.data);
+
+        let mut published = Vec::new();
+        while let Ok(event) = events.try_recv() { published.push(event); }
+        assert!(published.iter().any(|event| matches!(event, Event::DeviceChanged { device_id, command_id, previous: 0, value: 0, .. } if *device_id == device.id && *command_id == pulse.id)));
+        assert!(published.iter().any(|event| matches!(event, Event::CommandExecuted { device_id, command, .. } if *device_id == device.id && command == "pulse")));
+
+        // Same command again: same value, the date stays
+        let again = fixture.executor.execute(fixture.device.id, &pulse.id.to_string()).await.into_result().unwrap();
+        assert_eq!(again.date, device.date);
+    }
+
+    #[tokio::test]
+    async fn missing_or_disabled_rows_are_refused()
+    {
+        let fixture = fixture().await;
+        command(&fixture, "on", 1, 1, 0, true).await;
+        let off = command(&fixture, "off", 0, 0, 0, false).await;
+
+        assert_eq!(status(fixture.executor.execute(9999, "on").await), StatusCode::NOT_FOUND);
+        assert_eq!(status(fixture.executor.execute(fixture.device.id, "missing").await), StatusCode::NOT_FOUND);
+        assert_eq!(status(fixture.executor.execute(fixture.device.id, "9999").await), StatusCode::NOT_FOUND);
+        assert_eq!(status(fixture.executor.execute(fixture.device.id, "off").await), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(status(fixture.executor.execute(1, &off.id.to_string()).await), StatusCode::UNPROCESSABLE_ENTITY, "a command of another device");
+
+        let mut disabled: device::ActiveModel = fixture.device.clone().into();
+        disabled.enable = Set(false);
+        disabled.update(&fixture.executor.db).await.unwrap();
+        assert_eq!(status(fixture.executor.execute(fixture.device.id, "on").await), StatusCode::UNPROCESSABLE_ENTITY);
+
+        assert!(fixture.pins.writes.lock().unwrap().is_empty(), "nothing reaches the pins");
+        let log = log::Entity::find().order_by_desc(log::Column::Id).one(&fixture.executor.db).await.unwrap().unwrap();
+        assert!(!log.status && log.data.contains("disabled"), "{}", log.data);
+    }
+}
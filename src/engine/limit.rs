@@ -0,0 +1,436 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/limit.rs
+
+//--------------------------------------------------------------------------------- Description
+// Timer limit watcher: forces command_to once command_from has been active for timer_limit.value units.
+// Countdowns live in memory; on start and after missed events they are rebuilt from each device's
+// current command and its date (since when that command is active). A failed cutoff is retried
+// with a growing delay for as long as the device stays on command_from. The API refreshes a limit it
+// adds, changes, switches or deletes, and the cutoff reloads it so a stale countdown never fires
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::AbortHandle;
+use tracing::{debug, error, info, warn};
+use utoipa::ToSchema;
+use crate::logics::error::ApiError;
+use crate::orm::logics::device::DeviceORM;
+use crate::orm::logics::timer_limit::TimerLimitORM;
+use crate::orm::models::device::Model as DeviceModel;
+use crate::orm::models::timer_limit::Model as TimerLimitModel;
+use super::events::Event;
+use super::executor::Executor;
+
+//--------------------------------------------------------------------------------- Constants
+/// First wait before a failed cutoff is tried again, doubled up to RETRY_MAX
+const RETRY_MIN: Duration = Duration::from_secs(5);
+const RETRY_MAX: Duration = Duration::from_secs(300);
+
+//--------------------------------------------------------------------------------- Active Limit
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Running timer limit countdown")]
+pub struct ActiveTimerLimit
+{
+    pub timer_limit_id: i32,
+    pub device_id: i32,
+    pub command_from_id: i32,
+    pub command_to_id: i32,
+    pub started: String,
+    pub expires: String,
+    pub remaining_seconds: i64,
+    /// Failed cutoffs so far, the countdown then runs to the next retry
+    pub retries: u32,
+    #[serde(skip)]
+    expires_at: DateTime<Local>,
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct LimitWatcher
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub executor: Executor,
+    pub unit: Duration,
+    countdowns: Arc<Mutex<HashMap<i32, (ActiveTimerLimit, AbortHandle)>>>,
+}
+
+impl LimitWatcher
+{
+    //------------------------- New
+    pub fn new(executor: Executor) -> Self
+    {
+        let unit = match std::env::var("TIMER_LIMIT_UNIT").unwrap_or_default().to_lowercase().as_str()
+        {
+            "second" | "seconds" => Duration::from_secs(1),
+            "hour" | "hours" => Duration::from_secs(3600),
+            _ => Duration::from_secs(60),
+        };
+
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "LimitWatcher".to_string(),
+            executor,
+            unit,
+            countdowns: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    //------------------------- Spawn
+    /// Start the background task that follows every command executed on a device, whatever its source
+    pub fn spawn(&self)
+    {
+        let watcher = self.clone();
+        let mut receiver = watcher.executor.events.subscribe();
+
+        tokio::spawn(async move
+        {
+            watcher.restore().await;
+            loop
+            {
+                match receiver.recv().await
+                {
+                    Ok(Event::DeviceChanged { device_id, command_id, .. }) => watcher.on_device_changed(device_id, command_id).await,
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) =>
+                    {
+                        warn!("{}::spawn - Skipped {} events, rebuilding countdowns", watcher.this_class, skipped);
+                        watcher.restore().await;
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    //------------------------- Active
+    /// Running countdowns with their remaining time
+    pub fn active(&self) -> Vec<ActiveTimerLimit>
+    {
+        let now = Local::now();
+        let mut items: Vec<ActiveTimerLimit> = self.countdowns.lock().unwrap().values()
+            .map(|(active, _)| ActiveTimerLimit { remaining_seconds: (active.expires_at - now).num_seconds().max(0), ..active.clone() })
+            .collect();
+
+        items.sort_by_key(|active| active.timer_limit_id);
+        items
+    }
+
+    //------------------------- Restore
+    /// Match the countdowns to the devices: drop those whose device left command_from and start
+    /// the missing ones from the device's date, so time spent while down or lagging still counts
+    async fn restore(&self)
+    {
+        let this_method = "restore";
+        let db = &self.executor.db;
+
+        let limits = match TimerLimitORM::new(false, self.log).items(db, HashMap::from([("enable".to_string(), "true".to_string())])).await.into_result()
+        {
+            Ok(limits) => limits,
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return; }
+        };
+        let devices: HashMap<i32, DeviceModel> = match DeviceORM::new(false, false).items(db, HashMap::new()).await.into_result()
+        {
+            Ok(devices) => devices.into_iter().map(|device| (device.id, device)).collect(),
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return; }
+        };
+
+        self.countdowns.lock().unwrap().retain(|_, (active, handle)|
+        {
+            let keep = devices.get(&active.device_id).is_some_and(|device| device.command_id == active.command_from_id);
+            if !keep { handle.abort(); }
+            keep
+        });
+
+        for limit in limits
+        {
+            let Some(device) = devices.get(&limit.device_id) else { continue; };
+            if device.command_id != limit.command_from_id { continue; }
+            let since = active_since(device);
+            self.start(limit, since);
+        }
+    }
+
+    //------------------------- Refresh
+    /// Follow a limit added, changed, switched or deleted through the API: its countdown is dropped
+    /// and, while the limit is enabled and its device already on command_from, started again from
+    /// the device's date as restore does
+    pub async fn refresh(&self, timer_limit_id: i32)
+    {
+        let this_method = "refresh";
+        let db = &self.executor.db;
+        if let Some((_, handle)) = self.countdowns.lock().unwrap().remove(&timer_limit_id) { handle.abort(); }
+
+        let limit = match TimerLimitORM::new(false, self.log).item(db, timer_limit_id).await.into_result()
+        {
+            Ok(limit) if limit.enable => limit,
+            Ok(_) | Err(ApiError::NotFound(_)) => return,
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return; }
+        };
+        let device = match DeviceORM::new(false, false).item(db, limit.device_id).await.into_result()
+        {
+            Ok(device) => device,
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return; }
+        };
+        if device.command_id == limit.command_from_id { self.start(limit, active_since(&device)); }
+    }
+
+    //------------------------- On Device Changed
+    async fn on_device_changed(&self, device_id: i32, command_id: i32)
+    {
+        let this_method = "on_device_changed";
+
+        // The device left the from-command: its countdowns are no longer needed
+        self.countdowns.lock().unwrap().retain(|_, (active, handle)|
+        {
+            let keep = active.device_id != device_id || active.command_from_id == command_id;
+            if !keep { handle.abort(); }
+            keep
+        });
+
+        let filters = HashMap::from([("device_id".to_string(), device_id.to_string()), ("command_from_id".to_string(), command_id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let limits = match TimerLimitORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()
+        {
+            Ok(limits) => limits,
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return; }
+        };
+
+        for limit in limits { self.start(limit, Local::now()); }
+    }
+
+    //------------------------- Start
+    /// Start a countdown unless one is already running: re-running the from-command never extends the limit
+    fn start(&self, limit: TimerLimitModel, started: DateTime<Local>)
+    {
+        if self.countdowns.lock().unwrap().contains_key(&limit.id) { return; }
+
+        let duration = self.unit * u32::try_from(limit.value).unwrap_or(0);
+        let expires_at = started + chrono::Duration::from_std(duration).unwrap_or_default();
+        if self.verbose { debug!("{}::start - Timer limit {} on device {} expires at {}", self.this_class, limit.id, limit.device_id, expires_at.format("%Y-%m-%d %H:%M:%S")); }
+        self.schedule(limit, started, expires_at, 0);
+    }
+
+    //------------------------- Schedule
+    /// Run the cutoff at expires_at, at once when that has passed
+    fn schedule(&self, limit: TimerLimitModel, started: DateTime<Local>, expires_at: DateTime<Local>, retries: u32)
+    {
+        let mut countdowns = self.countdowns.lock().unwrap();
+        let wait = (expires_at - Local::now()).to_std().unwrap_or_default();
+
+        let watcher = self.clone();
+        let expiring = limit.clone();
+        let task = tokio::spawn(async move
+        {
+            tokio::time::sleep(wait).await;
+            watcher.expire(expiring, started, retries).await;
+        });
+
+        let active = ActiveTimerLimit
+        {
+            timer_limit_id: limit.id,
+            device_id: limit.device_id,
+            command_from_id: limit.command_from_id,
+            command_to_id: limit.command_to_id,
+            started: started.format("%Y-%m-%d %H:%M:%S").to_string(),
+            expires: expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            remaining_seconds: wait.as_secs() as i64,
+            retries,
+            expires_at,
+        };
+        if let Some((_, previous)) = countdowns.insert(limit.id, (active, task.abort_handle())) { previous.abort(); }
+    }
+
+    //------------------------- Expire
+    async fn expire(&self, limit: TimerLimitModel, started: DateTime<Local>, retries: u32)
+    {
+        // Drop the countdown first so the event raised by the cutoff does not abort this task
+        if self.countdowns.lock().unwrap().remove(&limit.id).is_none() { return; }
+
+        // The limit may have been switched off, changed or deleted since it started; on a database error
+        // the cutoff still runs with the limit it was started with
+        let limit = match TimerLimitORM::new(false, self.log).item(&self.executor.db, limit.id).await.into_result()
+        {
+            Ok(current) if current.enable && current.device_id == limit.device_id && current.command_from_id == limit.command_from_id && current.value == limit.value => current,
+            Ok(_) | Err(ApiError::NotFound(_)) =>
+            {
+                if self.verbose { debug!("{}::expire - Timer limit {} was disabled, changed or deleted, countdown dropped", self.this_class, limit.id); }
+                return;
+            }
+            Err(e) =>
+            {
+                warn!("{}::expire - Timer limit {} could not be reloaded, cutting off as started: {}", self.this_class, limit.id, e);
+                limit
+            }
+        };
+
+        let result = self.executor.execute(limit.device_id, &limit.command_to_id.to_string()).await;
+        let data = match result.status
+        {
+            true => format!("Timer limit {} reached on device {}: command {} applied after {} unit(s)", limit.id, limit.device_id, limit.command_to_id, limit.value),
+            false =>
+            {
+                // Still on command_from: try again later, a command run meanwhile aborts the retry
+                let backoff = RETRY_MIN.saturating_mul(2u32.saturating_pow(retries.min(16))).min(RETRY_MAX);
+                let retry_at = Local::now() + chrono::Duration::from_std(backoff).unwrap_or_default();
+                self.schedule(limit.clone(), started, retry_at, retries + 1);
+                format!("Timer limit {} reached on device {}: command {} failed, retry {} in {} s: {}", limit.id, limit.device_id, limit.command_to_id, retries + 1, backoff.as_secs(), result.error.clone().unwrap_or(result.message.clone()))
+            }
+        };
+
+        if self.verbose { info!("{}::expire - {}", self.this_class, data); }
+        let zone_id = match &result.data
+        {
+            Some(device) => device.zone_id,
+            None => DeviceORM::new(false, false).item(&self.executor.db, limit.device_id).await.data.map(|device| device.zone_id).unwrap_or_default(),
+        };
+        self.executor.events.publish(Event::LimitReached { timer_limit_id: limit.id, device_id: limit.device_id, zone_id, command_to_id: limit.command_to_id, status: result.status });
+        super::record(&self.executor, "Timer Limit", result.status, data).await;
+    }
+}
+
+//--------------------------------------------------------------------------------- Active Since
+/// When the device's current command was set, now when its date is missing or unreadable
+fn active_since(device: &DeviceModel) -> DateTime<Local>
+{
+    NaiveDateTime::parse_from_str(device.date.trim(), "%Y-%m-%d %H:%M:%S").ok()
+        .and_then(|date| date.and_local_timezone(Local).earliest())
+        .filter(|date| *date <= Local::now())
+        .unwrap_or_else(Local::now)
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use sea_orm::{ActiveModelTrait, Database, EntityTrait, Set};
+    use crate::hardware::Buses;
+    use crate::hardware::gpio::{MockPinDriver, PinDriver};
+    use crate::orm::models::{device, device_command, port, timer_limit};
+    use super::super::events::EventBus;
+    use super::*;
+
+    struct Fixture
+    {
+        watcher: LimitWatcher,
+        device: DeviceModel,
+        on: i32,
+        off: i32,
+    }
+
+    /// Samples plus a device of zone 1 on its own GPIO OUT line 5 with "on" and "off" commands,
+    /// and a watcher counting in units of 50 ms
+    async fn fixture() -> Fixture
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        let port = port::ActiveModel { user_id: Set(1), name: Set("T-Out".to_string()), pin: Set(0), port: Set(5), value: Set(0), description: Set(String::new()), enable: Set(true), protocol: Set("GPIO".to_string()), r#type: Set("OUT".to_string()), ..Default::default() };
+        let port = port.insert(&db).await.unwrap();
+        let device = device::ActiveModel { zone_id: Set(1), port_id: Set(port.id), power_id: Set(0), command_id: Set(0), value: Set(0), tune: Set(0), date: Set(String::new()), address: Set(String::new()), name: Set("T-Pump".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        let device = device.insert(&db).await.unwrap();
+        let command = |name: &str, value: i32| device_command::ActiveModel { device_id: Set(device.id), name: Set(name.to_string()), value_from: Set(Some(value)), value_to: Set(Some(value)), delay: Set(Some(0)), frequency: Set(None), description: Set(String::new()), reload: Set(false), enable: Set(true), r#type: Set("NONE".to_string()), ..Default::default() };
+        let on = command("on", 1).insert(&db).await.unwrap().id;
+        let off = command("off", 0).insert(&db).await.unwrap().id;
+
+        let pins: Arc<dyn PinDriver> = Arc::new(MockPinDriver::new());
+        let mut watcher = LimitWatcher::new(Executor::new(db, pins.clone(), Buses::mock(pins), EventBus::new(64)));
+        watcher.unit = Duration::from_millis(50);
+        Fixture { watcher, device, on, off }
+    }
+
+    async fn limit(fixture: &Fixture, value: i32) -> TimerLimitModel
+    {
+        let limit = timer_limit::ActiveModel { device_id: Set(fixture.device.id), command_from_id: Set(fixture.on), command_to_id: Set(fixture.off), value: Set(value), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        limit.insert(&fixture.watcher.executor.db).await.unwrap()
+    }
+
+    async fn command_id(fixture: &Fixture) -> i32
+    {
+        device::Entity::find_by_id(fixture.device.id).one(&fixture.watcher.executor.db).await.unwrap().unwrap().command_id
+    }
+
+    #[tokio::test]
+    async fn the_cutoff_runs_command_to()
+    {
+        let fixture = fixture().await;
+        fixture.watcher.executor.execute(fixture.device.id, "on").await.into_result().unwrap();
+
+        // Added while the device already runs command_from: the refresh starts it
+        let limit = limit(&fixture, 1).await;
+        fixture.watcher.refresh(limit.id).await;
+        assert_eq!(fixture.watcher.active().len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(command_id(&fixture).await, fixture.off);
+        assert!(fixture.watcher.active().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_disabled_changed_or_deleted_limit_does_not_fire()
+    {
+        let fixture = fixture().await;
+        fixture.watcher.executor.execute(fixture.device.id, "on").await.into_result().unwrap();
+        let db = &fixture.watcher.executor.db;
+
+        // Changed behind the watcher: the countdown started with the old value is dropped on expiry
+        let limit = limit(&fixture, 2).await;
+        fixture.watcher.on_device_changed(fixture.device.id, fixture.on).await;
+        let mut changed: timer_limit::ActiveModel = limit.clone().into();
+        changed.value = Set(100);
+        changed.update(db).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(command_id(&fixture).await, fixture.on);
+        assert!(fixture.watcher.active().is_empty());
+
+        // Disabled or deleted through the API: the refresh cancels it
+        fixture.watcher.refresh(limit.id).await;
+        assert_eq!(fixture.watcher.active().len(), 1, "the device already runs command_from");
+        let mut disabled: timer_limit::ActiveModel = limit.clone().into();
+        disabled.enable = Set(false);
+        disabled.update(db).await.unwrap();
+        fixture.watcher.refresh(limit.id).await;
+        assert!(fixture.watcher.active().is_empty());
+
+        let mut enabled: timer_limit::ActiveModel = limit.clone().into();
+        enabled.enable = Set(true);
+        enabled.update(db).await.unwrap();
+        fixture.watcher.refresh(limit.id).await;
+        assert_eq!(fixture.watcher.active().len(), 1);
+        timer_limit::Entity::delete_by_id(limit.id).exec(db).await.unwrap();
+        fixture.watcher.refresh(limit.id).await;
+        assert!(fixture.watcher.active().is_empty());
+        assert_eq!(command_id(&fixture).await, fixture.on);
+    }
+
+    fn device(date: &str) -> DeviceModel
+    {
+        DeviceModel { id: 1, zone_id: 1, port_id: 1, power_id: 0, command_id: 1, value: 0, tune: 0, date: date.to_string(), address: String::new(), name: "Motor".to_string(), description: String::new(), enable: true }
+    }
+
+    #[test]
+    fn counts_from_the_device_date()
+    {
+        let since = Local::now() - chrono::Duration::minutes(5);
+        let found = active_since(&device(&since.format("%Y-%m-%d %H:%M:%S").to_string()));
+        assert_eq!(found.timestamp(), since.timestamp());
+    }
+
+    #[test]
+    fn falls_back_to_now()
+    {
+        let now = Local::now();
+        for date in ["", "2024-01-01", "soon", "2999-01-01 00:00:00"]
+        {
+            assert!(active_since(&device(date)) >= now, "{}", date);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// Runtime engine that executes device commands, evaluates zone rules, runs timers and polls inputs
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::Set;
+use crate::orm::logics::log::LogORM;
+use crate::orm::models::log::ActiveModel as LogActiveModel;
+use self::events::Event;
+use self::executor::Executor;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod events;
+pub mod executor;
+pub mod limit;
+pub mod poller;
+pub mod rule;
+pub mod scheduler;
+pub mod webhook;
+
+//--------------------------------------------------------------------------------- Record
+/// Write a row to the `log` table describing an engine outcome, and announce it on the bus
+pub async fn record(executor: &Executor, name: &str, status: bool, data: String)
+{
+    let item = LogActiveModel
+    {
+        id: Default::default(),
+        date: Set(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+        name: Set(name.to_string()),
+        status: Set(status),
+        data: Set(data),
+    };
+
+    if let Some(log) = LogORM::new(false, true).add(&executor.db, item).await.data
+    {
+        executor.events.publish(Event::LogAdded { log_id: log.id, date: log.date, name: log.name, status: log.status, data: log.data });
+    }
+}
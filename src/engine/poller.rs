@@ -0,0 +1,278 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/poller.rs
+
+//--------------------------------------------------------------------------------- Description
+// Input poller: reads every enabled input device on an enabled GPIO IN, FILE, I2C, SPI or UART
+// port each INPUT_POLL_INTERVAL seconds, stores the reading plus device.tune in device.value and moves
+// device.command_id to the command whose value_from..value_to window holds it. A new value is
+// published as DeviceChanged, so zone rules see sensors like any other device, and as DeviceRead
+// for the MQTT state
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sea_orm::Set;
+use tracing::{debug, error, info, warn};
+use crate::logics::error::ApiError;
+use crate::hardware::file::{FileRoots, FileSource};
+use crate::hardware::i2c::I2cTarget;
+use crate::hardware::uart::modbus::RegisterMap;
+use crate::hardware::{self, HardwareError};
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, port::PortORM};
+use crate::orm::models::device::{ActiveModel as DeviceActiveModel, Model as DeviceModel};
+use crate::orm::models::device_command::Model as DeviceCommandModel;
+use crate::orm::models::port::Model as PortModel;
+use super::events::Event;
+use super::executor::Executor;
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct InputPoller
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub executor: Executor,
+    pub interval: Duration,
+    pub file_roots: FileRoots,
+    failures: Arc<Mutex<HashMap<i32, String>>>,
+}
+
+impl InputPoller
+{
+    //------------------------- New
+    pub fn new(executor: Executor) -> Self
+    {
+        let interval = std::env::var("INPUT_POLL_INTERVAL").ok().and_then(|value| value.parse::<u64>().ok()).unwrap_or(10);
+
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "InputPoller".to_string(),
+            executor,
+            interval: Duration::from_secs(interval.max(1)),
+            file_roots: FileRoots::from_env(),
+            failures: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    //------------------------- Spawn
+    /// Start the background task; ports and devices are reloaded on every tick so CRUD changes apply without a restart
+    pub fn spawn(&self)
+    {
+        let poller = self.clone();
+
+        tokio::spawn(async move
+        {
+            let mut ticker = tokio::time::interval(poller.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop
+            {
+                ticker.tick().await;
+                if let Err(e) = poller.tick().await { error!("{}::tick - Error: {}", poller.this_class, e); }
+            }
+        });
+    }
+
+    //------------------------- Tick
+    async fn tick(&self) -> Result<(), ApiError>
+    {
+        let db = &self.executor.db;
+        let enabled = HashMap::from([("enable".to_string(), "true".to_string())]);
+        let ports = PortORM::new(false, self.log).items(db, enabled.clone()).await.into_result()?
+            .into_iter().filter_map(|port| Protocol::of_port(&port.protocol, &port.r#type).map(|protocol| (port.id, (protocol, port)))).collect::<HashMap<_, _>>();
+        if ports.is_empty() { return Ok(()); }
+
+        // A GPIO device is its port's line; bus devices without an address are placeholders, nothing to read yet
+        let devices = DeviceORM::new(false, self.log).items(db, enabled).await.into_result()?
+            .into_iter().filter_map(|device| ports.get(&device.port_id).map(|(protocol, port)| (*protocol, port, device)))
+            .filter(|(protocol, _, device)| (*protocol == Protocol::Gpio || !device.address.trim().is_empty()) && protocol.is_input(&device.address)).collect::<Vec<_>>();
+
+        let ids = devices.iter().map(|(_, _, device)| device.id).collect::<Vec<_>>();
+        self.failures.lock().unwrap().retain(|id, _| ids.contains(id));
+
+        for (protocol, port, device) in devices
+        {
+            match self.read(protocol, port, &device).await
+            {
+                Ok(reading) =>
+                {
+                    self.recovered(&device).await;
+                    self.store(device, reading).await?;
+                }
+                Err(e) => self.failed(&device, e.to_string()).await,
+            }
+        }
+        Ok(())
+    }
+
+    //------------------------- Read
+    /// Raw reading of the device, off the async runtime: sensors take milliseconds to convert
+    async fn read(&self, protocol: Protocol, port: &PortModel, device: &DeviceModel) -> Result<i32, HardwareError>
+    {
+        let address = device.address.clone();
+        let task = match protocol
+        {
+            Protocol::Gpio =>
+            {
+                let (pins, port) = (self.executor.pins.clone(), port.clone());
+                tokio::task::spawn_blocking(move || hardware::port::read(pins.as_ref(), &port))
+            }
+            Protocol::File =>
+            {
+                let source = FileSource::from_address(&address, &self.file_roots)?;
+                tokio::task::spawn_blocking(move || source.read())
+            }
+            Protocol::I2c =>
+            {
+                let i2c = self.executor.buses.i2c.clone();
+                tokio::task::spawn_blocking(move || i2c.read(&address))
+            }
+            Protocol::Spi =>
+            {
+                let spi = self.executor.buses.spi.clone();
+                tokio::task::spawn_blocking(move || spi.read(&address))
+            }
+            Protocol::Uart =>
+            {
+                let uart = self.executor.buses.uart.clone();
+                tokio::task::spawn_blocking(move || uart.read(&address))
+            }
+        };
+        task.await.map_err(|e| HardwareError::Device(e.to_string()))?
+    }
+
+    //------------------------- Store
+    async fn store(&self, device: DeviceModel, reading: i32) -> Result<(), ApiError>
+    {
+        let value = reading.saturating_add(device.tune);
+
+        // A contact that stays at 0 still has to leave the seeded command for its "off" window
+        let filters = HashMap::from([("device_id".to_string(), device.id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let commands = DeviceCommandORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()?;
+        let command = window_of(&commands, device.command_id, value);
+        if value == device.value && command.is_none_or(|command| command.id == device.command_id) { return Ok(()); }
+
+        let previous = device.value;
+        let mut active_device: DeviceActiveModel = device.into();
+        active_device.value = Set(value);
+        if let Some(command) = command { active_device.command_id = Set(command.id); }
+        let device = DeviceORM::new(false, self.log).update(&self.executor.db, active_device).await.into_result()?;
+
+        if self.verbose { debug!("{}::store - Device '{}' ({}) read {} -> {}", self.this_class, device.name, device.id, previous, device.value); }
+        let command = command.map(|command| command.name.clone());
+        self.executor.events.publish(Event::DeviceChanged { device_id: device.id, zone_id: device.zone_id, command_id: device.command_id, previous, value: device.value });
+        self.executor.events.publish(Event::DeviceRead { device_id: device.id, zone_id: device.zone_id, command_id: device.command_id, command, value: device.value });
+        Ok(())
+    }
+
+    //------------------------- Failures
+    /// Log a failing device once, not on every tick
+    async fn failed(&self, device: &DeviceModel, error_msg: String)
+    {
+        let changed = self.failures.lock().unwrap().insert(device.id, error_msg.clone()).as_ref() != Some(&error_msg);
+        if !changed { return; }
+
+        let error_msg = format!("Device '{}' ({}) cannot be read from '{}': {}", device.name, device.id, device.address, error_msg);
+        warn!("{}::failed - {}", self.this_class, error_msg);
+        super::record(&self.executor, "Input Read", false, error_msg).await;
+    }
+
+    async fn recovered(&self, device: &DeviceModel)
+    {
+        if self.failures.lock().unwrap().remove(&device.id).is_none() { return; }
+
+        let message = format!("Device '{}' ({}) is read again from '{}'", device.name, device.id, device.address);
+        info!("{}::recovered - {}", self.this_class, message);
+        super::record(&self.executor, "Input Read", true, message).await;
+    }
+}
+
+//--------------------------------------------------------------------------------- Protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol
+{
+    Gpio,
+    File,
+    I2c,
+    Spi,
+    Uart,
+}
+
+impl Protocol
+{
+    /// GPIO ports are read when they are inputs, bus ports whatever their type
+    fn of_port(protocol: &str, r#type: &str) -> Option<Self>
+    {
+        match protocol.to_lowercase().as_str()
+        {
+            "gpio" if r#type.eq_ignore_ascii_case("in") => Some(Protocol::Gpio),
+            "file" => Some(Protocol::File),
+            "i2c" => Some(Protocol::I2c),
+            "spi" => Some(Protocol::Spi),
+            "uart" => Some(Protocol::Uart),
+            _ => None,
+        }
+    }
+
+    /// Outputs on a bus are driven by commands, not polled; a bad address is polled to report it
+    fn is_input(&self, address: &str) -> bool
+    {
+        match self
+        {
+            Protocol::Gpio | Protocol::File | Protocol::Spi => true,
+            Protocol::I2c => I2cTarget::from_address(address).ok().is_none_or(|target| target.is_input()),
+            Protocol::Uart => RegisterMap::from_address(address).ok().is_none_or(|map| map.is_input()),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Window
+/// The current command while its window still holds the value, else the first one that does
+fn window_of(commands: &[DeviceCommandModel], current: i32, value: i32) -> Option<&DeviceCommandModel>
+{
+    let inside = |command: &&DeviceCommandModel|
+    {
+        let value_from = command.value_from.unwrap_or(0);
+        let value_to = command.value_to.unwrap_or(value_from);
+        (value_from.min(value_to)..=value_from.max(value_to)).contains(&value)
+    };
+
+    commands.iter().filter(|command| command.id == current).find(inside)
+        .or_else(|| commands.iter().find(inside))
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn command(id: i32, value_from: i32, value_to: i32) -> DeviceCommandModel
+    {
+        DeviceCommandModel { id, device_id: 1, name: id.to_string(), value_from: Some(value_from), value_to: Some(value_to), delay: None, frequency: None, description: String::new(), reload: false, enable: true, r#type: String::new() }
+    }
+
+    #[test]
+    fn polls_gpio_inputs_only()
+    {
+        assert_eq!(Protocol::of_port("GPIO", "IN"), Some(Protocol::Gpio));
+        assert_eq!(Protocol::of_port("GPIO", "OUT"), None);
+        assert_eq!(Protocol::of_port("GPIO", "PWM"), None);
+        assert_eq!(Protocol::of_port("FILE", "IN"), Some(Protocol::File));
+        assert_eq!(Protocol::of_port("RESERVED", "IN"), None);
+    }
+
+    #[test]
+    fn keeps_the_current_window_while_it_holds_the_value()
+    {
+        let commands = [command(7, 0, 30), command(8, 30, 1000), command(9, 0, 25)];
+        assert_eq!(window_of(&commands, 1, 20).map(|command| command.id), Some(7));
+        assert_eq!(window_of(&commands, 9, 20).map(|command| command.id), Some(9));
+        assert_eq!(window_of(&commands, 9, 28).map(|command| command.id), Some(7));
+        assert_eq!(window_of(&commands, 7, 40).map(|command| command.id), Some(8));
+        assert_eq!(window_of(&commands, 7, -5).map(|command| command.id), None);
+    }
+}
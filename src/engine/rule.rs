@@ -0,0 +1,437 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/rule.rs
+
+//--------------------------------------------------------------------------------- Description
+// Zone rule engine: evaluates zone_command_if conditions (level and edge) and runs zone_command_action items
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::{BTreeSet, HashMap};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+use crate::logics::error::ApiError;
+use crate::logics::general::{IfTypes, ModelOutput};
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, zone::ZoneORM, zone_command::ZoneCommandORM, zone_command_action::ZoneCommandActionORM, zone_command_if::ZoneCommandIfORM};
+use crate::orm::models::zone_command::Model as ZoneCommandModel;
+use crate::orm::models::zone_command_if::Model as ZoneCommandIfModel;
+use super::events::Event;
+use super::executor::Executor;
+
+//--------------------------------------------------------------------------------- Change
+/// Device transition that triggered an evaluation, needed by edge conditions
+#[derive(Debug, Clone, Copy)]
+pub struct Change
+{
+    pub device_id: i32,
+    pub previous: i32,
+    pub value: i32,
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct RuleEngine
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub executor: Executor,
+}
+
+impl RuleEngine
+{
+    //------------------------- New
+    pub fn new(executor: Executor) -> Self
+    {
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "RuleEngine".to_string(),
+            executor,
+        }
+    }
+
+    //------------------------- Spawn
+    /// Start the background task that re-evaluates rules whenever a device changes
+    pub fn spawn(&self)
+    {
+        let engine = self.clone();
+        let mut receiver = engine.executor.events.subscribe();
+
+        tokio::spawn(async move
+        {
+            loop
+            {
+                match receiver.recv().await
+                {
+                    Ok(Event::DeviceChanged { device_id, previous, value, .. }) => engine.on_device_changed(Change { device_id, previous, value }).await,
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => warn!("{}::spawn - Skipped {} events", engine.this_class, skipped),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    //------------------------- On Device Changed
+    async fn on_device_changed(&self, change: Change)
+    {
+        let this_method = "on_device_changed";
+        if self.verbose { debug!("{}::{} - Device {} changed {} -> {}", self.this_class, this_method, change.device_id, change.previous, change.value); }
+
+        let filters = HashMap::from([("device_id".to_string(), change.device_id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let conditions = match ZoneCommandIfORM::new(self.verbose, self.log).items(&self.executor.db, filters).await.into_result()
+        {
+            Ok(conditions) => conditions,
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return; }
+        };
+
+        let zone_command_ids: BTreeSet<i32> = conditions.iter().map(|condition| condition.zone_command_id).collect();
+        for zone_command_id in zone_command_ids
+        {
+            if let Err(e) = self.evaluate(zone_command_id, Some(change)).await
+            {
+                error!("{}::{} - Error: {}", self.this_class, this_method, e);
+                super::record(&self.executor, "Zone Command Evaluate", false, format!("Zone command {}: {}", zone_command_id, e)).await;
+            }
+        }
+    }
+
+    //------------------------- Evaluate
+    /// Check every enabled condition of a zone command and run its actions when all of them hold;
+    /// edge conditions (Dtu, Utd) can only hold for the device transition given in `change`. A zone
+    /// command that is skipped still leaves a log entry saying why
+    pub async fn evaluate(&self, zone_command_id: i32, change: Option<Change>) -> Result<bool, String>
+    {
+        let zone_command = ZoneCommandORM::new(self.verbose, self.log).item(&self.executor.db, zone_command_id).await.into_result()?;
+        if let Some(reason) = self.inactive(&zone_command).await? { return Ok(self.skip(&zone_command, reason).await); }
+
+        let filters = HashMap::from([("zone_command_id".to_string(), zone_command.id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let conditions = ZoneCommandIfORM::new(self.verbose, self.log).items(&self.executor.db, filters).await.into_result()?;
+        if conditions.is_empty() { return Ok(self.skip(&zone_command, "it has no enabled condition".to_string()).await); }
+
+        let mut details = Vec::new();
+        let mut met = true;
+        for condition in &conditions
+        {
+            let (holds, detail) = self.condition_holds(condition, change).await?;
+            met &= holds;
+            details.push(detail);
+        }
+
+        let mut data = format!("Zone command '{}' ({}): conditions {} [{}]", zone_command.name, zone_command.id, if met { "met" } else { "not met" }, details.join("; "));
+        if met
+        {
+            let executed = self.run_actions(&zone_command, false).await?;
+            data.push_str(&format!(", {} action(s) executed", executed));
+            self.executor.events.publish(Event::RuleFired { zone_command_id: zone_command.id, zone_id: zone_command.zone_id, name: zone_command.name.clone(), manual: false, actions: executed });
+        }
+
+        if self.verbose { info!("{}::evaluate - {}", self.this_class, data); }
+        super::record(&self.executor, "Zone Command Evaluate", met, data).await;
+        Ok(met)
+    }
+
+    //------------------------- Run
+    /// Manually trigger a zone command: run all of its actions regardless of conditions
+    pub async fn run(&self, zone_command_id: i32) -> ModelOutput<ZoneCommandModel>
+    {
+        let this_method = "run";
+        let zone_command = match ZoneCommandORM::new(self.verbose, self.log).item(&self.executor.db, zone_command_id).await.into_result()
+        {
+            Ok(zone_command) => zone_command,
+            Err(e) => return ModelOutput::fail(e),
+        };
+
+        match self.run_actions(&zone_command, true).await
+        {
+            Ok(executed) =>
+            {
+                let message = format!("Zone command '{}' ({}) run manually, {} action(s) executed", zone_command.name, zone_command.id, executed);
+                if self.verbose { info!("{}::{} - {}", self.this_class, this_method, message); }
+                self.executor.events.publish(Event::RuleFired { zone_command_id: zone_command.id, zone_id: zone_command.zone_id, name: zone_command.name.clone(), manual: true, actions: executed });
+                super::record(&self.executor, "Zone Command Run", true, message.clone()).await;
+                ModelOutput::success(zone_command, message)
+            }
+            Err(e) =>
+            {
+                let error_msg = format!("Zone command '{}' ({}) run failed: {}", zone_command.name, zone_command.id, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                super::record(&self.executor, "Zone Command Run", false, error_msg.clone()).await;
+                ModelOutput::fail(e.with_message(error_msg))
+            }
+        }
+    }
+
+    //------------------------- Inactive
+    /// Why the zone command cannot fire, None when it and its zone are enabled
+    async fn inactive(&self, zone_command: &ZoneCommandModel) -> Result<Option<String>, String>
+    {
+        if !zone_command.enable { return Ok(Some("it is disabled".to_string())); }
+        let zone = ZoneORM::new(self.verbose, self.log).item(&self.executor.db, zone_command.zone_id).await.into_result()?;
+        Ok((!zone.enable).then(|| format!("zone '{}' ({}) is disabled", zone.name, zone.id)))
+    }
+
+    //------------------------- Skip
+    async fn skip(&self, zone_command: &ZoneCommandModel, reason: String) -> bool
+    {
+        let data = format!("Zone command '{}' ({}): skipped, {}", zone_command.name, zone_command.id, reason);
+        if self.verbose { info!("{}::evaluate - {}", self.this_class, data); }
+        super::record(&self.executor, "Zone Command Evaluate", false, data).await;
+        false
+    }
+
+    //------------------------- Condition Holds
+    /// None: device value inside the command's value_from..value_to window
+    /// Equal / Unequal: device value equal / not equal to the command's value_to
+    /// Dtu / Utd: the triggering change of this device entered the window while rising / falling
+    async fn condition_holds(&self, condition: &ZoneCommandIfModel, change: Option<Change>) -> Result<(bool, String), String>
+    {
+        let if_type = IfTypes::from_name(&condition.r#type).ok_or_else(|| format!("Invalid condition type '{}' on condition {}", condition.r#type, condition.id))?;
+        let device = DeviceORM::new(self.verbose, self.log).item(&self.executor.db, condition.device_id).await.into_result()?;
+        let command = DeviceCommandORM::new(self.verbose, self.log).item(&self.executor.db, condition.command_id).await.into_result()?;
+
+        let value_from = command.value_from.unwrap_or(0);
+        let value_to = command.value_to.unwrap_or(value_from);
+        let (low, high) = (value_from.min(value_to), value_from.max(value_to));
+        let inside = |value: i32| (low..=high).contains(&value);
+        let edge = change.filter(|change| change.device_id == device.id && !inside(change.previous) && inside(change.value));
+
+        let (holds, detail) = match if_type
+        {
+            IfTypes::None => (device.enable && inside(device.value), format!("value {} in {}..{}", device.value, low, high)),
+            IfTypes::Equal => (device.enable && device.value == value_to, format!("value {} == {}", device.value, value_to)),
+            IfTypes::Unequal => (device.enable && device.value != value_to, format!("value {} != {}", device.value, value_to)),
+            IfTypes::Dtu => (device.enable && edge.is_some_and(|change| change.previous < change.value), format!("rising into {}..{}", low, high)),
+            IfTypes::Utd => (device.enable && edge.is_some_and(|change| change.previous > change.value), format!("falling into {}..{}", low, high)),
+        };
+
+        Ok((holds, format!("{}: {} {} = {}", condition.name, device.name, detail, holds)))
+    }
+
+    //------------------------- Run Actions
+    /// Execute the enabled actions of a zone command; unless forced, actions already applied are skipped
+    async fn run_actions(&self, zone_command: &ZoneCommandModel, force: bool) -> Result<usize, ApiError>
+    {
+        let filters = HashMap::from([("zone_command_id".to_string(), zone_command.id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let actions = ZoneCommandActionORM::new(self.verbose, self.log).items(&self.executor.db, filters).await.into_result()?;
+
+        let mut executed = 0;
+        for action in actions
+        {
+            let Some(command_id) = action.command_id else { continue; };
+
+            if !force
+            {
+                let device = DeviceORM::new(self.verbose, self.log).item(&self.executor.db, action.device_id).await.into_result()?;
+                if device.command_id == command_id { continue; }
+            }
+
+            self.executor.execute(action.device_id, &command_id.to_string()).await.into_result()?;
+            executed += 1;
+        }
+
+        Ok(executed)
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use sea_orm::{ActiveModelTrait, Database, EntityTrait, QueryOrder, Set};
+    use crate::hardware::Buses;
+    use crate::hardware::gpio::{MockPinDriver, PinDriver};
+    use crate::orm::models::{device, device_command, log, port, zone, zone_command, zone_command_action, zone_command_if};
+    use super::super::events::EventBus;
+    use super::*;
+
+    /// Samples plus a GPIO OUT port of zone 1 for the devices of the test
+    async fn engine() -> (RuleEngine, i32)
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        let port = port::ActiveModel { user_id: Set(1), name: Set("T-Out".to_string()), pin: Set(0), port: Set(5), value: Set(0), description: Set(String::new()), enable: Set(true), protocol: Set("GPIO".to_string()), r#type: Set("OUT".to_string()), ..Default::default() };
+        let port = port.insert(&db).await.unwrap().id;
+
+        let pins: Arc<dyn PinDriver> = Arc::new(MockPinDriver::new());
+        (RuleEngine::new(Executor::new(db, pins.clone(), Buses::mock(pins), EventBus::new(64))), port)
+    }
+
+    async fn device(engine: &RuleEngine, port_id: i32, name: &str, value: i32) -> device::Model
+    {
+        let device = device::ActiveModel { zone_id: Set(1), port_id: Set(port_id), power_id: Set(0), command_id: Set(0), value: Set(value), tune: Set(0), date: Set(String::new()), address: Set(String::new()), name: Set(name.to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        device.insert(&engine.executor.db).await.unwrap()
+    }
+
+    async fn command(engine: &RuleEngine, device_id: i32, name: &str, value_from: i32, value_to: i32) -> i32
+    {
+        let command = device_command::ActiveModel { device_id: Set(device_id), name: Set(name.to_string()), value_from: Set(Some(value_from)), value_to: Set(Some(value_to)), delay: Set(Some(0)), frequency: Set(None), description: Set(String::new()), reload: Set(false), enable: Set(true), r#type: Set("NONE".to_string()), ..Default::default() };
+        command.insert(&engine.executor.db).await.unwrap().id
+    }
+
+    async fn set_value(engine: &RuleEngine, device: &device::Model, value: i32)
+    {
+        let mut active_device: device::ActiveModel = device.clone().into();
+        active_device.value = Set(value);
+        active_device.update(&engine.executor.db).await.unwrap();
+    }
+
+    async fn rule(engine: &RuleEngine) -> zone_command::Model
+    {
+        let zone_command = zone_command::ActiveModel { zone_id: Set(1), name: Set("Test rule".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        zone_command.insert(&engine.executor.db).await.unwrap()
+    }
+
+    async fn condition(engine: &RuleEngine, zone_command_id: i32, device_id: i32, command_id: i32, if_type: &str) -> ZoneCommandIfModel
+    {
+        let condition = zone_command_if::ActiveModel { name: Set(format!("{} on {}", if_type, device_id)), zone_command_id: Set(zone_command_id), device_id: Set(device_id), command_id: Set(command_id), r#type: Set(if_type.to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        condition.insert(&engine.executor.db).await.unwrap()
+    }
+
+    async fn action(engine: &RuleEngine, zone_command_id: i32, device_id: i32, command_id: i32)
+    {
+        let action = zone_command_action::ActiveModel { name: Set("Test action".to_string()), zone_command_id: Set(zone_command_id), device_id: Set(device_id), command_id: Set(Some(command_id)), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        action.insert(&engine.executor.db).await.unwrap();
+    }
+
+    async fn reload(engine: &RuleEngine, device: &device::Model) -> device::Model
+    {
+        device::Entity::find_by_id(device.id).one(&engine.executor.db).await.unwrap().unwrap()
+    }
+
+    async fn last_log(engine: &RuleEngine) -> log::Model
+    {
+        log::Entity::find().order_by_desc(log::Column::Id).one(&engine.executor.db).await.unwrap().unwrap()
+    }
+
+    async fn holds(engine: &RuleEngine, condition: &ZoneCommandIfModel, change: Option<Change>) -> bool
+    {
+        engine.condition_holds(condition, change).await.unwrap().0
+    }
+
+    #[tokio::test]
+    async fn edges_fire_on_the_change_into_the_window()
+    {
+        let (engine, port) = engine().await;
+        let sensor = device(&engine, port, "T-Sensor", 25).await;
+        let other = device(&engine, port, "T-Other", 25).await;
+        let window = command(&engine, sensor.id, "window", 20, 30).await;
+        let zone_command = rule(&engine).await;
+        let dtu = condition(&engine, zone_command.id, sensor.id, window, "Dtu").await;
+        let utd = condition(&engine, zone_command.id, sensor.id, window, "Utd").await;
+        let change = |device_id: i32, previous: i32, value: i32| Some(Change { device_id, previous, value });
+
+        // 10 -> 25 rises into 20..30
+        assert!(holds(&engine, &dtu, change(sensor.id, 10, 25)).await);
+        assert!(!holds(&engine, &utd, change(sensor.id, 10, 25)).await);
+        // 40 -> 25 falls into it
+        assert!(holds(&engine, &utd, change(sensor.id, 40, 25)).await);
+        assert!(!holds(&engine, &dtu, change(sensor.id, 40, 25)).await);
+        // Once inside, the next rise is not an edge, so Dtu fires once
+        assert!(!holds(&engine, &dtu, change(sensor.id, 25, 27)).await);
+        assert!(!holds(&engine, &utd, change(sensor.id, 27, 25)).await);
+        // A change of another device, or no change at all, is no edge of this one
+        assert!(!holds(&engine, &dtu, change(other.id, 10, 25)).await);
+        assert!(!holds(&engine, &dtu, None).await);
+        // Rising past the window is not into it
+        assert!(!holds(&engine, &dtu, change(sensor.id, 10, 35)).await);
+    }
+
+    #[tokio::test]
+    async fn levels_compare_against_the_command()
+    {
+        let (engine, port) = engine().await;
+        let sensor = device(&engine, port, "T-Sensor", 25).await;
+        let window = command(&engine, sensor.id, "window", 20, 30).await;
+        let zone_command = rule(&engine).await;
+        let inside = condition(&engine, zone_command.id, sensor.id, window, "None").await;
+        let equal = condition(&engine, zone_command.id, sensor.id, window, "Equal").await;
+        let unequal = condition(&engine, zone_command.id, sensor.id, window, "Unequal").await;
+
+        assert!(holds(&engine, &inside, None).await);
+        assert!(!holds(&engine, &equal, None).await && holds(&engine, &unequal, None).await);
+
+        // Equal and Unequal look at value_to, not at the window
+        set_value(&engine, &sensor, 30).await;
+        assert!(holds(&engine, &equal, None).await && !holds(&engine, &unequal, None).await);
+        set_value(&engine, &sensor, 20).await;
+        assert!(holds(&engine, &inside, None).await && !holds(&engine, &equal, None).await);
+        set_value(&engine, &sensor, 31).await;
+        assert!(!holds(&engine, &inside, None).await);
+
+        // A disabled device holds nothing
+        let mut active_device: device::ActiveModel = sensor.into();
+        active_device.enable = Set(false);
+        active_device.update(&engine.executor.db).await.unwrap();
+        assert!(!holds(&engine, &unequal, None).await);
+    }
+    #[tokio::test]
+    async fn actions_run_once_every_condition_holds()
+    {
+        let (engine, port) = engine().await;
+        let level = device(&engine, port, "T-Level", 25).await;
+        let temperature = device(&engine, port, "T-Temperature", 5).await;
+        let pump = device(&engine, port, "T-Pump", 0).await;
+        let (level_ok, temperature_ok) = (command(&engine, level.id, "ok", 20, 30).await, command(&engine, temperature.id, "ok", 20, 30).await);
+        let on = command(&engine, pump.id, "on", 1, 1).await;
+        let zone_command = rule(&engine).await;
+        condition(&engine, zone_command.id, level.id, level_ok, "None").await;
+        condition(&engine, zone_command.id, temperature.id, temperature_ok, "None").await;
+        action(&engine, zone_command.id, pump.id, on).await;
+
+        // Only the level holds: the conditions are ANDed
+        assert!(!engine.evaluate(zone_command.id, None).await.unwrap());
+        assert_eq!(reload(&engine, &pump).await.command_id, 0);
+        assert!(last_log(&engine).await.data.contains("conditions not met"));
+
+        set_value(&engine, &temperature, 25).await;
+        assert!(engine.evaluate(zone_command.id, None).await.unwrap());
+        let pumped = reload(&engine, &pump).await;
+        assert_eq!((pumped.command_id, pumped.value), (on, 1));
+        assert_eq!(engine.executor.pins.output_level(5).unwrap(), Some(1));
+        assert!(last_log(&engine).await.data.contains("1 action(s) executed"));
+
+        // The action is already applied, running it again would only feed the loop back
+        assert!(engine.evaluate(zone_command.id, None).await.unwrap());
+        assert!(last_log(&engine).await.data.contains("0 action(s) executed"));
+
+        // A manual run ignores that and the conditions
+        set_value(&engine, &temperature, 5).await;
+        assert!(engine.run(zone_command.id).await.message.contains("1 action(s) executed"));
+    }
+
+    #[tokio::test]
+    async fn skips_leave_the_reason_in_the_log()
+    {
+        let (engine, port) = engine().await;
+        let level = device(&engine, port, "T-Level", 25).await;
+        let pump = device(&engine, port, "T-Pump", 0).await;
+        let level_ok = command(&engine, level.id, "ok", 20, 30).await;
+        let on = command(&engine, pump.id, "on", 1, 1).await;
+        let zone_command = rule(&engine).await;
+        action(&engine, zone_command.id, pump.id, on).await;
+
+        assert!(!engine.evaluate(zone_command.id, None).await.unwrap());
+        assert!(last_log(&engine).await.data.ends_with("skipped, it has no enabled condition"));
+
+        condition(&engine, zone_command.id, level.id, level_ok, "None").await;
+        let mut disabled: zone_command::ActiveModel = zone_command.clone().into();
+        disabled.enable = Set(false);
+        disabled.update(&engine.executor.db).await.unwrap();
+        assert!(!engine.evaluate(zone_command.id, None).await.unwrap());
+        assert!(last_log(&engine).await.data.ends_with("skipped, it is disabled"));
+
+        let mut enabled: zone_command::ActiveModel = zone_command.clone().into();
+        enabled.enable = Set(true);
+        enabled.update(&engine.executor.db).await.unwrap();
+        let mut zone: zone::ActiveModel = zone::Entity::find_by_id(1).one(&engine.executor.db).await.unwrap().unwrap().into();
+        zone.enable = Set(false);
+        zone.update(&engine.executor.db).await.unwrap();
+        assert!(!engine.evaluate(zone_command.id, None).await.unwrap());
+        assert!(last_log(&engine).await.data.ends_with("(1) is disabled"));
+
+        assert_eq!(reload(&engine, &pump).await.command_id, 0, "a skipped rule runs no action");
+    }
+}
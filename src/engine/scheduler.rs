@@ -0,0 +1,430 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/scheduler.rs
+
+//--------------------------------------------------------------------------------- Description
+// Timer scheduler: opens and closes timer_item windows and applies the timer_device commands. Each
+// timer device follows the window on its own: a device that took its command is done, a failing one
+// is recorded once and tried again quietly with a growing delay, so one broken device neither holds
+// back nor re-runs the others
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::{NaiveTime, Utc};
+use chrono_tz::Tz;
+use tracing::{debug, error, info, warn};
+use crate::orm::logics::{config::ConfigORM, device_command::DeviceCommandORM, timer::TimerORM, timer_device::TimerDeviceORM, timer_item::TimerItemORM};
+use crate::orm::models::device::Model as DeviceModel;
+use crate::orm::models::device_command::Model as DeviceCommandModel;
+use crate::orm::models::timer::Model as TimerModel;
+use crate::orm::models::timer_device::Model as TimerDeviceModel;
+use crate::orm::models::timer_item::Model as TimerItemModel;
+use super::events::Event;
+use super::executor::Executor;
+
+//--------------------------------------------------------------------------------- Constants
+/// First wait before a failed device is tried again, doubled up to RETRY_MAX
+const RETRY_MIN: Duration = Duration::from_secs(60);
+const RETRY_MAX: Duration = Duration::from_secs(3600);
+
+//--------------------------------------------------------------------------------- Device State
+/// Window state a timer device was moved to, and while its command fails the attempts so far and
+/// when to try again
+#[derive(Debug, Clone, Copy)]
+struct DeviceState
+{
+    open: bool,
+    retry: Option<(u32, Instant)>,
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct TimerScheduler
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub executor: Executor,
+    pub interval: Duration,
+    /// Window state of each timer, announced once per change
+    windows: Arc<Mutex<HashMap<i32, bool>>>,
+    /// State of each (timer_id, device_id)
+    devices: Arc<Mutex<HashMap<(i32, i32), DeviceState>>>,
+}
+
+impl TimerScheduler
+{
+    //------------------------- New
+    pub fn new(executor: Executor) -> Self
+    {
+        let interval = std::env::var("TIMER_INTERVAL").ok().and_then(|value| value.parse::<u64>().ok()).unwrap_or(15);
+
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "TimerScheduler".to_string(),
+            executor,
+            interval: Duration::from_secs(interval.max(1)),
+            windows: Arc::new(Mutex::new(HashMap::new())),
+            devices: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    //------------------------- Spawn
+    /// Start the background task; timers are reloaded on every tick so CRUD changes apply without a restart
+    pub fn spawn(&self)
+    {
+        let scheduler = self.clone();
+
+        tokio::spawn(async move
+        {
+            let mut ticker = tokio::time::interval(scheduler.interval);
+            loop
+            {
+                ticker.tick().await;
+                let now = scheduler.now().await;
+                if let Err(e) = scheduler.advance(now).await { error!("{}::tick - Error: {}", scheduler.this_class, e); }
+            }
+        });
+    }
+
+    //------------------------- Advance
+    /// Bring every enabled timer and its devices to the window state at now
+    async fn advance(&self, now: NaiveTime) -> Result<(), String>
+    {
+        let filters = HashMap::from([("enable".to_string(), "true".to_string())]);
+        let timers = TimerORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()?;
+
+        // Disabled or deleted timers are forgotten without firing anything
+        let ids: Vec<i32> = timers.iter().map(|timer| timer.id).collect();
+        self.windows.lock().unwrap().retain(|id, _| ids.contains(id));
+        self.devices.lock().unwrap().retain(|(timer_id, _), _| ids.contains(timer_id));
+
+        for timer in timers
+        {
+            let open = self.is_open(&timer, now).await?;
+            let previous = self.windows.lock().unwrap().insert(timer.id, open);
+
+            // On startup only an already open window is announced
+            let changed = match previous
+            {
+                Some(previous) => previous != open,
+                None => open,
+            };
+            if changed && self.verbose { info!("{}::advance - {} '{}' ({})", self.this_class, if open { "Timer Open" } else { "Timer Close" }, timer.name, timer.id); }
+
+            let (device_ids, zone_ids) = self.follow(&timer, open).await;
+            if changed { self.executor.events.publish(Event::TimerChanged { timer_id: timer.id, name: timer.name.clone(), open, device_ids, zone_ids }); }
+        }
+
+        Ok(())
+    }
+
+    //------------------------- Now
+    /// Current wall-clock time in the zone from config.time_zone (UTC when missing or invalid)
+    async fn now(&self) -> NaiveTime
+    {
+        let time_zone = ConfigORM::new(false, self.log).items(&self.executor.db, HashMap::new()).await.data
+            .and_then(|configs| configs.into_iter().next())
+            .map(|config| config.time_zone)
+            .unwrap_or_default();
+
+        match time_zone.parse::<Tz>()
+        {
+            Ok(tz) => Utc::now().with_timezone(&tz).time(),
+            Err(_) =>
+            {
+                if !time_zone.is_empty() { warn!("{}::now - Unknown time zone '{}', using UTC", self.this_class, time_zone); }
+                Utc::now().time()
+            }
+        }
+    }
+
+    //------------------------- Is Open
+    async fn is_open(&self, timer: &TimerModel, now: NaiveTime) -> Result<bool, String>
+    {
+        let filters = HashMap::from([("timer_id".to_string(), timer.id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let items = TimerItemORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()?;
+
+        Ok(items.iter().any(|item| self.window_contains(item, now)))
+    }
+
+    //------------------------- Window Contains
+    /// Windows are [value_from, value_to); a window whose end is before its start wraps past midnight,
+    /// one that ends where it starts is empty
+    fn window_contains(&self, item: &TimerItemModel, now: NaiveTime) -> bool
+    {
+        let (Some(from), Some(to)) = (parse_time(&item.value_from), parse_time(&item.value_to)) else
+        {
+            warn!("{}::window_contains - Invalid window '{}'-'{}' on timer item {}", self.this_class, item.value_from, item.value_to, item.id);
+            return false;
+        };
+
+        if from <= to { from <= now && now < to } else { now >= from || now < to }
+    }
+
+    //------------------------- Follow
+    /// Move each timer device to the window state: the bound command when the window opens, the one
+    /// that undoes it when it closes. A device seen for the first time only follows an open window.
+    /// Returns the devices moved now and the zones of those that took their command
+    async fn follow(&self, timer: &TimerModel, open: bool) -> (Vec<i32>, Vec<i32>)
+    {
+        let this_method = "follow";
+        let name = if open { "Timer Open" } else { "Timer Close" };
+        let (mut device_ids, mut zone_ids) = (Vec::new(), Vec::new());
+
+        let filters = HashMap::from([("timer_id".to_string(), timer.id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let timer_devices = match TimerDeviceORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()
+        {
+            Ok(timer_devices) => timer_devices,
+            Err(e) => { error!("{}::{} - Error: {}", self.this_class, this_method, e); return (device_ids, zone_ids); }
+        };
+
+        // Devices taken off the timer are forgotten
+        let bound: Vec<i32> = timer_devices.iter().map(|timer_device| timer_device.device_id).collect();
+        self.devices.lock().unwrap().retain(|(timer_id, device_id), _| *timer_id != timer.id || bound.contains(device_id));
+
+        for timer_device in timer_devices
+        {
+            let key = (timer.id, timer_device.device_id);
+            let state = self.devices.lock().unwrap().get(&key).copied();
+            let attempts = match state
+            {
+                None if !open => { self.devices.lock().unwrap().insert(key, DeviceState { open, retry: None }); continue; }
+                Some(state) if state.open == open => match state.retry
+                {
+                    Some((attempts, at)) if Instant::now() >= at => attempts,
+                    _ => continue,
+                },
+                _ => 0,
+            };
+
+            let result = self.run(&timer_device, open).await;
+            let data = match &result
+            {
+                Ok(device) => format!("Timer '{}' ({}): device '{}' ({}) value {}", timer.name, timer.id, device.name, device.id, device.value),
+                Err(e) => format!("Timer '{}' ({}): device {} failed: {}", timer.name, timer.id, timer_device.device_id, e),
+            };
+            if attempts == 0 { device_ids.push(timer_device.device_id); }
+
+            let retry = match &result
+            {
+                Ok(device) =>
+                {
+                    if !zone_ids.contains(&device.zone_id) { zone_ids.push(device.zone_id); }
+                    None
+                }
+                Err(_) =>
+                {
+                    let backoff = RETRY_MIN.saturating_mul(2u32.saturating_pow(attempts.min(16))).min(RETRY_MAX);
+                    warn!("{}::{} - {}, retry {} in {} s", self.this_class, this_method, data, attempts + 1, backoff.as_secs());
+                    Some((attempts + 1, Instant::now() + backoff))
+                }
+            };
+            self.devices.lock().unwrap().insert(key, DeviceState { open, retry });
+
+            // A failure is recorded once per transition, a retry only when it succeeds
+            if attempts == 0 || result.is_ok()
+            {
+                if self.verbose { debug!("{}::{} - {}", self.this_class, this_method, data); }
+                super::record(&self.executor, name, result.is_ok(), data).await;
+            }
+        }
+
+        (device_ids, zone_ids)
+    }
+
+    //------------------------- Run
+    async fn run(&self, timer_device: &TimerDeviceModel, open: bool) -> Result<DeviceModel, String>
+    {
+        match open
+        {
+            true => self.executor.execute(timer_device.device_id, &timer_device.command_id.to_string()).await.into_result().map_err(String::from),
+            false => self.close(timer_device.device_id, timer_device.command_id).await,
+        }
+    }
+
+    //------------------------- Close
+    async fn close(&self, device_id: i32, command_id: i32) -> Result<DeviceModel, String>
+    {
+        let command = DeviceCommandORM::new(false, self.log).item(&self.executor.db, command_id).await.into_result()?;
+        let filters = HashMap::from([("device_id".to_string(), device_id.to_string()), ("enable".to_string(), "true".to_string())]);
+        let commands = DeviceCommandORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()?;
+        let close = close_command(&commands, &command).ok_or_else(|| format!("Command '{}' ({}) has no command that undoes it on device {}", command.name, command.id, device_id))?;
+
+        Ok(self.executor.execute(device_id, &close.id.to_string()).await.into_result()?)
+    }
+}
+
+//--------------------------------------------------------------------------------- Helpers
+fn parse_time(value: &str) -> Option<NaiveTime>
+{
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").or_else(|_| NaiveTime::parse_from_str(value.trim(), "%H:%M:%S")).ok()
+}
+
+/// Command of the same device that undoes the opening one: a command with value 0 after one that
+/// drives the device, else the first command that drives it (lowest id)
+fn close_command<'a>(commands: &'a [DeviceCommandModel], open: &DeviceCommandModel) -> Option<&'a DeviceCommandModel>
+{
+    let level = |command: &DeviceCommandModel| command.value_to.or(command.value_from).unwrap_or(0);
+    let others = commands.iter().filter(|command| command.id != open.id && command.device_id == open.device_id && level(command) != level(open));
+
+    match level(open) != 0
+    {
+        true => others.filter(|command| level(command) == 0).min_by_key(|command| command.id),
+        false => others.min_by_key(|command| command.id),
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use sea_orm::{ActiveModelTrait, ColumnTrait, Database, EntityTrait, PaginatorTrait, QueryFilter, Set};
+    use sea_orm::sea_query::Expr;
+    use crate::hardware::Buses;
+    use crate::hardware::gpio::{MockPinDriver, PinDriver};
+    use crate::orm::models::{device, device_command, log, port, timer, timer_device, timer_item};
+    use super::super::events::EventBus;
+    use super::*;
+
+    fn at(value: &str) -> NaiveTime
+    {
+        parse_time(value).unwrap()
+    }
+
+    /// Samples with their timers disabled, plus a timer open 06:00-07:00 on a GPIO OUT line 5
+    async fn scheduler() -> (TimerScheduler, i32, i32)
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        timer::Entity::update_many().col_expr(timer::Column::Enable, Expr::value(false)).exec(&db).await.unwrap();
+
+        let port = port::ActiveModel { user_id: Set(1), name: Set("T-Out".to_string()), pin: Set(0), port: Set(5), value: Set(0), description: Set(String::new()), enable: Set(true), protocol: Set("GPIO".to_string()), r#type: Set("OUT".to_string()), ..Default::default() };
+        let port = port.insert(&db).await.unwrap();
+        let timer = timer::ActiveModel { user_id: Set(1), name: Set("T-Morning".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        let timer = timer.insert(&db).await.unwrap();
+        let item = timer_item::ActiveModel { timer_id: Set(timer.id), name: Set("Morning".to_string()), value_from: Set("06:00".to_string()), value_to: Set("07:00".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        item.insert(&db).await.unwrap();
+
+        let pins: Arc<dyn PinDriver> = Arc::new(MockPinDriver::new());
+        (TimerScheduler::new(Executor::new(db, pins.clone(), Buses::mock(pins), EventBus::new(64))), timer.id, port.id)
+    }
+
+    /// A device of zone 1 bound to the timer with its "on" command, plus an "off" command when it has one
+    async fn bind(scheduler: &TimerScheduler, timer_id: i32, port_id: i32, name: &str, with_off: bool) -> (i32, i32, Option<i32>)
+    {
+        let db = &scheduler.executor.db;
+        let device = device::ActiveModel { zone_id: Set(1), port_id: Set(port_id), power_id: Set(0), command_id: Set(0), value: Set(0), tune: Set(0), date: Set(String::new()), address: Set(String::new()), name: Set(name.to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        let device = device.insert(db).await.unwrap().id;
+        let command = |name: &str, value: i32| device_command::ActiveModel { device_id: Set(device), name: Set(name.to_string()), value_from: Set(Some(value)), value_to: Set(Some(value)), delay: Set(Some(0)), frequency: Set(None), description: Set(String::new()), reload: Set(false), enable: Set(true), r#type: Set("NONE".to_string()), ..Default::default() };
+        let on = command("on", 1).insert(db).await.unwrap().id;
+        let off = match with_off
+        {
+            true => Some(command("off", 0).insert(db).await.unwrap().id),
+            false => None,
+        };
+        timer_device::ActiveModel { timer_id: Set(timer_id), device_id: Set(device), command_id: Set(on), description: Set(String::new()), enable: Set(true), ..Default::default() }.insert(db).await.unwrap();
+        (device, on, off)
+    }
+
+    async fn command_id(scheduler: &TimerScheduler, device_id: i32) -> i32
+    {
+        device::Entity::find_by_id(device_id).one(&scheduler.executor.db).await.unwrap().unwrap().command_id
+    }
+
+    async fn logs(scheduler: &TimerScheduler, name: &str, status: bool) -> u64
+    {
+        log::Entity::find().filter(log::Column::Name.eq(name)).filter(log::Column::Status.eq(status)).count(&scheduler.executor.db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn windows_wrap_past_midnight_and_an_empty_window_never_opens()
+    {
+        let (scheduler, _, _) = scheduler().await;
+        let window = |from: &str, to: &str| TimerItemModel { id: 1, timer_id: 1, name: String::new(), value_from: from.to_string(), value_to: to.to_string(), description: String::new(), enable: true };
+        let contains = |from: &str, to: &str, now: &str| scheduler.window_contains(&window(from, to), at(now));
+
+        assert!(contains("06:00", "07:00", "06:00") && contains("06:00", "07:00", "06:59:59"));
+        assert!(!contains("06:00", "07:00", "07:00") && !contains("06:00", "07:00", "05:59"));
+        assert!(contains("22:00", "02:00", "23:30") && contains("22:00", "02:00", "00:00") && contains("22:00", "02:00", "01:59"));
+        assert!(!contains("22:00", "02:00", "02:00") && !contains("22:00", "02:00", "12:00"));
+        assert!(!contains("06:00", "06:00", "06:00") && !contains("06:00", "06:00", "18:00"), "start == end is empty");
+        assert!(!contains("6 am", "07:00", "06:30"), "an invalid window never opens");
+    }
+
+    #[tokio::test]
+    async fn opens_and_closes_the_devices_once()
+    {
+        let (scheduler, timer_id, port_id) = scheduler().await;
+        let (device, on, off) = bind(&scheduler, timer_id, port_id, "T-Pump", true).await;
+        let mut events = scheduler.executor.events.subscribe();
+
+        // Closed at startup: nothing runs
+        scheduler.advance(at("05:00")).await.unwrap();
+        assert_eq!(command_id(&scheduler, device).await, 0);
+
+        scheduler.advance(at("06:00")).await.unwrap();
+        assert_eq!(command_id(&scheduler, device).await, on);
+        scheduler.advance(at("06:30")).await.unwrap();
+        assert_eq!(logs(&scheduler, "Timer Open", true).await, 1, "an open window is applied once");
+
+        scheduler.advance(at("07:00")).await.unwrap();
+        assert_eq!(command_id(&scheduler, device).await, off.unwrap());
+        scheduler.advance(at("08:00")).await.unwrap();
+        assert_eq!(logs(&scheduler, "Timer Close", true).await, 1);
+
+        let mut changes = Vec::new();
+        while let Ok(event) = events.try_recv()
+        {
+            if let Event::TimerChanged { timer_id: id, open, device_ids, .. } = event && id == timer_id { changes.push((open, device_ids)); }
+        }
+        assert_eq!(changes, vec![(true, vec![device]), (false, vec![device])]);
+    }
+
+    #[tokio::test]
+    async fn a_failing_device_is_recorded_once_and_does_not_hold_back_the_others()
+    {
+        let (scheduler, timer_id, port_id) = scheduler().await;
+        let (pump, _, off) = bind(&scheduler, timer_id, port_id, "T-Pump", true).await;
+        let (valve, valve_on, _) = bind(&scheduler, timer_id, port_id, "T-Valve", false).await;
+
+        // Already open at startup: both devices are switched on
+        scheduler.advance(at("06:30")).await.unwrap();
+        assert_eq!(command_id(&scheduler, valve).await, valve_on);
+
+        // The valve has nothing that undoes "on": it fails, the pump closes all the same
+        scheduler.advance(at("07:00")).await.unwrap();
+        assert_eq!(command_id(&scheduler, pump).await, off.unwrap());
+        assert_eq!(command_id(&scheduler, valve).await, valve_on);
+        let executed = logs(&scheduler, "Device Execute", true).await;
+
+        for now in ["07:00:15", "07:00:30", "07:01"] { scheduler.advance(at(now)).await.unwrap(); }
+        assert_eq!(logs(&scheduler, "Timer Close", false).await, 1, "the failure is recorded once");
+        assert_eq!(logs(&scheduler, "Timer Close", true).await, 1);
+        assert_eq!(logs(&scheduler, "Device Execute", true).await, executed, "the pump is not run again");
+        let state = scheduler.devices.lock().unwrap().get(&(timer_id, valve)).copied().unwrap();
+        assert!(!state.open && state.retry.is_some_and(|(attempts, at)| attempts == 1 && at > Instant::now()));
+    }
+
+    fn command(id: i32, name: &str, value_from: i32, value_to: i32) -> DeviceCommandModel
+    {
+        DeviceCommandModel { id, device_id: 1, name: name.to_string(), value_from: Some(value_from), value_to: Some(value_to), delay: None, frequency: None, description: String::new(), reload: false, enable: true, r#type: String::new() }
+    }
+
+    #[test]
+    fn closes_with_the_command_of_the_other_level()
+    {
+        let commands = [command(2, "off", 0, 0), command(1, "on", 1, 1), command(3, "dim", 30, 30), command(4, "bright", 0, 100)];
+        let close = |open: usize| close_command(&commands, &commands[open]).map(|command| command.name.as_str());
+
+        assert_eq!(close(1), Some("off"));
+        assert_eq!(close(2), Some("off"));
+        assert_eq!(close(3), Some("off"));
+        assert_eq!(close(0), Some("on"));
+        assert_eq!(close_command(&commands[1..], &commands[1]), None);
+    }
+}
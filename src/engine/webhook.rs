@@ -0,0 +1,470 @@
+//--------------------------------------------------------------------------------- Location
+// src/engine/webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// Webhook dispatcher: posts the engine events each enabled webhook subscribes to as JSON, signed
+// with HMAC-SHA256 over the body, and retries failed posts with exponential backoff. Every event
+// sent is kept in webhook_delivery with the outcome of its last attempt, so it can be replayed;
+// deliveries still pending at shutdown are resumed on the next start. Redirects are not followed
+// and, unless WEBHOOK_ALLOW_PRIVATE=true, loopback, link-local and private destinations are refused
+// both in the URL and once the host name is resolved
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use hmac::{Hmac, Mac};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect, Set};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+use crate::logics::error::ApiError;
+use crate::logics::general::{ModelOutput, Roles};
+use crate::orm::logics::user_role::UserRoleORM;
+use crate::orm::logics::webhook::WebhookORM;
+use crate::orm::logics::webhook_delivery::WebhookDeliveryORM;
+use crate::orm::models::webhook::Model as WebhookModel;
+use crate::orm::models::webhook_delivery::{ActiveModel as WebhookDeliveryActiveModel, Model as WebhookDeliveryModel};
+use crate::orm::models::zone::{Column as ZoneColumn, Entity as ZoneEntity};
+use super::events::Event;
+use super::executor::Executor;
+
+//--------------------------------------------------------------------------------- Constants
+/// Events sent to a webhook whose event filter is empty
+const DEFAULT_EVENTS: [&str; 4] = ["device_changed", "rule_fired", "limit_reached", "log_error"];
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct WebhookDispatcher
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub executor: Executor,
+    /// Attempts per delivery, the first one included
+    pub retries: u32,
+    /// Wait before the first retry, doubled after each failed attempt
+    pub backoff: Duration,
+    /// WEBHOOK_ALLOW_PRIVATE: loopback, link-local and private destinations are reachable
+    pub allow_private: bool,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher
+{
+    //------------------------- New
+    pub fn new(executor: Executor) -> Self
+    {
+        let var = |name: &str, default: u64| std::env::var(name).ok().and_then(|value| value.parse().ok()).unwrap_or(default);
+        let allow_private = private_allowed();
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(var("WEBHOOK_TIMEOUT", 10).max(1)))
+            .user_agent("raspberrypi_iot_core_rust")
+            .redirect(reqwest::redirect::Policy::none());
+        if !allow_private { builder = builder.dns_resolver(Arc::new(PublicResolver)); }
+        let client = builder.build().expect("Failed to build the webhook HTTP client");
+
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "WebhookDispatcher".to_string(),
+            executor,
+            retries: var("WEBHOOK_RETRIES", 5).clamp(1, 20) as u32,
+            backoff: Duration::from_secs(var("WEBHOOK_BACKOFF", 2).max(1)),
+            allow_private,
+            client,
+        }
+    }
+
+    //------------------------- Spawn
+    /// Resume the pending deliveries, then post every event the webhooks subscribe to
+    pub fn spawn(&self)
+    {
+        let dispatcher = self.clone();
+        let mut receiver = dispatcher.executor.events.subscribe();
+
+        tokio::spawn(async move
+        {
+            dispatcher.resume().await;
+            loop
+            {
+                match receiver.recv().await
+                {
+                    Ok(event) => dispatcher.dispatch(&event).await,
+                    Err(RecvError::Lagged(skipped)) => warn!("{}::spawn - Skipped {} events", dispatcher.this_class, skipped),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    //------------------------- Replay
+    /// Send a stored delivery again with the same payload, the attempts go on in the background
+    pub async fn replay(&self, id: i32) -> ModelOutput<WebhookDeliveryModel>
+    {
+        let orm = WebhookDeliveryORM::new(false, self.log);
+        let delivery = match orm.item(&self.executor.db, id).await.into_result()
+        {
+            Ok(delivery) => delivery,
+            Err(e) => return ModelOutput::fail(e),
+        };
+        if delivery.status == "pending"
+        {
+            return ModelOutput::fail(ApiError::Conflict(format!("Delivery {} is still being sent", id)));
+        }
+
+        let mut active: WebhookDeliveryActiveModel = delivery.into();
+        active.status = Set("pending".to_string());
+        active.updated = Set(now());
+        let mut output = orm.update(&self.executor.db, active).await;
+        if let Some(delivery) = &output.data
+        {
+            output.message = format!("Delivery {} queued again", delivery.id);
+            if self.verbose { info!("{}::replay - Delivery {} to webhook {}", self.this_class, delivery.id, delivery.webhook_id); }
+            tokio::spawn(self.clone().deliver(delivery.clone()));
+        }
+        output
+    }
+
+    //------------------------- Resume
+    async fn resume(&self)
+    {
+        let filters = HashMap::from([("status".to_string(), "pending".to_string())]);
+        match WebhookDeliveryORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()
+        {
+            Ok(deliveries) =>
+            {
+                if !deliveries.is_empty() { info!("{}::resume - {} pending deliveries", self.this_class, deliveries.len()); }
+                for delivery in deliveries { tokio::spawn(self.clone().deliver(delivery)); }
+            }
+            Err(e) => error!("{}::resume - Cannot load the pending deliveries: {}", self.this_class, e),
+        }
+    }
+
+    //------------------------- Dispatch
+    /// Store one delivery per subscribed webhook and start sending it; webhooks are read on every
+    /// event so CRUD changes apply without a restart
+    async fn dispatch(&self, event: &Event)
+    {
+        let this_method = "dispatch";
+        let db = &self.executor.db;
+
+        let filters = HashMap::from([("enable".to_string(), "true".to_string())]);
+        let webhooks = match WebhookORM::new(false, self.log).items(db, filters).await.into_result()
+        {
+            Ok(webhooks) => webhooks,
+            Err(e) => { error!("{}::{} - Cannot load the webhooks: {}", self.this_class, this_method, e); return; }
+        };
+        if webhooks.is_empty() { return; }
+
+        let (name, data) = split(event);
+        for webhook in webhooks.into_iter().filter(|webhook| subscribes(webhook, &name, event))
+        {
+            match self.visible(webhook.user_id, event).await
+            {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => { error!("{}::{} - Webhook {}: {}", self.this_class, this_method, webhook.id, e); continue; }
+            }
+
+            let payload = json!({ "event": name, "date": now(), "webhook_id": webhook.id, "data": data }).to_string();
+            let item = WebhookDeliveryActiveModel
+            {
+                id: Default::default(),
+                webhook_id: Set(webhook.id),
+                event: Set(name.clone()),
+                payload: Set(payload),
+                status: Set("pending".to_string()),
+                attempts: Set(0),
+                response_code: Set(0),
+                error: Set(String::new()),
+                date: Set(now()),
+                updated: Set(now()),
+            };
+            match WebhookDeliveryORM::new(false, self.log).add(db, item).await.into_result()
+            {
+                Ok(delivery) => { tokio::spawn(self.clone().deliver(delivery)); }
+                Err(e) => error!("{}::{} - Cannot store the delivery for webhook {}: {}", self.this_class, this_method, webhook.id, e),
+            }
+        }
+    }
+
+    /// Webhooks of non-admin users only get the events of their own zones, as on /ws: events without
+    /// a zone (log_added, log_error, timers that drive no device) are for admin-owned webhooks only
+    async fn visible(&self, user_id: i32, event: &Event) -> Result<bool, ApiError>
+    {
+        let db = &self.executor.db;
+        let role = match UserRoleORM::new(false, false).item_by_user(db, user_id).await.into_result()
+        {
+            Ok(role) => Roles::from_name(&role.role).unwrap_or(Roles::Viewer),
+            Err(ApiError::NotFound(_)) => Roles::Viewer,
+            Err(e) => return Err(e),
+        };
+        if role == Roles::Admin { return Ok(true); }
+
+        let zone_ids = event.zone_ids();
+        if zone_ids.is_empty() { return Ok(false); }
+
+        let owned: Vec<i32> = ZoneEntity::find().select_only().column(ZoneColumn::Id)
+            .filter(ZoneColumn::UserId.eq(user_id))
+            .filter(ZoneColumn::Id.is_in(zone_ids))
+            .into_tuple().all(db).await
+            .map_err(|e| ApiError::from_db(e.to_string(), &e))?;
+        Ok(!owned.is_empty())
+    }
+
+    //------------------------- Deliver
+    /// Post the payload until it is accepted or the attempts run out, recording each attempt
+    async fn deliver(self, delivery: WebhookDeliveryModel)
+    {
+        let this_method = "deliver";
+        let db = &self.executor.db;
+        let mut wait = self.backoff;
+
+        for attempt in 1..=self.retries
+        {
+            // Read the webhook on each attempt: it may have been disabled, removed or given a new secret meanwhile
+            let outcome = match WebhookORM::new(false, false).item(db, delivery.webhook_id).await.into_result()
+            {
+                Ok(webhook) if webhook.enable => self.post(&webhook, &delivery).await,
+                Ok(_) => Attempt::stop("Webhook is disabled"),
+                Err(e) => Attempt::stop(&e.to_string()),
+            };
+            let last = !outcome.retry || attempt == self.retries;
+
+            let mut active: WebhookDeliveryActiveModel = delivery.clone().into();
+            active.status = Set(match (outcome.delivered, last) { (true, _) => "delivered", (false, true) => "failed", (false, false) => "pending" }.to_string());
+            active.attempts = Set(delivery.attempts + attempt as i32);
+            active.response_code = Set(outcome.response_code);
+            active.error = Set(outcome.error.clone());
+            active.updated = Set(now());
+            if let Err(e) = WebhookDeliveryORM::new(false, false).update(db, active).await.into_result()
+            {
+                error!("{}::{} - Cannot record delivery {}: {}", self.this_class, this_method, delivery.id, e);
+            }
+
+            if outcome.delivered
+            {
+                if self.verbose { debug!("{}::{} - Delivery {} ({}) accepted with {}", self.this_class, this_method, delivery.id, delivery.event, outcome.response_code); }
+                return;
+            }
+            if last
+            {
+                warn!("{}::{} - Delivery {} ({}) failed after {} attempt(s): {}", self.this_class, this_method, delivery.id, delivery.event, attempt, outcome.error);
+                return;
+            }
+
+            debug!("{}::{} - Delivery {} attempt {} failed ({}), retrying in {}s", self.this_class, this_method, delivery.id, attempt, outcome.error, wait.as_secs());
+            tokio::time::sleep(wait).await;
+            wait *= 2;
+        }
+    }
+
+    /// One signed POST; any 2xx answer is a success
+    async fn post(&self, webhook: &WebhookModel, delivery: &WebhookDeliveryModel) -> Attempt
+    {
+        if let Err(e) = check_url(&webhook.url, self.allow_private) { return Attempt::stop(&e); }
+        let request = self.client.post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Event", &delivery.event)
+            .header("X-Webhook-Delivery", delivery.id.to_string())
+            .header("X-Webhook-Signature", format!("sha256={}", sign(&webhook.secret, &delivery.payload)))
+            .body(delivery.payload.clone());
+
+        match request.send().await
+        {
+            Ok(response) if response.status().is_success() => Attempt { delivered: true, retry: false, response_code: i32::from(response.status().as_u16()), error: String::new() },
+            Ok(response) => Attempt { delivered: false, retry: true, response_code: i32::from(response.status().as_u16()), error: format!("HTTP {}", response.status()) },
+            Err(e) => Attempt { delivered: false, retry: true, response_code: 0, error: e.to_string() },
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Attempt
+/// Outcome of one delivery attempt
+struct Attempt
+{
+    delivered: bool,
+    retry: bool,
+    response_code: i32,
+    error: String,
+}
+
+impl Attempt
+{
+    /// Failed for a reason that retrying will not fix
+    fn stop(error: &str) -> Self
+    {
+        Self { delivered: false, retry: false, response_code: 0, error: error.to_string() }
+    }
+}
+
+//--------------------------------------------------------------------------------- Destination
+pub fn private_allowed() -> bool
+{
+    std::env::var("WEBHOOK_ALLOW_PRIVATE").is_ok_and(|value| value == "true")
+}
+
+/// An http or https URL with a host; unless private destinations are allowed, an address or
+/// localhost name must be public (other names are checked by PublicResolver when they resolve)
+pub fn check_url(url: &str, allow_private: bool) -> Result<reqwest::Url, String>
+{
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("invalid url: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") { return Err("url must be http or https".to_string()); }
+    let host = parsed.host_str().ok_or_else(|| "url has no host".to_string())?.to_lowercase();
+    if allow_private { return Ok(parsed); }
+
+    let private = match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>()
+    {
+        Ok(ip) => !is_public(ip),
+        Err(_) => host == "localhost" || host.ends_with(".localhost"),
+    };
+    match private
+    {
+        true => Err(format!("{} is a loopback, link-local or private destination", host)),
+        false => Ok(parsed),
+    }
+}
+
+/// Reachable on the internet: not loopback, private, link-local, shared, multicast or unspecified
+pub fn is_public(ip: IpAddr) -> bool
+{
+    match ip
+    {
+        IpAddr::V4(ip) =>
+        {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() || ip.is_broadcast() || ip.is_multicast()
+                || first == 0 || (first == 100 && (64..128).contains(&second)))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped()
+        {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None =>
+            {
+                let first = ip.segments()[0];
+                !(ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() || (first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// DNS for webhook posts that drops every address that is not public, so a name cannot point
+/// the request at the LAN or a metadata endpoint
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver
+{
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving
+    {
+        let host = name.as_str().to_string();
+        Box::pin(async move
+        {
+            let addresses = tokio::net::lookup_host((host.as_str(), 0)).await?.filter(|address| is_public(address.ip())).collect::<Vec<SocketAddr>>();
+            if addresses.is_empty() { return Err(format!("{} resolves to no public address", host).into()); }
+            Ok(Box::new(addresses.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+//--------------------------------------------------------------------------------- Helpers
+/// Event name (the "event" tag) and its fields
+fn split(event: &Event) -> (String, Value)
+{
+    let mut value = serde_json::to_value(event).unwrap_or_default();
+    let name = value.as_object_mut().and_then(|fields| fields.remove("event")).and_then(|name| name.as_str().map(str::to_string)).unwrap_or_default();
+    (name, value)
+}
+
+/// The webhook's filter names the event; "log_error" stands for log entries with a false status, "*" for everything
+fn subscribes(webhook: &WebhookModel, name: &str, event: &Event) -> bool
+{
+    let names = webhook.events.split(',').map(str::trim).filter(|name| !name.is_empty()).collect::<Vec<_>>();
+    let names = if names.is_empty() { DEFAULT_EVENTS.to_vec() } else { names };
+    let log_error = matches!(event, Event::LogAdded { status: false, .. });
+
+    names.iter().any(|wanted| *wanted == "*" || *wanted == name || (*wanted == "log_error" && log_error))
+}
+
+/// Hex HMAC-SHA256 of the body, the receiver recomputes it with the shared secret
+pub fn sign(secret: &str, body: &str) -> String
+{
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes a key of any length");
+    mac.update(body.as_bytes());
+    mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn now() -> String
+{
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn webhook(events: &str) -> WebhookModel
+    {
+        WebhookModel { id: 1, user_id: 1, name: "test".to_string(), url: "http://localhost".to_string(), secret: "s".to_string(), events: events.to_string(), description: String::new(), enable: true }
+    }
+
+    fn check(webhook: &WebhookModel, event: &Event) -> bool
+    {
+        subscribes(webhook, &split(event).0, event)
+    }
+
+    #[test]
+    fn empty_filter_is_the_default_events()
+    {
+        let changed = Event::DeviceChanged { device_id: 1, zone_id: 1, command_id: 1, previous: 0, value: 1 };
+        let read = Event::DeviceRead { device_id: 1, zone_id: 1, command_id: 1, command: None, value: 1 };
+        let error = Event::LogAdded { log_id: 1, date: String::new(), name: "x".to_string(), status: false, data: String::new() };
+        let info = Event::LogAdded { log_id: 2, date: String::new(), name: "x".to_string(), status: true, data: String::new() };
+
+        assert!(check(&webhook(""), &changed));
+        assert!(check(&webhook(""), &error));
+        assert!(!check(&webhook(""), &read));
+        assert!(!check(&webhook(""), &info));
+        assert!(check(&webhook("*"), &read) && check(&webhook("*"), &info));
+        assert!(check(&webhook("device_read, log_error"), &read) && !check(&webhook("device_read"), &changed));
+    }
+    #[test]
+    fn private_destinations_are_refused()
+    {
+        for url in ["http://127.0.0.1:3000/x", "http://localhost/x", "https://api.localhost", "http://10.0.0.5", "http://192.168.1.1", "http://172.16.0.1",
+                    "http://169.254.169.254/latest/meta-data", "http://100.64.0.1", "http://0.0.0.0", "http://[::1]/x", "http://[fe80::1]", "http://[fd00::1]", "http://[::ffff:127.0.0.1]"]
+        {
+            assert!(check_url(url, false).is_err(), "{} is refused", url);
+            assert!(check_url(url, true).is_ok(), "{} is allowed with WEBHOOK_ALLOW_PRIVATE", url);
+        }
+        for url in ["https://example.com/hook", "http://93.184.216.34", "http://[2606:4700::1111]"]
+        {
+            assert!(check_url(url, false).is_ok(), "{} is public", url);
+        }
+        for url in ["ftp://example.com", "file:///etc/passwd", "gopher://127.0.0.1", "example.com", "http://"]
+        {
+            assert!(check_url(url, true).is_err(), "{} is not a webhook url", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn names_resolving_to_private_addresses_are_dropped()
+    {
+        use reqwest::dns::Resolve;
+        use std::str::FromStr;
+        assert!(PublicResolver.resolve(reqwest::dns::Name::from_str("localhost").unwrap()).await.is_err());
+    }
+
+    #[test]
+    fn sign_is_hex_hmac_sha256()
+    {
+        // RFC 4231, test case 2
+        assert_eq!(sign("Jefe", "what do ya want for nothing?"), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_ne!(sign("other", "what do ya want for nothing?"), sign("Jefe", "what do ya want for nothing?"));
+    }
+}
@@ -0,0 +1,290 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/file.rs
+
+//--------------------------------------------------------------------------------- Description
+// FILE port sources: a device on a FILE port reads its value from the path in device.address
+//   28-0922a03b54a4                    DS18B20 id, read from {FILE_W1_ROOT}/{id}/w1_slave
+//   /sys/bus/w1/devices/28-.../w1_slave DS18B20 output, "YES" CRC line then t=<millidegrees>
+//   /sys/class/thermal/.../temp        sysfs attribute: first token, a number or on/off word
+//   /var/lib/sensors/level             plain integer file
+// The raw reading is divided by an optional ":<divisor>" suffix, 1000 by default for DS18B20 so
+// the value is in whole degrees (":100" keeps tenths). Paths are read only under FILE_W1_ROOT, /sys
+// and the FILE_ROOTS directories, at most MAX_BYTES of them, and errors never quote the content
+
+//--------------------------------------------------------------------------------- Import
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use super::HardwareError;
+
+//--------------------------------------------------------------------------------- Limits
+/// A w1_slave file is 75 bytes, a sysfs attribute a page at most
+pub const MAX_BYTES: u64 = 4096;
+
+//--------------------------------------------------------------------------------- Roots
+/// Directories a FILE source may read under
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRoots
+{
+    pub w1: PathBuf,
+    pub allowed: Vec<PathBuf>,
+}
+
+impl FileRoots
+{
+    //------------------------- New
+    /// The 1-Wire root, /sys and the comma separated `extra` directories
+    pub fn new(w1_root: &str, extra: &str) -> Self
+    {
+        let mut allowed = vec![PathBuf::from(w1_root), PathBuf::from("/sys")];
+        allowed.extend(extra.split(',').map(str::trim).filter(|root| root.starts_with('/')).map(PathBuf::from));
+        Self { w1: PathBuf::from(w1_root), allowed }
+    }
+
+    //------------------------- From Env
+    pub fn from_env() -> Self
+    {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        Self::new(&var("FILE_W1_ROOT").unwrap_or_else(|| "/sys/bus/w1/devices".to_string()), &var("FILE_ROOTS").unwrap_or_default())
+    }
+
+    /// Absolute, without any "..", and under one of the roots
+    pub fn contains(&self, path: &Path) -> bool
+    {
+        path.is_absolute()
+            && path.components().all(|component| !matches!(component, Component::ParentDir))
+            && self.allowed.iter().any(|root| path.starts_with(root))
+    }
+}
+
+//--------------------------------------------------------------------------------- Format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat
+{
+    W1Slave,
+    Sysfs,
+    Integer,
+}
+
+impl FileFormat
+{
+    /// Parser picked from the path
+    pub fn of_path(path: &Path) -> Self
+    {
+        if path.file_name().is_some_and(|name| name == "w1_slave") { return FileFormat::W1Slave; }
+        match path.starts_with("/sys")
+        {
+            true => FileFormat::Sysfs,
+            false => FileFormat::Integer,
+        }
+    }
+
+    /// Raw units per value unit when the address has no divisor
+    fn default_divisor(&self) -> i64
+    {
+        match self
+        {
+            FileFormat::W1Slave => 1000,
+            FileFormat::Sysfs | FileFormat::Integer => 1,
+        }
+    }
+
+    //------------------------- Parse
+    /// Raw reading of the file content
+    pub fn parse(&self, content: &str) -> Result<f64, String>
+    {
+        match self
+        {
+            FileFormat::W1Slave => parse_w1_slave(content),
+            FileFormat::Sysfs => parse_sysfs(content),
+            FileFormat::Integer => parse_integer(content),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSource
+{
+    pub path: PathBuf,
+    pub format: FileFormat,
+    pub divisor: i64,
+}
+
+impl FileSource
+{
+    //------------------------- From Address
+    /// Source of a device address; a bare 1-Wire id is looked up under the w1 root
+    pub fn from_address(address: &str, roots: &FileRoots) -> Result<Self, HardwareError>
+    {
+        let address = address.trim();
+        let (location, divisor) = match address.rsplit_once(':')
+        {
+            Some((location, divisor)) => match divisor.trim().parse::<i64>()
+            {
+                Ok(divisor) if divisor > 0 => (location.trim(), Some(divisor)),
+                _ => return Err(HardwareError::Unsupported(format!("invalid divisor in file address '{}'", address))),
+            },
+            None => (address, None),
+        };
+        if location.is_empty() { return Err(HardwareError::Unsupported("empty file address".to_string())); }
+
+        let path = match location.contains('/')
+        {
+            true => PathBuf::from(location),
+            false => roots.w1.join(location).join("w1_slave"),
+        };
+        if !roots.contains(&path) { return Err(HardwareError::Unsupported(format!("file address '{}' is outside FILE_W1_ROOT, /sys and FILE_ROOTS", address))); }
+        let format = FileFormat::of_path(&path);
+        Ok(Self { divisor: divisor.unwrap_or_else(|| format.default_divisor()), path, format })
+    }
+
+    //------------------------- Read
+    /// Blocking: a DS18B20 takes up to 750 ms to answer
+    pub fn read(&self) -> Result<i32, HardwareError>
+    {
+        let failed = |reason: &str| HardwareError::Device(format!("{}: {}", self.path.display(), reason));
+        let file = std::fs::File::open(&self.path).map_err(|e| failed(&e.kind().to_string()))?;
+        let mut bytes = Vec::new();
+        file.take(MAX_BYTES + 1).read_to_end(&mut bytes).map_err(|e| failed(&e.kind().to_string()))?;
+        if bytes.len() as u64 > MAX_BYTES { return Err(failed(&format!("larger than {} bytes", MAX_BYTES))); }
+
+        let content = String::from_utf8(bytes).map_err(|_| failed("not text"))?;
+        let raw = self.format.parse(&content).map_err(|e| failed(&e))?;
+        let value = (raw / self.divisor as f64).round();
+
+        match value >= i32::MIN as f64 && value <= i32::MAX as f64
+        {
+            true => Ok(value as i32),
+            false => Err(HardwareError::Device(format!("{}: value {} out of range", self.path.display(), value))),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Parsers
+/// "72 01 4b 46 7f ff 0e 10 57 : crc=57 YES" then "72 01 4b 46 7f ff 0e 10 57 t=23125"
+pub fn parse_w1_slave(content: &str) -> Result<f64, String>
+{
+    let mut lines = content.lines();
+    let crc = lines.next().unwrap_or_default();
+    if !crc.trim_end().ends_with("YES") { return Err("CRC check failed".to_string()); }
+
+    let temperature = lines.next().and_then(|line| line.rsplit_once("t=")).map(|(_, value)| value.trim())
+        .ok_or_else(|| "no t= reading".to_string())?;
+    let millidegrees = temperature.parse::<i64>().map_err(|_| "invalid t= reading".to_string())?;
+
+    // 85000 is the power-on value, the sensor has not converted yet
+    if millidegrees == 85000 { return Err("power-on reading 85000".to_string()); }
+    Ok(millidegrees as f64)
+}
+
+/// First token as a number, or a state word such as on/off, up/down, enabled/disabled
+pub fn parse_sysfs(content: &str) -> Result<f64, String>
+{
+    let token = content.split_whitespace().next().ok_or_else(|| "empty attribute".to_string())?;
+    if let Ok(value) = token.parse::<f64>() && value.is_finite() { return Ok(value); }
+
+    match token.to_lowercase().as_str()
+    {
+        "on" | "up" | "yes" | "true" | "enabled" | "high" => Ok(1.0),
+        "off" | "down" | "no" | "false" | "disabled" | "low" => Ok(0.0),
+        _ => Err("not a number or on/off word".to_string()),
+    }
+}
+
+/// The whole content is one integer
+pub fn parse_integer(content: &str) -> Result<f64, String>
+{
+    content.trim().parse::<i64>().map(|value| value as f64).map_err(|_| "not an integer".to_string())
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const W1_SLAVE: &str = "72 01 4b 46 7f ff 0e 10 57 : crc=57 YES\n72 01 4b 46 7f ff 0e 10 57 t=23125\n";
+
+    #[test]
+    fn w1_slave_needs_a_good_crc_and_a_converted_reading()
+    {
+        assert_eq!(parse_w1_slave(W1_SLAVE), Ok(23125.0));
+        assert_eq!(parse_w1_slave("ff ff ff ff ff ff ff ff ff : crc=c9 YES\nff ff ff ff ff ff ff ff ff t=-1250"), Ok(-1250.0));
+        assert!(parse_w1_slave(&W1_SLAVE.replace("YES", "NO")).unwrap_err().contains("CRC"));
+        assert!(parse_w1_slave(&W1_SLAVE.replace("23125", "85000")).unwrap_err().contains("85000"));
+        assert!(parse_w1_slave("72 01 4b 46 7f ff 0e 10 57 : crc=57 YES\n").is_err());
+        assert!(parse_w1_slave("").is_err());
+    }
+
+    #[test]
+    fn sysfs_and_integer_values()
+    {
+        assert_eq!(parse_sysfs("42000\n"), Ok(42000.0));
+        assert_eq!(parse_sysfs("1.5 extra"), Ok(1.5));
+        assert_eq!(parse_sysfs("Enabled"), Ok(1.0));
+        assert_eq!(parse_sysfs("down"), Ok(0.0));
+        assert!(parse_sysfs("").is_err() && parse_sysfs("NaN").is_err() && parse_sysfs("maybe").is_err());
+
+        assert_eq!(parse_integer(" -17\n"), Ok(-17.0));
+        assert!(parse_integer("1.5").is_err());
+    }
+
+    #[test]
+    fn from_address_picks_path_format_and_divisor()
+    {
+        let roots = FileRoots::new("/sys/bus/w1/devices", "/var/lib/sensors");
+        let sensor = FileSource::from_address("28-0316a2795cff", &roots).unwrap();
+        assert_eq!(sensor, FileSource { path: PathBuf::from("/sys/bus/w1/devices/28-0316a2795cff/w1_slave"), format: FileFormat::W1Slave, divisor: 1000 });
+
+        let thermal = FileSource::from_address("/sys/class/thermal/thermal_zone0/temp:100", &roots).unwrap();
+        assert_eq!((thermal.format, thermal.divisor), (FileFormat::Sysfs, 100));
+        assert_eq!(FileSource::from_address("/var/lib/sensors/level", &roots).unwrap().format, FileFormat::Integer);
+
+        for address in ["", ":10", "/var/lib/sensors/level:0", "/var/lib/sensors/level:ten"]
+        {
+            assert!(FileSource::from_address(address, &roots).is_err(), "'{}' is rejected", address);
+        }
+    }
+
+    #[test]
+    fn only_paths_under_the_roots_are_read()
+    {
+        let roots = FileRoots::new("/sys/bus/w1/devices", " /var/lib/sensors , relative");
+        assert_eq!(roots.allowed, vec![PathBuf::from("/sys/bus/w1/devices"), PathBuf::from("/sys"), PathBuf::from("/var/lib/sensors")]);
+
+        for address in ["/etc/shadow", "/dev/zero", "/sys/../etc/shadow", "/var/lib/sensors/../../../etc/shadow", "/var/lib/sensorsX/level", "sys/class/x", "../28-test"]
+        {
+            assert!(matches!(FileSource::from_address(address, &roots), Err(HardwareError::Unsupported(_))), "'{}' is refused", address);
+        }
+    }
+
+    #[test]
+    fn read_divides_and_rounds()
+    {
+        let dir = std::env::temp_dir().join(format!("file_source_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("28-test")).unwrap();
+        std::fs::write(dir.join("28-test/w1_slave"), W1_SLAVE).unwrap();
+        let roots = FileRoots::new(dir.to_str().unwrap(), "");
+
+        let sensor = FileSource::from_address("28-test", &roots).unwrap();
+        assert_eq!(sensor.read().unwrap(), 23);
+        assert!(FileSource::from_address("28-missing", &roots).unwrap().read().is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn read_is_bounded_and_keeps_the_content_out_of_errors()
+    {
+        let dir = std::env::temp_dir().join(format!("file_source_errors_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("secret"), "root:$6$hash:19000:0:99999:7:::\n").unwrap();
+        std::fs::write(dir.join("large"), vec![b'1'; MAX_BYTES as usize + 1]).unwrap();
+        let roots = FileRoots::new("/unused", dir.to_str().unwrap());
+
+        let error = FileSource::from_address(dir.join("secret").to_str().unwrap(), &roots).unwrap().read().unwrap_err().to_string();
+        assert!(error.contains("not an integer") && !error.contains("root:"), "{}", error);
+        let error = FileSource::from_address(dir.join("large").to_str().unwrap(), &roots).unwrap().read().unwrap_err().to_string();
+        assert!(error.contains("larger than"), "{}", error);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
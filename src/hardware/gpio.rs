@@ -0,0 +1,190 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/gpio.rs
+
+//--------------------------------------------------------------------------------- Description
+// GPIO pin drivers: Linux character-device backend and in-memory mock backend
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::{hash_map::Entry, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use gpio_cdev::{Chip, LineHandle, LineRequestFlags};
+use tracing::{info, warn};
+use super::HardwareError;
+
+//--------------------------------------------------------------------------------- Trait
+pub trait PinDriver: Send + Sync
+{
+    /// Backend name, used in logs
+    fn name(&self) -> &'static str;
+
+    /// Drive an output line (BCM offset) to the given level
+    fn write(&self, line: u32, value: i32) -> Result<(), HardwareError>;
+
+    /// Read the live level of a line (BCM offset)
+    fn read(&self, line: u32) -> Result<i32, HardwareError>;
+
+    /// Level an output line is driven to, None while this process has not driven it: reading it
+    /// as an input would turn the line around
+    fn output_level(&self, line: u32) -> Result<Option<i32>, HardwareError>;
+}
+
+//--------------------------------------------------------------------------------- Factory
+/// GPIO_DRIVER=cdev opens GPIO_CHIP or fails, mock simulates the lines, auto takes the chip when
+/// it exists and only falls back to the mock on a machine without one
+pub fn create_driver() -> Result<Arc<dyn PinDriver>, HardwareError>
+{
+    let chip_path = std::env::var("GPIO_CHIP").unwrap_or_else(|_| "/dev/gpiochip0".to_string());
+    let driver = std::env::var("GPIO_DRIVER").unwrap_or_else(|_| "auto".to_string());
+    open_driver(&driver, &chip_path)
+}
+
+pub fn open_driver(driver: &str, chip_path: &str) -> Result<Arc<dyn PinDriver>, HardwareError>
+{
+    let open = ||
+    {
+        let driver = CdevPinDriver::new(chip_path).map_err(|e| HardwareError::Device(format!("GPIO chip {}: {}", chip_path, e)))?;
+        info!("GPIO driver: cdev ({})", chip_path);
+        Ok(Arc::new(driver) as Arc<dyn PinDriver>)
+    };
+
+    match driver.to_lowercase().as_str()
+    {
+        "cdev" => open(),
+        "mock" =>
+        {
+            info!("GPIO driver: mock");
+            Ok(Arc::new(MockPinDriver::new()))
+        }
+        "auto" if Path::new(chip_path).exists() => open(),
+        "auto" =>
+        {
+            warn!("GPIO driver: mock, {} not found, no pin is driven (GPIO_DRIVER=cdev makes it required)", chip_path);
+            Ok(Arc::new(MockPinDriver::new()))
+        }
+        _ => Err(HardwareError::Unsupported(format!("GPIO_DRIVER '{}', expected auto, cdev or mock", driver))),
+    }
+}
+
+//--------------------------------------------------------------------------------- Cdev
+pub struct CdevPinDriver
+{
+    chip: Mutex<Chip>,
+    handles: Mutex<HashMap<u32, (LineRequestFlags, LineHandle)>>,
+}
+
+impl CdevPinDriver
+{
+    //------------------------- New
+    pub fn new(path: &str) -> Result<Self, HardwareError>
+    {
+        let chip = Chip::new(path).map_err(|e| HardwareError::Device(e.to_string()))?;
+        Ok(Self { chip: Mutex::new(chip), handles: Mutex::new(HashMap::new()) })
+    }
+
+    //------------------------- Request
+    fn request(&self, line: u32, flags: LineRequestFlags, default: u8) -> Result<LineHandle, HardwareError>
+    {
+        let mut chip = self.chip.lock().unwrap();
+        let line = chip.get_line(line).map_err(|e| HardwareError::Device(e.to_string()))?;
+        line.request(flags, default, "raspberrypi_iot_core_rust").map_err(|e| HardwareError::Device(e.to_string()))
+    }
+}
+
+impl PinDriver for CdevPinDriver
+{
+    fn name(&self) -> &'static str { "cdev" }
+
+    fn write(&self, line: u32, value: i32) -> Result<(), HardwareError>
+    {
+        let level = match value { 0 => 0, 1 => 1, _ => return Err(HardwareError::InvalidValue(value, line)) };
+        let mut handles = self.handles.lock().unwrap();
+
+        if let Some((flags, handle)) = handles.get(&line) && flags.contains(LineRequestFlags::OUTPUT)
+        {
+            return handle.set_value(level).map_err(|e| HardwareError::Device(e.to_string()));
+        }
+
+        // Release any input request before taking the line as output
+        handles.remove(&line);
+        let handle = self.request(line, LineRequestFlags::OUTPUT, level)?;
+        handles.insert(line, (LineRequestFlags::OUTPUT, handle));
+        Ok(())
+    }
+
+    fn read(&self, line: u32) -> Result<i32, HardwareError>
+    {
+        let mut handles = self.handles.lock().unwrap();
+
+        let (_, handle) = match handles.entry(line)
+        {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert((LineRequestFlags::INPUT, self.request(line, LineRequestFlags::INPUT, 0)?)),
+        };
+        handle.get_value().map(i32::from).map_err(|e| HardwareError::Device(e.to_string()))
+    }
+
+    fn output_level(&self, line: u32) -> Result<Option<i32>, HardwareError>
+    {
+        match self.handles.lock().unwrap().get(&line)
+        {
+            Some((flags, handle)) if flags.contains(LineRequestFlags::OUTPUT) => handle.get_value().map(|level| Some(i32::from(level))).map_err(|e| HardwareError::Device(e.to_string())),
+            _ => Ok(None),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Mock
+#[derive(Default)]
+pub struct MockPinDriver
+{
+    levels: Mutex<HashMap<u32, i32>>,
+}
+
+impl MockPinDriver
+{
+    //------------------------- New
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+}
+
+impl PinDriver for MockPinDriver
+{
+    fn name(&self) -> &'static str { "mock" }
+
+    fn write(&self, line: u32, value: i32) -> Result<(), HardwareError>
+    {
+        if value != 0 && value != 1 { return Err(HardwareError::InvalidValue(value, line)); }
+        self.levels.lock().unwrap().insert(line, value);
+        Ok(())
+    }
+
+    fn read(&self, line: u32) -> Result<i32, HardwareError>
+    {
+        Ok(self.levels.lock().unwrap().get(&line).copied().unwrap_or(0))
+    }
+
+    fn output_level(&self, line: u32) -> Result<Option<i32>, HardwareError>
+    {
+        Ok(self.levels.lock().unwrap().get(&line).copied())
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn only_auto_falls_back_to_the_mock()
+    {
+        let missing = "/nonexistent/gpiochip0";
+        assert!(open_driver("cdev", missing).is_err(), "a required chip that does not open stops the start");
+        assert!(open_driver("gpio", missing).is_err(), "an unknown driver is refused");
+        assert_eq!(open_driver("auto", missing).unwrap().name(), "mock");
+        assert_eq!(open_driver("MOCK", missing).unwrap().name(), "mock");
+    }
+}
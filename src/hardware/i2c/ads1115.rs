@@ -0,0 +1,47 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/i2c/ads1115.rs
+
+//--------------------------------------------------------------------------------- Description
+// TI ADS1115 16-bit ADC: single-shot conversion of one single-ended input at ±4.096 V, in mV
+
+//--------------------------------------------------------------------------------- Import
+use std::thread;
+use std::time::Duration;
+use super::super::HardwareError;
+use super::I2cBus;
+
+//--------------------------------------------------------------------------------- Registers
+const REG_CONVERSION: u8 = 0x00;
+const REG_CONFIG: u8 = 0x01;
+
+/// OS: start a conversion when written, conversion done when read
+const CONFIG_OS: u16 = 0x8000;
+/// MUX 100 + channel: AINx against GND
+const CONFIG_MUX_SINGLE: u16 = 0b100;
+/// PGA 001 ±4.096 V, single-shot mode, 128 SPS, comparator off
+const CONFIG_BASE: u16 = (0b001 << 9) | (1 << 8) | (0b100 << 5) | 0b11;
+
+/// mV per count over the full ±4.096 V scale
+const MILLIVOLTS_PER_COUNT: f64 = 4096.0 / 32768.0;
+
+//--------------------------------------------------------------------------------- Read
+pub fn read(bus: &dyn I2cBus, address: u16, channel: u8) -> Result<i32, HardwareError>
+{
+    let config = CONFIG_OS | ((CONFIG_MUX_SINGLE | u16::from(channel)) << 12) | CONFIG_BASE;
+    let [high, low] = config.to_be_bytes();
+    bus.transfer(address, &[REG_CONFIG, high, low], &mut [])?;
+
+    // 128 SPS is a conversion every 7.8 ms
+    let mut state = [0u8; 2];
+    for _ in 0..10
+    {
+        thread::sleep(Duration::from_millis(8));
+        bus.read_registers(address, REG_CONFIG, &mut state)?;
+        if u16::from_be_bytes(state) & CONFIG_OS != 0 { break; }
+    }
+    if u16::from_be_bytes(state) & CONFIG_OS == 0 { return Err(HardwareError::Device(format!("ADS1115 0x{:02x} conversion timed out", address))); }
+
+    let mut conversion = [0u8; 2];
+    bus.read_registers(address, REG_CONVERSION, &mut conversion)?;
+    Ok((f64::from(i16::from_be_bytes(conversion)) * MILLIVOLTS_PER_COUNT).round() as i32)
+}
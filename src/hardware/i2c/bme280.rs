@@ -0,0 +1,199 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/i2c/bme280.rs
+
+//--------------------------------------------------------------------------------- Description
+// Bosch BME280 temperature, humidity and pressure sensor: one forced measurement per read,
+// compensated with the integer formulas of the datasheet (section 4.2.3)
+
+//--------------------------------------------------------------------------------- Import
+use std::thread;
+use std::time::Duration;
+use super::super::HardwareError;
+use super::I2cBus;
+
+//--------------------------------------------------------------------------------- Registers
+const CHIP_ID: u8 = 0x60;
+const REG_ID: u8 = 0xD0;
+const REG_CALIB_TP: u8 = 0x88;
+const REG_CALIB_H: u8 = 0xE1;
+const REG_CTRL_HUM: u8 = 0xF2;
+const REG_STATUS: u8 = 0xF3;
+const REG_CTRL_MEAS: u8 = 0xF4;
+const REG_DATA: u8 = 0xF7;
+
+/// Oversampling x1 for every quantity (osrs_t 001, osrs_p 001), forced mode (01)
+const CTRL_HUM: u8 = 0x01;
+const CTRL_MEAS: u8 = 0x25;
+const STATUS_MEASURING: u8 = 0x08;
+
+//--------------------------------------------------------------------------------- Quantity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity
+{
+    Temperature,
+    Humidity,
+    Pressure,
+}
+
+impl Quantity
+{
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name.to_lowercase().as_str()
+        {
+            "temperature" | "t" => Some(Quantity::Temperature),
+            "humidity" | "h" => Some(Quantity::Humidity),
+            "pressure" | "p" => Some(Quantity::Pressure),
+            _ => None,
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Calibration
+#[derive(Debug, Clone, Copy)]
+struct Calibration
+{
+    t1: i32, t2: i32, t3: i32,
+    p1: i64, p2: i64, p3: i64, p4: i64, p5: i64, p6: i64, p7: i64, p8: i64, p9: i64,
+    h1: i32, h2: i32, h3: i32, h4: i32, h5: i32, h6: i32,
+}
+
+impl Calibration
+{
+    fn from_registers(tp: &[u8; 26], h: &[u8; 7]) -> Self
+    {
+        let u16_at = |index: usize| u16::from_le_bytes([tp[index], tp[index + 1]]);
+        let i16_at = |index: usize| i16::from_le_bytes([tp[index], tp[index + 1]]);
+
+        Self
+        {
+            t1: u16_at(0).into(), t2: i16_at(2).into(), t3: i16_at(4).into(),
+            p1: u16_at(6).into(), p2: i16_at(8).into(), p3: i16_at(10).into(), p4: i16_at(12).into(), p5: i16_at(14).into(),
+            p6: i16_at(16).into(), p7: i16_at(18).into(), p8: i16_at(20).into(), p9: i16_at(22).into(),
+            h1: tp[25].into(),
+            h2: i16::from_le_bytes([h[0], h[1]]).into(),
+            h3: h[2].into(),
+            h4: (i32::from(h[3] as i8) << 4) | i32::from(h[4] & 0x0F),
+            h5: (i32::from(h[5] as i8) << 4) | i32::from(h[4] >> 4),
+            h6: (h[6] as i8).into(),
+        }
+    }
+
+    /// t_fine and the temperature in 0.01 °C
+    fn temperature(&self, adc: i32) -> (i32, i32)
+    {
+        let var1 = (((adc >> 3) - (self.t1 << 1)) * self.t2) >> 11;
+        let var2 = (((((adc >> 4) - self.t1) * ((adc >> 4) - self.t1)) >> 12) * self.t3) >> 14;
+        let t_fine = var1 + var2;
+        (t_fine, (t_fine * 5 + 128) >> 8)
+    }
+
+    /// Pressure in Pa
+    fn pressure(&self, t_fine: i32, adc: i32) -> i64
+    {
+        let mut var1 = i64::from(t_fine) - 128000;
+        let mut var2 = var1 * var1 * self.p6;
+        var2 += (var1 * self.p5) << 17;
+        var2 += self.p4 << 35;
+        var1 = ((var1 * var1 * self.p3) >> 8) + ((var1 * self.p2) << 12);
+        var1 = (((1i64 << 47) + var1) * self.p1) >> 33;
+        if var1 == 0 { return 0; }
+
+        let mut p = 1048576 - i64::from(adc);
+        p = (((p << 31) - var2) * 3125) / var1;
+        let var1 = (self.p9 * (p >> 13) * (p >> 13)) >> 25;
+        let var2 = (self.p8 * p) >> 19;
+        (((p + var1 + var2) >> 8) + (self.p7 << 4)) / 256
+    }
+
+    /// Relative humidity in 1/1024 %
+    fn humidity(&self, t_fine: i32, adc: i32) -> i32
+    {
+        let v = t_fine - 76800;
+        let v = ((((adc << 14) - (self.h4 << 20) - (self.h5 * v)) + 16384) >> 15)
+            * (((((((v * self.h6) >> 10) * (((v * self.h3) >> 11) + 32768)) >> 10) + 2097152) * self.h2 + 8192) >> 14);
+        let v = v - (((((v >> 15) * (v >> 15)) >> 7) * self.h1) >> 4);
+        v.clamp(0, 419430400) >> 12
+    }
+}
+
+//--------------------------------------------------------------------------------- Read
+/// °C, %RH or hPa, rounded
+pub fn read(bus: &dyn I2cBus, address: u16, quantity: Quantity) -> Result<i32, HardwareError>
+{
+    let mut id = [0u8; 1];
+    bus.read_registers(address, REG_ID, &mut id)?;
+    if id[0] != CHIP_ID { return Err(HardwareError::Device(format!("I2C 0x{:02x} is not a BME280 (chip id 0x{:02x})", address, id[0]))); }
+
+    let mut tp = [0u8; 26];
+    let mut h = [0u8; 7];
+    bus.read_registers(address, REG_CALIB_TP, &mut tp)?;
+    bus.read_registers(address, REG_CALIB_H, &mut h)?;
+    let calibration = Calibration::from_registers(&tp, &h);
+
+    // ctrl_hum only applies after the next ctrl_meas write
+    bus.write_register(address, REG_CTRL_HUM, CTRL_HUM)?;
+    bus.write_register(address, REG_CTRL_MEAS, CTRL_MEAS)?;
+    let mut status = [STATUS_MEASURING];
+    for _ in 0..10
+    {
+        thread::sleep(Duration::from_millis(5));
+        bus.read_registers(address, REG_STATUS, &mut status)?;
+        if status[0] & STATUS_MEASURING == 0 { break; }
+    }
+    if status[0] & STATUS_MEASURING != 0 { return Err(HardwareError::Device(format!("BME280 0x{:02x} measurement timed out", address))); }
+
+    let mut data = [0u8; 8];
+    bus.read_registers(address, REG_DATA, &mut data)?;
+    let adc_p = (i32::from(data[0]) << 12) | (i32::from(data[1]) << 4) | (i32::from(data[2]) >> 4);
+    let adc_t = (i32::from(data[3]) << 12) | (i32::from(data[4]) << 4) | (i32::from(data[5]) >> 4);
+    let adc_h = (i32::from(data[6]) << 8) | i32::from(data[7]);
+
+    let (t_fine, temperature) = calibration.temperature(adc_t);
+    Ok(match quantity
+    {
+        Quantity::Temperature => (f64::from(temperature) / 100.0).round() as i32,
+        Quantity::Humidity => (f64::from(calibration.humidity(t_fine, adc_h)) / 1024.0).round() as i32,
+        Quantity::Pressure => (calibration.pressure(t_fine, adc_p) as f64 / 100.0).round() as i32,
+    })
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::mock::MockI2cBus;
+
+    /// Calibration and readings of the datasheet-style sample used by the mock bus
+    fn calibration() -> Calibration
+    {
+        Calibration
+        {
+            t1: 27504, t2: 26435, t3: -1000,
+            p1: 36477, p2: -10685, p3: 3024, p4: 2855, p5: 140, p6: -7, p7: 15500, p8: -14600, p9: 6000,
+            h1: 75, h2: 362, h3: 0, h4: 313, h5: 50, h6: 30,
+        }
+    }
+
+    #[test]
+    fn compensation_follows_the_datasheet()
+    {
+        let calibration = calibration();
+        let (t_fine, temperature) = calibration.temperature(519888);
+        assert_eq!((t_fine, temperature), (128422, 2508));
+        assert_eq!(calibration.pressure(t_fine, 415148), 100653);
+        assert_eq!(calibration.humidity(t_fine, 28000), 44909);
+    }
+
+    #[test]
+    fn read_rounds_each_quantity()
+    {
+        let bus = MockI2cBus::new();
+        assert_eq!(read(&bus, 0x76, Quantity::Temperature).unwrap(), 25);
+        assert_eq!(read(&bus, 0x76, Quantity::Humidity).unwrap(), 44);
+        assert_eq!(read(&bus, 0x76, Quantity::Pressure).unwrap(), 1007);
+        assert_eq!(Quantity::from_name("HUMIDITY"), Some(Quantity::Humidity));
+        assert_eq!(Quantity::from_name("light"), None);
+    }
+}
@@ -0,0 +1,78 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/i2c/linux.rs
+
+//--------------------------------------------------------------------------------- Description
+// /dev/i2c-N backend: each transfer is one I2C_RDWR ioctl, so a register write and the read that
+// follows it go out with a repeated start and no other master can slip in between
+
+//--------------------------------------------------------------------------------- Import
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::sync::Mutex;
+use super::super::HardwareError;
+use super::I2cBus;
+
+//--------------------------------------------------------------------------------- Kernel ABI
+/// linux/i2c-dev.h
+const I2C_RDWR: u32 = 0x0707;
+/// linux/i2c.h, read message
+const I2C_M_RD: u16 = 0x0001;
+
+#[repr(C)]
+struct I2cMsg
+{
+    addr: u16,
+    flags: u16,
+    len: u16,
+    buf: *mut u8,
+}
+
+#[repr(C)]
+struct I2cRdwrIoctlData
+{
+    msgs: *mut I2cMsg,
+    nmsgs: u32,
+}
+
+//--------------------------------------------------------------------------------- Class
+pub struct LinuxI2cBus
+{
+    file: Mutex<File>,
+}
+
+impl LinuxI2cBus
+{
+    //------------------------- New
+    pub fn new(path: &str) -> Result<Self, HardwareError>
+    {
+        let file = OpenOptions::new().read(true).write(true).open(path).map_err(|e| HardwareError::Device(e.to_string()))?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+
+impl I2cBus for LinuxI2cBus
+{
+    fn name(&self) -> &'static str { "linux" }
+
+    fn transfer(&self, address: u16, write: &[u8], read: &mut [u8]) -> Result<(), HardwareError>
+    {
+        let length = |buffer: usize| u16::try_from(buffer).map_err(|_| HardwareError::Device(format!("I2C transfer of {} bytes is too long", buffer)));
+
+        // The kernel only reads from the write buffer, the cast to *mut is what the ABI asks for
+        let mut messages = Vec::with_capacity(2);
+        if !write.is_empty() { messages.push(I2cMsg { addr: address, flags: 0, len: length(write.len())?, buf: write.as_ptr() as *mut u8 }); }
+        if !read.is_empty() { messages.push(I2cMsg { addr: address, flags: I2C_M_RD, len: length(read.len())?, buf: read.as_mut_ptr() }); }
+        if messages.is_empty() { return Ok(()); }
+
+        let mut data = I2cRdwrIoctlData { msgs: messages.as_mut_ptr(), nmsgs: messages.len() as u32 };
+        let file = self.file.lock().unwrap();
+
+        // SAFETY: the messages and their buffers outlive the call and their lengths match the buffers
+        let result = unsafe { libc::ioctl(file.as_raw_fd(), I2C_RDWR as _, &mut data as *mut I2cRdwrIoctlData) };
+        match result < 0
+        {
+            true => Err(HardwareError::Device(format!("I2C 0x{:02x}: {}", address, std::io::Error::last_os_error()))),
+            false => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/i2c/mock.rs
+
+//--------------------------------------------------------------------------------- Description
+// In-memory I2C bus with simulated chips at their usual addresses, so the drivers run without
+// hardware: a BME280 at 0x76 (25 °C, 44 %RH, 1007 hPa, datasheet calibration), an ADS1115 at 0x48
+// (1000, 2000, 3000 and 0 mV) and a PCF8574 at 0x20 with every input high
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::sync::Mutex;
+use super::super::HardwareError;
+use super::I2cBus;
+
+//--------------------------------------------------------------------------------- Chips
+pub enum MockChip
+{
+    /// Byte registers with an auto-incrementing pointer, as on the BME280
+    Registers { registers: Box<[u8; 256]>, pointer: u8 },
+    /// ADS1115: 16-bit registers, a conversion completes as soon as it is started
+    Adc { channels: [i16; 4], config: u16, conversion: i16, pointer: u8 },
+    /// PCF8574: pin levels are the latch pulled down by the inputs
+    Expander { latch: u8, inputs: u8 },
+}
+
+impl MockChip
+{
+    //------------------------- BME280
+    pub fn bme280() -> Self
+    {
+        let mut registers = Box::new([0u8; 256]);
+        registers[0xD0] = 0x60;
+
+        let calibration_tp: [i32; 12] = [27504, 26435, -1000, 36477, -10685, 3024, 2855, 140, -7, 15500, -14600, 6000];
+        for (index, value) in calibration_tp.iter().enumerate()
+        {
+            let [low, high, ..] = value.to_le_bytes();
+            registers[0x88 + index * 2] = low;
+            registers[0x89 + index * 2] = high;
+        }
+        // H1 75, H2 362, H3 0, H4 313, H5 50, H6 30
+        registers[0xA1] = 75;
+        registers[0xE1..=0xE7].copy_from_slice(&[0x6A, 0x01, 0x00, 0x13, 0x29, 0x03, 0x1E]);
+
+        // Raw pressure 415148, temperature 519888, humidity 28000
+        registers[0xF7..=0xFE].copy_from_slice(&[0x65, 0x5A, 0xC0, 0x7E, 0xED, 0x00, 0x6D, 0x60]);
+        MockChip::Registers { registers, pointer: 0 }
+    }
+
+    //------------------------- ADS1115
+    pub fn ads1115(millivolts: [i16; 4]) -> Self
+    {
+        MockChip::Adc { channels: millivolts.map(|millivolts| millivolts.saturating_mul(8)), config: 0x8583, conversion: 0, pointer: 0 }
+    }
+
+    //------------------------- PCF8574
+    pub fn pcf8574(inputs: u8) -> Self
+    {
+        MockChip::Expander { latch: 0xFF, inputs }
+    }
+
+    //------------------------- Transfer
+    fn transfer(&mut self, write: &[u8], read: &mut [u8])
+    {
+        match self
+        {
+            MockChip::Registers { registers, pointer } =>
+            {
+                if let Some((first, values)) = write.split_first()
+                {
+                    *pointer = *first;
+                    for value in values { registers[*pointer as usize] = *value; *pointer = pointer.wrapping_add(1); }
+                }
+                for byte in read.iter_mut() { *byte = registers[*pointer as usize]; *pointer = pointer.wrapping_add(1); }
+            }
+            MockChip::Adc { channels, config, conversion, pointer } =>
+            {
+                if let Some((first, values)) = write.split_first()
+                {
+                    *pointer = *first & 0x03;
+                    if let ([high, low, ..], 1) = (values, *pointer)
+                    {
+                        *config = u16::from_be_bytes([*high, *low]);
+                        let mux = (*config >> 12) & 0x07;
+                        if *config & 0x8000 != 0 && mux >= 4 { *conversion = channels[usize::from(mux - 4)]; }
+                    }
+                }
+                let value = match *pointer { 0 => *conversion as u16, 1 => *config | 0x8000, _ => 0 };
+                for (byte, source) in read.iter_mut().zip(value.to_be_bytes()) { *byte = source; }
+            }
+            MockChip::Expander { latch, inputs } =>
+            {
+                if let Some(value) = write.last() { *latch = *value; }
+                for byte in read.iter_mut() { *byte = *latch & *inputs; }
+            }
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Mock
+pub struct MockI2cBus
+{
+    chips: Mutex<HashMap<u16, MockChip>>,
+}
+
+impl Default for MockI2cBus
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl MockI2cBus
+{
+    //------------------------- New
+    pub fn new() -> Self
+    {
+        let chips = HashMap::from([
+            (0x76, MockChip::bme280()),
+            (0x48, MockChip::ads1115([1000, 2000, 3000, 0])),
+            (0x20, MockChip::pcf8574(0xFF)),
+        ]);
+        Self { chips: Mutex::new(chips) }
+    }
+}
+
+impl I2cBus for MockI2cBus
+{
+    fn name(&self) -> &'static str { "mock" }
+
+    fn transfer(&self, address: u16, write: &[u8], read: &mut [u8]) -> Result<(), HardwareError>
+    {
+        match self.chips.lock().unwrap().get_mut(&address)
+        {
+            Some(chip) => { chip.transfer(write, read); Ok(()) }
+            None => Err(HardwareError::Device(format!("I2C 0x{:02x}: no device answered", address))),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/i2c/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// I2C bus and the sensor drivers behind it. Every device on an I2C port shares the bus and picks
+// its driver and bus address in device.address, "<driver>:<address>[:<channel>]":
+//   bme280:0x76:temperature    °C, also humidity (%RH) and pressure (hPa)
+//   ads1115:0x48:0             single-ended channel 0-3 in mV (±4.096 V range)
+//   pcf8574:0x20:3             expander pin 0-7 read as an input
+//   pcf8574:0x20:3:out         expander pin 0-7 driven by the device commands
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+use super::HardwareError;
+use self::linux::LinuxI2cBus;
+use self::mock::MockI2cBus;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod ads1115;
+pub mod bme280;
+pub mod linux;
+pub mod mock;
+pub mod pcf8574;
+
+//--------------------------------------------------------------------------------- Trait
+pub trait I2cBus: Send + Sync
+{
+    /// Backend name, used in logs
+    fn name(&self) -> &'static str;
+
+    /// Write `write` then read `read.len()` bytes from the 7-bit address, either may be empty
+    fn transfer(&self, address: u16, write: &[u8], read: &mut [u8]) -> Result<(), HardwareError>;
+
+    /// Read consecutive registers starting at `register`
+    fn read_registers(&self, address: u16, register: u8, buffer: &mut [u8]) -> Result<(), HardwareError>
+    {
+        self.transfer(address, &[register], buffer)
+    }
+
+    /// Write one register
+    fn write_register(&self, address: u16, register: u8, value: u8) -> Result<(), HardwareError>
+    {
+        self.transfer(address, &[register, value], &mut [])
+    }
+}
+
+//--------------------------------------------------------------------------------- Factory
+/// I2C_DRIVER=linux opens I2C_BUS or fails, mock simulates the chips, auto takes the bus when it
+/// exists; without one every transfer fails, simulated readings never reach a real installation
+pub fn create_bus() -> Result<Arc<I2c>, HardwareError>
+{
+    let bus_path = std::env::var("I2C_BUS").unwrap_or_else(|_| "/dev/i2c-1".to_string());
+    let driver = std::env::var("I2C_DRIVER").unwrap_or_else(|_| "auto".to_string());
+    open_bus(&driver, &bus_path)
+}
+
+pub fn open_bus(driver: &str, bus_path: &str) -> Result<Arc<I2c>, HardwareError>
+{
+    let open = ||
+    {
+        let bus = LinuxI2cBus::new(bus_path).map_err(|e| HardwareError::Device(format!("I2C bus {}: {}", bus_path, e)))?;
+        info!("I2C driver: linux ({})", bus_path);
+        Ok(Arc::new(I2c::new(Box::new(bus))))
+    };
+
+    match driver.to_lowercase().as_str()
+    {
+        "linux" => open(),
+        "mock" =>
+        {
+            info!("I2C driver: mock");
+            Ok(Arc::new(I2c::new(Box::new(MockI2cBus::new()))))
+        }
+        "auto" if Path::new(bus_path).exists() => open(),
+        "auto" =>
+        {
+            info!("I2C driver: none, {} not found", bus_path);
+            Ok(Arc::new(I2c::new(Box::new(NoI2cBus(bus_path.to_string())))))
+        }
+        _ => Err(HardwareError::Unsupported(format!("I2C_DRIVER '{}', expected auto, linux or mock", driver))),
+    }
+}
+
+/// Stands in for a bus that is not there
+struct NoI2cBus(String);
+
+impl I2cBus for NoI2cBus
+{
+    fn name(&self) -> &'static str { "none" }
+
+    fn transfer(&self, _: u16, _: &[u8], _: &mut [u8]) -> Result<(), HardwareError>
+    {
+        Err(HardwareError::Device(format!("No I2C bus at {}", self.0)))
+    }
+}
+
+//--------------------------------------------------------------------------------- Target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum I2cDriver
+{
+    Bme280(bme280::Quantity),
+    Ads1115 { channel: u8 },
+    Pcf8574 { pin: u8, output: bool },
+}
+
+/// Driver and bus address parsed from device.address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct I2cTarget
+{
+    pub driver: I2cDriver,
+    pub address: u16,
+}
+
+impl I2cTarget
+{
+    //------------------------- From Address
+    pub fn from_address(address: &str) -> Result<Self, HardwareError>
+    {
+        let invalid = |reason: &str| HardwareError::Unsupported(format!("I2C address '{}': {}", address, reason));
+        let parts = address.trim().split(':').map(str::trim).collect::<Vec<_>>();
+        let (driver, bus_address, rest) = match parts.as_slice()
+        {
+            [driver, bus_address, rest @ ..] => (driver.to_lowercase(), bus_address, rest),
+            _ => return Err(invalid("expected <driver>:<address>[:<channel>]")),
+        };
+
+        let bus_address = match bus_address.strip_prefix("0x").or_else(|| bus_address.strip_prefix("0X"))
+        {
+            Some(hex) => u16::from_str_radix(hex, 16),
+            None => bus_address.parse::<u16>(),
+        }.ok().filter(|bus_address| (0x03..=0x77).contains(bus_address)).ok_or_else(|| invalid("bus address must be 0x03-0x77"))?;
+
+        let channel = |max: u8| rest.first().and_then(|channel| channel.parse::<u8>().ok()).filter(|channel| *channel <= max);
+        let driver = match (driver.as_str(), rest)
+        {
+            ("bme280", [quantity]) => I2cDriver::Bme280(bme280::Quantity::from_name(quantity).ok_or_else(|| invalid("expected temperature, humidity or pressure"))?),
+            ("ads1115", [_]) => I2cDriver::Ads1115 { channel: channel(3).ok_or_else(|| invalid("channel must be 0-3"))? },
+            ("pcf8574", [_]) => I2cDriver::Pcf8574 { pin: channel(7).ok_or_else(|| invalid("pin must be 0-7"))?, output: false },
+            ("pcf8574", [_, mode]) if mode.eq_ignore_ascii_case("out") => I2cDriver::Pcf8574 { pin: channel(7).ok_or_else(|| invalid("pin must be 0-7"))?, output: true },
+            ("bme280" | "ads1115" | "pcf8574", _) => return Err(invalid("wrong channel")),
+            _ => return Err(invalid("unknown driver, expected bme280, ads1115 or pcf8574")),
+        };
+
+        Ok(Self { driver, address: bus_address })
+    }
+
+    /// Polled for its value, as opposed to driven by commands
+    pub fn is_input(&self) -> bool
+    {
+        !matches!(self.driver, I2cDriver::Pcf8574 { output: true, .. })
+    }
+}
+
+//--------------------------------------------------------------------------------- Devices
+/// The bus plus the PCF8574 output latches: the expander cannot report what was written, and
+/// its input pins must stay high, so every write starts from the last written byte. The first
+/// write after a start takes the pin levels as the latch, so outputs another run left low stay
+/// low; pins read as inputs are kept high whatever they read
+pub struct I2c
+{
+    bus: Box<dyn I2cBus>,
+    latches: Mutex<HashMap<u16, u8>>,
+    inputs: Mutex<HashMap<u16, u8>>,
+}
+
+impl I2c
+{
+    //------------------------- New
+    pub fn new(bus: Box<dyn I2cBus>) -> Self
+    {
+        Self { bus, latches: Mutex::new(HashMap::new()), inputs: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        self.bus.name()
+    }
+
+    //------------------------- Read
+    pub fn read(&self, address: &str) -> Result<i32, HardwareError>
+    {
+        let target = I2cTarget::from_address(address)?;
+        match target.driver
+        {
+            I2cDriver::Bme280(quantity) => bme280::read(self.bus.as_ref(), target.address, quantity),
+            I2cDriver::Ads1115 { channel } => ads1115::read(self.bus.as_ref(), target.address, channel),
+            I2cDriver::Pcf8574 { pin, output } =>
+            {
+                if !output { *self.inputs.lock().unwrap().entry(target.address).or_default() |= 1 << pin; }
+                pcf8574::read(self.bus.as_ref(), target.address, pin)
+            }
+        }
+    }
+
+    //------------------------- Write
+    pub fn write(&self, address: &str, value: i32) -> Result<(), HardwareError>
+    {
+        let target = I2cTarget::from_address(address)?;
+        let I2cDriver::Pcf8574 { pin, output: true } = target.driver else
+        {
+            return Err(HardwareError::Unsupported(format!("write on I2C input '{}'", address)));
+        };
+        if value != 0 && value != 1 { return Err(HardwareError::InvalidValue(value, u32::from(pin))); }
+
+        let mut latches = self.latches.lock().unwrap();
+        let latch = match latches.get(&target.address)
+        {
+            Some(latch) => *latch,
+            None => pcf8574::levels(self.bus.as_ref(), target.address)?,
+        };
+        let latch = latch | self.inputs.lock().unwrap().get(&target.address).copied().unwrap_or(0);
+        let latch = match value { 0 => latch & !(1 << pin), _ => latch | (1 << pin) };
+        pcf8574::write(self.bus.as_ref(), target.address, latch)?;
+        latches.insert(target.address, latch);
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn target_from_address()
+    {
+        assert_eq!(I2cTarget::from_address("bme280:0x76:Temperature").unwrap(), I2cTarget { driver: I2cDriver::Bme280(bme280::Quantity::Temperature), address: 0x76 });
+        assert_eq!(I2cTarget::from_address(" ADS1115:72:3 ").unwrap(), I2cTarget { driver: I2cDriver::Ads1115 { channel: 3 }, address: 0x48 });
+        assert_eq!(I2cTarget::from_address("pcf8574:0X20:7:OUT").unwrap().driver, I2cDriver::Pcf8574 { pin: 7, output: true });
+        assert!(I2cTarget::from_address("pcf8574:0x20:7").unwrap().is_input());
+        assert!(!I2cTarget::from_address("pcf8574:0x20:7:out").unwrap().is_input());
+
+        for address in ["bme280", "bme280:0x76", "bme280:0x76:light", "bme280:0x02:pressure", "bme280:0x78:pressure", "bme280:0xzz:pressure",
+                        "ads1115:0x48:4", "ads1115:0x48", "pcf8574:0x20:8", "pcf8574:0x20:1:in", "sht31:0x44:0"]
+        {
+            assert!(matches!(I2cTarget::from_address(address), Err(HardwareError::Unsupported(_))), "{} is rejected", address);
+        }
+    }
+
+    #[test]
+    fn only_the_mock_driver_simulates_chips()
+    {
+        let missing = "/nonexistent/i2c-1";
+        assert!(open_bus("linux", missing).is_err(), "a required bus that does not open stops the start");
+        assert!(open_bus("i2c", missing).is_err());
+        assert_eq!(open_bus("mock", missing).unwrap().read("bme280:0x76:temperature").unwrap(), 25);
+
+        let bus = open_bus("auto", missing).unwrap();
+        assert_eq!(bus.name(), "none");
+        assert!(matches!(bus.read("bme280:0x76:temperature"), Err(HardwareError::Device(_))));
+        assert!(bus.write("pcf8574:0x20:0:out", 1).is_err());
+    }
+
+    #[test]
+    fn pcf8574_keeps_the_levels_found_at_start()
+    {
+        // A previous run left P0 and P3 low
+        let bus = MockI2cBus::new();
+        bus.transfer(0x20, &[0b1111_0110], &mut []).unwrap();
+        let i2c = I2c::new(Box::new(bus));
+
+        assert_eq!(i2c.read("pcf8574:0x20:3").unwrap(), 0);
+        i2c.write("pcf8574:0x20:5:out", 0).unwrap();
+
+        assert_eq!(i2c.read("pcf8574:0x20:0:out").unwrap(), 0);
+        assert_eq!(i2c.read("pcf8574:0x20:5:out").unwrap(), 0);
+        assert_eq!(i2c.read("pcf8574:0x20:1:out").unwrap(), 1);
+        assert_eq!(i2c.read("pcf8574:0x20:3").unwrap(), 1, "a pin read as an input is released high");
+
+        i2c.write("pcf8574:0x20:0:out", 1).unwrap();
+        assert_eq!((i2c.read("pcf8574:0x20:0:out").unwrap(), i2c.read("pcf8574:0x20:5:out").unwrap()), (1, 0));
+    }
+}
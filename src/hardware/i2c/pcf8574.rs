@@ -0,0 +1,30 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/i2c/pcf8574.rs
+
+//--------------------------------------------------------------------------------- Description
+// NXP PCF8574 8-bit quasi-bidirectional expander: no registers, one byte read is the pin levels
+// and one byte written is the output latch; a pin reads as an input while its latch bit is high
+
+//--------------------------------------------------------------------------------- Import
+use super::super::HardwareError;
+use super::I2cBus;
+
+//--------------------------------------------------------------------------------- Read
+pub fn read(bus: &dyn I2cBus, address: u16, pin: u8) -> Result<i32, HardwareError>
+{
+    Ok(i32::from((levels(bus, address)? >> pin) & 1))
+}
+
+/// Level of every pin, bit 0 is P0
+pub fn levels(bus: &dyn I2cBus, address: u16) -> Result<u8, HardwareError>
+{
+    let mut levels = [0u8; 1];
+    bus.transfer(address, &[], &mut levels)?;
+    Ok(levels[0])
+}
+
+//--------------------------------------------------------------------------------- Write
+pub fn write(bus: &dyn I2cBus, address: u16, latch: u8) -> Result<(), HardwareError>
+{
+    bus.transfer(address, &[latch], &mut [])
+}
@@ -0,0 +1,106 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// Hardware abstraction layer that drives physical ports
+
+//--------------------------------------------------------------------------------- Import
+use std::sync::Arc;
+use serde::Serialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use self::gpio::PinDriver;
+use self::i2c::I2c;
+use self::pwm::Pwm;
+use self::spi::Spi;
+use self::uart::Uart;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod file;
+pub mod gpio;
+pub mod i2c;
+pub mod port;
+pub mod pwm;
+pub mod spi;
+pub mod uart;
+
+//--------------------------------------------------------------------------------- Error
+#[derive(Debug, Error)]
+pub enum HardwareError
+{
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
+    #[error("Port is disabled: {0}")]
+    Disabled(String),
+    #[error("Invalid value {0} for line {1}")]
+    InvalidValue(i32, u32),
+    #[error("Device error: {0}")]
+    Device(String),
+}
+
+//--------------------------------------------------------------------------------- Buses
+/// Shared buses; a device on a bus port is reached through its device.address
+#[derive(Clone)]
+pub struct Buses
+{
+    pub i2c: Arc<I2c>,
+    pub pwm: Arc<Pwm>,
+    pub spi: Arc<Spi>,
+    pub uart: Arc<Uart>,
+}
+
+impl Buses
+{
+    //------------------------- Create
+    /// Software PWM drives its lines through the GPIO driver; a bus driver that is set but does
+    /// not open is an error
+    pub fn create(pins: Arc<dyn PinDriver>) -> Result<Self, HardwareError>
+    {
+        Ok(Self { i2c: i2c::create_bus()?, pwm: pwm::create_pwm(pins), spi: spi::create_spi()?, uart: uart::create_uart()? })
+    }
+
+    /// Simulated chips on every bus
+    #[cfg(test)]
+    pub fn mock(pins: Arc<dyn PinDriver>) -> Self
+    {
+        let serial = uart::SerialConfig { path: "mock".to_string(), baud: 9600, data_bits: 8, parity: uart::Parity::None, stop_bits: 1, timeout: std::time::Duration::from_millis(50) };
+        Self
+        {
+            i2c: i2c::open_bus("mock", "mock").unwrap(),
+            pwm: pwm::create_pwm(pins),
+            spi: spi::open_spi("mock", 0, 1_000_000).unwrap(),
+            uart: uart::open_uart("mock", &serial).unwrap(),
+        }
+    }
+
+    //------------------------- Drivers
+    pub fn drivers(&self, pins: &dyn PinDriver) -> Drivers
+    {
+        Drivers
+        {
+            gpio: pins.name().to_string(),
+            i2c: self.i2c.name().to_string(),
+            pwm: self.pwm.driver().to_string(),
+            spi: self.spi.name().to_string(),
+            uart: self.uart.name().to_string(),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Drivers
+/// Backend behind each kind of port: mock is simulated, none means the bus is not present
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Hardware backend in use for each kind of port")]
+pub struct Drivers
+{
+    #[schema(example = "cdev")]
+    pub gpio: String,
+    #[schema(example = "linux")]
+    pub i2c: String,
+    #[schema(example = "auto")]
+    pub pwm: String,
+    #[schema(example = "none")]
+    pub spi: String,
+    #[schema(example = "linux")]
+    pub uart: String,
+}
@@ -0,0 +1,85 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/port.rs
+
+//--------------------------------------------------------------------------------- Description
+// Dispatch port reads and writes to the matching hardware driver
+
+//--------------------------------------------------------------------------------- Import
+use crate::orm::models::port::Model as PortModel;
+use super::gpio::PinDriver;
+use super::{Buses, HardwareError};
+
+//--------------------------------------------------------------------------------- Write
+pub fn write(pins: &dyn PinDriver, port: &PortModel, value: i32) -> Result<(), HardwareError>
+{
+    if !port.enable { return Err(HardwareError::Disabled(port.name.clone())); }
+
+    match (port.protocol.to_lowercase().as_str(), port.r#type.to_lowercase().as_str())
+    {
+        ("gpio", "out") => pins.write(line(port)?, value),
+        (protocol, r#type) => Err(HardwareError::Unsupported(format!("write on {} port '{}' of type {}", protocol, port.name, r#type))),
+    }
+}
+
+//--------------------------------------------------------------------------------- Read
+/// An output is read through its output handle; before its first write this process does not know
+/// its level, so the stored port value is returned rather than requesting the line as an input
+pub fn read(pins: &dyn PinDriver, port: &PortModel) -> Result<i32, HardwareError>
+{
+    if !port.enable { return Err(HardwareError::Disabled(port.name.clone())); }
+
+    match (port.protocol.to_lowercase().as_str(), port.r#type.to_lowercase().as_str())
+    {
+        ("gpio", "in") => pins.read(line(port)?),
+        ("gpio", "out") => Ok(pins.output_level(line(port)?)?.unwrap_or(port.value)),
+        (protocol, r#type) => Err(HardwareError::Unsupported(format!("read on {} port '{}' of type {}", protocol, port.name, r#type))),
+    }
+}
+
+//--------------------------------------------------------------------------------- Device
+/// Write for a device: bus ports carry many devices, each one at its own address, and on a
+/// PWM port the value is the duty cycle (%) with an optional frequency (Hz)
+pub fn write_device(pins: &dyn PinDriver, buses: &Buses, port: &PortModel, address: &str, value: i32, frequency: Option<i32>) -> Result<(), HardwareError>
+{
+    if !port.enable { return Err(HardwareError::Disabled(port.name.clone())); }
+
+    match port.protocol.to_lowercase().as_str()
+    {
+        "i2c" => buses.i2c.write(address, value),
+        "spi" => buses.spi.write(address, value),
+        "uart" => buses.uart.write(address, value),
+        "gpio" if port.r#type.eq_ignore_ascii_case("pwm") => buses.pwm.write(line(port)?, value, frequency),
+        _ => write(pins, port, value),
+    }
+}
+
+//--------------------------------------------------------------------------------- Line
+fn line(port: &PortModel) -> Result<u32, HardwareError>
+{
+    u32::try_from(port.port).map_err(|_| HardwareError::Unsupported(format!("invalid GPIO line {} on port '{}'", port.port, port.name)))
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::super::gpio::MockPinDriver;
+    use super::*;
+
+    fn port(r#type: &str, value: i32) -> PortModel
+    {
+        PortModel { id: 1, user_id: 1, name: "P".to_string(), pin: 13, port: 27, value, description: String::new(), enable: true, protocol: "GPIO".to_string(), r#type: r#type.to_string() }
+    }
+
+    #[test]
+    fn reads_an_output_without_turning_it_into_an_input()
+    {
+        let pins = MockPinDriver::new();
+        assert_eq!(read(&pins, &port("OUT", 1)).unwrap(), 1);
+
+        write(&pins, &port("OUT", 1), 0).unwrap();
+        assert_eq!(read(&pins, &port("OUT", 1)).unwrap(), 0);
+        assert_eq!(read(&pins, &port("IN", 1)).unwrap(), 0);
+        assert!(read(&pins, &PortModel { enable: false, ..port("OUT", 1) }).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/pwm/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// PWM outputs on GPIO ports of type PWM: the device value is the duty cycle in percent (0-100)
+// and a device_command.frequency in Hz changes the frequency, else it stays as it was. A line
+// listed in PWM_CHANNELS whose pwmchip exists under PWM_ROOT runs on the hardware PWM, any other
+// line falls back to software PWM toggled through the GPIO driver
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+use super::gpio::PinDriver;
+use super::HardwareError;
+use self::soft::SoftPwm;
+use self::sysfs::SysfsPwm;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod soft;
+pub mod sysfs;
+
+//--------------------------------------------------------------------------------- Config
+#[derive(Debug, Clone)]
+pub struct PwmConfig
+{
+    /// auto, sysfs or soft
+    pub driver: String,
+    pub root: String,
+    /// BCM line to (pwmchip, channel)
+    pub channels: HashMap<u32, (u32, u32)>,
+    /// Hz, until a command sets one
+    pub frequency: u32,
+}
+
+impl PwmConfig
+{
+    //------------------------- From Env
+    pub fn from_env() -> Self
+    {
+        let driver = std::env::var("PWM_DRIVER").unwrap_or_else(|_| "auto".to_string()).to_lowercase();
+        let root = std::env::var("PWM_ROOT").unwrap_or_else(|_| "/sys/class/pwm".to_string());
+        let channels = std::env::var("PWM_CHANNELS").unwrap_or_else(|_| "12=0:0,13=0:1,18=0:0,19=0:1".to_string());
+        let frequency = std::env::var("PWM_FREQUENCY").ok().and_then(|frequency| frequency.parse::<u32>().ok()).filter(|frequency| *frequency > 0).unwrap_or(1000);
+
+        Self { driver, root, channels: parse_channels(&channels), frequency }
+    }
+}
+
+/// "12=0:0,13=0:1": BCM line = pwmchip:channel, bad entries are skipped with a warning
+pub fn parse_channels(channels: &str) -> HashMap<u32, (u32, u32)>
+{
+    let mut map = HashMap::new();
+    for entry in channels.split(',').map(str::trim).filter(|entry| !entry.is_empty())
+    {
+        let parsed = entry.split_once('=').and_then(|(line, target)|
+        {
+            let (chip, channel) = target.split_once(':')?;
+            Some((line.trim().parse::<u32>().ok()?, (chip.trim().parse::<u32>().ok()?, channel.trim().parse::<u32>().ok()?)))
+        });
+        match parsed
+        {
+            Some((line, target)) => { map.insert(line, target); }
+            None => warn!("PWM_CHANNELS: ignoring '{}', expected <line>=<chip>:<channel>", entry),
+        }
+    }
+    map
+}
+
+//--------------------------------------------------------------------------------- Factory
+pub fn create_pwm(pins: Arc<dyn PinDriver>) -> Arc<Pwm>
+{
+    let config = PwmConfig::from_env();
+    match config.driver.as_str()
+    {
+        "soft" => info!("PWM driver: soft"),
+        _ => info!("PWM driver: {} ({}, hardware lines {:?})", config.driver, config.root, config.channels.keys().collect::<Vec<_>>()),
+    }
+    Arc::new(Pwm::new(config, pins))
+}
+
+//--------------------------------------------------------------------------------- Outputs
+enum Output
+{
+    Sysfs(SysfsPwm),
+    Soft(SoftPwm),
+}
+
+struct Channel
+{
+    output: Output,
+    frequency: u32,
+}
+
+/// Every PWM line in use, with the frequency it runs at
+pub struct Pwm
+{
+    config: PwmConfig,
+    pins: Arc<dyn PinDriver>,
+    channels: Mutex<HashMap<u32, Channel>>,
+}
+
+impl Pwm
+{
+    //------------------------- New
+    pub fn new(config: PwmConfig, pins: Arc<dyn PinDriver>) -> Self
+    {
+        Self { config, pins, channels: Mutex::new(HashMap::new()) }
+    }
+
+    /// PWM_DRIVER, used in logs and the driver report
+    pub fn driver(&self) -> &str
+    {
+        &self.config.driver
+    }
+
+    //------------------------- Write
+    /// Set the duty cycle (0-100 %) and, when given, the frequency (Hz) of the line
+    pub fn write(&self, line: u32, duty: i32, frequency: Option<i32>) -> Result<(), HardwareError>
+    {
+        if !(0..=100).contains(&duty) { return Err(HardwareError::InvalidValue(duty, line)); }
+
+        let mut channels = self.channels.lock().unwrap();
+        let channel = match channels.entry(line)
+        {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Channel { output: self.output(line)?, frequency: self.config.frequency }),
+        };
+
+        let frequency = match frequency
+        {
+            Some(frequency) => u32::try_from(frequency).ok().filter(|frequency| *frequency > 0).ok_or_else(|| HardwareError::Unsupported(format!("PWM frequency {} Hz on line {}", frequency, line)))?,
+            None => channel.frequency,
+        };
+
+        match &mut channel.output
+        {
+            Output::Sysfs(output) => output.set(duty as u32, frequency)?,
+            Output::Soft(output) => output.set(duty as u32, frequency)?,
+        }
+        channel.frequency = frequency;
+        Ok(())
+    }
+
+    //------------------------- Output
+    /// Hardware PWM when the line has a channel and its chip is there, else software PWM
+    fn output(&self, line: u32) -> Result<Output, HardwareError>
+    {
+        if self.config.driver != "soft" && let Some((chip, channel)) = self.config.channels.get(&line).copied()
+        {
+            let chip_path = Path::new(&self.config.root).join(format!("pwmchip{}", chip));
+            if chip_path.exists() || self.config.driver == "sysfs"
+            {
+                let output = SysfsPwm::open(&chip_path, channel)?;
+                info!("PWM line {}: hardware ({}/pwm{})", line, chip_path.display(), channel);
+                return Ok(Output::Sysfs(output));
+            }
+        }
+
+        info!("PWM line {}: software", line);
+        Ok(Output::Soft(SoftPwm::new(self.pins.clone(), line)))
+    }
+}
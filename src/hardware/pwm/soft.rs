@@ -0,0 +1,114 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/pwm/soft.rs
+
+//--------------------------------------------------------------------------------- Description
+// Software PWM on an ordinary GPIO line: a thread toggles the line through the GPIO driver. Timing
+// follows the OS scheduler, which is fine for lights and fan relays up to MAX_FREQUENCY but not
+// for servos; 0 % and 100 % stop the thread and hold the line low or high
+
+//--------------------------------------------------------------------------------- Import
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tracing::warn;
+use super::super::gpio::PinDriver;
+use super::super::HardwareError;
+
+//--------------------------------------------------------------------------------- Limits
+/// Hz; above that a sleep-timed period is mostly jitter
+pub const MAX_FREQUENCY: u32 = 1000;
+
+//--------------------------------------------------------------------------------- Class
+struct Worker
+{
+    /// Duty cycle (%) and frequency (Hz), read at the start of every period
+    settings: Arc<Mutex<(u32, u32)>>,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+pub struct SoftPwm
+{
+    pins: Arc<dyn PinDriver>,
+    line: u32,
+    worker: Option<Worker>,
+}
+
+impl SoftPwm
+{
+    //------------------------- New
+    pub fn new(pins: Arc<dyn PinDriver>, line: u32) -> Self
+    {
+        Self { pins, line, worker: None }
+    }
+
+    //------------------------- Set
+    pub fn set(&mut self, duty: u32, frequency: u32) -> Result<(), HardwareError>
+    {
+        if frequency > MAX_FREQUENCY { return Err(HardwareError::Unsupported(format!("software PWM on line {} runs at most {} Hz, not {}", self.line, MAX_FREQUENCY, frequency))); }
+
+        if duty == 0 || duty == 100
+        {
+            self.stop();
+            return self.pins.write(self.line, i32::from(duty == 100));
+        }
+
+        if let Some(worker) = &self.worker && !worker.handle.is_finished()
+        {
+            *worker.settings.lock().unwrap() = (duty, frequency);
+            return Ok(());
+        }
+
+        // Fail here rather than in the thread when the line cannot be driven
+        self.pins.write(self.line, 0)?;
+        self.worker = Some(self.spawn(duty, frequency)?);
+        Ok(())
+    }
+
+    //------------------------- Spawn
+    fn spawn(&self, duty: u32, frequency: u32) -> Result<Worker, HardwareError>
+    {
+        let settings = Arc::new(Mutex::new((duty, frequency)));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (pins, line, thread_settings, thread_stop) = (self.pins.clone(), self.line, settings.clone(), stop.clone());
+
+        let handle = thread::Builder::new().name(format!("pwm-{}", line)).spawn(move ||
+        {
+            while !thread_stop.load(Ordering::Relaxed)
+            {
+                let (duty, frequency) = *thread_settings.lock().unwrap();
+                let period = Duration::from_secs(1) / frequency;
+                let high = period * duty / 100;
+
+                if let Err(e) = pins.write(line, 1).and_then(|_| { thread::sleep(high); pins.write(line, 0) })
+                {
+                    warn!("Software PWM on line {} stopped: {}", line, e);
+                    break;
+                }
+                thread::sleep(period - high);
+            }
+        }).map_err(|e| HardwareError::Device(format!("software PWM on line {}: {}", line, e)))?;
+
+        Ok(Worker { settings, stop, handle })
+    }
+
+    //------------------------- Stop
+    /// Waits at most one period for the thread, so it cannot drive the line after this returns
+    fn stop(&mut self)
+    {
+        if let Some(worker) = self.worker.take()
+        {
+            worker.stop.store(true, Ordering::Relaxed);
+            let _ = worker.handle.join();
+        }
+    }
+}
+
+impl Drop for SoftPwm
+{
+    fn drop(&mut self)
+    {
+        self.stop();
+    }
+}
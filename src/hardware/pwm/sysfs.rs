@@ -0,0 +1,141 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/pwm/sysfs.rs
+
+//--------------------------------------------------------------------------------- Description
+// Hardware PWM channel under /sys/class/pwm/pwmchipN: exported on first use, then period and
+// duty_cycle in nanoseconds. The kernel rejects a duty cycle longer than the period, so a
+// shorter period is written after the duty cycle and a longer one before it
+
+//--------------------------------------------------------------------------------- Import
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use super::super::HardwareError;
+
+//--------------------------------------------------------------------------------- Class
+pub struct SysfsPwm
+{
+    path: PathBuf,
+    period: u64,
+    enabled: bool,
+}
+
+impl SysfsPwm
+{
+    //------------------------- Open
+    pub fn open(chip_path: &Path, channel: u32) -> Result<Self, HardwareError>
+    {
+        let path = chip_path.join(format!("pwm{}", channel));
+        if !path.exists()
+        {
+            write(&chip_path.join("export"), &channel.to_string())?;
+
+            // udev fixes the permissions of the new channel right after the export
+            for _ in 0..20
+            {
+                if path.join("period").exists() { break; }
+                thread::sleep(Duration::from_millis(10));
+            }
+            if !path.exists() { return Err(HardwareError::Device(format!("{}: not created by the export", path.display()))); }
+        }
+
+        let read = |name: &str| fs::read_to_string(path.join(name)).ok().and_then(|value| value.trim().parse::<u64>().ok()).unwrap_or(0);
+        let (period, enabled) = (read("period"), read("enable") == 1);
+        Ok(Self { path, period, enabled })
+    }
+
+    //------------------------- Set
+    pub fn set(&mut self, duty: u32, frequency: u32) -> Result<(), HardwareError>
+    {
+        let period = 1_000_000_000 / u64::from(frequency);
+        if period == 0 { return Err(HardwareError::Unsupported(format!("PWM frequency {} Hz on {}", frequency, self.path.display()))); }
+        let duty_cycle = period * u64::from(duty) / 100;
+
+        for (name, value) in order(self.period, period, duty_cycle)
+        {
+            write(&self.path.join(name), &value.to_string())?;
+        }
+        self.period = period;
+
+        if !self.enabled
+        {
+            write(&self.path.join("enable"), "1")?;
+            self.enabled = true;
+        }
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------- Order
+/// Attribute writes in an order the kernel accepts: the duty cycle may never exceed the period in
+/// place, so a shorter period goes after the duty cycle and a longer one before it
+fn order(current: u64, period: u64, duty_cycle: u64) -> [(&'static str, u64); 2]
+{
+    match period < current
+    {
+        true => [("duty_cycle", duty_cycle), ("period", period)],
+        false => [("period", period), ("duty_cycle", duty_cycle)],
+    }
+}
+
+//--------------------------------------------------------------------------------- Write
+fn write(path: &Path, value: &str) -> Result<(), HardwareError>
+{
+    fs::write(path, value).map_err(|e| HardwareError::Device(format!("{}: {}", path.display(), e)))
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// pwmchip0 with channel 0 already exported, as the kernel leaves it
+    fn chip() -> tempfile::TempDir
+    {
+        let root = tempfile::tempdir().unwrap();
+        let channel = root.path().join("pwm0");
+        fs::create_dir(&channel).unwrap();
+        for (name, value) in [("period", "0"), ("duty_cycle", "0"), ("enable", "0")] { fs::write(channel.join(name), value).unwrap(); }
+        root
+    }
+
+    fn value(root: &Path, name: &str) -> String
+    {
+        fs::read_to_string(root.join("pwm0").join(name)).unwrap()
+    }
+
+    #[test]
+    fn writes_the_duty_cycle_first_when_the_period_shrinks()
+    {
+        assert_eq!(order(0, 1_000_000, 500_000), [("period", 1_000_000), ("duty_cycle", 500_000)]);
+        assert_eq!(order(1_000_000, 100_000, 25_000), [("duty_cycle", 25_000), ("period", 100_000)]);
+        assert_eq!(order(100_000, 100_000, 75_000), [("period", 100_000), ("duty_cycle", 75_000)]);
+    }
+
+    #[test]
+    fn sets_period_duty_cycle_and_enable_in_nanoseconds()
+    {
+        let root = chip();
+        let mut pwm = SysfsPwm::open(root.path(), 0).unwrap();
+
+        pwm.set(50, 1000).unwrap();
+        assert_eq!((value(root.path(), "period"), value(root.path(), "duty_cycle"), value(root.path(), "enable")), ("1000000".to_string(), "500000".to_string(), "1".to_string()));
+
+        pwm.set(25, 10_000).unwrap();
+        assert_eq!((value(root.path(), "period"), value(root.path(), "duty_cycle")), ("100000".to_string(), "25000".to_string()));
+        assert!(pwm.set(50, 2_000_000_000).is_err());
+    }
+
+    #[test]
+    fn exports_a_missing_channel()
+    {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(root.path().join("export"), "").unwrap();
+
+        // Nothing creates pwm1 here, so the export is written and then reported as not applied
+        assert!(SysfsPwm::open(root.path(), 1).is_err());
+        assert_eq!(fs::read_to_string(root.path().join("export")).unwrap(), "1");
+    }
+}
@@ -0,0 +1,130 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/spi/linux.rs
+
+//--------------------------------------------------------------------------------- Description
+// /dev/spidevB.C backend: one device file per chip select (CE0, CE1), set to mode 0, 8 bits per
+// word and SPI_SPEED; each transfer is one SPI_IOC_MESSAGE ioctl, so the chip select stays low
+// for the whole frame
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::sync::Mutex;
+use super::super::HardwareError;
+use super::SpiBus;
+
+//--------------------------------------------------------------------------------- Kernel ABI
+/// linux/spi/spidev.h: _IOW('k', 0, char[32]), one transfer
+const SPI_IOC_MESSAGE_1: u32 = 0x4020_6B00;
+/// _IOW('k', 1, u8)
+const SPI_IOC_WR_MODE: u32 = 0x4001_6B01;
+/// _IOW('k', 3, u8)
+const SPI_IOC_WR_BITS_PER_WORD: u32 = 0x4001_6B03;
+/// _IOW('k', 4, u32)
+const SPI_IOC_WR_MAX_SPEED_HZ: u32 = 0x4004_6B04;
+
+const SPI_MODE_0: u8 = 0;
+
+#[repr(C)]
+#[derive(Default)]
+struct SpiIocTransfer
+{
+    tx_buf: u64,
+    rx_buf: u64,
+    len: u32,
+    speed_hz: u32,
+    delay_usecs: u16,
+    bits_per_word: u8,
+    cs_change: u8,
+    tx_nbits: u8,
+    rx_nbits: u8,
+    word_delay_usecs: u8,
+    pad: u8,
+}
+
+//--------------------------------------------------------------------------------- Path
+pub fn device_path(bus: u8, chip_select: u8) -> String
+{
+    format!("/dev/spidev{}.{}", bus, chip_select)
+}
+
+//--------------------------------------------------------------------------------- Class
+pub struct LinuxSpiBus
+{
+    bus: u8,
+    speed: u32,
+    devices: Mutex<HashMap<u8, File>>,
+}
+
+impl LinuxSpiBus
+{
+    //------------------------- New
+    /// Fails when neither chip select of the bus has a device file
+    pub fn new(bus: u8, speed: u32) -> Result<Self, HardwareError>
+    {
+        let mut devices = HashMap::new();
+        let mut errors = Vec::new();
+        for chip_select in 0..=1
+        {
+            match open(bus, chip_select, speed)
+            {
+                Ok(file) => { devices.insert(chip_select, file); }
+                Err(e) => errors.push(e),
+            }
+        }
+        if devices.is_empty() { return Err(HardwareError::Device(errors.join(", "))); }
+        Ok(Self { bus, speed, devices: Mutex::new(devices) })
+    }
+}
+
+//--------------------------------------------------------------------------------- Open
+fn open(bus: u8, chip_select: u8, speed: u32) -> Result<File, String>
+{
+    let path = device_path(bus, chip_select);
+    let file = OpenOptions::new().read(true).write(true).open(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let fd = file.as_raw_fd();
+
+    let mode = SPI_MODE_0;
+    let bits: u8 = 8;
+    // SAFETY: each request reads one value of the type it is declared with, from a live local
+    let results = unsafe {
+        [
+            libc::ioctl(fd, SPI_IOC_WR_MODE as _, &mode as *const u8),
+            libc::ioctl(fd, SPI_IOC_WR_BITS_PER_WORD as _, &bits as *const u8),
+            libc::ioctl(fd, SPI_IOC_WR_MAX_SPEED_HZ as _, &speed as *const u32),
+        ]
+    };
+    if results.iter().any(|result| *result < 0) { return Err(format!("{}: {}", path, std::io::Error::last_os_error())); }
+    Ok(file)
+}
+
+impl SpiBus for LinuxSpiBus
+{
+    fn name(&self) -> &'static str { "linux" }
+
+    fn transfer(&self, chip_select: u8, buffer: &mut [u8]) -> Result<(), HardwareError>
+    {
+        if buffer.is_empty() { return Ok(()); }
+        let len = u32::try_from(buffer.len()).map_err(|_| HardwareError::Device(format!("SPI transfer of {} bytes is too long", buffer.len())))?;
+
+        let mut devices = self.devices.lock().unwrap();
+        let file = match devices.entry(chip_select)
+        {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(open(self.bus, chip_select, self.speed).map_err(HardwareError::Device)?),
+        };
+
+        // spidev reads tx_buf completely before it writes rx_buf, so both may be the same buffer
+        let transfer = SpiIocTransfer { tx_buf: buffer.as_ptr() as u64, rx_buf: buffer.as_mut_ptr() as u64, len, speed_hz: self.speed, bits_per_word: 8, ..Default::default() };
+
+        // SAFETY: the buffer outlives the call and len matches it
+        let result = unsafe { libc::ioctl(file.as_raw_fd(), SPI_IOC_MESSAGE_1 as _, &transfer as *const SpiIocTransfer) };
+        match result < 0
+        {
+            true => Err(HardwareError::Device(format!("{}: {}", device_path(self.bus, chip_select), std::io::Error::last_os_error()))),
+            false => Ok(()),
+        }
+    }
+}
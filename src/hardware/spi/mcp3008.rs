@@ -0,0 +1,121 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/spi/mcp3008.rs
+
+//--------------------------------------------------------------------------------- Description
+// Microchip MCP3008 (10-bit) and MCP3208 (12-bit) 8-channel ADCs: one single-ended conversion
+// per 3-byte transfer. The command is aligned so the result ends in the last byte: start bit,
+// SGL/DIFF and D2-D0, then a sample clock, a null bit and the result MSB first
+
+//--------------------------------------------------------------------------------- Import
+use super::super::HardwareError;
+use super::SpiBus;
+
+//--------------------------------------------------------------------------------- Chip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chip
+{
+    Mcp3008,
+    Mcp3208,
+}
+
+impl Chip
+{
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name.to_lowercase().as_str()
+        {
+            "mcp3008" => Some(Chip::Mcp3008),
+            "mcp3208" => Some(Chip::Mcp3208),
+            _ => None,
+        }
+    }
+
+    pub fn bits(&self) -> u32
+    {
+        match self { Chip::Mcp3008 => 10, Chip::Mcp3208 => 12 }
+    }
+
+    /// Counts across the reference voltage
+    pub fn full_scale(&self) -> u32
+    {
+        1 << self.bits()
+    }
+
+    fn name(&self) -> &'static str
+    {
+        match self { Chip::Mcp3008 => "MCP3008", Chip::Mcp3208 => "MCP3208" }
+    }
+
+    //------------------------- Frame
+    /// Command for a single-ended conversion of the channel
+    fn frame(&self, channel: u8) -> [u8; 3]
+    {
+        match self
+        {
+            // 7 leading zeros, start bit, SGL, D2 D1 D0
+            Chip::Mcp3008 => [0x01, 0x80 | (channel << 4), 0x00],
+            // 5 leading zeros, start bit, SGL, D2 | D1 D0
+            Chip::Mcp3208 => [0x06 | (channel >> 2), (channel & 0x03) << 6, 0x00],
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Read
+/// Raw counts, 0 to full scale - 1
+pub fn read(bus: &dyn SpiBus, chip: Chip, chip_select: u8, channel: u8) -> Result<u32, HardwareError>
+{
+    let mut buffer = chip.frame(channel);
+    bus.transfer(chip_select, &mut buffer)?;
+
+    // The null bit right before the result is always 0; a 1 means nothing drove MISO
+    let null_bit = 1u8 << (chip.bits() - 8);
+    if buffer[1] & null_bit != 0 { return Err(HardwareError::Device(format!("{} on CE{}: no answer", chip.name(), chip_select))); }
+
+    let counts = u32::from(u16::from_be_bytes([buffer[1], buffer[2]]));
+    Ok(counts & (chip.full_scale() - 1))
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::mock::MockSpiBus;
+
+    /// Nothing on the chip select, MISO floats high
+    struct FloatingBus;
+
+    impl SpiBus for FloatingBus
+    {
+        fn name(&self) -> &'static str { "floating" }
+
+        fn transfer(&self, _chip_select: u8, buffer: &mut [u8]) -> Result<(), HardwareError>
+        {
+            buffer.fill(0xFF);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn frames_select_a_single_ended_channel()
+    {
+        assert_eq!(Chip::Mcp3008.frame(0), [0x01, 0x80, 0x00]);
+        assert_eq!(Chip::Mcp3008.frame(5), [0x01, 0xD0, 0x00]);
+        assert_eq!(Chip::Mcp3208.frame(0), [0x06, 0x00, 0x00]);
+        assert_eq!(Chip::Mcp3208.frame(5), [0x07, 0x40, 0x00]);
+        assert_eq!(Chip::Mcp3208.frame(7), [0x07, 0xC0, 0x00]);
+    }
+
+    #[test]
+    fn read_decodes_the_counts()
+    {
+        let bus = MockSpiBus::new();
+        assert_eq!(read(&bus, Chip::Mcp3008, 0, 3).unwrap(), 1023);
+        assert_eq!(read(&bus, Chip::Mcp3008, 0, 5).unwrap(), 102);
+        assert_eq!(read(&bus, Chip::Mcp3208, 1, 3).unwrap(), 4095);
+        assert_eq!(read(&bus, Chip::Mcp3208, 1, 7).unwrap(), 1229);
+
+        let floating = read(&FloatingBus, Chip::Mcp3008, 0, 0).unwrap_err().to_string();
+        assert!(floating.contains("no answer"), "{}", floating);
+    }
+}
@@ -0,0 +1,89 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/spi/mock.rs
+
+//--------------------------------------------------------------------------------- Description
+// In-memory SPI bus with simulated ADCs, so the drivers run without hardware: an MCP3008 on CE0
+// and an MCP3208 on CE1. Both decode the command bit by bit as the chips do, so a misaligned
+// frame reads back wrong instead of passing
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use super::super::HardwareError;
+use super::SpiBus;
+
+//--------------------------------------------------------------------------------- Chips
+pub struct MockAdc
+{
+    bits: u32,
+    channels: [u16; 8],
+}
+
+impl MockAdc
+{
+    //------------------------- New
+    pub fn new(bits: u32, channels: [u16; 8]) -> Self
+    {
+        Self { bits, channels }
+    }
+
+    //------------------------- Transfer
+    /// After the start bit come SGL/DIFF, D2-D0 and a sample clock; the chip then drives a null
+    /// bit and the result MSB first. Differential inputs read as channel 0
+    fn transfer(&self, buffer: &mut [u8])
+    {
+        let bit = |buffer: &[u8], index: usize| buffer.get(index / 8).is_some_and(|byte| byte & (0x80 >> (index % 8)) != 0);
+        let Some(start) = (0..buffer.len() * 8).find(|index| bit(buffer, *index)) else { buffer.fill(0); return; };
+
+        let single = bit(buffer, start + 1);
+        let channel = (2..5).fold(0usize, |channel, offset| (channel << 1) | usize::from(bit(buffer, start + offset)));
+        let value = match single { true => self.channels[channel], false => self.channels[0] };
+
+        buffer.fill(0);
+        for index in 0..self.bits as usize
+        {
+            let position = start + 7 + index;
+            if value & (1 << (self.bits as usize - 1 - index)) != 0 && position < buffer.len() * 8 { buffer[position / 8] |= 0x80 >> (position % 8); }
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Mock
+pub struct MockSpiBus
+{
+    chips: HashMap<u8, MockAdc>,
+}
+
+impl Default for MockSpiBus
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl MockSpiBus
+{
+    //------------------------- New
+    pub fn new() -> Self
+    {
+        let chips = HashMap::from([
+            (0, MockAdc::new(10, [512, 256, 768, 1023, 0, 102, 205, 307])),
+            (1, MockAdc::new(12, [2048, 1024, 3072, 4095, 0, 410, 819, 1229])),
+        ]);
+        Self { chips }
+    }
+}
+
+impl SpiBus for MockSpiBus
+{
+    fn name(&self) -> &'static str { "mock" }
+
+    fn transfer(&self, chip_select: u8, buffer: &mut [u8]) -> Result<(), HardwareError>
+    {
+        match self.chips.get(&chip_select)
+        {
+            Some(chip) => { chip.transfer(buffer); Ok(()) }
+            None => Err(HardwareError::Device(format!("SPI CE{}: no device selected", chip_select))),
+        }
+    }
+}
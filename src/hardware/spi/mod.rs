@@ -0,0 +1,204 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/spi/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// SPI bus and the ADC drivers behind it. Every device on an SPI port shares the bus and picks its
+// driver, chip select and channel in device.address, "<driver>:<chip select>:<channel>[:<vref>]":
+//   mcp3008:0:3                channel 0-7 on CE0 in raw counts (0-1023)
+//   mcp3208:cs1:7:3300         channel 0-7 on CE1 in mV against a 3300 mV reference (0-4095 counts)
+
+//--------------------------------------------------------------------------------- Import
+use std::path::Path;
+use std::sync::Arc;
+use tracing::info;
+use super::HardwareError;
+use self::linux::LinuxSpiBus;
+use self::mock::MockSpiBus;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod linux;
+pub mod mcp3008;
+pub mod mock;
+
+//--------------------------------------------------------------------------------- Trait
+pub trait SpiBus: Send + Sync
+{
+    /// Backend name, used in logs
+    fn name(&self) -> &'static str;
+
+    /// Full-duplex transfer with the chip select held low: `buffer` is sent and replaced by
+    /// the bytes clocked in at the same time
+    fn transfer(&self, chip_select: u8, buffer: &mut [u8]) -> Result<(), HardwareError>;
+}
+
+//--------------------------------------------------------------------------------- Factory
+/// SPI_DRIVER=linux opens /dev/spidev<SPI_BUS>.x or fails, mock simulates the ADCs, auto takes the
+/// bus when it exists; without one every conversion fails, simulated readings never reach a real
+/// installation
+pub fn create_spi() -> Result<Arc<Spi>, HardwareError>
+{
+    let bus = std::env::var("SPI_BUS").ok().and_then(|bus| bus.parse::<u8>().ok()).unwrap_or(0);
+    let speed = std::env::var("SPI_SPEED").ok().and_then(|speed| speed.parse::<u32>().ok()).filter(|speed| *speed > 0).unwrap_or(1_000_000);
+    let driver = std::env::var("SPI_DRIVER").unwrap_or_else(|_| "auto".to_string());
+    open_spi(&driver, bus, speed)
+}
+
+pub fn open_spi(driver: &str, bus: u8, speed: u32) -> Result<Arc<Spi>, HardwareError>
+{
+    let open = ||
+    {
+        let spi = LinuxSpiBus::new(bus, speed).map_err(|e| HardwareError::Device(format!("SPI bus /dev/spidev{}.x: {}", bus, e)))?;
+        info!("SPI driver: linux (/dev/spidev{}.x at {} Hz)", bus, speed);
+        Ok(Arc::new(Spi::new(Box::new(spi))))
+    };
+
+    match driver.to_lowercase().as_str()
+    {
+        "linux" => open(),
+        "mock" =>
+        {
+            info!("SPI driver: mock");
+            Ok(Arc::new(Spi::new(Box::new(MockSpiBus::new()))))
+        }
+        "auto" if Path::new(&linux::device_path(bus, 0)).exists() || Path::new(&linux::device_path(bus, 1)).exists() => open(),
+        "auto" =>
+        {
+            info!("SPI driver: none, /dev/spidev{}.x not found", bus);
+            Ok(Arc::new(Spi::new(Box::new(NoSpiBus(bus)))))
+        }
+        _ => Err(HardwareError::Unsupported(format!("SPI_DRIVER '{}', expected auto, linux or mock", driver))),
+    }
+}
+
+/// Stands in for a bus that is not there
+struct NoSpiBus(u8);
+
+impl SpiBus for NoSpiBus
+{
+    fn name(&self) -> &'static str { "none" }
+
+    fn transfer(&self, chip_select: u8, _: &mut [u8]) -> Result<(), HardwareError>
+    {
+        Err(HardwareError::Device(format!("No SPI bus at {}", linux::device_path(self.0, chip_select))))
+    }
+}
+
+//--------------------------------------------------------------------------------- Target
+/// Driver, chip select and channel parsed from device.address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpiTarget
+{
+    pub chip: mcp3008::Chip,
+    pub chip_select: u8,
+    pub channel: u8,
+    /// Reference voltage in mV; the reading is in raw counts without one
+    pub vref: Option<u32>,
+}
+
+impl SpiTarget
+{
+    //------------------------- From Address
+    pub fn from_address(address: &str) -> Result<Self, HardwareError>
+    {
+        let invalid = |reason: &str| HardwareError::Unsupported(format!("SPI address '{}': {}", address, reason));
+        let parts = address.trim().split(':').map(str::trim).collect::<Vec<_>>();
+        let (chip, chip_select, channel, vref) = match parts.as_slice()
+        {
+            [chip, chip_select, channel] => (chip, chip_select, channel, None),
+            [chip, chip_select, channel, vref] => (chip, chip_select, channel, Some(vref)),
+            _ => return Err(invalid("expected <driver>:<chip select>:<channel>[:<vref>]")),
+        };
+
+        let chip = mcp3008::Chip::from_name(chip).ok_or_else(|| invalid("unknown driver, expected mcp3008 or mcp3208"))?;
+        let chip_select = chip_select.to_lowercase();
+        let chip_select = chip_select.strip_prefix("cs").unwrap_or(&chip_select).parse::<u8>().ok().filter(|chip_select| *chip_select <= 1).ok_or_else(|| invalid("chip select must be 0 or 1"))?;
+        let channel = channel.parse::<u8>().ok().filter(|channel| *channel <= 7).ok_or_else(|| invalid("channel must be 0-7"))?;
+        let vref = match vref
+        {
+            Some(vref) => Some(vref.parse::<u32>().ok().filter(|vref| (1..=5500).contains(vref)).ok_or_else(|| invalid("reference must be 1-5500 mV"))?),
+            None => None,
+        };
+
+        Ok(Self { chip, chip_select, channel, vref })
+    }
+}
+
+//--------------------------------------------------------------------------------- Devices
+/// The shared bus; each transfer is a whole conversion, so readers never interleave
+pub struct Spi
+{
+    bus: Box<dyn SpiBus>,
+}
+
+impl Spi
+{
+    //------------------------- New
+    pub fn new(bus: Box<dyn SpiBus>) -> Self
+    {
+        Self { bus }
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        self.bus.name()
+    }
+
+    //------------------------- Read
+    pub fn read(&self, address: &str) -> Result<i32, HardwareError>
+    {
+        let target = SpiTarget::from_address(address)?;
+        let counts = mcp3008::read(self.bus.as_ref(), target.chip, target.chip_select, target.channel)?;
+        match target.vref
+        {
+            Some(vref) => Ok((f64::from(counts) * f64::from(vref) / f64::from(target.chip.full_scale())).round() as i32),
+            None => Ok(counts as i32),
+        }
+    }
+
+    //------------------------- Write
+    pub fn write(&self, address: &str, _value: i32) -> Result<(), HardwareError>
+    {
+        Err(HardwareError::Unsupported(format!("write on SPI input '{}'", address)))
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn target_from_address()
+    {
+        assert_eq!(SpiTarget::from_address("mcp3008:0:3").unwrap(), SpiTarget { chip: mcp3008::Chip::Mcp3008, chip_select: 0, channel: 3, vref: None });
+        assert_eq!(SpiTarget::from_address(" MCP3208:CS1:7:3300 ").unwrap(), SpiTarget { chip: mcp3008::Chip::Mcp3208, chip_select: 1, channel: 7, vref: Some(3300) });
+
+        for address in ["mcp3008:0", "mcp3004:0:0", "mcp3008:2:0", "mcp3008:cs:0", "mcp3008:0:8", "mcp3008:0:0:0", "mcp3008:0:0:5501", "mcp3008:0:0:3300:1"]
+        {
+            assert!(matches!(SpiTarget::from_address(address), Err(HardwareError::Unsupported(_))), "{} is rejected", address);
+        }
+    }
+
+    #[test]
+    fn read_scales_to_the_reference()
+    {
+        let spi = Spi::new(Box::new(MockSpiBus::new()));
+        assert_eq!(spi.read("mcp3008:0:0").unwrap(), 512);
+        assert_eq!(spi.read("mcp3008:0:0:3300").unwrap(), 1650);
+        assert_eq!(spi.read("mcp3208:1:1:5000").unwrap(), 1250);
+        assert!(spi.write("mcp3008:0:0", 1).is_err());
+    }
+
+    #[test]
+    fn only_the_mock_driver_simulates_chips()
+    {
+        assert!(open_spi("linux", 250, 1_000_000).is_err(), "a required bus that does not open stops the start");
+        assert!(open_spi("spidev", 250, 1_000_000).is_err());
+        assert_eq!(open_spi("mock", 250, 1_000_000).unwrap().read("mcp3008:0:0").unwrap(), 512);
+
+        let spi = open_spi("auto", 250, 1_000_000).unwrap();
+        assert_eq!(spi.name(), "none");
+        assert!(matches!(spi.read("mcp3008:0:0"), Err(HardwareError::Device(_))));
+    }
+}
@@ -0,0 +1,72 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/uart/linux.rs
+
+//--------------------------------------------------------------------------------- Description
+// Serial device backend (/dev/serial0, /dev/ttyUSB0 for an RS-485 adapter...)
+
+//--------------------------------------------------------------------------------- Import
+use std::io::{ErrorKind, Read, Write};
+use serialport::{ClearBuffer, DataBits, SerialPort, StopBits};
+use super::super::HardwareError;
+use super::{Parity, SerialConfig, SerialLine};
+
+//--------------------------------------------------------------------------------- Class
+pub struct LinuxSerialLine
+{
+    port: Box<dyn SerialPort>,
+}
+
+impl LinuxSerialLine
+{
+    //------------------------- Open
+    pub fn open(config: &SerialConfig) -> Result<Self, HardwareError>
+    {
+        let data_bits = match config.data_bits
+        {
+            5 => DataBits::Five,
+            6 => DataBits::Six,
+            7 => DataBits::Seven,
+            _ => DataBits::Eight,
+        };
+        let parity = match config.parity
+        {
+            Parity::None => serialport::Parity::None,
+            Parity::Even => serialport::Parity::Even,
+            Parity::Odd => serialport::Parity::Odd,
+        };
+        let stop_bits = match config.stop_bits { 2 => StopBits::Two, _ => StopBits::One };
+
+        let port = serialport::new(&config.path, config.baud)
+            .data_bits(data_bits)
+            .parity(parity)
+            .stop_bits(stop_bits)
+            .timeout(config.timeout)
+            .open()
+            .map_err(|e| HardwareError::Device(e.to_string()))?;
+        Ok(Self { port })
+    }
+}
+
+impl SerialLine for LinuxSerialLine
+{
+    fn name(&self) -> &'static str { "linux" }
+
+    fn clear(&mut self) -> Result<(), HardwareError>
+    {
+        self.port.clear(ClearBuffer::Input).map_err(|e| HardwareError::Device(e.to_string()))
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> Result<(), HardwareError>
+    {
+        self.port.write_all(data).and_then(|_| self.port.flush()).map_err(|e| HardwareError::Device(e.to_string()))
+    }
+
+    fn read_exact(&mut self, buffer: &mut [u8]) -> Result<(), HardwareError>
+    {
+        self.port.read_exact(buffer).map_err(|e| match e.kind()
+        {
+            ErrorKind::TimedOut => HardwareError::Device("no answer on the serial line".to_string()),
+            _ => HardwareError::Device(e.to_string()),
+        })
+    }
+}
@@ -0,0 +1,137 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/uart/mock.rs
+
+//--------------------------------------------------------------------------------- Description
+// In-memory serial line with simulated Modbus RTU slaves, so the master runs without an RS-485
+// bus: unit 1 is a pump VFD (holding 0 frequency in 0.1 Hz while coil 0 runs it, holding 1 rpm)
+// and unit 2 a chlorine controller (input 0 free chlorine and input 1 pH, both x100; coil 0 dosing)
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::{HashMap, VecDeque};
+use super::super::HardwareError;
+use super::modbus::crc16;
+use super::SerialLine;
+
+//--------------------------------------------------------------------------------- Slave
+const REGISTERS: usize = 64;
+
+struct MockSlave
+{
+    holding: [u16; REGISTERS],
+    input: [u16; REGISTERS],
+    coils: [bool; REGISTERS],
+}
+
+impl MockSlave
+{
+    fn new(holding: &[u16], input: &[u16]) -> Self
+    {
+        let mut slave = Self { holding: [0; REGISTERS], input: [0; REGISTERS], coils: [false; REGISTERS] };
+        slave.holding[..holding.len()].copy_from_slice(holding);
+        slave.input[..input.len()].copy_from_slice(input);
+        slave
+    }
+
+    /// Response PDU to a request PDU
+    fn answer(&mut self, unit: u8, pdu: &[u8]) -> Vec<u8>
+    {
+        let exception = |code: u8| vec![pdu[0] | 0x80, code];
+        let (Some(function), Some(first), Some(second)) = (pdu.first().copied(), pdu.get(1..3), pdu.get(3..5)) else { return exception(0x03); };
+        let address = usize::from(u16::from_be_bytes([first[0], first[1]]));
+        let argument = u16::from_be_bytes([second[0], second[1]]);
+
+        match function
+        {
+            0x03 | 0x04 =>
+            {
+                let count = usize::from(argument);
+                if count == 0 || address + count > REGISTERS { return exception(0x02); }
+                let registers = if function == 0x03 { self.holding } else { self.input };
+
+                // The VFD only reports a frequency while it runs
+                let running = unit != 1 || self.coils[0];
+                let mut response = vec![function, (count * 2) as u8];
+                for (register, value) in registers.iter().enumerate().skip(address).take(count)
+                {
+                    let value = if function == 0x03 && register == 0 && !running { 0 } else { *value };
+                    response.extend_from_slice(&value.to_be_bytes());
+                }
+                response
+            }
+            0x05 =>
+            {
+                if address >= REGISTERS { return exception(0x02); }
+                match argument
+                {
+                    0xFF00 => self.coils[address] = true,
+                    0x0000 => self.coils[address] = false,
+                    _ => return exception(0x03),
+                }
+                pdu.to_vec()
+            }
+            _ => exception(0x01),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Mock
+pub struct MockSerialLine
+{
+    slaves: HashMap<u8, MockSlave>,
+    pending: VecDeque<u8>,
+}
+
+impl Default for MockSerialLine
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl MockSerialLine
+{
+    //------------------------- New
+    pub fn new() -> Self
+    {
+        let slaves = HashMap::from([
+            (1, MockSlave::new(&[500, 1480], &[])),
+            (2, MockSlave::new(&[], &[150, 720])),
+        ]);
+        Self { slaves, pending: VecDeque::new() }
+    }
+}
+
+impl SerialLine for MockSerialLine
+{
+    fn name(&self) -> &'static str { "mock" }
+
+    fn clear(&mut self) -> Result<(), HardwareError>
+    {
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// A valid frame to a known unit queues its answer; anything else stays silent, as on a real bus
+    fn write_all(&mut self, data: &[u8]) -> Result<(), HardwareError>
+    {
+        if data.len() < 4 { return Ok(()); }
+        let (body, crc) = data.split_at(data.len() - 2);
+        if crc16(body).to_le_bytes() != crc { return Ok(()); }
+
+        let unit = body[0];
+        let Some(slave) = self.slaves.get_mut(&unit) else { return Ok(()); };
+        let mut frame = vec![unit];
+        frame.extend(slave.answer(unit, &body[1..]));
+        frame.extend_from_slice(&crc16(&frame).to_le_bytes());
+        self.pending.extend(frame);
+        Ok(())
+    }
+
+    fn read_exact(&mut self, buffer: &mut [u8]) -> Result<(), HardwareError>
+    {
+        if self.pending.len() < buffer.len() { return Err(HardwareError::Device("no answer on the serial line".to_string())); }
+        for byte in buffer.iter_mut() { *byte = self.pending.pop_front().unwrap_or_default(); }
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/uart/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// Serial line (UART_PORT at UART_BAUD and UART_FRAMING) and the Modbus RTU master on top of it.
+// Every device on a UART port is a Modbus slave register mapped in device.address, for example
+//   unit=1,read=holding:0x2103,scale=10,write=coil:0    pump VFD frequency in Hz, run coil
+//   unit=2,read=input:0,type=i16                        chlorine controller reading
+// read is polled into device.value, write takes the device command values 0 and 1
+
+//--------------------------------------------------------------------------------- Import
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+use super::HardwareError;
+use self::linux::LinuxSerialLine;
+use self::mock::MockSerialLine;
+use self::modbus::RegisterMap;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod linux;
+pub mod mock;
+pub mod modbus;
+
+//--------------------------------------------------------------------------------- Trait
+/// One serial line, used by a single transaction at a time
+pub trait SerialLine: Send
+{
+    /// Backend name, used in logs
+    fn name(&self) -> &'static str;
+
+    /// Drop whatever is left in the input buffer
+    fn clear(&mut self) -> Result<(), HardwareError>;
+
+    fn write_all(&mut self, data: &[u8]) -> Result<(), HardwareError>;
+
+    /// Fill the buffer or fail once the line timeout passes
+    fn read_exact(&mut self, buffer: &mut [u8]) -> Result<(), HardwareError>;
+}
+
+//--------------------------------------------------------------------------------- Config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity
+{
+    None,
+    Even,
+    Odd,
+}
+
+#[derive(Debug, Clone)]
+pub struct SerialConfig
+{
+    pub path: String,
+    pub baud: u32,
+    pub data_bits: u8,
+    pub parity: Parity,
+    pub stop_bits: u8,
+    pub timeout: Duration,
+}
+
+impl SerialConfig
+{
+    //------------------------- From Env
+    pub fn from_env() -> Self
+    {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let framing = var("UART_FRAMING").unwrap_or_else(|| "8N1".to_string());
+        let (data_bits, parity, stop_bits) = parse_framing(&framing).unwrap_or_else(||
+        {
+            warn!("UART: invalid framing '{}', using 8N1", framing);
+            (8, Parity::None, 1)
+        });
+
+        Self
+        {
+            path: var("UART_PORT").unwrap_or_else(|| "/dev/serial0".to_string()),
+            baud: var("UART_BAUD").and_then(|value| value.parse().ok()).unwrap_or(9600),
+            data_bits,
+            parity,
+            stop_bits,
+            timeout: Duration::from_millis(var("UART_TIMEOUT").and_then(|value| value.parse().ok()).unwrap_or(500)),
+        }
+    }
+
+    /// Bits on the wire per character: start, data, parity and stop
+    pub fn character_bits(&self) -> u32
+    {
+        1 + u32::from(self.data_bits) + u32::from(self.parity != Parity::None) + u32::from(self.stop_bits)
+    }
+}
+
+/// "8N1", "8E1", "7O2"...
+pub fn parse_framing(framing: &str) -> Option<(u8, Parity, u8)>
+{
+    let mut chars = framing.trim().chars();
+    let data_bits = chars.next()?.to_digit(10).filter(|bits| (5..=8).contains(bits))? as u8;
+    let parity = match chars.next()?.to_ascii_uppercase()
+    {
+        'N' => Parity::None,
+        'E' => Parity::Even,
+        'O' => Parity::Odd,
+        _ => return None,
+    };
+    let stop_bits = chars.next()?.to_digit(10).filter(|bits| (1..=2).contains(bits))? as u8;
+    chars.next().is_none().then_some((data_bits, parity, stop_bits))
+}
+
+//--------------------------------------------------------------------------------- Factory
+/// UART_DRIVER=linux opens UART_PORT or fails, mock simulates the slaves, auto takes the port when
+/// it exists; without one every transaction fails, simulated readings never reach a real installation
+pub fn create_uart() -> Result<Arc<Uart>, HardwareError>
+{
+    let driver = std::env::var("UART_DRIVER").unwrap_or_else(|_| "auto".to_string());
+    open_uart(&driver, &SerialConfig::from_env())
+}
+
+pub fn open_uart(driver: &str, config: &SerialConfig) -> Result<Arc<Uart>, HardwareError>
+{
+    let open = ||
+    {
+        let line = LinuxSerialLine::open(config).map_err(|e| HardwareError::Device(format!("UART {}: {}", config.path, e)))?;
+        info!("UART driver: linux ({} {} baud)", config.path, config.baud);
+        Ok(Arc::new(Uart::new(Box::new(line), config)))
+    };
+
+    match driver.to_lowercase().as_str()
+    {
+        "linux" => open(),
+        "mock" =>
+        {
+            info!("UART driver: mock");
+            Ok(Arc::new(Uart::new(Box::new(MockSerialLine::new()), config)))
+        }
+        "auto" if Path::new(&config.path).exists() => open(),
+        "auto" =>
+        {
+            info!("UART driver: none, {} not found", config.path);
+            Ok(Arc::new(Uart::new(Box::new(NoSerialLine(config.path.clone())), config)))
+        }
+        _ => Err(HardwareError::Unsupported(format!("UART_DRIVER '{}', expected auto, linux or mock", driver))),
+    }
+}
+
+/// Stands in for a port that is not there
+struct NoSerialLine(String);
+
+impl SerialLine for NoSerialLine
+{
+    fn name(&self) -> &'static str { "none" }
+
+    fn clear(&mut self) -> Result<(), HardwareError> { Ok(()) }
+
+    fn write_all(&mut self, _: &[u8]) -> Result<(), HardwareError>
+    {
+        Err(HardwareError::Device(format!("No serial port at {}", self.0)))
+    }
+
+    fn read_exact(&mut self, _: &mut [u8]) -> Result<(), HardwareError>
+    {
+        Err(HardwareError::Device(format!("No serial port at {}", self.0)))
+    }
+}
+
+//--------------------------------------------------------------------------------- Devices
+/// The shared line; a Modbus transaction holds it from request to response
+pub struct Uart
+{
+    line: Mutex<Box<dyn SerialLine>>,
+    silence: Duration,
+}
+
+impl Uart
+{
+    //------------------------- New
+    pub fn new(line: Box<dyn SerialLine>, config: &SerialConfig) -> Self
+    {
+        // Modbus RTU frames are separated by 3.5 characters of silence, at least 1.75 ms
+        let silence = Duration::from_secs_f64(3.5 * f64::from(config.character_bits()) / f64::from(config.baud.max(1)));
+        Self { line: Mutex::new(line), silence: silence.max(Duration::from_micros(1750)) }
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        self.line.lock().unwrap().name()
+    }
+
+    //------------------------- Read
+    pub fn read(&self, address: &str) -> Result<i32, HardwareError>
+    {
+        let map = RegisterMap::from_address(address)?;
+        let Some(source) = map.read else { return Err(HardwareError::Unsupported(format!("Modbus device '{}' has no read register", address))); };
+
+        let mut line = self.line.lock().unwrap();
+        std::thread::sleep(self.silence);
+        let registers = modbus::read_registers(line.as_mut(), map.unit, source.table, source.register, map.format.registers())?;
+        Ok(map.value_of(&registers))
+    }
+
+    //------------------------- Write
+    pub fn write(&self, address: &str, value: i32) -> Result<(), HardwareError>
+    {
+        let map = RegisterMap::from_address(address)?;
+        let Some(coil) = map.write else { return Err(HardwareError::Unsupported(format!("Modbus device '{}' has no write coil", address))); };
+        if value != 0 && value != 1 { return Err(HardwareError::InvalidValue(value, u32::from(coil))); }
+
+        let mut line = self.line.lock().unwrap();
+        std::thread::sleep(self.silence);
+        modbus::write_coil(line.as_mut(), map.unit, coil, value == 1)
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn only_the_mock_driver_simulates_slaves()
+    {
+        let config = SerialConfig { path: "/nonexistent/serial0".to_string(), baud: 9600, data_bits: 8, parity: Parity::None, stop_bits: 1, timeout: Duration::from_millis(50) };
+        assert!(open_uart("linux", &config).is_err(), "a required port that does not open stops the start");
+        assert!(open_uart("serial", &config).is_err());
+        assert_eq!(open_uart("mock", &config).unwrap().read("unit=1,read=holding:1").unwrap(), 1480);
+
+        let uart = open_uart("auto", &config).unwrap();
+        assert_eq!(uart.name(), "none");
+        assert!(matches!(uart.read("unit=1,read=holding:1"), Err(HardwareError::Device(_))));
+        assert!(uart.write("unit=1,write=coil:0", 1).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//--------------------------------------------------------------------------------- Location
+// src/hardware/uart/modbus.rs
+
+//--------------------------------------------------------------------------------- Description
+// Modbus RTU master: read holding (0x03) and input (0x04) registers, write a single coil (0x05),
+// and the per-device register map parsed from device.address
+
+//--------------------------------------------------------------------------------- Import
+use super::super::HardwareError;
+use super::SerialLine;
+
+//--------------------------------------------------------------------------------- Functions
+const READ_HOLDING_REGISTERS: u8 = 0x03;
+const READ_INPUT_REGISTERS: u8 = 0x04;
+const WRITE_SINGLE_COIL: u8 = 0x05;
+const EXCEPTION: u8 = 0x80;
+
+//--------------------------------------------------------------------------------- Register Map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Table
+{
+    Holding,
+    Input,
+}
+
+impl Table
+{
+    fn function(&self) -> u8
+    {
+        match self
+        {
+            Table::Holding => READ_HOLDING_REGISTERS,
+            Table::Input => READ_INPUT_REGISTERS,
+        }
+    }
+}
+
+/// How the read registers make a number; 32-bit values span two registers, high word first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format
+{
+    U16,
+    I16,
+    U32,
+    I32,
+}
+
+impl Format
+{
+    pub fn registers(&self) -> u16
+    {
+        match self
+        {
+            Format::U16 | Format::I16 => 1,
+            Format::U32 | Format::I32 => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source
+{
+    pub table: Table,
+    pub register: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterMap
+{
+    pub unit: u8,
+    pub read: Option<Source>,
+    pub write: Option<u16>,
+    pub format: Format,
+    pub scale: i64,
+}
+
+impl RegisterMap
+{
+    //------------------------- From Address
+    /// "unit=1,read=holding:0x2103,type=u16,scale=10,write=coil:0"
+    pub fn from_address(address: &str) -> Result<Self, HardwareError>
+    {
+        let invalid = |reason: String| HardwareError::Unsupported(format!("Modbus address '{}': {}", address, reason));
+        let number = |text: &str| match text.trim().strip_prefix("0x").or_else(|| text.trim().strip_prefix("0X"))
+        {
+            Some(hex) => u16::from_str_radix(hex, 16).ok(),
+            None => text.trim().parse::<u16>().ok(),
+        };
+
+        let mut map = RegisterMap { unit: 0, read: None, write: None, format: Format::U16, scale: 1 };
+        for entry in address.split(',').map(str::trim).filter(|entry| !entry.is_empty())
+        {
+            let (key, value) = entry.split_once('=').ok_or_else(|| invalid(format!("'{}' is not key=value", entry)))?;
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str()
+            {
+                "unit" => map.unit = number(value).and_then(|unit| u8::try_from(unit).ok()).filter(|unit| (1..=247).contains(unit)).ok_or_else(|| invalid("unit must be 1-247".to_string()))?,
+                "read" =>
+                {
+                    let (table, register) = value.split_once(':').ok_or_else(|| invalid("read must be holding:<register> or input:<register>".to_string()))?;
+                    let table = match table.trim().to_lowercase().as_str()
+                    {
+                        "holding" => Table::Holding,
+                        "input" => Table::Input,
+                        other => return Err(invalid(format!("unknown register table '{}'", other))),
+                    };
+                    map.read = Some(Source { table, register: number(register).ok_or_else(|| invalid(format!("invalid register '{}'", register)))? });
+                }
+                "write" =>
+                {
+                    let coil = value.strip_prefix("coil:").and_then(number).ok_or_else(|| invalid("write must be coil:<coil>".to_string()))?;
+                    map.write = Some(coil);
+                }
+                "type" => map.format = match value.to_lowercase().as_str()
+                {
+                    "u16" => Format::U16,
+                    "i16" => Format::I16,
+                    "u32" => Format::U32,
+                    "i32" => Format::I32,
+                    other => return Err(invalid(format!("unknown type '{}', expected u16, i16, u32 or i32", other))),
+                },
+                "scale" => map.scale = value.parse::<i64>().ok().filter(|scale| *scale > 0).ok_or_else(|| invalid("scale must be a positive integer".to_string()))?,
+                other => return Err(invalid(format!("unknown key '{}'", other))),
+            }
+        }
+
+        if map.unit == 0 { return Err(invalid("missing unit".to_string())); }
+        if map.read.is_none() && map.write.is_none() { return Err(invalid("needs read or write".to_string())); }
+        Ok(map)
+    }
+
+    /// Polled for its value, as opposed to only driven by commands
+    pub fn is_input(&self) -> bool
+    {
+        self.read.is_some()
+    }
+
+    //------------------------- Value
+    /// Registers as a number, divided by the scale
+    pub fn value_of(&self, registers: &[u16]) -> i32
+    {
+        let word = |index: usize| registers.get(index).copied().unwrap_or(0);
+        let raw = match self.format
+        {
+            Format::U16 => i64::from(word(0)),
+            Format::I16 => i64::from(word(0) as i16),
+            Format::U32 => i64::from((u32::from(word(0)) << 16) | u32::from(word(1))),
+            Format::I32 => i64::from(((u32::from(word(0)) << 16) | u32::from(word(1))) as i32),
+        };
+        (raw as f64 / self.scale as f64).round().clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32
+    }
+}
+
+//--------------------------------------------------------------------------------- Requests
+pub fn read_registers(line: &mut dyn SerialLine, unit: u8, table: Table, register: u16, count: u16) -> Result<Vec<u16>, HardwareError>
+{
+    let [register_high, register_low] = register.to_be_bytes();
+    let [count_high, count_low] = count.to_be_bytes();
+    let response = transaction(line, unit, &[table.function(), register_high, register_low, count_high, count_low])?;
+
+    // function, byte count, registers
+    let data = response.get(2..).unwrap_or_default();
+    if response.get(1).copied() != Some(count as u8 * 2) || data.len() != count as usize * 2
+    {
+        return Err(HardwareError::Device(format!("Modbus unit {}: expected {} registers", unit, count)));
+    }
+    Ok(data.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect())
+}
+
+pub fn write_coil(line: &mut dyn SerialLine, unit: u8, coil: u16, on: bool) -> Result<(), HardwareError>
+{
+    let [coil_high, coil_low] = coil.to_be_bytes();
+    let request = [WRITE_SINGLE_COIL, coil_high, coil_low, if on { 0xFF } else { 0x00 }, 0x00];
+    let response = transaction(line, unit, &request)?;
+
+    // The slave echoes the request
+    match response == request
+    {
+        true => Ok(()),
+        false => Err(HardwareError::Device(format!("Modbus unit {}: unexpected answer to coil {} write", unit, coil))),
+    }
+}
+
+//--------------------------------------------------------------------------------- Transaction
+/// Send one request PDU and return the response PDU, checked for unit, function and CRC
+fn transaction(line: &mut dyn SerialLine, unit: u8, pdu: &[u8]) -> Result<Vec<u8>, HardwareError>
+{
+    let mut frame = Vec::with_capacity(pdu.len() + 3);
+    frame.push(unit);
+    frame.extend_from_slice(pdu);
+    frame.extend_from_slice(&crc16(&frame).to_le_bytes());
+
+    line.clear()?;
+    line.write_all(&frame)?;
+
+    // unit and function, then a length that depends on them
+    let mut response = vec![0u8; 2];
+    line.read_exact(&mut response)?;
+    if response[0] != unit { return Err(HardwareError::Device(format!("Modbus unit {}: answer from unit {}", unit, response[0]))); }
+
+    let function = response[1];
+    let rest = match function
+    {
+        _ if function == pdu[0] | EXCEPTION => 1,
+        READ_HOLDING_REGISTERS | READ_INPUT_REGISTERS if function == pdu[0] =>
+        {
+            let mut count = [0u8; 1];
+            line.read_exact(&mut count)?;
+            response.push(count[0]);
+            usize::from(count[0])
+        }
+        WRITE_SINGLE_COIL if function == pdu[0] => 4,
+        _ => return Err(HardwareError::Device(format!("Modbus unit {}: unexpected function 0x{:02x}", unit, function))),
+    };
+    let mut tail = vec![0u8; rest + 2];
+    line.read_exact(&mut tail)?;
+    response.extend_from_slice(&tail);
+
+    let (body, crc) = response.split_at(response.len() - 2);
+    if crc16(body).to_le_bytes() != crc { return Err(HardwareError::Device(format!("Modbus unit {}: CRC mismatch", unit))); }
+    if function & EXCEPTION != 0 { return Err(HardwareError::Device(format!("Modbus unit {}: exception {}", unit, exception_name(body[2])))); }
+    Ok(body[1..].to_vec())
+}
+
+fn exception_name(code: u8) -> String
+{
+    match code
+    {
+        0x01 => "01 illegal function".to_string(),
+        0x02 => "02 illegal data address".to_string(),
+        0x03 => "03 illegal data value".to_string(),
+        0x04 => "04 slave device failure".to_string(),
+        0x06 => "06 slave device busy".to_string(),
+        code => format!("{:02x}", code),
+    }
+}
+
+//--------------------------------------------------------------------------------- CRC
+/// CRC-16/MODBUS, sent low byte first
+pub fn crc16(data: &[u8]) -> u16
+{
+    data.iter().fold(0xFFFF, |crc, byte|
+    {
+        (0..8).fold(crc ^ u16::from(*byte), |crc, _| match crc & 1
+        {
+            1 => (crc >> 1) ^ 0xA001,
+            _ => crc >> 1,
+        })
+    })
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::mock::MockSerialLine;
+
+    #[test]
+    fn crc16_matches_the_modbus_check_value()
+    {
+        assert_eq!(crc16(b"123456789"), 0x4B37);
+        // Read 10 holding registers from unit 1, the CRC goes on the wire as C5 CD
+        assert_eq!(crc16(&[0x01, 0x03, 0x00, 0x00, 0x00, 0x0A]).to_le_bytes(), [0xC5, 0xCD]);
+    }
+
+    #[test]
+    fn register_map_parses_and_rejects()
+    {
+        let map = RegisterMap::from_address("unit=1, read=holding:0x2103, type=i32, scale=10, write=coil:0").unwrap();
+        assert_eq!(map, RegisterMap { unit: 1, read: Some(Source { table: Table::Holding, register: 0x2103 }), write: Some(0), format: Format::I32, scale: 10 });
+        assert!(map.is_input());
+        assert!(!RegisterMap::from_address("unit=2,write=coil:3").unwrap().is_input());
+
+        for address in ["read=input:0", "unit=0,read=input:0", "unit=248,read=input:0", "unit=1", "unit=1,read=discrete:0",
+                        "unit=1,read=input:0,type=f32", "unit=1,read=input:0,scale=0", "unit=1,write=3", "unit=1,speed=9600", "unit"]
+        {
+            assert!(matches!(RegisterMap::from_address(address), Err(HardwareError::Unsupported(_))), "{} is rejected", address);
+        }
+    }
+
+    #[test]
+    fn value_of_applies_format_and_scale()
+    {
+        let map = |format: Format, scale: i64| RegisterMap { unit: 1, read: Some(Source { table: Table::Input, register: 0 }), write: None, format, scale };
+
+        assert_eq!(map(Format::U16, 1).value_of(&[0xFFFF]), 65535);
+        assert_eq!(map(Format::I16, 1).value_of(&[0xFFFF]), -1);
+        assert_eq!(map(Format::U32, 1).value_of(&[0x0001, 0x0002]), 65538);
+        assert_eq!(map(Format::I32, 1).value_of(&[0xFFFF, 0xFFFE]), -2);
+        assert_eq!(map(Format::U16, 10).value_of(&[725]), 73, "rounded, not truncated");
+        assert_eq!(map(Format::U32, 1).value_of(&[0xFFFF, 0xFFFF]), i32::MAX, "clamped to i32");
+        assert_eq!(map(Format::U32, 1).value_of(&[7]), 0x0007_0000, "a missing register reads as 0");
+    }
+
+    #[test]
+    fn requests_go_through_the_mock_bus()
+    {
+        let mut line = MockSerialLine::new();
+
+        assert_eq!(read_registers(&mut line, 2, Table::Input, 0, 2).unwrap(), vec![150, 720]);
+        assert_eq!(read_registers(&mut line, 1, Table::Holding, 0, 2).unwrap(), vec![0, 1480], "the VFD reports no frequency while stopped");
+        write_coil(&mut line, 1, 0, true).unwrap();
+        assert_eq!(read_registers(&mut line, 1, Table::Holding, 0, 1).unwrap(), vec![500]);
+
+        let exception = read_registers(&mut line, 1, Table::Holding, 100, 1).unwrap_err().to_string();
+        assert!(exception.contains("02 illegal data address"), "{}", exception);
+        let silent = read_registers(&mut line, 9, Table::Holding, 0, 1).unwrap_err().to_string();
+        assert!(silent.contains("no answer"), "{}", silent);
+    }
+}
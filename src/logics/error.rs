@@ -0,0 +1,81 @@
+//--------------------------------------------------------------------------------- Location
+// src/logics/error.rs
+
+//--------------------------------------------------------------------------------- Description
+// Typed errors shared by the ORM, engine and services; the API maps each kind to its HTTP status
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::{DbErr, SqlErr};
+use thiserror::Error;
+use crate::hardware::HardwareError;
+
+//--------------------------------------------------------------------------------- Error
+#[derive(Debug, Clone, Error)]
+pub enum ApiError
+{
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("{0}")]
+    Database(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl ApiError
+{
+    //------------------------- From Db
+    /// Classify a database error: unique violations are conflicts, missing records are not found
+    pub fn from_db(message: String, error: &DbErr) -> Self
+    {
+        match (error, error.sql_err())
+        {
+            (_, Some(SqlErr::UniqueConstraintViolation(_))) => ApiError::Conflict(message),
+            (DbErr::RecordNotFound(_), _) | (DbErr::RecordNotUpdated, _) => ApiError::NotFound(message),
+            _ => ApiError::Database(message),
+        }
+    }
+
+    //------------------------- With Message
+    /// Same kind of error with a more descriptive message
+    pub fn with_message(self, message: String) -> Self
+    {
+        match self
+        {
+            ApiError::NotFound(_) => ApiError::NotFound(message),
+            ApiError::Unauthorized(_) => ApiError::Unauthorized(message),
+            ApiError::Forbidden(_) => ApiError::Forbidden(message),
+            ApiError::Conflict(_) => ApiError::Conflict(message),
+            ApiError::Validation(_) => ApiError::Validation(message),
+            ApiError::Database(_) => ApiError::Database(message),
+            ApiError::Internal(_) => ApiError::Internal(message),
+        }
+    }
+}
+
+impl From<ApiError> for String
+{
+    fn from(error: ApiError) -> Self
+    {
+        error.to_string()
+    }
+}
+
+impl From<HardwareError> for ApiError
+{
+    fn from(error: HardwareError) -> Self
+    {
+        match error
+        {
+            HardwareError::Device(_) => ApiError::Internal(error.to_string()),
+            _ => ApiError::Validation(error.to_string()),
+        }
+    }
+}
@@ -6,6 +6,8 @@
 
 //--------------------------------------------------------------------------------- Import
 use serde::{Deserialize, Serialize};
+use sea_orm::DbErr;
+use crate::logics::error::ApiError;
 
 //--------------------------------------------------------------------------------- Models
 //------------------------- Output
@@ -16,6 +18,10 @@ pub struct ModelOutput<T>
     pub message: String,
     pub data: Option<T>,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    #[serde(skip)]
+    pub fault: Option<ApiError>,
 }
 
 impl<T> ModelOutput<T> 
@@ -28,6 +34,8 @@ impl<T> ModelOutput<T>
             message,
             data: Some(data),
             error: None,
+            total: None,
+            fault: None,
         }
     }
 
@@ -39,17 +47,70 @@ impl<T> ModelOutput<T>
             message,
             data: None,
             error: None,
+            total: None,
+            fault: None,
         }
     }
 
     pub fn error(message: String) -> Self 
+    {
+        Self::fail(ApiError::Internal(message))
+    }
+
+    pub fn not_found(message: String) -> Self 
+    {
+        Self::fail(ApiError::NotFound(message))
+    }
+
+    pub fn invalid(message: String) -> Self 
+    {
+        Self::fail(ApiError::Validation(message))
+    }
+
+    pub fn db_error(message: String, error: &DbErr) -> Self 
+    {
+        Self::fail(ApiError::from_db(message, error))
+    }
+
+    pub fn fail(error: ApiError) -> Self 
     {
         Self 
         {
             status: false,
             message: "Error".to_string(),
             data: None,
-            error: Some(message),
+            error: Some(error.to_string()),
+            total: None,
+            fault: Some(error),
+        }
+    }
+
+    pub fn with_total(mut self, total: u64) -> Self 
+    {
+        self.total = Some(total);
+        self
+    }
+
+    /// Same output with the data converted
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ModelOutput<U> 
+    {
+        ModelOutput 
+        {
+            status: self.status,
+            message: self.message,
+            data: self.data.map(f),
+            error: self.error,
+            total: self.total,
+            fault: self.fault,
+        }
+    }
+
+    pub fn into_result(self) -> Result<T, ApiError> 
+    {
+        match self.data 
+        {
+            Some(data) if self.status => Ok(data),
+            _ => Err(self.fault.unwrap_or_else(|| ApiError::Internal(self.error.unwrap_or(self.message)))),
         }
     }
 }
@@ -86,14 +147,14 @@ impl PortTypes
     {
         matches!(type_str.to_lowercase().as_str(), 
             "in" | "out" | "tx" | "rx" | "sda" | "scl" | 
-            "mosi" | "miso" | "sclk" | "cs0" | "cs1"
+            "mosi" | "miso" | "sclk" | "cs0" | "cs1" | "pwm"
         )
     }
 
     /// Get all valid port type strings
     pub fn valid_types() -> Vec<&'static str> 
     {
-        vec!["In", "Out", "Tx", "Rx", "Sda", "Scl", "Mosi", "Miso", "Sclk", "Cs0", "Cs1"]
+        vec!["In", "Out", "Tx", "Rx", "Sda", "Scl", "Mosi", "Miso", "Sclk", "Cs0", "Cs1", "Pwm"]
     }
 }
 
@@ -107,3 +168,79 @@ pub enum IfTypes
     Dtu,
     Utd,
 }
+
+impl IfTypes 
+{
+    /// Parse a condition type; an empty string means None
+    pub fn from_name(type_str: &str) -> Option<Self> 
+    {
+        match type_str.to_lowercase().as_str() 
+        {
+            "" | "none" => Some(IfTypes::None),
+            "equal" => Some(IfTypes::Equal),
+            "unequal" => Some(IfTypes::Unequal),
+            "dtu" => Some(IfTypes::Dtu),
+            "utd" => Some(IfTypes::Utd),
+            _ => None,
+        }
+    }
+
+    /// Validate if a string represents a valid condition type
+    pub fn is_valid_type(type_str: &str) -> bool 
+    {
+        Self::from_name(type_str).is_some()
+    }
+
+    /// Get all valid condition type strings
+    pub fn valid_types() -> Vec<&'static str> 
+    {
+        vec!["None", "Equal", "Unequal", "Dtu", "Utd"]
+    }
+}
+
+//------------------------- ROLES
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Roles 
+{
+    Viewer,
+    Operator,
+    Admin,
+}
+
+impl Roles 
+{
+    /// Parse a role name
+    pub fn from_name(role_str: &str) -> Option<Self> 
+    {
+        match role_str.to_lowercase().as_str() 
+        {
+            "admin" => Some(Roles::Admin),
+            "operator" => Some(Roles::Operator),
+            "viewer" => Some(Roles::Viewer),
+            _ => None,
+        }
+    }
+
+    /// Name as stored in user_role
+    pub fn name(&self) -> &'static str 
+    {
+        match self 
+        {
+            Roles::Admin => "Admin",
+            Roles::Operator => "Operator",
+            Roles::Viewer => "Viewer",
+        }
+    }
+
+    /// Validate if a string represents a valid role
+    pub fn is_valid_role(role_str: &str) -> bool 
+    {
+        Self::from_name(role_str).is_some()
+    }
+
+    /// Get all valid role strings
+    pub fn valid_roles() -> Vec<&'static str> 
+    {
+        vec!["Admin", "Operator", "Viewer"]
+    }
+}
@@ -8,6 +8,7 @@
 pub mod user;
 pub mod admin;
 pub mod general;
+pub mod error;
 pub mod config;
 pub mod device;
 pub mod device_command;
@@ -190,7 +190,7 @@ pub async fn add_sample_ports(db: &DatabaseConnection) -> Result<(), Box<dyn std
         PortActiveModel {
             id: sea_orm::ActiveValue::NotSet,
             user_id: Set(1),
-            name: Set("Reserved".to_string()),
+            name: Set("Reserved-2".to_string()),
             pin: Set(28),
             port: Set(0),
             value: Set(0),
@@ -296,7 +296,7 @@ pub async fn add_sample_ports(db: &DatabaseConnection) -> Result<(), Box<dyn std
             description: Set("Pool Light".to_string()),
             enable: Set(true),
             protocol: Set("GPIO".to_string()),
-            r#type: Set("OUT".to_string()),
+            r#type: Set("PWM".to_string()),
         },
         PortActiveModel {
             id: sea_orm::ActiveValue::NotSet,
@@ -392,7 +392,7 @@ pub async fn add_sample_ports(db: &DatabaseConnection) -> Result<(), Box<dyn std
             description: Set("Fan Motor".to_string()),
             enable: Set(true),
             protocol: Set("GPIO".to_string()),
-            r#type: Set("OUT".to_string()),
+            r#type: Set("PWM".to_string()),
         },
         PortActiveModel {
             id: sea_orm::ActiveValue::NotSet,
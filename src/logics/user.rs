@@ -9,22 +9,27 @@ use crate::orm::logics::user::UserORM;
 use sea_orm::DatabaseConnection;
 use crate::orm::models::user::ActiveModel as UserActiveModel;
 use sea_orm::ActiveValue::Set;
+use crate::api::auth::{generate_key, hash_password};
+use crate::orm::logics::user_role::UserRoleORM;
+use crate::logics::general::Roles;
 
 //--------------------------------------------------------------------------------- Add Users Logic
 pub async fn add_sample_users(db: &DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> 
 {
+    // No fixed credential ships in the binary: the password and key are random and shown only here
     let user_orm = UserORM::new(true, true);
+    let password = generate_key();
     let sample_users = vec![
         UserActiveModel 
         {
             id: sea_orm::ActiveValue::NotSet,
             name: Set("raspberrypi".to_string()),
             username: Set("raspberrypi".to_string()),
-            password: Set("123456".to_string()),
-            key: Set("my_key".to_string()),
+            password: Set(hash_password(&password)?),
+            key: Set(generate_key()),
             email: Set("app@raspberrypi.com".to_string()),
             phone: Set("+1".to_string()),
-            tg_id: Set("@raspberrypi".to_string()),
+            tg_id: Set(String::new()),
             enable: Set(true),
         },
     ];
@@ -46,6 +51,10 @@ pub async fn add_sample_users(db: &DatabaseConnection) -> Result<(), Box<dyn std
             if let Some(added_user) = result.data 
             {
                 println!("✅ Successfully added user: {} (ID: {})", added_user.name, added_user.id);
+                println!("🔑 Password: {}", password);
+                println!("🔑 API key: {}", added_user.key);
+                println!("⚠️  Store them now, they are not shown again");
+                UserRoleORM::new(true, true).set(db, added_user.id, Roles::Admin.name(), Some(true)).await;
             }
         } 
         else 
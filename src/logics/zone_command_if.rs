@@ -22,6 +22,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is on".to_string()),
             device_id: Set(4),
             command_id: Set(7),
+            r#type: Set("None".to_string()),
             description: Set("Pool shir on condition".to_string()),
             enable: Set(true),
         },
@@ -31,6 +32,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is off".to_string()),
             device_id: Set(4),
             command_id: Set(8),
+            r#type: Set("None".to_string()),
             description: Set("Pool shir off condition".to_string()),
             enable: Set(true),
         },
@@ -40,6 +42,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is reon".to_string()),
             device_id: Set(4),
             command_id: Set(9),
+            r#type: Set("Utd".to_string()),
             description: Set("Pool shir restart condition".to_string()),
             enable: Set(true),
         },
@@ -50,6 +53,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is on".to_string()),
             device_id: Set(10),
             command_id: Set(20),
+            r#type: Set("None".to_string()),
             description: Set("Jacuzzi shir on condition".to_string()),
             enable: Set(true),
         },
@@ -59,6 +63,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is off".to_string()),
             device_id: Set(10),
             command_id: Set(21),
+            r#type: Set("None".to_string()),
             description: Set("Jacuzzi shir off condition".to_string()),
             enable: Set(true),
         },
@@ -68,6 +73,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is reon".to_string()),
             device_id: Set(10),
             command_id: Set(22),
+            r#type: Set("Utd".to_string()),
             description: Set("Jacuzzi shir restart condition".to_string()),
             enable: Set(true),
         },
@@ -77,6 +83,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If fuse is on".to_string()),
             device_id: Set(8),
             command_id: Set(16),
+            r#type: Set("None".to_string()),
             description: Set("Jacuzzi erjet on condition".to_string()),
             enable: Set(true),
         },
@@ -86,6 +93,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If fuse is off".to_string()),
             device_id: Set(8),
             command_id: Set(17),
+            r#type: Set("None".to_string()),
             description: Set("Jacuzzi erjet off condition".to_string()),
             enable: Set(true),
         },
@@ -96,6 +104,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is on".to_string()),
             device_id: Set(13),
             command_id: Set(27),
+            r#type: Set("None".to_string()),
             description: Set("Sauna dry heater on condition".to_string()),
             enable: Set(true),
         },
@@ -105,6 +114,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is off".to_string()),
             device_id: Set(13),
             command_id: Set(28),
+            r#type: Set("None".to_string()),
             description: Set("Sauna dry heater off condition".to_string()),
             enable: Set(true),
         },
@@ -114,6 +124,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is reon".to_string()),
             device_id: Set(13),
             command_id: Set(29),
+            r#type: Set("Utd".to_string()),
             description: Set("Sauna dry heater restart condition".to_string()),
             enable: Set(true),
         },
@@ -124,6 +135,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is on".to_string()),
             device_id: Set(15),
             command_id: Set(32),
+            r#type: Set("None".to_string()),
             description: Set("Fan motor on condition".to_string()),
             enable: Set(true),
         },
@@ -133,6 +145,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is off".to_string()),
             device_id: Set(15),
             command_id: Set(33),
+            r#type: Set("None".to_string()),
             description: Set("Fan motor off condition".to_string()),
             enable: Set(true),
         },
@@ -142,6 +155,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is reon".to_string()),
             device_id: Set(15),
             command_id: Set(34),
+            r#type: Set("Utd".to_string()),
             description: Set("Fan motor restart condition".to_string()),
             enable: Set(true),
         },
@@ -152,6 +166,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor WL is off".to_string()),
             device_id: Set(21),
             command_id: Set(46),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna shir on condition".to_string()),
             enable: Set(true),
         },
@@ -161,6 +176,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor WH is on".to_string()),
             device_id: Set(20),
             command_id: Set(43),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna shir off condition".to_string()),
             enable: Set(true),
         },
@@ -170,6 +186,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor WL is on".to_string()),
             device_id: Set(21),
             command_id: Set(45),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna heater on condition 1".to_string()),
             enable: Set(true),
         },
@@ -179,6 +196,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is on".to_string()),
             device_id: Set(22),
             command_id: Set(47),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna heater on condition 2".to_string()),
             enable: Set(true),
         },
@@ -188,6 +206,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor WL is off".to_string()),
             device_id: Set(21),
             command_id: Set(46),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna heater off condition 1".to_string()),
             enable: Set(true),
         },
@@ -197,6 +216,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is off".to_string()),
             device_id: Set(22),
             command_id: Set(48),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna heater off condition 2".to_string()),
             enable: Set(true),
         },
@@ -206,6 +226,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor WL is on".to_string()),
             device_id: Set(21),
             command_id: Set(45),
+            r#type: Set("None".to_string()),
             description: Set("Steam sauna heater restart condition 1".to_string()),
             enable: Set(true),
         },
@@ -215,6 +236,7 @@ pub async fn add_sample_zone_command_ifs(db: &DatabaseConnection) -> Result<(),
             name: Set("If sensor is reon".to_string()),
             device_id: Set(22),
             command_id: Set(49),
+            r#type: Set("Utd".to_string()),
             description: Set("Steam sauna heater restart condition 2".to_string()),
             enable: Set(true),
         },
@@ -8,11 +8,16 @@
 pub use dotenvy::dotenv;
 pub use sea_orm::{Database, DatabaseConnection};
 pub use std::net::SocketAddr;
+pub use std::sync::Arc;
 pub use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 pub mod api;
 pub mod orm;
 pub mod logics;
 pub mod args;
+pub mod hardware;
+pub mod engine;
+pub mod mqtt;
+pub mod telegram;
 mod doc;
 mod route;
 
@@ -21,6 +26,13 @@ mod route;
 pub struct AppState 
 {
     pub db: DatabaseConnection,
+    pub pins: Arc<dyn hardware::gpio::PinDriver>,
+    pub executor: engine::executor::Executor,
+    pub rules: engine::rule::RuleEngine,
+    pub limits: engine::limit::LimitWatcher,
+    pub webhooks: engine::webhook::WebhookDispatcher,
+    pub auth: api::auth::Auth,
+    pub graphql: api::graphql::ApiSchema,
 }
 
 //--------------------------------------------------------------------------------- Main
@@ -40,7 +52,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
 
     // Database connection
     let database_url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite::memory:".to_string());
-    let db: DatabaseConnection = Database::connect(&database_url).await.expect("Failed to connect to database");
+    let db: DatabaseConnection = Database::connect(&database_url).await.map_err(|e| format!("Failed to connect to database: {}", e))?;
+
+    // Database migrations (skipped with DATABASE_MIGRATE=false; --migrate applies and reports them itself)
+    let migrate = std::env::var("DATABASE_MIGRATE").map(|value| value != "false").unwrap_or(true);
+    if migrate && std::env::args().nth(1).as_deref() != Some("--migrate")
+    {
+        orm::migrations::migrate(&db).await.map_err(|e| format!("Failed to apply database migrations: {}", e))?;
+    }
 
     // Handle command-line arguments
     if args::handle_arguments(&db).await? 
@@ -48,8 +67,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
         return Ok(()); // Exit if arguments were handled (like --add-users)
     }
 
+    // Authentication, before anything starts so a bad AUTH_SECRET stops here
+    let auth = api::auth::Auth::new()?;
+
+    // Hardware drivers, a driver that is set but does not open stops here
+    let pins = hardware::gpio::create_driver().map_err(|e| format!("Failed to open the GPIO driver: {}", e))?;
+    let buses = hardware::Buses::create(pins.clone()).map_err(|e| format!("Failed to open the bus drivers: {}", e))?;
+
+    // Engine
+    let events = engine::events::EventBus::new(256);
+    let executor = engine::executor::Executor::new(db.clone(), pins.clone(), buses, events);
+    let rules = engine::rule::RuleEngine::new(executor.clone());
+    rules.spawn();
+    let scheduler = engine::scheduler::TimerScheduler::new(executor.clone());
+    scheduler.spawn();
+    let limits = engine::limit::LimitWatcher::new(executor.clone());
+    limits.spawn();
+    let webhooks = engine::webhook::WebhookDispatcher::new(executor.clone());
+    webhooks.spawn();
+    let poller = engine::poller::InputPoller::new(executor.clone());
+    poller.spawn();
+
+    // MQTT bridge (MQTT_MODE=off | external | embedded)
+    mqtt::start(mqtt::MqttConfig::from_env(), executor.clone(), rules.clone()).await;
+
+    // Telegram notifications and bot commands (TELEGRAM_BOT_TOKEN)
+    telegram::start(telegram::TelegramConfig::from_env(), executor.clone(), rules.clone());
+
     // State management
-    let state = AppState { db };
+    let graphql = api::graphql::schema();
+    let state = AppState { db, pins, executor, rules, limits, webhooks, auth, graphql };
 
     // Create application with routes and middleware
     let app = route::create_app(state);
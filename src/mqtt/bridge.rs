@@ -0,0 +1,265 @@
+//--------------------------------------------------------------------------------- Location
+// src/mqtt/bridge.rs
+
+//--------------------------------------------------------------------------------- Description
+// MQTT client side of the bridge: publishes every device's state on connect and after each executed
+// command, runs the command names received on the set topics through the executor and the zone
+// commands pressed on the run topics through the rule engine
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::time::Duration;
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, MqttOptions, Packet, QoS};
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+use crate::logics::error::ApiError;
+use crate::engine::events::Event;
+use crate::engine::executor::Executor;
+use crate::engine::rule::RuleEngine;
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, zone_command::ZoneCommandORM};
+use super::discovery::Discovery;
+use super::{parse_topic, state_topic, MqttConfig, TopicRequest};
+
+//--------------------------------------------------------------------------------- Constants
+/// Wait before polling again after a connection error, the event loop reconnects on the next poll
+const RETRY: Duration = Duration::from_secs(5);
+
+//--------------------------------------------------------------------------------- State
+/// Retained payload of {prefix}/{zone_id}/{device_id}/state
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceState
+{
+    pub device_id: i32,
+    pub zone_id: i32,
+    pub command_id: i32,
+    pub command: Option<String>,
+    pub value: i32,
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct MqttBridge
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub prefix: String,
+    pub client: AsyncClient,
+    pub executor: Executor,
+    pub rules: RuleEngine,
+    pub discovery: Option<Discovery>,
+}
+
+impl MqttBridge
+{
+    //------------------------- Spawn
+    /// Connect and start the connection, event and discovery tasks
+    pub fn spawn(config: MqttConfig, executor: Executor, rules: RuleEngine) -> Self
+    {
+        let mut options = MqttOptions::new(config.client_id.clone(), config.host.clone(), config.port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some(username) = &config.username { options.set_credentials(username.clone(), config.password.clone().unwrap_or_default()); }
+
+        let (client, eventloop) = AsyncClient::new(options, 64);
+        let discovery = config.discovery.then(|| Discovery::new(&config, client.clone(), executor.db.clone()));
+        let bridge = Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "MqttBridge".to_string(),
+            prefix: config.prefix,
+            client,
+            executor,
+            rules,
+            discovery,
+        };
+
+        tokio::spawn(bridge.clone().connection(eventloop));
+        tokio::spawn(bridge.clone().events());
+        if let Some(discovery) = &bridge.discovery { discovery.spawn(); }
+        bridge
+    }
+
+    //------------------------- Connection
+    /// Drive the MQTT event loop; client requests made from here would wait on this very loop, so they run in their own tasks
+    async fn connection(self, mut eventloop: EventLoop)
+    {
+        loop
+        {
+            match eventloop.poll().await
+            {
+                Ok(MqttEvent::Incoming(Packet::ConnAck(_))) =>
+                {
+                    info!("{}::connection - Connected", self.this_class);
+                    tokio::spawn(self.clone().on_connected());
+                }
+                Ok(MqttEvent::Incoming(Packet::Publish(publish))) =>
+                {
+                    let payload = String::from_utf8_lossy(&publish.payload).trim().to_string();
+                    match &self.discovery
+                    {
+                        Some(discovery) if publish.topic == discovery.status_topic() =>
+                        {
+                            if payload == "online" { discovery.refresh(); }
+                        }
+                        _ => { tokio::spawn(self.clone().on_message(publish.topic, payload)); }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) =>
+                {
+                    warn!("{}::connection - {}, retrying in {}s", self.this_class, e, RETRY.as_secs());
+                    tokio::time::sleep(RETRY).await;
+                }
+            }
+        }
+    }
+
+    //------------------------- Events
+    /// Publish the new state after every executed command, whatever its source, and every new reading
+    async fn events(self)
+    {
+        let mut receiver = self.executor.events.subscribe();
+        loop
+        {
+            match receiver.recv().await
+            {
+                Ok(Event::CommandExecuted { device_id, zone_id, command_id, command, value }) =>
+                {
+                    self.publish_state(&DeviceState { device_id, zone_id, command_id, command: Some(command), value }).await;
+                }
+                Ok(Event::DeviceRead { device_id, zone_id, command_id, command, value }) =>
+                {
+                    self.publish_state(&DeviceState { device_id, zone_id, command_id, command, value }).await;
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => warn!("{}::events - Skipped {} events", self.this_class, skipped),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    //------------------------- On Connected
+    /// Subscribe and publish every state and config again: the broker may have restarted without its retained messages
+    async fn on_connected(self)
+    {
+        let mut filters = vec![format!("{}/+/+/set", self.prefix), format!("{}/+/zone_command/+/run", self.prefix)];
+        if let Some(discovery) = &self.discovery { filters.push(discovery.status_topic()); }
+        for filter in filters
+        {
+            if let Err(e) = self.client.subscribe(&filter, QoS::AtLeastOnce).await
+            {
+                error!("{}::on_connected - Cannot subscribe to {}: {}", self.this_class, filter, e);
+            }
+        }
+        if let Some(discovery) = &self.discovery { discovery.refresh(); }
+
+        match self.states().await
+        {
+            Ok(states) => for state in states { self.publish_state(&state).await; },
+            Err(e) => error!("{}::on_connected - Cannot load the device states: {}", self.this_class, e),
+        }
+    }
+
+    /// Current state of every device
+    async fn states(&self) -> Result<Vec<DeviceState>, ApiError>
+    {
+        let db = &self.executor.db;
+        let devices = DeviceORM::new(false, self.log).items(db, HashMap::new()).await.into_result()?;
+        let commands: HashMap<i32, String> = DeviceCommandORM::new(false, self.log).items(db, HashMap::new()).await.into_result()?
+            .into_iter().map(|command| (command.id, command.name)).collect();
+
+        Ok(devices.into_iter().map(|device| DeviceState
+        {
+            device_id: device.id,
+            zone_id: device.zone_id,
+            command_id: device.command_id,
+            command: commands.get(&device.command_id).cloned(),
+            value: device.value,
+        }).collect())
+    }
+
+    //------------------------- Publish State
+    async fn publish_state(&self, state: &DeviceState)
+    {
+        let topic = state_topic(&self.prefix, state.zone_id, state.device_id);
+        let payload = match serde_json::to_vec(state)
+        {
+            Ok(payload) => payload,
+            Err(e) => { error!("{}::publish_state - Failed to serialize: {}", self.this_class, e); return; }
+        };
+
+        match self.client.publish(&topic, QoS::AtLeastOnce, true, payload).await
+        {
+            Ok(()) => if self.verbose { debug!("{}::publish_state - {} value {}", self.this_class, topic, state.value); },
+            Err(e) => warn!("{}::publish_state - Cannot publish {}: {}", self.this_class, topic, e),
+        }
+    }
+
+    //------------------------- On Message
+    async fn on_message(self, topic: String, payload: String)
+    {
+        match parse_topic(&self.prefix, &topic)
+        {
+            Some(TopicRequest::Set { zone_id, device_id }) => self.on_set(&topic, zone_id, device_id, &payload).await,
+            Some(TopicRequest::Run { zone_id, zone_command_id }) => self.on_run(&topic, zone_id, zone_command_id).await,
+            None => debug!("{}::on_message - Ignoring topic {}", self.this_class, topic),
+        }
+    }
+
+    //------------------------- On Set
+    /// Run the device command named in the payload; the outcome is logged by the executor
+    async fn on_set(&self, topic: &str, zone_id: i32, device_id: i32, command: &str)
+    {
+        let this_method = "on_set";
+
+        // The zone in the topic must be the device's, so a stale topic never drives another device
+        let error_msg = match DeviceORM::new(false, self.log).item(&self.executor.db, device_id).await.into_result()
+        {
+            Ok(device) if device.zone_id != zone_id => Some(format!("Device {} is not in zone {} ({})", device_id, zone_id, topic)),
+            Ok(_) if command.is_empty() => Some(format!("Empty command on {}", topic)),
+            Ok(_) => None,
+            Err(e) => Some(format!("Device {} from {}: {}", device_id, topic, e)),
+        };
+        if let Some(error_msg) = error_msg
+        {
+            self.reject(this_method, error_msg).await;
+            return;
+        }
+
+        if self.verbose { info!("{}::{} - Command '{}' on device {} from {}", self.this_class, this_method, command, device_id, topic); }
+        let result = self.executor.execute(device_id, command).await;
+        if !result.status { warn!("{}::{} - {}", self.this_class, this_method, result.error.unwrap_or(result.message)); }
+    }
+
+    //------------------------- On Run
+    /// Run a zone command's actions, like POST /zone_command/run; the outcome is logged by the rule engine
+    async fn on_run(&self, topic: &str, zone_id: i32, zone_command_id: i32)
+    {
+        let this_method = "on_run";
+
+        let error_msg = match ZoneCommandORM::new(false, self.log).item(&self.executor.db, zone_command_id).await.into_result()
+        {
+            Ok(zone_command) if zone_command.zone_id != zone_id => Some(format!("Zone command {} is not in zone {} ({})", zone_command_id, zone_id, topic)),
+            Ok(_) => None,
+            Err(e) => Some(format!("Zone command {} from {}: {}", zone_command_id, topic, e)),
+        };
+        if let Some(error_msg) = error_msg
+        {
+            self.reject(this_method, error_msg).await;
+            return;
+        }
+
+        if self.verbose { info!("{}::{} - Zone command {} from {}", self.this_class, this_method, zone_command_id, topic); }
+        let result = self.rules.run(zone_command_id).await;
+        if !result.status { warn!("{}::{} - {}", self.this_class, this_method, result.error.unwrap_or(result.message)); }
+    }
+
+    /// A message that names the wrong zone or a missing row is logged, never executed
+    async fn reject(&self, this_method: &str, error_msg: String)
+    {
+        warn!("{}::{} - {}", self.this_class, this_method, error_msg);
+        crate::engine::record(&self.executor, "MQTT Command", false, error_msg).await;
+    }
+}
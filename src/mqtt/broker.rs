@@ -0,0 +1,365 @@
+//--------------------------------------------------------------------------------- Location
+// src/mqtt/broker.rs
+
+//--------------------------------------------------------------------------------- Description
+// Embedded MQTT 3.1.1 broker, so the bridge runs with no external service: retained messages,
+// wildcard subscriptions and an optional username/password. Messages are delivered at QoS 0,
+// incoming QoS 1 and 2 publishes are acknowledged. No persistent sessions and no last wills.
+// A client must CONNECT within CONNECT_TIMEOUT and send something within 1.5 times its keepalive,
+// and one that lets QUEUE packets pile up is disconnected
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Result};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use bytes::BytesMut;
+use rumqttc::mqttbytes::{self, matches, valid_filter, valid_topic, QoS};
+use rumqttc::mqttbytes::v4::{self, ConnAck, ConnectReturnCode, Packet, PingResp, PubAck, PubComp, PubRec, Publish, SubAck, SubscribeReasonCode, UnsubAck};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Notify};
+use tokio::time::timeout;
+use tracing::{debug, warn};
+
+//--------------------------------------------------------------------------------- Constants
+/// Largest packet a client may send, state and command payloads are a few bytes
+const MAX_PACKET: usize = 256 * 1024;
+
+/// Packets waiting for a client before it is disconnected
+const QUEUE: usize = 256;
+
+/// Time from accepting a socket to its CONNECT
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time a packet may take to reach a client's socket
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+//--------------------------------------------------------------------------------- State
+#[derive(Default)]
+struct State
+{
+    /// Last retained message per topic
+    retained: HashMap<String, Publish>,
+    /// Connected clients by connection id
+    clients: HashMap<u64, Client>,
+}
+
+struct Client
+{
+    filters: Vec<String>,
+    sender: mpsc::Sender<Packet>,
+    /// Woken when the queue overflows, the session then ends
+    overflow: Arc<Notify>,
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct Broker
+{
+    login: Option<(String, String)>,
+    state: Arc<Mutex<State>>,
+    next_id: Arc<AtomicU64>,
+    queue: usize,
+    connect_timeout: Duration,
+}
+
+impl Broker
+{
+    //------------------------- New
+    fn new(login: Option<(String, String)>, queue: usize, connect_timeout: Duration) -> Self
+    {
+        Self { login, state: Arc::new(Mutex::new(State::default())), next_id: Arc::new(AtomicU64::new(1)), queue, connect_timeout }
+    }
+
+    //------------------------- Bind
+    /// Listen on the address (port 0 picks a free one) and serve clients in the background
+    pub async fn bind(address: &str, login: Option<(String, String)>) -> Result<SocketAddr>
+    {
+        Self::new(login, QUEUE, CONNECT_TIMEOUT).listen(address).await
+    }
+
+    async fn listen(self, address: &str) -> Result<SocketAddr>
+    {
+        let listener = TcpListener::bind(address).await?;
+        let local = listener.local_addr()?;
+        let broker = self;
+
+        tokio::spawn(async move
+        {
+            loop
+            {
+                match listener.accept().await
+                {
+                    Ok((stream, peer)) =>
+                    {
+                        let broker = broker.clone();
+                        tokio::spawn(async move
+                        {
+                            if let Err(e) = broker.serve(stream).await { debug!("Broker::serve - Client {} disconnected: {}", peer, e); }
+                        });
+                    }
+                    Err(e) => warn!("Broker::bind - Accept failed: {}", e),
+                }
+            }
+        });
+        Ok(local)
+    }
+
+    //------------------------- Serve
+    async fn serve(&self, stream: TcpStream) -> Result<()>
+    {
+        let (mut reader, mut writer) = stream.into_split();
+        let mut buffer = BytesMut::with_capacity(4096);
+
+        // The first packet is a CONNECT carrying the configured credentials
+        let first = timeout(self.connect_timeout, read(&mut reader, &mut buffer)).await.map_err(|_| Error::new(ErrorKind::TimedOut, "no CONNECT"))??;
+        let Packet::Connect(connect) = first else { return Err(invalid("expected CONNECT")); };
+        let allowed = match &self.login
+        {
+            Some((username, password)) => connect.login.as_ref().is_some_and(|login| login.validate(username, password)),
+            None => true,
+        };
+        let code = if allowed { ConnectReturnCode::Success } else { ConnectReturnCode::BadUserNamePassword };
+        write(&mut writer, &Packet::ConnAck(ConnAck::new(code, false))).await?;
+        if !allowed { return Err(invalid("bad username or password")); }
+
+        // Replies and deliveries go through one bounded queue so a slow client never blocks the publishers
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, mut outgoing) = mpsc::channel(self.queue);
+        let overflow = Arc::new(Notify::new());
+        self.state.lock().unwrap().clients.insert(id, Client { filters: Vec::new(), sender: sender.clone(), overflow: overflow.clone() });
+
+        let mut write_task = tokio::spawn(async move
+        {
+            while let Some(packet) = outgoing.recv().await
+            {
+                if !matches!(timeout(WRITE_TIMEOUT, write(&mut writer, &packet)).await, Ok(Ok(()))) { break; }
+            }
+        });
+
+        // Keepalive 0 turns the check off
+        let keep_alive = (connect.keep_alive > 0).then(|| Duration::from_millis(u64::from(connect.keep_alive) * 1500));
+        let result = tokio::select!
+        {
+            result = self.session(id, &sender, keep_alive, &mut reader, &mut buffer) => result,
+            _ = overflow.notified() => Err(Error::new(ErrorKind::WouldBlock, "queue full")),
+        };
+        self.state.lock().unwrap().clients.remove(&id);
+        drop(sender);
+        if timeout(WRITE_TIMEOUT, &mut write_task).await.is_err() { write_task.abort(); }
+        result
+    }
+
+    //------------------------- Session
+    async fn session(&self, id: u64, sender: &mpsc::Sender<Packet>, keep_alive: Option<Duration>, reader: &mut OwnedReadHalf, buffer: &mut BytesMut) -> Result<()>
+    {
+        loop
+        {
+            let packet = match keep_alive
+            {
+                Some(limit) => timeout(limit, read(reader, buffer)).await.map_err(|_| Error::new(ErrorKind::TimedOut, "keepalive expired"))??,
+                None => read(reader, buffer).await?,
+            };
+            let replies = match packet
+            {
+                Packet::Publish(publish) =>
+                {
+                    if !valid_topic(&publish.topic) { return Err(invalid("invalid topic")); }
+                    let reply = match publish.qos
+                    {
+                        QoS::AtMostOnce => Vec::new(),
+                        QoS::AtLeastOnce => vec![Packet::PubAck(PubAck::new(publish.pkid))],
+                        QoS::ExactlyOnce => vec![Packet::PubRec(PubRec::new(publish.pkid))],
+                    };
+                    self.route(publish);
+                    reply
+                }
+                Packet::PubRel(release) => vec![Packet::PubComp(PubComp::new(release.pkid))],
+                Packet::Subscribe(subscribe) =>
+                {
+                    let filters = subscribe.filters.into_iter().map(|filter| filter.path).collect::<Vec<_>>();
+                    let codes = filters.iter()
+                        .map(|filter| if valid_filter(filter) { SubscribeReasonCode::Success(QoS::AtMostOnce) } else { SubscribeReasonCode::Failure })
+                        .collect();
+
+                    let mut replies = vec![Packet::SubAck(SubAck::new(subscribe.pkid, codes))];
+                    replies.extend(self.subscribe(id, filters).into_iter().map(Packet::Publish));
+                    replies
+                }
+                Packet::Unsubscribe(unsubscribe) =>
+                {
+                    if let Some(client) = self.state.lock().unwrap().clients.get_mut(&id)
+                    {
+                        client.filters.retain(|filter| !unsubscribe.topics.contains(filter));
+                    }
+                    vec![Packet::UnsubAck(UnsubAck::new(unsubscribe.pkid))]
+                }
+                Packet::PingReq => vec![Packet::PingResp],
+                Packet::Disconnect => return Ok(()),
+                Packet::Connect(_) => return Err(invalid("second CONNECT")),
+                // Deliveries are QoS 0, so no acks are expected from the client
+                _ => Vec::new(),
+            };
+
+            for reply in replies
+            {
+                if sender.try_send(reply).is_err() { return Err(Error::new(ErrorKind::WouldBlock, "queue full or writer closed")); }
+            }
+        }
+    }
+
+    //------------------------- Subscribe
+    /// Add the valid filters and return the retained messages they match
+    fn subscribe(&self, id: u64, filters: Vec<String>) -> Vec<Publish>
+    {
+        let mut state = self.state.lock().unwrap();
+        let filters = filters.into_iter().filter(|filter| valid_filter(filter)).collect::<Vec<_>>();
+
+        let retained = state.retained.values()
+            .filter(|publish| filters.iter().any(|filter| matches(&publish.topic, filter)))
+            .cloned()
+            .collect();
+
+        if let Some(client) = state.clients.get_mut(&id)
+        {
+            for filter in filters
+            {
+                if !client.filters.contains(&filter) { client.filters.push(filter); }
+            }
+        }
+        retained
+    }
+
+    //------------------------- Route
+    /// Keep the retained message and forward the publish to every matching client; a client whose
+    /// queue is full is dropped rather than buffered without limit
+    fn route(&self, publish: Publish)
+    {
+        let mut state = self.state.lock().unwrap();
+        let retain = publish.retain;
+        let delivery = Publish { dup: false, qos: QoS::AtMostOnce, retain: false, pkid: 0, ..publish };
+
+        // An empty retained payload clears the topic
+        if retain && delivery.payload.is_empty() { state.retained.remove(&delivery.topic); }
+        else if retain { state.retained.insert(delivery.topic.clone(), Publish { retain: true, ..delivery.clone() }); }
+
+        let overflowed = state.clients.iter()
+            .filter(|(_, client)| client.filters.iter().any(|filter| matches(&delivery.topic, filter)))
+            .filter(|(_, client)| client.sender.try_send(Packet::Publish(delivery.clone())).is_err())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in overflowed
+        {
+            if let Some(client) = state.clients.remove(&id)
+            {
+                warn!("Broker::route - Client {} does not keep up, disconnecting it", id);
+                client.overflow.notify_one();
+            }
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Packets
+fn invalid(message: &str) -> Error
+{
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+/// Next packet, reading more from the socket until a whole one is buffered
+async fn read(reader: &mut OwnedReadHalf, buffer: &mut BytesMut) -> Result<Packet>
+{
+    loop
+    {
+        match v4::read(buffer, MAX_PACKET)
+        {
+            Ok(packet) => return Ok(packet),
+            Err(mqttbytes::Error::InsufficientBytes(_)) => {}
+            Err(e) => return Err(invalid(&e.to_string())),
+        }
+        if reader.read_buf(buffer).await? == 0 { return Err(Error::new(ErrorKind::UnexpectedEof, "connection closed")); }
+    }
+}
+
+/// Encode and send the packets a broker sends
+async fn write(writer: &mut OwnedWriteHalf, packet: &Packet) -> Result<()>
+{
+    let mut buffer = BytesMut::new();
+    let encoded = match packet
+    {
+        Packet::ConnAck(connack) => connack.write(&mut buffer),
+        Packet::Publish(publish) => publish.write(&mut buffer),
+        Packet::PubAck(puback) => puback.write(&mut buffer),
+        Packet::PubRec(pubrec) => pubrec.write(&mut buffer),
+        Packet::PubComp(pubcomp) => pubcomp.write(&mut buffer),
+        Packet::SubAck(suback) => suback.write(&mut buffer),
+        Packet::UnsubAck(unsuback) => unsuback.write(&mut buffer),
+        Packet::PingResp => PingResp.write(&mut buffer),
+        other => return Err(invalid(&format!("{:?} is not sent by a broker", other))),
+    };
+    encoded.map_err(|e| invalid(&e.to_string()))?;
+    writer.write_all(&buffer).await
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Time until the broker closes the socket
+    async fn closed_after(stream: &mut TcpStream) -> Duration
+    {
+        let started = tokio::time::Instant::now();
+        let mut bytes = [0u8; 64];
+        loop
+        {
+            match timeout(Duration::from_secs(5), stream.read(&mut bytes)).await.expect("the broker keeps the socket open")
+            {
+                Ok(0) | Err(_) => return started.elapsed(),
+                Ok(_) => {}
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn a_silent_socket_is_closed_before_and_after_connect()
+    {
+        let address = Broker::new(None, QUEUE, Duration::from_millis(200)).listen("127.0.0.1:0").await.unwrap();
+
+        let mut silent = TcpStream::connect(address).await.unwrap();
+        assert!(closed_after(&mut silent).await < Duration::from_secs(2), "no CONNECT in time");
+
+        // Keepalive 1 s: the broker waits 1.5 s for a packet
+        let mut connect = v4::Connect::new("test-idle");
+        connect.keep_alive = 1;
+        let mut buffer = BytesMut::new();
+        connect.write(&mut buffer).unwrap();
+        let mut idle = TcpStream::connect(address).await.unwrap();
+        idle.write_all(&buffer).await.unwrap();
+        let elapsed = closed_after(&mut idle).await;
+        assert!(elapsed >= Duration::from_millis(1400) && elapsed < Duration::from_secs(4), "{:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn a_client_that_does_not_keep_up_is_dropped()
+    {
+        let broker = Broker::new(None, 2, CONNECT_TIMEOUT);
+        let (sender, _outgoing) = mpsc::channel(2);
+        let overflow = Arc::new(Notify::new());
+        broker.state.lock().unwrap().clients.insert(1, Client { filters: vec!["iot/#".to_string()], sender, overflow: overflow.clone() });
+
+        broker.route(Publish::new("iot/1/3/state", QoS::AtMostOnce, "1"));
+        broker.route(Publish::new("other/topic", QoS::AtMostOnce, "1"));
+        broker.route(Publish::new("iot/1/3/state", QoS::AtMostOnce, "2"));
+        assert!(broker.state.lock().unwrap().clients.contains_key(&1), "two queued packets fit");
+
+        broker.route(Publish::new("iot/1/3/state", QoS::AtMostOnce, "3"));
+        assert!(!broker.state.lock().unwrap().clients.contains_key(&1));
+        timeout(Duration::from_secs(1), overflow.notified()).await.expect("the session is told to end");
+    }
+}
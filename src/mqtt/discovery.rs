@@ -0,0 +1,339 @@
+//--------------------------------------------------------------------------------- Location
+// src/mqtt/discovery.rs
+
+//--------------------------------------------------------------------------------- Description
+// Home Assistant MQTT discovery: GPIO OUT devices become switches, GPIO IN devices binary sensors,
+// FILE, SPI and polled I2C / UART devices sensors, I2C / UART outputs (expander pins, coils)
+// switches, GPIO PWM devices selects of their commands and zone commands buttons, with zones as
+// HA areas.
+// Configs are rebuilt from the database on every tick, so rows added, changed or removed through
+// the API show up in HA without YAML or a restart
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use rumqttc::{AsyncClient, QoS};
+use sea_orm::DatabaseConnection;
+use serde_json::{json, Value};
+use tokio::sync::Notify;
+use tracing::{debug, error, info, warn};
+use crate::logics::error::ApiError;
+use crate::hardware::i2c::I2cTarget;
+use crate::hardware::uart::modbus::RegisterMap;
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, port::PortORM, zone::ZoneORM, zone_command::ZoneCommandORM};
+use crate::orm::models::device::Model as DeviceModel;
+use crate::orm::models::device_command::Model as DeviceCommandModel;
+use crate::orm::models::port::Model as PortModel;
+use crate::orm::models::zone::Model as ZoneModel;
+use crate::orm::models::zone_command::Model as ZoneCommandModel;
+use super::{run_topic, set_topic, state_topic, MqttConfig};
+
+//--------------------------------------------------------------------------------- Component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component
+{
+    Switch,
+    BinarySensor,
+    Sensor,
+    Select,
+    Button,
+}
+
+impl Component
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self
+        {
+            Component::Switch => "switch",
+            Component::BinarySensor => "binary_sensor",
+            Component::Sensor => "sensor",
+            Component::Select => "select",
+            Component::Button => "button",
+        }
+    }
+
+    /// Entity type of a device, from its port and, on a bus, its address; power and ground ports
+    /// and unparsable bus addresses have none
+    pub fn of_device(port: &PortModel, device: &DeviceModel) -> Option<Self>
+    {
+        let of_input = |input: bool| if input { Component::Sensor } else { Component::Switch };
+        match (port.protocol.to_lowercase().as_str(), port.r#type.to_lowercase().as_str())
+        {
+            ("pwr" | "gnd", _) => None,
+            ("gpio", "out") => Some(Component::Switch),
+            ("gpio", "in") => Some(Component::BinarySensor),
+            ("gpio", "pwm") => Some(Component::Select),
+            ("i2c", _) => I2cTarget::from_address(&device.address).ok().map(|target| of_input(target.is_input())),
+            ("uart", _) => RegisterMap::from_address(&device.address).ok().map(|map| of_input(map.is_input())),
+            ("spi" | "file", _) => Some(Component::Sensor),
+            (_, "in") => Some(Component::Sensor),
+            _ => None,
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct Discovery
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub prefix: String,
+    pub discovery_prefix: String,
+    pub node_id: String,
+    pub interval: Duration,
+    client: AsyncClient,
+    db: DatabaseConnection,
+    /// Config payloads already on the broker, by discovery topic
+    published: Arc<Mutex<HashMap<String, String>>>,
+    refresh: Arc<Notify>,
+}
+
+impl Discovery
+{
+    //------------------------- New
+    pub fn new(config: &MqttConfig, client: AsyncClient, db: DatabaseConnection) -> Self
+    {
+        // The node id is part of every discovery topic and unique id, HA only accepts [a-zA-Z0-9_-] there
+        let node_id = config.client_id.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' }).collect();
+
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "Discovery".to_string(),
+            prefix: config.prefix.clone(),
+            discovery_prefix: config.discovery_prefix.clone(),
+            node_id,
+            interval: config.discovery_interval,
+            client,
+            db,
+            published: Arc::new(Mutex::new(HashMap::new())),
+            refresh: Arc::new(Notify::new()),
+        }
+    }
+
+    //------------------------- Spawn
+    /// Start the background task that syncs the configs on every tick and on refresh
+    pub fn spawn(&self)
+    {
+        let discovery = self.clone();
+
+        tokio::spawn(async move
+        {
+            let mut ticker = tokio::time::interval(discovery.interval);
+            loop
+            {
+                tokio::select!
+                {
+                    _ = ticker.tick() => {}
+                    _ = discovery.refresh.notified() => {}
+                }
+                discovery.sync().await;
+            }
+        });
+    }
+
+    //------------------------- Refresh
+    /// Publish every config again, after a (re)connect or when Home Assistant comes back online
+    pub fn refresh(&self)
+    {
+        self.published.lock().unwrap().clear();
+        self.refresh.notify_one();
+    }
+
+    /// Home Assistant announces itself here ("online") after a restart
+    pub fn status_topic(&self) -> String
+    {
+        format!("{}/status", self.discovery_prefix)
+    }
+
+    //------------------------- Sync
+    /// Publish new and changed configs and clear the ones whose rows are gone
+    async fn sync(&self)
+    {
+        let this_method = "sync";
+        let configs = match self.configs().await
+        {
+            Ok(configs) => configs,
+            Err(e) => { error!("{}::{} - Cannot load the entities: {}", self.this_class, this_method, e); return; }
+        };
+
+        let (changed, removed) =
+        {
+            let published = self.published.lock().unwrap();
+            let changed = configs.iter().filter(|(topic, payload)| published.get(*topic) != Some(*payload)).map(|(topic, payload)| (topic.clone(), payload.clone())).collect::<Vec<_>>();
+            let removed = published.keys().filter(|topic| !configs.contains_key(*topic)).cloned().collect::<Vec<_>>();
+            (changed, removed)
+        };
+        if changed.is_empty() && removed.is_empty() { return; }
+
+        // An empty retained config removes the entity from Home Assistant
+        for (topic, payload) in changed.iter().cloned().chain(removed.iter().map(|topic| (topic.clone(), String::new())))
+        {
+            match self.client.publish(&topic, QoS::AtLeastOnce, true, payload.clone()).await
+            {
+                Ok(()) =>
+                {
+                    let mut published = self.published.lock().unwrap();
+                    if payload.is_empty() { published.remove(&topic); } else { published.insert(topic, payload); }
+                }
+                Err(e) => warn!("{}::{} - Cannot publish {}: {}", self.this_class, this_method, topic, e),
+            }
+        }
+        if self.verbose { info!("{}::{} - {} config(s) published, {} removed", self.this_class, this_method, changed.len(), removed.len()); }
+    }
+
+    //------------------------- Configs
+    /// Discovery topic and config payload of every entity
+    async fn configs(&self) -> Result<HashMap<String, String>, ApiError>
+    {
+        let enabled = || HashMap::from([("enable".to_string(), "true".to_string())]);
+        let zones: HashMap<i32, ZoneModel> = ZoneORM::new(false, self.log).items(&self.db, enabled()).await.into_result()?
+            .into_iter().map(|zone| (zone.id, zone)).collect();
+        let ports: HashMap<i32, PortModel> = PortORM::new(false, self.log).items(&self.db, HashMap::new()).await.into_result()?
+            .into_iter().map(|port| (port.id, port)).collect();
+        let devices = DeviceORM::new(false, self.log).items(&self.db, enabled()).await.into_result()?;
+        let commands = DeviceCommandORM::new(false, self.log).items(&self.db, enabled()).await.into_result()?;
+        let zone_commands = ZoneCommandORM::new(false, self.log).items(&self.db, enabled()).await.into_result()?;
+
+        let mut configs = HashMap::new();
+        for device in &devices
+        {
+            let (Some(zone), Some(port)) = (zones.get(&device.zone_id), ports.get(&device.port_id)) else { continue; };
+            let Some(component) = Component::of_device(port, device) else { continue; };
+
+            let device_commands = commands.iter().filter(|command| command.device_id == device.id).collect::<Vec<_>>();
+            let config = self.device_config(component, device, zone, port, &device_commands);
+            configs.insert(self.topic(component, &format!("device_{}", device.id)), config.to_string());
+        }
+        for zone_command in &zone_commands
+        {
+            let Some(zone) = zones.get(&zone_command.zone_id) else { continue; };
+            configs.insert(self.topic(Component::Button, &format!("zone_command_{}", zone_command.id)), self.button_config(zone_command, zone).to_string());
+        }
+
+        if self.verbose { debug!("{}::configs - {} entities", self.this_class, configs.len()); }
+        Ok(configs)
+    }
+
+    fn topic(&self, component: Component, object_id: &str) -> String
+    {
+        format!("{}/{}/{}/{}/config", self.discovery_prefix, component.as_str(), self.node_id, object_id)
+    }
+
+    //------------------------- Device Config
+    /// The entity takes the device's name; its HA device sits in the zone's area
+    fn device_config(&self, component: Component, device: &DeviceModel, zone: &ZoneModel, port: &PortModel, commands: &[&DeviceCommandModel]) -> Value
+    {
+        let mut config = json!({
+            "name": null,
+            "unique_id": format!("{}_device_{}", self.node_id, device.id),
+            "state_topic": state_topic(&self.prefix, device.zone_id, device.id),
+            "value_template": "{{ value_json.value }}",
+            "device": {
+                "identifiers": [format!("{}_device_{}", self.node_id, device.id)],
+                "name": device.name,
+                "model": format!("{} {}", port.protocol, port.r#type),
+                "manufacturer": "raspberrypi_iot_core_rust",
+                "suggested_area": zone.name,
+            },
+        });
+
+        match component
+        {
+            Component::Switch =>
+            {
+                config["command_topic"] = json!(set_topic(&self.prefix, device.zone_id, device.id));
+                config["payload_on"] = json!(switch_command(commands, true));
+                config["payload_off"] = json!(switch_command(commands, false));
+                config["state_on"] = json!("1");
+                config["state_off"] = json!("0");
+            }
+            Component::BinarySensor =>
+            {
+                config["payload_on"] = json!("1");
+                config["payload_off"] = json!("0");
+            }
+            Component::Sensor => config["state_class"] = json!("measurement"),
+            // A duty cycle has more than two levels: the commands (off, dim, on...) are the options
+            Component::Select =>
+            {
+                config["command_topic"] = json!(set_topic(&self.prefix, device.zone_id, device.id));
+                config["options"] = json!(commands.iter().map(|command| command.name.clone()).collect::<Vec<_>>());
+                config["value_template"] = json!("{{ value_json.command }}");
+            }
+            Component::Button => {}
+        }
+        config
+    }
+
+    //------------------------- Button Config
+    /// Zone commands are grouped under one HA device per zone
+    fn button_config(&self, zone_command: &ZoneCommandModel, zone: &ZoneModel) -> Value
+    {
+        json!({
+            "name": zone_command.name,
+            "unique_id": format!("{}_zone_command_{}", self.node_id, zone_command.id),
+            "command_topic": run_topic(&self.prefix, zone.id, zone_command.id),
+            "payload_press": "run",
+            "device": {
+                "identifiers": [format!("{}_zone_{}", self.node_id, zone.id)],
+                "name": zone.name,
+                "manufacturer": "raspberrypi_iot_core_rust",
+                "suggested_area": zone.name,
+            },
+        })
+    }
+}
+
+//--------------------------------------------------------------------------------- Switch Command
+/// Name of the command that leaves the device on (non-zero) or off (zero), preferring the ones called on and off
+fn switch_command(commands: &[&DeviceCommandModel], on: bool) -> String
+{
+    let preferred = if on { "on" } else { "off" };
+    let leaves = |command: &&&DeviceCommandModel| (command.value_to.or(command.value_from).unwrap_or(0) != 0) == on;
+
+    commands.iter().filter(leaves).find(|command| command.name.eq_ignore_ascii_case(preferred))
+        .or_else(|| commands.iter().find(leaves))
+        .map(|command| command.name.clone())
+        .unwrap_or_else(|| preferred.to_string())
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn port(protocol: &str, r#type: &str) -> PortModel
+    {
+        PortModel { id: 1, user_id: 1, name: "P".to_string(), pin: 3, port: 2, value: 0, description: String::new(), enable: true, protocol: protocol.to_string(), r#type: r#type.to_string() }
+    }
+
+    fn device(address: &str) -> DeviceModel
+    {
+        DeviceModel { id: 1, zone_id: 1, port_id: 1, power_id: 1, command_id: 1, value: 0, tune: 0, date: String::new(), address: address.to_string(), name: "D".to_string(), description: String::new(), enable: true }
+    }
+
+    #[test]
+    fn maps_every_device_to_an_entity()
+    {
+        let of = |protocol: &str, r#type: &str, address: &str| Component::of_device(&port(protocol, r#type), &device(address));
+
+        assert_eq!(of("GPIO", "OUT", ""), Some(Component::Switch));
+        assert_eq!(of("GPIO", "IN", ""), Some(Component::BinarySensor));
+        assert_eq!(of("GPIO", "PWM", ""), Some(Component::Select));
+        assert_eq!(of("FILE", "IN", "28-0922a03b54a4"), Some(Component::Sensor));
+        assert_eq!(of("I2C", "BUS", "bme280:0x76:temperature"), Some(Component::Sensor));
+        assert_eq!(of("I2C", "BUS", "pcf8574:0x20:3:out"), Some(Component::Switch));
+        assert_eq!(of("I2C", "BUS", "nonsense"), None);
+        assert_eq!(of("SPI", "BUS", "mcp3008:0:1"), Some(Component::Sensor));
+        assert_eq!(of("UART", "BUS", "unit=1,read=holding:0x0001"), Some(Component::Sensor));
+        assert_eq!(of("UART", "BUS", "unit=1,write=coil:0"), Some(Component::Switch));
+        assert_eq!(of("PWR", "IN", ""), None);
+    }
+}
@@ -0,0 +1,249 @@
+//--------------------------------------------------------------------------------- Location
+// src/mqtt/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// MQTT bridge: device state goes out on {prefix}/{zone_id}/{device_id}/state (retained), device
+// command names come in on {prefix}/{zone_id}/{device_id}/set and zone commands are run from
+// {prefix}/{zone_id}/zone_command/{id}/run. Connects to an external broker or starts the embedded
+// one, so the bridge also runs with no other service around; Home Assistant discovery is optional.
+// The topics have no user behind them, so roles and zone grants do not apply: whoever may publish
+// to the broker drives every device. The embedded broker therefore only listens without a login
+// on a loopback host
+
+//--------------------------------------------------------------------------------- Import
+use std::time::Duration;
+use tracing::{error, info};
+use crate::engine::executor::Executor;
+use crate::engine::rule::RuleEngine;
+use self::bridge::MqttBridge;
+use self::broker::Broker;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod bridge;
+pub mod broker;
+pub mod discovery;
+
+//--------------------------------------------------------------------------------- Config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MqttMode
+{
+    Off,
+    External,
+    Embedded,
+}
+
+#[derive(Debug, Clone)]
+pub struct MqttConfig
+{
+    pub mode: MqttMode,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub client_id: String,
+    pub prefix: String,
+    pub discovery: bool,
+    pub discovery_prefix: String,
+    pub discovery_interval: Duration,
+}
+
+impl MqttConfig
+{
+    //------------------------- From Env
+    pub fn from_env() -> Self
+    {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let mode = match var("MQTT_MODE").unwrap_or_default().to_lowercase().as_str()
+        {
+            "external" => MqttMode::External,
+            "embedded" => MqttMode::Embedded,
+            _ => MqttMode::Off,
+        };
+
+        Self
+        {
+            mode,
+            host: var("MQTT_HOST").unwrap_or_else(|| "127.0.0.1".to_string()),
+            port: var("MQTT_PORT").and_then(|port| port.parse().ok()).unwrap_or(1883),
+            username: var("MQTT_USERNAME"),
+            password: var("MQTT_PASSWORD"),
+            client_id: var("MQTT_CLIENT_ID").unwrap_or_else(|| "raspberrypi_iot_core_rust".to_string()),
+            prefix: var("MQTT_TOPIC_PREFIX").unwrap_or_else(|| "iot".to_string()).trim_matches('/').to_string(),
+            discovery: var("MQTT_DISCOVERY").is_some_and(|value| value == "true"),
+            discovery_prefix: var("MQTT_DISCOVERY_PREFIX").unwrap_or_else(|| "homeassistant".to_string()).trim_matches('/').to_string(),
+            discovery_interval: Duration::from_secs(var("MQTT_DISCOVERY_INTERVAL").and_then(|value| value.parse().ok()).unwrap_or(60u64).max(1)),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Start
+/// Start the embedded broker when asked for, then the bridge; nothing runs when MQTT is off or the broker cannot listen
+pub async fn start(mut config: MqttConfig, executor: Executor, rules: RuleEngine)
+{
+    match config.mode
+    {
+        MqttMode::Off => return,
+        MqttMode::External => {}
+        MqttMode::Embedded =>
+        {
+            if !is_loopback(&config.host) && (config.username.is_none() || config.password.is_none())
+            {
+                error!("MQTT broker: MQTT_USERNAME and MQTT_PASSWORD are required to listen on {}, the bridge topics bypass user roles", config.host);
+                return;
+            }
+            let login = config.username.clone().map(|username| (username, config.password.clone().unwrap_or_default()));
+            match Broker::bind(&format!("{}:{}", config.host, config.port), login).await
+            {
+                Ok(address) =>
+                {
+                    info!("MQTT broker: embedded on {}", address);
+                    config.port = address.port();
+                }
+                Err(e) =>
+                {
+                    error!("MQTT broker: cannot listen on {}:{} ({})", config.host, config.port, e);
+                    return;
+                }
+            }
+        }
+    }
+
+    info!("MQTT bridge: {}:{} under '{}/'", config.host, config.port, config.prefix);
+    MqttBridge::spawn(config, executor, rules);
+}
+
+/// Only this machine can reach the host
+fn is_loopback(host: &str) -> bool
+{
+    host.eq_ignore_ascii_case("localhost") || host.trim_matches(['[', ']']).parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+//--------------------------------------------------------------------------------- Topics
+pub fn state_topic(prefix: &str, zone_id: i32, device_id: i32) -> String
+{
+    format!("{}/{}/{}/state", prefix, zone_id, device_id)
+}
+
+pub fn set_topic(prefix: &str, zone_id: i32, device_id: i32) -> String
+{
+    format!("{}/{}/{}/set", prefix, zone_id, device_id)
+}
+
+pub fn run_topic(prefix: &str, zone_id: i32, zone_command_id: i32) -> String
+{
+    format!("{}/{}/zone_command/{}/run", prefix, zone_id, zone_command_id)
+}
+
+/// What an incoming message on the bridge topics asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopicRequest
+{
+    Set { zone_id: i32, device_id: i32 },
+    Run { zone_id: i32, zone_command_id: i32 },
+}
+
+pub fn parse_topic(prefix: &str, topic: &str) -> Option<TopicRequest>
+{
+    let parts = topic.strip_prefix(prefix)?.strip_prefix('/')?.split('/').collect::<Vec<_>>();
+    match parts.as_slice()
+    {
+        [zone_id, device_id, "set"] => Some(TopicRequest::Set { zone_id: zone_id.parse().ok()?, device_id: device_id.parse().ok()? }),
+        [zone_id, "zone_command", zone_command_id, "run"] => Some(TopicRequest::Run { zone_id: zone_id.parse().ok()?, zone_command_id: zone_command_id.parse().ok()? }),
+        _ => None,
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use rumqttc::{AsyncClient, Event as MqttEvent, MqttOptions, Packet, QoS};
+    use sea_orm::Database;
+    use serde_json::Value;
+    use tokio::sync::mpsc;
+    use crate::engine::events::EventBus;
+    use crate::hardware::{gpio::{MockPinDriver, PinDriver}, Buses};
+    use super::*;
+
+    #[test]
+    fn parses_the_bridge_topics()
+    {
+        assert_eq!(parse_topic("iot", "iot/1/3/set"), Some(TopicRequest::Set { zone_id: 1, device_id: 3 }));
+        assert_eq!(parse_topic("iot", "iot/2/zone_command/7/run"), Some(TopicRequest::Run { zone_id: 2, zone_command_id: 7 }));
+        assert_eq!(parse_topic("iot", "iot/1/3/state"), None);
+        assert_eq!(parse_topic("iot", "iot/x/3/set"), None);
+        assert_eq!(parse_topic("iot", "other/1/3/set"), None);
+        assert_eq!(parse_topic("iot", "iota/1/3/set"), None);
+    }
+
+    #[test]
+    fn only_loopback_hosts_go_without_a_login()
+    {
+        assert!(is_loopback("127.0.0.1") && is_loopback("localhost") && is_loopback("::1") && is_loopback("[::1]"));
+        assert!(!is_loopback("0.0.0.0") && !is_loopback("192.168.1.10") && !is_loopback("raspberrypi.local"));
+    }
+
+    /// Client that forwards every received publish as (topic, payload)
+    async fn client(id: &str, port: u16, topic: &str) -> (AsyncClient, mpsc::UnboundedReceiver<(String, Value)>)
+    {
+        let (client, mut eventloop) = AsyncClient::new(MqttOptions::new(id, "127.0.0.1", port), 16);
+        client.subscribe(topic, QoS::AtLeastOnce).await.unwrap();
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move
+        {
+            while let Ok(event) = eventloop.poll().await
+            {
+                if let MqttEvent::Incoming(Packet::Publish(publish)) = event
+                {
+                    let payload = serde_json::from_slice(&publish.payload).unwrap_or(Value::Null);
+                    if sender.send((publish.topic, payload)).is_err() { break; }
+                }
+            }
+        });
+        (client, receiver)
+    }
+
+    /// First state that passes the check
+    async fn state(receiver: &mut mpsc::UnboundedReceiver<(String, Value)>, check: impl Fn(&Value) -> bool) -> Value
+    {
+        tokio::time::timeout(Duration::from_secs(10), async
+        {
+            loop
+            {
+                let (_, payload) = receiver.recv().await.expect("client stopped");
+                if check(&payload) { return payload; }
+            }
+        }).await.expect("no matching state")
+    }
+
+    #[tokio::test]
+    async fn set_topic_runs_the_command_and_retains_the_state()
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+
+        let address = Broker::bind("127.0.0.1:0", None).await.unwrap();
+        let pins: Arc<dyn PinDriver> = Arc::new(MockPinDriver::new());
+        let executor = Executor::new(db, pins.clone(), Buses::mock(pins), EventBus::new(64));
+        let rules = RuleEngine::new(executor.clone());
+        let mut config = MqttConfig::from_env();
+        (config.mode, config.host, config.port, config.prefix, config.discovery) = (MqttMode::Embedded, "127.0.0.1".to_string(), address.port(), "iot".to_string(), false);
+        MqttBridge::spawn(config, executor, rules);
+
+        // Sample device 3 is the Light of zone 1, its "on" command is 5; the bridge publishes every state once connected
+        let (control, mut states) = client("test-control", address.port(), "iot/1/3/state").await;
+        state(&mut states, |payload| payload["device_id"] == 3).await;
+        control.publish("iot/1/3/set", QoS::AtLeastOnce, false, "on").await.unwrap();
+        let live = state(&mut states, |payload| payload["command_id"] == 5).await;
+        assert_eq!((live["zone_id"].as_i64(), live["command"].as_str()), (Some(1), Some("on")));
+
+        // A client that subscribes afterwards gets the retained state
+        let (_late, mut retained) = client("test-late", address.port(), "iot/1/3/state").await;
+        let (topic, payload) = tokio::time::timeout(Duration::from_secs(10), retained.recv()).await.unwrap().unwrap();
+        assert_eq!((topic.as_str(), payload["command_id"].as_i64()), ("iot/1/3/state", Some(5)));
+    }
+}
@@ -7,9 +7,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::config::{ActiveModel as ConfigActiveModel, Entity as ConfigEntity, Model as ConfigModel, Column as ConfigColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 //--------------------------------------------------------------------------------- Class
 pub struct ConfigORM 
@@ -51,7 +52,7 @@ impl ConfigORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -104,12 +105,18 @@ impl ConfigORM
             query = query.filter(condition);
         }
 
-        match query.order_by_asc(ConfigColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, ConfigColumn::Id) 
         {
-            Ok(items) => 
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((items, total)) => 
             {
                 let message = if filters.is_empty() { "Configs retrieved successfully".to_string() } else { format!("Filtered configs retrieved successfully (found {} items)", items.len()) };
-                let output = ModelOutput::success(items, message);
+                let output = ModelOutput::success(items, message).with_total(total);
                 if self.verbose { info!("{}::{} - Success: Configs retrieved", self.this_class, this_method); }
                 if self.log { info!("LOG: {}::{} - Configs retrieved", self.this_class, this_method); }
                 output
@@ -117,7 +124,7 @@ impl ConfigORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -142,14 +149,14 @@ impl ConfigORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("Config not found".to_string());
+                let output = ModelOutput::not_found("Config not found".to_string());
                 if self.verbose { info!("{}::{} - Config {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -175,7 +182,7 @@ impl ConfigORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -202,7 +209,7 @@ impl ConfigORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("Config not found".to_string());
+                    let output = ModelOutput::not_found("Config not found".to_string());
                     if self.verbose { info!("{}::{} - Config {} not found", self.this_class, this_method, id); }
                     output
                 }
@@ -210,7 +217,7 @@ impl ConfigORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -246,15 +253,15 @@ impl ConfigORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Config not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Config not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
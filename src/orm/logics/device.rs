@@ -7,9 +7,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::device::{ActiveModel as DeviceActiveModel, Entity as DeviceEntity, Model as DeviceModel, Column as DeviceColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 //--------------------------------------------------------------------------------- Class
 pub struct DeviceORM 
@@ -51,7 +52,7 @@ impl DeviceORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -61,11 +62,18 @@ impl DeviceORM
 
     //------------------------- Items
     pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<DeviceModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<DeviceModel>> 
     {
         let this_method = "items";
         if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
 
-        let mut query = DeviceEntity::find();
+        let mut query = DeviceEntity::find().filter(scope);
         if !filters.is_empty() {
             let mut condition = Condition::all();
 
@@ -100,12 +108,18 @@ impl DeviceORM
             query = query.filter(condition);
         }
 
-        match query.order_by_asc(DeviceColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, DeviceColumn::Id) 
+        {
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
         {
-            Ok(items) => 
+            Ok((items, total)) => 
             {
                 let message = if filters.is_empty() { "Devices retrieved successfully".to_string() } else { format!("Filtered devices retrieved successfully (found {} items)", items.len()) };
-                let output = ModelOutput::success(items, message);
+                let output = ModelOutput::success(items, message).with_total(total);
                 if self.verbose { info!("{}::{} - Success: Devices retrieved", self.this_class, this_method); }
                 if self.log { info!("LOG: {}::{} - Devices retrieved", self.this_class, this_method); }
                 output
@@ -113,7 +127,7 @@ impl DeviceORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -138,14 +152,14 @@ impl DeviceORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("Device not found".to_string());
+                let output = ModelOutput::not_found("Device not found".to_string());
                 if self.verbose { info!("{}::{} - Device {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -171,7 +185,7 @@ impl DeviceORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -198,7 +212,7 @@ impl DeviceORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("Device not found".to_string());
+                    let output = ModelOutput::not_found("Device not found".to_string());
                     if self.verbose { info!("{}::{} - Device {} not found", self.this_class, this_method, id); }
                     output
                 }
@@ -206,7 +220,7 @@ impl DeviceORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -239,7 +253,7 @@ impl DeviceORM
                     Err(e) =>
                     {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         output
                     }
@@ -247,14 +261,14 @@ impl DeviceORM
             }
             Ok(None) =>
             {
-                let output = ModelOutput::error("Device not found".to_string());
+                let output = ModelOutput::not_found("Device not found".to_string());
                 if self.verbose { info!("{}::{} - Device {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) =>
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 output
             }
@@ -286,7 +300,7 @@ impl DeviceORM
                     Err(e) =>
                     {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         output
                     }
@@ -294,14 +308,14 @@ impl DeviceORM
             }
             Ok(None) =>
             {
-                let output = ModelOutput::error("Device not found".to_string());
+                let output = ModelOutput::not_found("Device not found".to_string());
                 if self.verbose { info!("{}::{} - Device {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) =>
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 output
             }
@@ -341,15 +355,15 @@ pub async fn status(&self, db: &DbConn, id: i32) -> ModelOutput<DeviceModel>
                 Err(e) => {
                     let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                     error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                    ModelOutput::error(error_msg)
+                    ModelOutput::db_error(error_msg, &e)
                 }
             }
         }
-        Ok(None) => ModelOutput::error("Zone not found".to_string()),
+        Ok(None) => ModelOutput::not_found("Zone not found".to_string()),
         Err(e) => {
             let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
             error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-            ModelOutput::error(error_msg)
+            ModelOutput::db_error(error_msg, &e)
         }
     }
 }
@@ -7,9 +7,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::device_command::{ActiveModel as DeviceCommandActiveModel, Entity as DeviceCommandEntity, Model as DeviceCommandModel, Column as DeviceCommandColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 //--------------------------------------------------------------------------------- Class
 pub struct DeviceCommandORM 
@@ -51,7 +52,7 @@ impl DeviceCommandORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -61,11 +62,18 @@ impl DeviceCommandORM
 
     //------------------------- Items
     pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<DeviceCommandModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<DeviceCommandModel>> 
     {
         let this_method = "items";
         if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
 
-        let mut query = DeviceCommandEntity::find();
+        let mut query = DeviceCommandEntity::find().filter(scope);
         if !filters.is_empty() {
             let mut condition = Condition::all();
 
@@ -75,6 +83,7 @@ impl DeviceCommandORM
             if let Some(value_from_str) = filters.get("value_from") { if let Ok(value_from) = value_from_str.parse::<i32>() { condition = condition.add(DeviceCommandColumn::ValueFrom.eq(Some(value_from))); } }
             if let Some(value_to_str) = filters.get("value_to") { if let Ok(value_to) = value_to_str.parse::<i32>() { condition = condition.add(DeviceCommandColumn::ValueTo.eq(Some(value_to))); } }
             if let Some(delay_str) = filters.get("delay") { if let Ok(delay) = delay_str.parse::<i32>() { condition = condition.add(DeviceCommandColumn::Delay.eq(Some(delay))); } }
+            if let Some(frequency_str) = filters.get("frequency") && let Ok(frequency) = frequency_str.parse::<i32>() { condition = condition.add(DeviceCommandColumn::Frequency.eq(Some(frequency))); }
             if let Some(description) = filters.get("description") { condition = condition.add(DeviceCommandColumn::Description.contains(description)); }
             if let Some(reload_str) = filters.get("reload") { if let Ok(reload) = reload_str.parse::<bool>() { condition = condition.add(DeviceCommandColumn::Reload.eq(reload)); } }
             if let Some(enable_str) = filters.get("enable") { if let Ok(enable) = enable_str.parse::<bool>() { condition = condition.add(DeviceCommandColumn::Enable.eq(enable)); } }
@@ -83,12 +92,18 @@ impl DeviceCommandORM
             query = query.filter(condition);
         }
 
-        match query.order_by_asc(DeviceCommandColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, DeviceCommandColumn::Id) 
         {
-            Ok(items) => 
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((items, total)) => 
             {
                 let message = if filters.is_empty() { "DeviceCommands retrieved successfully".to_string() } else { format!("Filtered device commands retrieved successfully (found {} items)", items.len()) };
-                let output = ModelOutput::success(items, message);
+                let output = ModelOutput::success(items, message).with_total(total);
                 if self.verbose { info!("{}::{} - Success: DeviceCommands retrieved", self.this_class, this_method); }
                 if self.log { info!("LOG: {}::{} - DeviceCommands retrieved", self.this_class, this_method); }
                 output
@@ -96,7 +111,7 @@ impl DeviceCommandORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -121,14 +136,50 @@ impl DeviceCommandORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("DeviceCommand not found".to_string());
+                let output = ModelOutput::not_found("DeviceCommand not found".to_string());
                 if self.verbose { info!("{}::{} - DeviceCommand {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Item By Name
+    pub async fn item_by_name(&self, db: &DbConn, device_id: i32, name: &str) -> ModelOutput<DeviceCommandModel>
+    {
+        let this_method = "item_by_name";
+        if self.verbose { debug!("{}::{} - Starting item_by_name operation for device {}: {}", self.this_class, this_method, device_id, name); }
+
+        let query = DeviceCommandEntity::find()
+            .filter(DeviceCommandColumn::DeviceId.eq(device_id))
+            .filter(DeviceCommandColumn::Name.eq(name));
+
+        match query.one(db).await
+        {
+            Ok(Some(model)) =>
+            {
+                let output = ModelOutput::success(model, "DeviceCommand retrieved successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: DeviceCommand {} found for device {}", self.this_class, this_method, name, device_id); }
+                if self.log { info!("LOG: {}::{} - DeviceCommand {} retrieved for device {}", self.this_class, this_method, name, device_id); }
+                output
+            }
+            Ok(None) =>
+            {
+                let output = ModelOutput::not_found("DeviceCommand not found".to_string());
+                if self.verbose { info!("{}::{} - DeviceCommand {} not found for device {}", self.this_class, this_method, name, device_id); }
+                output
+            }
+            Err(e) =>
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -154,7 +205,7 @@ impl DeviceCommandORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -181,7 +232,7 @@ impl DeviceCommandORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("DeviceCommand not found".to_string());
+                    let output = ModelOutput::not_found("DeviceCommand not found".to_string());
                     if self.verbose { info!("{}::{} - DeviceCommand {} not found", self.this_class, this_method, id); }
                     output
                 }
@@ -189,7 +240,7 @@ impl DeviceCommandORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -220,7 +271,7 @@ impl DeviceCommandORM
                     },
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                         output
@@ -228,13 +279,13 @@ impl DeviceCommandORM
                 }
             }
             Ok(None) => {
-                let output = ModelOutput::error("Device command not found".to_string());
+                let output = ModelOutput::not_found("Device command not found".to_string());
                 if self.verbose { info!("{}::{} - Device command {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -265,7 +316,7 @@ impl DeviceCommandORM
                     },
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                         output
@@ -273,13 +324,13 @@ impl DeviceCommandORM
                 }
             }
             Ok(None) => {
-                let output = ModelOutput::error("Device command not found".to_string());
+                let output = ModelOutput::not_found("Device command not found".to_string());
                 if self.verbose { info!("{}::{} - Device command {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -315,15 +366,15 @@ impl DeviceCommandORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Device command not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Device command not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -0,0 +1,302 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/graph.rs
+
+//--------------------------------------------------------------------------------- Description
+// Expanded zone and device responses (?expand=devices,commands,rules,timers), built on the model
+// relations. Each level is loaded with one query for all its parents, so the number of queries
+// depends on what is expanded, not on how many rows come back
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::BTreeSet;
+use tracing::{debug, error, info};
+use serde::Serialize;
+use utoipa::ToSchema;
+use sea_orm::{ColumnTrait, Condition, DbConn, DbErr, EntityTrait, LoaderTrait, QueryFilter, QueryOrder};
+use crate::logics::error::ApiError;
+use crate::logics::general::ModelOutput;
+use crate::orm::models::device::{Column as DeviceColumn, Entity as DeviceEntity, Model as DeviceModel};
+use crate::orm::models::device_command::{Entity as DeviceCommandEntity, Model as DeviceCommandModel};
+use crate::orm::models::timer::{Column as TimerColumn, Entity as TimerEntity, Model as TimerModel};
+use crate::orm::models::timer_device::{Column as TimerDeviceColumn, Entity as TimerDeviceEntity, Model as TimerDeviceModel};
+use crate::orm::models::timer_item::{Entity as TimerItemEntity, Model as TimerItemModel};
+use crate::orm::models::zone::Model as ZoneModel;
+use crate::orm::models::zone_command::{Column as ZoneCommandColumn, Entity as ZoneCommandEntity, Model as ZoneCommandModel};
+use crate::orm::models::zone_command_action::{Column as ZoneCommandActionColumn, Entity as ZoneCommandActionEntity, Model as ZoneCommandActionModel};
+use crate::orm::models::zone_command_if::{Column as ZoneCommandIfColumn, Entity as ZoneCommandIfEntity, Model as ZoneCommandIfModel};
+
+//--------------------------------------------------------------------------------- Expand
+/// Parts of the graph asked for; commands on a zone also bring its devices
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Expand
+{
+    pub devices: bool,
+    pub commands: bool,
+    pub rules: bool,
+    pub timers: bool,
+}
+
+impl Expand
+{
+    /// Comma separated list, empty or missing means nothing is expanded
+    pub fn parse(value: Option<&str>) -> Result<Self, ApiError>
+    {
+        let mut expand = Self::default();
+        for part in value.unwrap_or_default().split(',').map(str::trim).filter(|part| !part.is_empty())
+        {
+            match part.to_lowercase().as_str()
+            {
+                "devices" => expand.devices = true,
+                "commands" => expand.commands = true,
+                "rules" => expand.rules = true,
+                "timers" => expand.timers = true,
+                other => return Err(ApiError::Validation(format!("Invalid expand '{}'. Valid values: devices, commands, rules, timers", other))),
+            }
+        }
+        Ok(expand)
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        *self == Self::default()
+    }
+}
+
+//--------------------------------------------------------------------------------- Graph
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Zone with the expanded devices, rules and timers")]
+pub struct ZoneGraph
+{
+    #[serde(flatten)]
+    pub zone: ZoneModel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devices: Option<Vec<DeviceGraph>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<RuleGraph>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timers: Option<Vec<TimerGraph>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Device with the expanded commands, rules and timers")]
+pub struct DeviceGraph
+{
+    #[serde(flatten)]
+    pub device: DeviceModel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commands: Option<Vec<DeviceCommandModel>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<RuleGraph>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timers: Option<Vec<TimerGraph>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Zone command with its conditions and actions")]
+pub struct RuleGraph
+{
+    #[serde(flatten)]
+    pub command: ZoneCommandModel,
+    pub ifs: Vec<ZoneCommandIfModel>,
+    pub actions: Vec<ZoneCommandActionModel>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "Timer with its schedule items and driven devices")]
+pub struct TimerGraph
+{
+    #[serde(flatten)]
+    pub timer: TimerModel,
+    pub items: Vec<TimerItemModel>,
+    pub devices: Vec<TimerDeviceModel>,
+}
+
+impl ZoneGraph
+{
+    pub fn plain(zone: ZoneModel) -> Self
+    {
+        Self { zone, devices: None, rules: None, timers: None }
+    }
+}
+
+impl DeviceGraph
+{
+    pub fn plain(device: DeviceModel) -> Self
+    {
+        Self { device, commands: None, rules: None, timers: None }
+    }
+}
+
+//--------------------------------------------------------------------------------- Class
+pub struct GraphORM
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl GraphORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self
+    {
+        Self
+        {
+            verbose,
+            log,
+            this_class: "GraphORM".to_string(),
+            module: "graph".to_string(),
+        }
+    }
+
+    //------------------------- Zone
+    /// Expand a zone; timer_scope limits the timers to the ones the user may see
+    pub async fn zone(&self, db: &DbConn, zone: ZoneModel, expand: Expand, timer_scope: Condition) -> ModelOutput<ZoneGraph>
+    {
+        let this_method = "zone";
+        if self.verbose { debug!("{}::{} - Expanding zone {} with {:?}", self.this_class, this_method, zone.id, expand); }
+
+        let result = self.load_zone(db, zone, expand, timer_scope).await;
+        self.output(this_method, result)
+    }
+
+    //------------------------- Device
+    /// Expand a device; rule_scope and timer_scope limit the rules and timers to the ones the user may see
+    pub async fn device(&self, db: &DbConn, device: DeviceModel, expand: Expand, rule_scope: Condition, timer_scope: Condition) -> ModelOutput<DeviceGraph>
+    {
+        let this_method = "device";
+        if self.verbose { debug!("{}::{} - Expanding device {} with {:?}", self.this_class, this_method, device.id, expand); }
+
+        let result = self.load_device(db, device, expand, rule_scope, timer_scope).await;
+        self.output(this_method, result)
+    }
+
+    //------------------------- Output
+    fn output<T>(&self, this_method: &str, result: Result<T, DbErr>) -> ModelOutput<T>
+    {
+        match result
+        {
+            Ok(graph) =>
+            {
+                if self.verbose { info!("{}::{} - Success: graph loaded", self.this_class, this_method); }
+                ModelOutput::success(graph, "Graph retrieved successfully".to_string())
+            }
+            Err(e) =>
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Loaders
+    async fn load_zone(&self, db: &DbConn, zone: ZoneModel, expand: Expand, timer_scope: Condition) -> Result<ZoneGraph, DbErr>
+    {
+        let mut graph = ZoneGraph::plain(zone);
+
+        let devices = match expand.devices || expand.commands || expand.timers
+        {
+            true => DeviceEntity::find().filter(DeviceColumn::ZoneId.eq(graph.zone.id)).order_by_asc(DeviceColumn::Id).all(db).await?,
+            false => Vec::new(),
+        };
+
+        if expand.devices || expand.commands
+        {
+            graph.devices = Some(self.devices(db, devices.clone(), expand.commands).await?);
+        }
+        if expand.rules
+        {
+            let commands = ZoneCommandEntity::find().filter(ZoneCommandColumn::ZoneId.eq(graph.zone.id)).order_by_asc(ZoneCommandColumn::Id).all(db).await?;
+            graph.rules = Some(self.rules(db, commands).await?);
+        }
+        if expand.timers
+        {
+            let device_ids = devices.iter().map(|device| device.id).collect::<Vec<_>>();
+            graph.timers = Some(self.timers(db, device_ids, timer_scope).await?);
+        }
+        Ok(graph)
+    }
+
+    async fn load_device(&self, db: &DbConn, device: DeviceModel, expand: Expand, rule_scope: Condition, timer_scope: Condition) -> Result<DeviceGraph, DbErr>
+    {
+        let id = device.id;
+        let mut graph = self.devices(db, vec![device], expand.commands).await?.remove(0);
+
+        if expand.rules
+        {
+            // Rules that test or switch the device
+            let mut command_ids = ZoneCommandIfEntity::find().filter(ZoneCommandIfColumn::DeviceId.eq(id)).all(db).await?
+                .into_iter().map(|row| row.zone_command_id).collect::<BTreeSet<_>>();
+            command_ids.extend(ZoneCommandActionEntity::find().filter(ZoneCommandActionColumn::DeviceId.eq(id)).all(db).await?
+                .into_iter().map(|row| row.zone_command_id));
+
+            let commands = ZoneCommandEntity::find().filter(ZoneCommandColumn::Id.is_in(command_ids)).filter(rule_scope)
+                .order_by_asc(ZoneCommandColumn::Id).all(db).await?;
+            graph.rules = Some(self.rules(db, commands).await?);
+        }
+        if expand.timers
+        {
+            graph.timers = Some(self.timers(db, vec![id], timer_scope).await?);
+        }
+        Ok(graph)
+    }
+
+    /// Devices with their commands when asked, one query for all the commands
+    async fn devices(&self, db: &DbConn, devices: Vec<DeviceModel>, commands: bool) -> Result<Vec<DeviceGraph>, DbErr>
+    {
+        if !commands
+        {
+            return Ok(devices.into_iter().map(DeviceGraph::plain).collect());
+        }
+
+        let loaded = devices.load_many(DeviceCommandEntity, db).await?;
+        Ok(devices.into_iter().zip(loaded).map(|(device, commands)| DeviceGraph { commands: Some(commands), ..DeviceGraph::plain(device) }).collect())
+    }
+
+    /// Zone commands with their ifs and actions, one query per level
+    async fn rules(&self, db: &DbConn, commands: Vec<ZoneCommandModel>) -> Result<Vec<RuleGraph>, DbErr>
+    {
+        let ifs = commands.load_many(ZoneCommandIfEntity, db).await?;
+        let actions = commands.load_many(ZoneCommandActionEntity, db).await?;
+
+        Ok(commands.into_iter().zip(ifs).zip(actions).map(|((command, ifs), actions)| RuleGraph { command, ifs, actions }).collect())
+    }
+
+    /// Timers driving any of the devices, with their items and timer_device rows
+    async fn timers(&self, db: &DbConn, device_ids: Vec<i32>, timer_scope: Condition) -> Result<Vec<TimerGraph>, DbErr>
+    {
+        if device_ids.is_empty()
+        {
+            return Ok(Vec::new());
+        }
+
+        let timer_ids = TimerDeviceEntity::find().filter(TimerDeviceColumn::DeviceId.is_in(device_ids)).all(db).await?
+            .into_iter().map(|row| row.timer_id).collect::<BTreeSet<_>>();
+        let timers = TimerEntity::find().filter(TimerColumn::Id.is_in(timer_ids)).filter(timer_scope)
+            .order_by_asc(TimerColumn::Id).all(db).await?;
+
+        let items = timers.load_many(TimerItemEntity, db).await?;
+        let devices = timers.load_many(TimerDeviceEntity, db).await?;
+
+        Ok(timers.into_iter().zip(items).zip(devices).map(|((timer, items), devices)| TimerGraph { timer, items, devices }).collect())
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn expand_parses_a_comma_list()
+    {
+        assert!(Expand::parse(None).unwrap().is_empty());
+        assert!(Expand::parse(Some(" , ")).unwrap().is_empty());
+        assert_eq!(Expand::parse(Some("Devices, timers")).unwrap(), Expand { devices: true, timers: true, ..Default::default() });
+        assert_eq!(Expand::parse(Some("devices,commands,rules,timers")).unwrap(), Expand { devices: true, commands: true, rules: true, timers: true });
+        assert!(matches!(Expand::parse(Some("devices,ports")), Err(ApiError::Validation(message)) if message.contains("'ports'")));
+    }
+}
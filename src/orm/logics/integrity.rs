@@ -0,0 +1,356 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/integrity.rs
+
+//--------------------------------------------------------------------------------- Description
+// Referential checks on delete, built on the model relations. The database declares the same
+// foreign keys (db_postgres.sql and the foreign_keys migration), none where 0 means "none" or
+// "every" (device.power_id and command_id, the device_id of notifications), so a delete that
+// skipped these checks would fail there instead.
+// A row that is still referenced cannot be deleted unless cascade is asked for; cascade removes
+// the dependent rows (recursively) and clears soft references such as device.power_id, and is
+// refused when any of those rows is outside the caller's scope
+
+//--------------------------------------------------------------------------------- Import
+use std::future::Future;
+use std::pin::Pin;
+use sea_orm::{Condition, ConnectionTrait, DbConn, DbErr, Identity, RelationDef, RelationTrait, TransactionTrait};
+use sea_orm::sea_query::{Alias, Asterisk, Expr, Query, SimpleExpr, TableRef};
+use tracing::info;
+use crate::logics::general::ModelOutput;
+use crate::logics::error::ApiError;
+use crate::orm::models::{device, device_command, timer_device, timer_item, timer_limit, zone_command, zone_command_action, zone_command_if, webhook_delivery, notification};
+
+//--------------------------------------------------------------------------------- Scope
+/// The caller's rows of a table, by table name; Condition::all() for an admin
+pub type Scope<'a> = &'a (dyn Fn(&str) -> Condition + Sync);
+
+//--------------------------------------------------------------------------------- Parent
+/// Rows other tables point at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parent
+{
+    Port,
+    Zone,
+    Timer,
+    Device,
+    DeviceCommand,
+    ZoneCommand,
+    Webhook,
+}
+
+/// What cascade does with a dependent row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnCascade
+{
+    Delete,
+    Reset,
+}
+
+struct Dependent
+{
+    relation: RelationDef,
+    child: Option<Parent>,
+    on_cascade: OnCascade,
+}
+
+fn dependent(relation: impl RelationTrait, child: Option<Parent>, on_cascade: OnCascade) -> Dependent
+{
+    Dependent { relation: relation.def(), child, on_cascade }
+}
+
+impl Parent
+{
+    fn table(&self) -> &'static str
+    {
+        match self
+        {
+            Parent::Port => "port",
+            Parent::Zone => "zone",
+            Parent::Timer => "timer",
+            Parent::Device => "device",
+            Parent::DeviceCommand => "device_command",
+            Parent::ZoneCommand => "zone_command",
+            Parent::Webhook => "webhook",
+        }
+    }
+
+    /// Relations of other tables that point at this one
+    fn dependents(&self) -> Vec<Dependent>
+    {
+        use OnCascade::*;
+        match self
+        {
+            Parent::Port => vec![
+                dependent(device::Relation::Port, Some(Parent::Device), Delete),
+                dependent(device::Relation::Power, None, Reset),
+            ],
+            Parent::Zone => vec![
+                dependent(device::Relation::Zone, Some(Parent::Device), Delete),
+                dependent(zone_command::Relation::Zone, Some(Parent::ZoneCommand), Delete),
+            ],
+            Parent::Timer => vec![
+                dependent(timer_device::Relation::Timer, None, Delete),
+                dependent(timer_item::Relation::Timer, None, Delete),
+            ],
+            Parent::Device => vec![
+                dependent(device_command::Relation::Device, Some(Parent::DeviceCommand), Delete),
+                dependent(timer_device::Relation::Device, None, Delete),
+                dependent(timer_limit::Relation::Device, None, Delete),
+                dependent(zone_command_if::Relation::Device, None, Delete),
+                dependent(zone_command_action::Relation::Device, None, Delete),
+                dependent(notification::Relation::Device, None, Delete),
+            ],
+            Parent::DeviceCommand => vec![
+                dependent(device::Relation::Command, None, Reset),
+                dependent(timer_device::Relation::Command, None, Delete),
+                dependent(timer_limit::Relation::CommandFrom, None, Delete),
+                dependent(timer_limit::Relation::CommandTo, None, Delete),
+                dependent(zone_command_if::Relation::Command, None, Delete),
+                dependent(zone_command_action::Relation::Command, None, Delete),
+            ],
+            Parent::ZoneCommand => vec![
+                dependent(zone_command_if::Relation::ZoneCommand, None, Delete),
+                dependent(zone_command_action::Relation::ZoneCommand, None, Delete),
+            ],
+            Parent::Webhook => vec![
+                dependent(webhook_delivery::Relation::Webhook, None, Delete),
+            ],
+        }
+    }
+}
+
+impl Dependent
+{
+    fn table(&self) -> TableRef
+    {
+        self.relation.from_tbl.clone()
+    }
+
+    fn table_name(&self) -> String
+    {
+        match &self.relation.from_tbl
+        {
+            TableRef::Table(name) => name.to_string(),
+            other => format!("{:?}", other),
+        }
+    }
+
+    fn column(&self) -> String
+    {
+        match &self.relation.from_col
+        {
+            Identity::Unary(column) => column.to_string(),
+            other => panic!("composite relation {:?} is not supported", other),
+        }
+    }
+
+    fn references(&self, id: i32) -> SimpleExpr
+    {
+        Expr::col(Alias::new(self.column())).eq(id)
+    }
+}
+
+//--------------------------------------------------------------------------------- References
+/// Rows still pointing at the parent, per table and column
+pub async fn references<C: ConnectionTrait>(db: &C, parent: Parent, id: i32) -> Result<Vec<(String, i64)>, DbErr>
+{
+    let mut found = Vec::new();
+    for dependent in parent.dependents()
+    {
+        let query = Query::select()
+            .expr_as(Expr::col(Asterisk).count(), Alias::new("count"))
+            .from(dependent.table())
+            .and_where(dependent.references(id))
+            .to_owned();
+        let count = match db.query_one(db.get_database_backend().build(&query)).await?
+        {
+            Some(row) => row.try_get::<i64>("", "count")?,
+            None => 0,
+        };
+        if count > 0
+        {
+            found.push((format!("{}.{}", dependent.table_name(), dependent.column()), count));
+        }
+    }
+    Ok(found)
+}
+
+//--------------------------------------------------------------------------------- Guard
+/// Error message when the parent is still referenced, None when it can be deleted
+pub async fn guard(db: &DbConn, parent: Parent, id: i32) -> Result<Option<String>, DbErr>
+{
+    let found = references(db, parent, id).await?;
+    if found.is_empty()
+    {
+        return Ok(None);
+    }
+
+    let list = found.iter().map(|(reference, count)| format!("{} {}", count, reference)).collect::<Vec<_>>().join(", ");
+    Ok(Some(format!("{} {} is still referenced by {}; remove them first or delete with ?cascade=true", parent.table(), id, list)))
+}
+
+//--------------------------------------------------------------------------------- Before Delete
+/// Used by the services before deleting a parent: refuses with a conflict while it is referenced,
+/// or does the whole cascade delete; None means nothing references it and the plain delete can go on
+pub async fn before_delete(db: &DbConn, parent: Parent, id: i32, cascade: bool, scope: Scope<'_>) -> Option<ModelOutput<String>>
+{
+    if cascade
+    {
+        return Some(match delete_cascade(db, parent, id, scope).await
+        {
+            Ok(affected) =>
+            {
+                info!("Integrity::delete_cascade - {} {} deleted, {} dependent rows removed or reset", parent.table(), id, affected);
+                ModelOutput::success("deleted".to_string(), format!("Deleted with {} dependent rows removed or reset", affected))
+            }
+            Err(e) => ModelOutput::fail(e),
+        });
+    }
+
+    match guard(db, parent, id).await
+    {
+        Ok(None) => None,
+        Ok(Some(message)) => Some(ModelOutput::fail(ApiError::Conflict(message))),
+        Err(e) => Some(ModelOutput::db_error(format!("Database error in Integrity::guard: {}", e), &e)),
+    }
+}
+
+//--------------------------------------------------------------------------------- Cascade
+/// Delete the parent and everything depending on it in one transaction, returns the dependent rows affected.
+/// A conflict when a dependent row belongs to another tenant
+pub async fn delete_cascade(db: &DbConn, parent: Parent, id: i32, scope: Scope<'_>) -> Result<u64, ApiError>
+{
+    let db_error = |e: DbErr| ApiError::from_db(format!("Database error in Integrity::delete_cascade: {}", e), &e);
+    let txn = db.begin().await.map_err(db_error)?;
+    if let Some(message) = outside(&txn, parent, id, scope).await.map_err(db_error)?
+    {
+        return Err(ApiError::Conflict(message));
+    }
+    let affected = release(&txn, parent, id).await.map_err(db_error)?;
+
+    let query = Query::delete().from_table(Alias::new(parent.table())).and_where(Expr::col(Alias::new("id")).eq(id)).to_owned();
+    let result = txn.execute(txn.get_database_backend().build(&query)).await.map_err(db_error)?;
+    if result.rows_affected() == 0
+    {
+        return Err(ApiError::NotFound(format!("{} {} not found", parent.table(), id)));
+    }
+
+    txn.commit().await.map_err(db_error)?;
+    Ok(affected)
+}
+
+/// The first row cascade would remove or reset that is outside the scope, as the conflict message
+fn outside<'a, C>(db: &'a C, parent: Parent, id: i32, scope: Scope<'a>) -> Pin<Box<dyn Future<Output = Result<Option<String>, DbErr>> + Send + 'a>>
+where
+    C: ConnectionTrait + Send + Sync,
+{
+    Box::pin(async move
+    {
+        let backend = db.get_database_backend();
+        for dependent in parent.dependents()
+        {
+            let table = dependent.table_name();
+            let scoped = Query::select().column(Alias::new("id")).from(dependent.table()).cond_where(scope(&table)).to_owned();
+            let query = Query::select().column(Alias::new("id")).from(dependent.table())
+                .and_where(dependent.references(id))
+                .and_where(Expr::col(Alias::new("id")).not_in_subquery(scoped))
+                .limit(1)
+                .to_owned();
+            if let Some(row) = db.query_one(backend.build(&query)).await?
+            {
+                let row_id: i32 = row.try_get("", "id")?;
+                return Ok(Some(format!("{} {} is referenced by {} {} of another owner; cascade cannot remove it, ask an administrator", parent.table(), id, table, row_id)));
+            }
+
+            if let Some(child) = dependent.child
+            {
+                let query = Query::select().column(Alias::new("id")).from(dependent.table()).and_where(dependent.references(id)).to_owned();
+                for row in db.query_all(backend.build(&query)).await?
+                {
+                    let child_id: i32 = row.try_get("", "id")?;
+                    if let Some(message) = outside(db, child, child_id, scope).await? { return Ok(Some(message)); }
+                }
+            }
+        }
+        Ok(None)
+    })
+}
+
+/// Remove or reset the rows pointing at the parent, children first
+fn release<'a, C>(db: &'a C, parent: Parent, id: i32) -> Pin<Box<dyn Future<Output = Result<u64, DbErr>> + Send + 'a>>
+where
+    C: ConnectionTrait + Send + Sync,
+{
+    Box::pin(async move
+    {
+        let backend = db.get_database_backend();
+        let mut affected = 0;
+        for dependent in parent.dependents()
+        {
+            if let Some(child) = dependent.child
+            {
+                let query = Query::select().column(Alias::new("id")).from(dependent.table()).and_where(dependent.references(id)).to_owned();
+                for row in db.query_all(backend.build(&query)).await?
+                {
+                    let child_id: i32 = row.try_get("", "id")?;
+                    affected += release(db, child, child_id).await?;
+                }
+            }
+
+            let result = match dependent.on_cascade
+            {
+                OnCascade::Delete =>
+                {
+                    let query = Query::delete().from_table(dependent.table()).and_where(dependent.references(id)).to_owned();
+                    db.execute(backend.build(&query)).await?
+                }
+                OnCascade::Reset =>
+                {
+                    let query = Query::update().table(dependent.table()).value(Alias::new(dependent.column()), 0).and_where(dependent.references(id)).to_owned();
+                    db.execute(backend.build(&query)).await?
+                }
+            };
+            affected += result.rows_affected();
+        }
+        Ok(affected)
+    })
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use sea_orm::{ActiveModelTrait, Database, EntityTrait, IntoActiveModel, Set};
+    use crate::api::access::Access;
+    use crate::api::services::port::PortService;
+    use crate::logics::error::ApiError;
+    use crate::logics::general::Roles;
+    use crate::orm::models::{device, port, zone};
+
+    #[tokio::test]
+    async fn cascade_stops_at_rows_of_another_owner()
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+
+        // Device 3 moves to a zone of user 2 while staying on user 1's port
+        let other = zone::ActiveModel { user_id: Set(2), name: Set("Other".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        let other = other.insert(&db).await.unwrap();
+        let mut light = device::Entity::find_by_id(3).one(&db).await.unwrap().unwrap().into_active_model();
+        light.zone_id = Set(other.id);
+        let light = light.update(&db).await.unwrap();
+
+        // User 1 may delete its port, but not with a device of user 2 on it
+        let owner = Access { user_id: 1, role: Roles::Operator, zones: Vec::new(), all_zones: true };
+        match PortService::new().delete(&db, &owner, light.port_id, true).await.into_result()
+        {
+            Err(ApiError::Conflict(message)) => assert!(message.contains(&format!("device {}", light.id)), "{}", message),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        assert!(port::Entity::find_by_id(light.port_id).one(&db).await.unwrap().is_some(), "nothing is deleted");
+
+        PortService::new().delete(&db, &Access::unrestricted(), light.port_id, true).await.into_result().unwrap();
+        assert!(device::Entity::find_by_id(light.id).one(&db).await.unwrap().is_none());
+    }
+}
@@ -7,9 +7,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::log::{ActiveModel as LogActiveModel, Entity as LogEntity, Model as LogModel, Column as LogColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 //--------------------------------------------------------------------------------- Class
 pub struct LogORM 
@@ -51,7 +52,7 @@ impl LogORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -77,12 +78,18 @@ impl LogORM
             query = query.filter(condition);
         }
 
-        match query.order_by_asc(LogColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, LogColumn::Id) 
         {
-            Ok(items) => 
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((items, total)) => 
             {
                 let message = if filters.is_empty() { "Logs retrieved successfully".to_string() } else { format!("Filtered logs retrieved successfully (found {} items)", items.len()) };
-                let output = ModelOutput::success(items, message);
+                let output = ModelOutput::success(items, message).with_total(total);
                 if self.verbose { info!("{}::{} - Success: Logs retrieved", self.this_class, this_method); }
                 if self.log { info!("LOG: {}::{} - Logs retrieved", self.this_class, this_method); }
                 output
@@ -90,7 +97,7 @@ impl LogORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -115,14 +122,14 @@ impl LogORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("Log not found".to_string());
+                let output = ModelOutput::not_found("Log not found".to_string());
                 if self.verbose { info!("{}::{} - Log {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -148,7 +155,7 @@ impl LogORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -175,7 +182,7 @@ impl LogORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("Log not found".to_string());
+                    let output = ModelOutput::not_found("Log not found".to_string());
                     if self.verbose { info!("{}::{} - Log {} not found", self.this_class, this_method, id); }
                     output
                 }
@@ -183,7 +190,7 @@ impl LogORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -219,15 +226,15 @@ impl LogORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Log not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Log not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -1,4 +1,6 @@
 pub mod user;
+pub mod user_role;
+pub mod user_zone;
 pub mod config;
 pub mod device;
 pub mod device_command;
@@ -12,3 +14,10 @@ pub mod zone;
 pub mod zone_command;
 pub mod zone_command_action;
 pub mod zone_command_if;
+pub mod page;
+pub mod integrity;
+pub mod graph;
+pub mod webhook;
+pub mod webhook_delivery;
+pub mod notification;
+pub mod telegram_chat;
@@ -0,0 +1,315 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/notification.rs
+
+//--------------------------------------------------------------------------------- Description
+// ORM logic for notification
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use tracing::{info, error, debug};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
+use crate::orm::models::notification::{ActiveModel as NotificationActiveModel, Entity as NotificationEntity, Model as NotificationModel, Column as NotificationColumn};
+use crate::logics::general::ModelOutput;
+use super::page::Page;
+
+//--------------------------------------------------------------------------------- Class
+pub struct NotificationORM 
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl NotificationORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self 
+    {
+        Self 
+        {
+            verbose,
+            log,
+            this_class: "NotificationORM".to_string(),
+            module: "notification".to_string(),
+        }
+    }
+
+    //------------------------- Add
+    pub async fn add(&self, db: &DbConn, item: NotificationActiveModel) -> ModelOutput<NotificationModel> 
+    {
+        let this_method = "add";
+        if self.verbose { debug!("{}::{} - Starting add operation", self.this_class, this_method); }
+
+        match item.insert(db).await 
+        {
+            Ok(model) => {
+                let output = ModelOutput::success(model, "Notification added successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: Notification added", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - Notification added", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Items
+    pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<NotificationModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<NotificationModel>> 
+    {
+        let this_method = "items";
+        if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
+
+        let mut query = NotificationEntity::find().filter(scope);
+        if !filters.is_empty() {
+            let mut condition = Condition::all();
+
+            if let Some(id_str) = filters.get("id") && let Ok(id) = id_str.parse::<i32>() { condition = condition.add(NotificationColumn::Id.eq(id)); }
+            if let Some(user_id_str) = filters.get("user_id") && let Ok(user_id) = user_id_str.parse::<i32>() { condition = condition.add(NotificationColumn::UserId.eq(user_id)); }
+            if let Some(device_id_str) = filters.get("device_id") && let Ok(device_id) = device_id_str.parse::<i32>() { condition = condition.add(NotificationColumn::DeviceId.eq(device_id)); }
+            if let Some(kind) = filters.get("kind") { condition = condition.add(NotificationColumn::Kind.eq(kind)); }
+            if let Some(description) = filters.get("description") { condition = condition.add(NotificationColumn::Description.contains(description)); }
+            if let Some(enable_str) = filters.get("enable") && let Ok(enable) = enable_str.parse::<bool>() { condition = condition.add(NotificationColumn::Enable.eq(enable)); }
+
+            query = query.filter(condition);
+        }
+
+        let page = match Page::from_filters(&filters, NotificationColumn::Id) 
+        {
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((items, total)) => 
+            {
+                let message = if filters.is_empty() { "Notifications retrieved successfully".to_string() } else { format!("Filtered notifications retrieved successfully (found {} items)", items.len()) };
+                let output = ModelOutput::success(items, message).with_total(total);
+                if self.verbose { info!("{}::{} - Success: Notifications retrieved", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - Notifications retrieved", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Item
+    pub async fn item(&self, db: &DbConn, id: i32) -> ModelOutput<NotificationModel> 
+    {
+        let this_method = "item";
+        if self.verbose { debug!("{}::{} - Starting item operation for id: {}", self.this_class, this_method, id); }
+
+        match NotificationEntity::find_by_id(id).one(db).await 
+        {
+            Ok(Some(model)) => 
+            {
+                let output = ModelOutput::success(model, "Notification retrieved successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: Notification {} found", self.this_class, this_method, id); }
+                if self.log { info!("LOG: {}::{} - Notification {} retrieved", self.this_class, this_method, id); }
+                output
+            }
+            Ok(None) => 
+            {
+                let output = ModelOutput::not_found("Notification not found".to_string());
+                if self.verbose { info!("{}::{} - Notification {} not found", self.this_class, this_method, id); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Update
+    pub async fn update(&self, db: &DbConn, item: NotificationActiveModel) -> ModelOutput<NotificationModel> 
+    {
+        let this_method = "update";
+        if self.verbose { debug!("{}::{} - Starting update operation", self.this_class, this_method); }
+
+        match item.update(db).await 
+        {
+            Ok(model) => 
+            {
+                let output = ModelOutput::success(model, "Notification updated successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: Notification updated", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - Notification updated", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DbConn, id: i32) -> ModelOutput<String> 
+    {
+        let this_method = "delete";
+        if self.verbose { debug!("{}::{} - Starting delete operation for id: {}", self.this_class, this_method, id); }
+
+        match NotificationEntity::delete_by_id(id).exec(db).await 
+        {
+            Ok(result) => 
+            {
+                if result.rows_affected > 0 
+                {
+                    let output = ModelOutput::success("deleted".to_string(), "Notification deleted successfully".to_string());
+                    if self.verbose { info!("{}::{} - Success: Notification {} deleted", self.this_class, this_method, id); }
+                    if self.log { info!("LOG: {}::{} - Notification {} deleted", self.this_class, this_method, id); }
+                    output
+                } 
+                else 
+                {
+                    let output = ModelOutput::not_found("Notification not found".to_string());
+                    if self.verbose { info!("{}::{} - Notification {} not found", self.this_class, this_method, id); }
+                    output
+                }
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Disable
+    pub async fn disable(&self, db: &DbConn, id: i32) -> ModelOutput<NotificationModel>
+    {
+        let this_method = "disable";
+        if self.verbose { debug!("{}::{} - Starting disable operation for id: {}", self.this_class, this_method, id); }
+
+        match NotificationEntity::find_by_id(id).one(db).await
+        {
+            Ok(Some(existing)) =>
+            {
+                let mut active: NotificationActiveModel = existing.into();
+                active.enable = sea_orm::Set(false);
+
+                match active.update(db).await
+                {
+                    Ok(updated) => ModelOutput::success(updated, "Notification disabled successfully".to_string()),
+                    Err(e) => {
+                        let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                        error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                        ModelOutput::db_error(error_msg, &e)
+                    }
+                }
+            }
+            Ok(None) => ModelOutput::not_found("Notification not found".to_string()),
+            Err(e) => {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Enable
+    pub async fn enable(&self, db: &DbConn, id: i32) -> ModelOutput<NotificationModel>
+    {
+        let this_method = "enable";
+        if self.verbose { debug!("{}::{} - Starting enable operation for id: {}", self.this_class, this_method, id); }
+
+        match NotificationEntity::find_by_id(id).one(db).await
+        {
+            Ok(Some(existing)) =>
+            {
+                let mut active: NotificationActiveModel = existing.into();
+                active.enable = sea_orm::Set(true);
+
+                match active.update(db).await
+                {
+                    Ok(updated) => ModelOutput::success(updated, "Notification enabled successfully".to_string()),
+                    Err(e) => {
+                        let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                        error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                        ModelOutput::db_error(error_msg, &e)
+                    }
+                }
+            }
+            Ok(None) => ModelOutput::not_found("Notification not found".to_string()),
+            Err(e) => {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Status (Toggle Enable)
+    pub async fn status(&self, db: &DbConn, id: i32) -> ModelOutput<NotificationModel>
+    {
+        let this_method = "status";
+        if self.verbose { debug!("{}::{} - Starting status operation for id: {}", self.this_class, this_method, id); }
+
+        match NotificationEntity::find_by_id(id).one(db).await
+        {
+            Ok(Some(existing)) =>
+            {
+                // Get the current enable value before moving existing
+                let current_enable = existing.enable;
+                let mut active: NotificationActiveModel = existing.into();
+                // Toggle the enable field: if true, set to false; if false, set to true
+                active.enable = sea_orm::Set(!current_enable);
+
+                match active.update(db).await
+                {
+                    Ok(updated) => {
+                        let message = if current_enable {
+                            "Notification disabled successfully".to_string()
+                        } else {
+                            "Notification enabled successfully".to_string()
+                        };
+                        ModelOutput::success(updated, message)
+                    },
+                    Err(e) => {
+                        let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                        error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                        ModelOutput::db_error(error_msg, &e)
+                    }
+                }
+            }
+            Ok(None) => ModelOutput::not_found("Notification not found".to_string()),
+            Err(e) => {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+}
\ No newline at end of file
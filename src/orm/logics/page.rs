@@ -0,0 +1,137 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/page.rs
+
+//--------------------------------------------------------------------------------- Description
+// Shared limit/offset/sort/order handling for the items methods of every ORM logic
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::str::FromStr;
+use sea_orm::{ColumnTrait, DbConn, DbErr, EntityTrait, Iterable, Order, PaginatorTrait, PrimaryKeyToColumn, QueryOrder, QuerySelect, Select};
+
+//--------------------------------------------------------------------------------- Page
+pub struct Page<C>
+{
+    pub limit: Option<u64>,
+    pub offset: u64,
+    pub sort: C,
+    pub order: Order,
+}
+
+impl<C> Page<C>
+where
+    C: ColumnTrait + FromStr,
+{
+    //------------------------- From Filters
+    /// Read limit, offset, sort and order from the query filters; sort defaults to the given column
+    pub fn from_filters(filters: &HashMap<String, String>, default_sort: C) -> Result<Self, String>
+    {
+        let limit = match filters.get("limit")
+        {
+            Some(value) => Some(value.parse::<u64>().ok().filter(|limit| *limit > 0).ok_or_else(|| format!("Invalid limit '{}'", value))?),
+            None => None,
+        };
+
+        let offset = match filters.get("offset")
+        {
+            Some(value) => value.parse::<u64>().map_err(|_| format!("Invalid offset '{}'", value))?,
+            None => 0,
+        };
+
+        let sort = match filters.get("sort")
+        {
+            Some(value) => C::from_str(value).map_err(|_| format!("Invalid sort column '{}'", value))?,
+            None => default_sort,
+        };
+
+        let order = match filters.get("order").map(|value| value.to_lowercase())
+        {
+            None => Order::Asc,
+            Some(value) if value == "asc" => Order::Asc,
+            Some(value) if value == "desc" => Order::Desc,
+            Some(value) => return Err(format!("Invalid order '{}', expected asc or desc", value)),
+        };
+
+        Ok(Self { limit, offset, sort, order })
+    }
+
+    //------------------------- Fetch
+    /// Run the query for the requested page, returns the rows and the total count before paging
+    pub async fn fetch<E>(&self, db: &DbConn, query: Select<E>) -> Result<(Vec<E::Model>, u64), DbErr>
+    where
+        E: EntityTrait<Column = C>,
+        E::Model: Sync,
+    {
+        let total = query.clone().count(db).await?;
+        let mut query = query.order_by(self.sort, self.order.clone());
+        // The primary key breaks ties, so pages over a non-unique sort neither repeat nor skip rows
+        for key in E::PrimaryKey::iter()
+        {
+            let column = key.into_column();
+            if column.as_str() != self.sort.as_str() { query = query.order_by(column, Order::Asc); }
+        }
+        // SQLite rejects OFFSET without LIMIT, so an offset alone gets an unbounded limit
+        if self.offset > 0
+        {
+            query = query.offset(self.offset).limit(self.limit.unwrap_or(i64::MAX as u64));
+        }
+        else if let Some(limit) = self.limit
+        {
+            query = query.limit(limit);
+        }
+        let items = query.all(db).await?;
+        Ok((items, total))
+    }
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use sea_orm::Database;
+    use crate::orm::models::device::{Column as DeviceColumn, Entity as DeviceEntity};
+
+    fn page(filters: &[(&str, &str)]) -> Result<Page<DeviceColumn>, String>
+    {
+        let filters = filters.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        Page::from_filters(&filters, DeviceColumn::Id)
+    }
+
+    #[test]
+    fn from_filters_reads_the_paging()
+    {
+        let defaults = page(&[]).unwrap();
+        assert!(defaults.limit.is_none() && defaults.offset == 0 && defaults.order == Order::Asc);
+        assert!(matches!(defaults.sort, DeviceColumn::Id));
+
+        let custom = page(&[("limit", "10"), ("offset", "20"), ("sort", "zone_id"), ("order", "DESC")]).unwrap();
+        assert_eq!((custom.limit, custom.offset, custom.order), (Some(10), 20, Order::Desc));
+        assert!(matches!(custom.sort, DeviceColumn::ZoneId));
+
+        for (key, value) in [("limit", "0"), ("limit", "-1"), ("offset", "x"), ("sort", "password"), ("order", "up")]
+        {
+            assert!(page(&[(key, value)]).err().is_some_and(|message| message.contains(value)), "{}={} is rejected", key, value);
+        }
+    }
+
+    #[tokio::test]
+    async fn pages_over_a_non_unique_sort_are_stable()
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+
+        let (all, total) = page(&[("sort", "zone_id"), ("order", "desc")]).unwrap().fetch(&db, DeviceEntity::find()).await.unwrap();
+        let mut paged = Vec::new();
+        for offset in (0..total).step_by(2)
+        {
+            let (items, _) = page(&[("sort", "zone_id"), ("order", "desc"), ("limit", "2"), ("offset", &offset.to_string())]).unwrap().fetch(&db, DeviceEntity::find()).await.unwrap();
+            paged.extend(items.into_iter().map(|device| device.id));
+        }
+
+        let ids: Vec<i32> = all.iter().map(|device| device.id).collect();
+        assert_eq!(paged, ids);
+        assert!(all.windows(2).all(|pair| pair[0].zone_id > pair[1].zone_id || (pair[0].zone_id == pair[1].zone_id && pair[0].id < pair[1].id)), "ties ordered by id");
+    }
+}
@@ -7,9 +7,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::port::{ActiveModel as PortActiveModel, Entity as PortEntity, Model as PortModel, Column as PortColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 //--------------------------------------------------------------------------------- Class
 pub struct PortORM 
@@ -51,7 +52,7 @@ impl PortORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -61,12 +62,19 @@ impl PortORM
 
     //------------------------- Items
     pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<PortModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<PortModel>> 
     {
         let this_method = "items";
         if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
 
         // Build query with optional filters
-        let mut query = PortEntity::find();
+        let mut query = PortEntity::find().filter(scope);
         
         // Apply filters if they exist
         if !filters.is_empty() 
@@ -127,9 +135,15 @@ impl PortORM
         }
         
         // Execute query with ordering
-        match query.order_by_asc(PortColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, PortColumn::Id) 
+        {
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
         {
-            Ok(items) => 
+            Ok((items, total)) => 
             {
                 let message = if filters.is_empty() {
                     "All ports retrieved successfully".to_string()
@@ -137,7 +151,7 @@ impl PortORM
                     format!("Filtered ports retrieved successfully (found {} items)", items.len())
                 };
                 
-                let output = ModelOutput::success(items, message);
+                let output = ModelOutput::success(items, message).with_total(total);
                 if self.verbose { info!("{}::{} - Success: Ports retrieved with filters", self.this_class, this_method); }
                 if self.log { info!("LOG: {}::{} - Ports retrieved with filters", self.this_class, this_method); }
                 output
@@ -145,7 +159,7 @@ impl PortORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -170,14 +184,14 @@ impl PortORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("Port not found".to_string());
+                let output = ModelOutput::not_found("Port not found".to_string());
                 if self.verbose { info!("{}::{} - Port {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -203,7 +217,7 @@ impl PortORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -230,7 +244,7 @@ impl PortORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("Port not found".to_string());
+                    let output = ModelOutput::not_found("Port not found".to_string());
                     if self.verbose { info!("{}::{} - Port {} not found", self.this_class, this_method, id); }
                     output
                 }
@@ -238,7 +252,7 @@ impl PortORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -271,7 +285,7 @@ impl PortORM
                     Err(e) =>
                     {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         output
                     }
@@ -279,14 +293,14 @@ impl PortORM
             }
             Ok(None) =>
             {
-                let output = ModelOutput::error("Port not found".to_string());
+                let output = ModelOutput::not_found("Port not found".to_string());
                 if self.verbose { info!("{}::{} - Port {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) =>
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 output
             }
@@ -318,7 +332,7 @@ impl PortORM
                     Err(e) =>
                     {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         output
                     }
@@ -326,14 +340,14 @@ impl PortORM
             }
             Ok(None) =>
             {
-                let output = ModelOutput::error("Port not found".to_string());
+                let output = ModelOutput::not_found("Port not found".to_string());
                 if self.verbose { info!("{}::{} - Port {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) =>
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 output
             }
@@ -368,15 +382,15 @@ impl PortORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Port not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Port not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -0,0 +1,193 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/telegram_chat.rs
+
+//--------------------------------------------------------------------------------- Description
+// ORM logic for telegram_chat
+
+//--------------------------------------------------------------------------------- Import
+use tracing::{info, error, debug};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Set};
+use sea_orm::sea_query::Expr;
+use crate::orm::models::telegram_chat::{ActiveModel as TelegramChatActiveModel, Entity as TelegramChatEntity, Model as TelegramChatModel, Column as TelegramChatColumn};
+use crate::logics::general::ModelOutput;
+
+//--------------------------------------------------------------------------------- Class
+pub struct TelegramChatORM 
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl TelegramChatORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self 
+    {
+        Self 
+        {
+            verbose,
+            log,
+            this_class: "TelegramChatORM".to_string(),
+            module: "telegram_chat".to_string(),
+        }
+    }
+
+    //------------------------- Item By User
+    pub async fn item_by_user(&self, db: &DbConn, user_id: i32) -> ModelOutput<TelegramChatModel> 
+    {
+        let this_method = "item_by_user";
+        if self.verbose { debug!("{}::{} - Starting item operation for user: {}", self.this_class, this_method, user_id); }
+
+        match TelegramChatEntity::find().filter(TelegramChatColumn::UserId.eq(user_id)).one(db).await 
+        {
+            Ok(Some(chat)) => ModelOutput::success(chat, "Telegram chat retrieved successfully".to_string()),
+            Ok(None) => ModelOutput::not_found("Telegram chat not found".to_string()),
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Item By Sender
+    /// Linked chat of a numeric Telegram user id
+    pub async fn item_by_sender(&self, db: &DbConn, sender_id: i64) -> ModelOutput<TelegramChatModel> 
+    {
+        let this_method = "item_by_sender";
+        if self.verbose { debug!("{}::{} - Starting item operation for sender: {}", self.this_class, this_method, sender_id); }
+
+        match TelegramChatEntity::find().filter(TelegramChatColumn::SenderId.eq(sender_id.to_string())).one(db).await 
+        {
+            Ok(Some(chat)) => ModelOutput::success(chat, "Telegram chat retrieved successfully".to_string()),
+            Ok(None) => ModelOutput::not_found("Telegram chat not found".to_string()),
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Set Code
+    /// Store a new one-time link code for the user, replacing a pending one; the linked chat stays until the code is used
+    pub async fn set_code(&self, db: &DbConn, user_id: i32, code: &str, expires: &str) -> ModelOutput<TelegramChatModel> 
+    {
+        let this_method = "set_code";
+        if self.verbose { debug!("{}::{} - Setting link code for user: {}", self.this_class, this_method, user_id); }
+
+        let existing = match TelegramChatEntity::find().filter(TelegramChatColumn::UserId.eq(user_id)).one(db).await 
+        {
+            Ok(existing) => existing,
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                return ModelOutput::db_error(error_msg, &e);
+            }
+        };
+
+        let result = match existing 
+        {
+            Some(existing) => 
+            {
+                let mut item: TelegramChatActiveModel = existing.into();
+                item.link_code = Set(code.to_string());
+                item.link_expires = Set(expires.to_string());
+                item.update(db).await
+            }
+            None => TelegramChatActiveModel
+            {
+                id: Default::default(),
+                user_id: Set(user_id),
+                chat_id: Set(String::new()),
+                username: Set(String::new()),
+                date: Set(String::new()),
+                sender_id: Set(String::new()),
+                link_code: Set(code.to_string()),
+                link_expires: Set(expires.to_string()),
+            }.insert(db).await,
+        };
+
+        match result 
+        {
+            Ok(chat) => 
+            {
+                if self.log { info!("LOG: {}::{} - Link code issued for user {} until {}", self.this_class, this_method, user_id, expires); }
+                ModelOutput::success(chat, "Telegram link code created successfully".to_string())
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Link
+    /// Use a pending code: the chat and the numeric id of its sender become the user's, the code is cleared
+    pub async fn link(&self, db: &DbConn, code: &str, chat_id: &str, sender_id: i64, username: &str) -> ModelOutput<TelegramChatModel> 
+    {
+        let this_method = "link";
+        if self.verbose { debug!("{}::{} - Linking chat {} with a code", self.this_class, this_method, chat_id); }
+
+        let date = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let pending = TelegramChatEntity::find()
+            .filter(TelegramChatColumn::LinkCode.eq(code))
+            .filter(TelegramChatColumn::LinkCode.ne(""))
+            .filter(TelegramChatColumn::LinkExpires.gte(date.clone()))
+            .one(db).await;
+
+        let result = match pending 
+        {
+            Ok(Some(pending)) => 
+            {
+                // A Telegram id belongs to one user, an earlier link of it is dropped
+                let released = TelegramChatEntity::update_many()
+                    .col_expr(TelegramChatColumn::SenderId, Expr::value(""))
+                    .filter(TelegramChatColumn::SenderId.eq(sender_id.to_string()))
+                    .filter(TelegramChatColumn::Id.ne(pending.id))
+                    .exec(db).await;
+                if let Err(e) = released
+                {
+                    let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                    error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                    return ModelOutput::db_error(error_msg, &e);
+                }
+
+                let mut item: TelegramChatActiveModel = pending.into();
+                item.chat_id = Set(chat_id.to_string());
+                item.sender_id = Set(sender_id.to_string());
+                item.username = Set(username.to_string());
+                item.date = Set(date);
+                item.link_code = Set(String::new());
+                item.link_expires = Set(String::new());
+                item.update(db).await
+            }
+            Ok(None) => return ModelOutput::not_found("Invalid or expired link code".to_string()),
+            Err(e) => Err(e),
+        };
+
+        match result 
+        {
+            Ok(chat) => 
+            {
+                if self.log { info!("LOG: {}::{} - User {} linked to Telegram id {} in chat {}", self.this_class, this_method, chat.user_id, chat.sender_id, chat.chat_id); }
+                ModelOutput::success(chat, "Telegram chat linked successfully".to_string())
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+}
@@ -7,9 +7,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::timer::{ActiveModel as TimerActiveModel, Entity as TimerEntity, Model as TimerModel, Column as TimerColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 //--------------------------------------------------------------------------------- Class
 pub struct TimerORM 
@@ -51,7 +52,7 @@ impl TimerORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -61,11 +62,18 @@ impl TimerORM
 
     //------------------------- Items
     pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<TimerModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<TimerModel>> 
     {
         let this_method = "items";
         if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
 
-        let mut query = TimerEntity::find();
+        let mut query = TimerEntity::find().filter(scope);
         if !filters.is_empty() {
             let mut condition = Condition::all();
 
@@ -78,12 +86,18 @@ impl TimerORM
             query = query.filter(condition);
         }
 
-        match query.order_by_asc(TimerColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, TimerColumn::Id) 
+        {
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
         {
-            Ok(items) => 
+            Ok((items, total)) => 
             {
                 let message = if filters.is_empty() { "Timers retrieved successfully".to_string() } else { format!("Filtered timers retrieved successfully (found {} items)", items.len()) };
-                let output = ModelOutput::success(items, message);
+                let output = ModelOutput::success(items, message).with_total(total);
                 if self.verbose { info!("{}::{} - Success: Timers retrieved", self.this_class, this_method); }
                 if self.log { info!("LOG: {}::{} - Timers retrieved", self.this_class, this_method); }
                 output
@@ -91,7 +105,7 @@ impl TimerORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -116,14 +130,14 @@ impl TimerORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("Timer not found".to_string());
+                let output = ModelOutput::not_found("Timer not found".to_string());
                 if self.verbose { info!("{}::{} - Timer {} not found", self.this_class, this_method, id); }
                 output
             }
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -149,7 +163,7 @@ impl TimerORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -176,7 +190,7 @@ impl TimerORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("Timer not found".to_string());
+                    let output = ModelOutput::not_found("Timer not found".to_string());
                     if self.verbose { info!("{}::{} - Timer {} not found", self.this_class, this_method, id); }
                     output
                 }
@@ -184,7 +198,7 @@ impl TimerORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
                 output
@@ -211,15 +225,15 @@ impl TimerORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Timer not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Timer not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -243,15 +257,15 @@ impl TimerORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Timer not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Timer not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -285,15 +299,15 @@ impl TimerORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("Timer not found".to_string()),
+            Ok(None) => ModelOutput::not_found("Timer not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -9,9 +9,10 @@
 //--------------------------------------------------------------------------------- Import
 use std::collections::HashMap;
 use tracing::{info, error, debug};
-use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryOrder, QueryFilter, ColumnTrait, Condition};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
 use crate::orm::models::user::{ActiveModel as UserActiveModel, Entity as UserEntity, Model as UserModel, Column as UserColumn};
 use crate::logics::general::ModelOutput;
+use super::page::Page;
 
 
 
@@ -64,7 +65,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log 
                 {
@@ -98,12 +99,18 @@ impl UserORM
             query = query.filter(condition);
         }
 
-        match query.order_by_asc(UserColumn::Id).all(db).await 
+        let page = match Page::from_filters(&filters, UserColumn::Id) 
         {
-            Ok(users) => 
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((users, total)) => 
             {
                 let message = if filters.is_empty() { "Users retrieved successfully".to_string() } else { format!("Filtered users retrieved successfully (found {} items)", users.len()) };
-                let output = ModelOutput::success(users, message);
+                let output = ModelOutput::success(users, message).with_total(total);
                 if self.verbose 
                 {
                     info!("{}::{} - Success: Retrieved {} users", self.this_class, this_method, output.data.as_ref().map_or(0, |d| d.len()));
@@ -117,7 +124,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log 
                 {
@@ -155,7 +162,7 @@ impl UserORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("User not found".to_string());
+                let output = ModelOutput::not_found("User not found".to_string());
                 if self.verbose 
                 {
                     info!("{}::{} - User {} not found", self.this_class, this_method, id);
@@ -165,7 +172,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log 
                 {
@@ -176,6 +183,43 @@ impl UserORM
         }
     }
 
+    //------------------------- Item By Username
+    /// Enabled user with this username, used by login
+    pub async fn item_by_username(&self, db: &DbConn, username: &str) -> ModelOutput<UserModel> 
+    {
+        let condition = Condition::all().add(UserColumn::Username.eq(username)).add(UserColumn::Enable.eq(true));
+        self.first(db, "item_by_username", condition).await
+    }
+
+    //------------------------- Item By Key
+    /// Enabled user with this API key, used by the auth middleware
+    pub async fn item_by_key(&self, db: &DbConn, key: &str) -> ModelOutput<UserModel> 
+    {
+        let condition = Condition::all().add(UserColumn::Key.eq(key)).add(UserColumn::Enable.eq(true));
+        self.first(db, "item_by_key", condition).await
+    }
+
+    //------------------------- First
+    async fn first(&self, db: &DbConn, this_method: &str, condition: Condition) -> ModelOutput<UserModel> 
+    {
+        if self.verbose 
+        {
+            debug!("{}::{} - Starting lookup", self.this_class, this_method);
+        }
+
+        match UserEntity::find().filter(condition).one(db).await 
+        {
+            Ok(Some(user)) => ModelOutput::success(user, "User retrieved successfully".to_string()),
+            Ok(None) => ModelOutput::not_found("User not found".to_string()),
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
     //------------------------- Update
     pub async fn update(&self, db: &DbConn, item: UserActiveModel) -> ModelOutput<UserModel> 
     {
@@ -204,7 +248,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log 
                 {
@@ -244,7 +288,7 @@ impl UserORM
                 } 
                 else 
                 {
-                    let output = ModelOutput::error("User not found".to_string());
+                    let output = ModelOutput::not_found("User not found".to_string());
                     if self.verbose 
                     {
                         info!("{}::{} - User {} not found", self.this_class, this_method, id);
@@ -255,7 +299,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log 
                 {
@@ -301,7 +345,7 @@ impl UserORM
                     Err(e) => 
                     {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         output
                     }
@@ -309,7 +353,7 @@ impl UserORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("User not found".to_string());
+                let output = ModelOutput::not_found("User not found".to_string());
                 if self.verbose 
                 {
                     info!("{}::{} - User {} not found", self.this_class, this_method, id);
@@ -319,7 +363,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 output
             }
@@ -361,7 +405,7 @@ impl UserORM
                     Err(e) => 
                     {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                        let output = ModelOutput::error(error_msg.clone());
+                        let output = ModelOutput::db_error(error_msg.clone(), &e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                         output
                     }
@@ -369,7 +413,7 @@ impl UserORM
             }
             Ok(None) => 
             {
-                let output = ModelOutput::error("User not found".to_string());
+                let output = ModelOutput::not_found("User not found".to_string());
                 if self.verbose 
                 {
                     info!("{}::{} - User {} not found", self.this_class, this_method, id);
@@ -379,7 +423,7 @@ impl UserORM
             Err(e) => 
             {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
-                let output = ModelOutput::error(error_msg.clone());
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
                 if self.log 
                 {
@@ -432,15 +476,15 @@ impl UserORM
                     Err(e) => {
                         let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                         error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                        ModelOutput::error(error_msg)
+                        ModelOutput::db_error(error_msg, &e)
                     }
                 }
             }
-            Ok(None) => ModelOutput::error("User not found".to_string()),
+            Ok(None) => ModelOutput::not_found("User not found".to_string()),
             Err(e) => {
                 let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
                 error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
-                ModelOutput::error(error_msg)
+                ModelOutput::db_error(error_msg, &e)
             }
         }
     }
@@ -0,0 +1,119 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/user_role.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is orm for user_role
+
+
+
+//--------------------------------------------------------------------------------- Import
+use tracing::{info, error, debug};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Set};
+use crate::orm::models::user_role::{ActiveModel as UserRoleActiveModel, Entity as UserRoleEntity, Model as UserRoleModel, Column as UserRoleColumn};
+use crate::logics::general::ModelOutput;
+
+
+
+//--------------------------------------------------------------------------------- Class
+pub struct UserRoleORM 
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl UserRoleORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self 
+    {
+        Self 
+        {
+            verbose,
+            log,
+            this_class: "UserRoleORM".to_string(),
+            module: "user_role".to_string(),
+        }
+    }
+
+    //------------------------- Item By User
+    pub async fn item_by_user(&self, db: &DbConn, user_id: i32) -> ModelOutput<UserRoleModel> 
+    {
+        let this_method = "item_by_user";
+
+        if self.verbose 
+        {
+            debug!("{}::{} - Starting item operation for user: {}", self.this_class, this_method, user_id);
+        }
+
+        match UserRoleEntity::find().filter(UserRoleColumn::UserId.eq(user_id)).one(db).await 
+        {
+            Ok(Some(role)) => ModelOutput::success(role, "User role retrieved successfully".to_string()),
+            Ok(None) => ModelOutput::not_found("User role not found".to_string()),
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Set
+    /// Insert or replace the role of a user; all_zones is kept when not given, false for a new row
+    pub async fn set(&self, db: &DbConn, user_id: i32, role: &str, all_zones: Option<bool>) -> ModelOutput<UserRoleModel> 
+    {
+        let this_method = "set";
+
+        if self.verbose 
+        {
+            debug!("{}::{} - Setting role {} for user: {}", self.this_class, this_method, role, user_id);
+        }
+
+        let existing = match UserRoleEntity::find().filter(UserRoleColumn::UserId.eq(user_id)).one(db).await 
+        {
+            Ok(existing) => existing,
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                return ModelOutput::db_error(error_msg, &e);
+            }
+        };
+
+        let result = match existing 
+        {
+            Some(existing) => 
+            {
+                let mut item: UserRoleActiveModel = existing.into();
+                item.role = Set(role.to_string());
+                if let Some(all_zones) = all_zones { item.all_zones = Set(all_zones); }
+                item.update(db).await
+            }
+            None => UserRoleActiveModel { id: Default::default(), user_id: Set(user_id), role: Set(role.to_string()), all_zones: Set(all_zones.unwrap_or(false)) }.insert(db).await,
+        };
+
+        match result 
+        {
+            Ok(role) => 
+            {
+                if self.log 
+                {
+                    info!("LOG: {}::{} - User {} is now {} (all zones: {})", self.this_class, this_method, user_id, role.role, role.all_zones);
+                }
+                ModelOutput::success(role, "User role updated successfully".to_string())
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log 
+                {
+                    error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg);
+                }
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+}
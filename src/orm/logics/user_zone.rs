@@ -0,0 +1,122 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/user_zone.rs
+
+//--------------------------------------------------------------------------------- Description
+// This is orm for user_zone
+
+
+
+//--------------------------------------------------------------------------------- Import
+use tracing::{info, error, debug};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, QueryOrder, ColumnTrait, Condition, Set};
+use crate::orm::models::user_zone::{ActiveModel as UserZoneActiveModel, Entity as UserZoneEntity, Model as UserZoneModel, Column as UserZoneColumn};
+use crate::logics::general::ModelOutput;
+
+
+
+//--------------------------------------------------------------------------------- Class
+pub struct UserZoneORM 
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl UserZoneORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self 
+    {
+        Self 
+        {
+            verbose,
+            log,
+            this_class: "UserZoneORM".to_string(),
+            module: "user_zone".to_string(),
+        }
+    }
+
+    //------------------------- Items By User
+    pub async fn items_by_user(&self, db: &DbConn, user_id: i32) -> ModelOutput<Vec<UserZoneModel>> 
+    {
+        let this_method = "items_by_user";
+
+        if self.verbose 
+        {
+            debug!("{}::{} - Starting items operation for user: {}", self.this_class, this_method, user_id);
+        }
+
+        match UserZoneEntity::find().filter(UserZoneColumn::UserId.eq(user_id)).order_by_asc(UserZoneColumn::ZoneId).all(db).await 
+        {
+            Ok(grants) => ModelOutput::success(grants, "Zone grants retrieved successfully".to_string()),
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Add
+    pub async fn add(&self, db: &DbConn, user_id: i32, zone_id: i32) -> ModelOutput<UserZoneModel> 
+    {
+        let this_method = "add";
+
+        if self.verbose 
+        {
+            debug!("{}::{} - Granting zone {} to user: {}", self.this_class, this_method, zone_id, user_id);
+        }
+
+        let item = UserZoneActiveModel { id: Default::default(), user_id: Set(user_id), zone_id: Set(zone_id) };
+        match item.insert(db).await 
+        {
+            Ok(grant) => 
+            {
+                if self.log 
+                {
+                    info!("LOG: {}::{} - Zone {} granted to user {}", self.this_class, this_method, zone_id, user_id);
+                }
+                ModelOutput::success(grant, "Zone granted successfully".to_string())
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DbConn, user_id: i32, zone_id: i32) -> ModelOutput<String> 
+    {
+        let this_method = "delete";
+
+        if self.verbose 
+        {
+            debug!("{}::{} - Revoking zone {} from user: {}", self.this_class, this_method, zone_id, user_id);
+        }
+
+        let condition = Condition::all().add(UserZoneColumn::UserId.eq(user_id)).add(UserZoneColumn::ZoneId.eq(zone_id));
+        match UserZoneEntity::delete_many().filter(condition).exec(db).await 
+        {
+            Ok(result) if result.rows_affected > 0 => 
+            {
+                if self.log 
+                {
+                    info!("LOG: {}::{} - Zone {} revoked from user {}", self.this_class, this_method, zone_id, user_id);
+                }
+                ModelOutput::success("deleted".to_string(), "Zone grant deleted successfully".to_string())
+            }
+            Ok(_) => ModelOutput::not_found("Zone grant not found".to_string()),
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// ORM logic for webhook
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use tracing::{info, error, debug};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
+use crate::orm::models::webhook::{ActiveModel as WebhookActiveModel, Entity as WebhookEntity, Model as WebhookModel, Column as WebhookColumn};
+use crate::logics::general::ModelOutput;
+use super::page::Page;
+
+//--------------------------------------------------------------------------------- Class
+pub struct WebhookORM 
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl WebhookORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self 
+    {
+        Self 
+        {
+            verbose,
+            log,
+            this_class: "WebhookORM".to_string(),
+            module: "webhook".to_string(),
+        }
+    }
+
+    //------------------------- Add
+    pub async fn add(&self, db: &DbConn, item: WebhookActiveModel) -> ModelOutput<WebhookModel> 
+    {
+        let this_method = "add";
+        if self.verbose { debug!("{}::{} - Starting add operation", self.this_class, this_method); }
+
+        match item.insert(db).await 
+        {
+            Ok(model) => {
+                let output = ModelOutput::success(model, "Webhook added successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: Webhook added", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - Webhook added", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Items
+    pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<WebhookModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<WebhookModel>> 
+    {
+        let this_method = "items";
+        if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
+
+        let mut query = WebhookEntity::find().filter(scope);
+        if !filters.is_empty() {
+            let mut condition = Condition::all();
+
+            if let Some(id_str) = filters.get("id") && let Ok(id) = id_str.parse::<i32>() { condition = condition.add(WebhookColumn::Id.eq(id)); }
+            if let Some(user_id_str) = filters.get("user_id") && let Ok(user_id) = user_id_str.parse::<i32>() { condition = condition.add(WebhookColumn::UserId.eq(user_id)); }
+            if let Some(name) = filters.get("name") { condition = condition.add(WebhookColumn::Name.contains(name)); }
+            if let Some(url) = filters.get("url") { condition = condition.add(WebhookColumn::Url.contains(url)); }
+            if let Some(events) = filters.get("events") { condition = condition.add(WebhookColumn::Events.contains(events)); }
+            if let Some(description) = filters.get("description") { condition = condition.add(WebhookColumn::Description.contains(description)); }
+            if let Some(enable_str) = filters.get("enable") && let Ok(enable) = enable_str.parse::<bool>() { condition = condition.add(WebhookColumn::Enable.eq(enable)); }
+
+            query = query.filter(condition);
+        }
+
+        let page = match Page::from_filters(&filters, WebhookColumn::Id) 
+        {
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((items, total)) => 
+            {
+                let message = if filters.is_empty() { "Webhooks retrieved successfully".to_string() } else { format!("Filtered webhooks retrieved successfully (found {} items)", items.len()) };
+                let output = ModelOutput::success(items, message).with_total(total);
+                if self.verbose { info!("{}::{} - Success: Webhooks retrieved", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - Webhooks retrieved", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Item
+    pub async fn item(&self, db: &DbConn, id: i32) -> ModelOutput<WebhookModel> 
+    {
+        let this_method = "item";
+        if self.verbose { debug!("{}::{} - Starting item operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookEntity::find_by_id(id).one(db).await 
+        {
+            Ok(Some(model)) => 
+            {
+                let output = ModelOutput::success(model, "Webhook retrieved successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: Webhook {} found", self.this_class, this_method, id); }
+                if self.log { info!("LOG: {}::{} - Webhook {} retrieved", self.this_class, this_method, id); }
+                output
+            }
+            Ok(None) => 
+            {
+                let output = ModelOutput::not_found("Webhook not found".to_string());
+                if self.verbose { info!("{}::{} - Webhook {} not found", self.this_class, this_method, id); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Update
+    pub async fn update(&self, db: &DbConn, item: WebhookActiveModel) -> ModelOutput<WebhookModel> 
+    {
+        let this_method = "update";
+        if self.verbose { debug!("{}::{} - Starting update operation", self.this_class, this_method); }
+
+        match item.update(db).await 
+        {
+            Ok(model) => 
+            {
+                let output = ModelOutput::success(model, "Webhook updated successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: Webhook updated", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - Webhook updated", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DbConn, id: i32) -> ModelOutput<String> 
+    {
+        let this_method = "delete";
+        if self.verbose { debug!("{}::{} - Starting delete operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookEntity::delete_by_id(id).exec(db).await 
+        {
+            Ok(result) => 
+            {
+                if result.rows_affected > 0 
+                {
+                    let output = ModelOutput::success("deleted".to_string(), "Webhook deleted successfully".to_string());
+                    if self.verbose { info!("{}::{} - Success: Webhook {} deleted", self.this_class, this_method, id); }
+                    if self.log { info!("LOG: {}::{} - Webhook {} deleted", self.this_class, this_method, id); }
+                    output
+                } 
+                else 
+                {
+                    let output = ModelOutput::not_found("Webhook not found".to_string());
+                    if self.verbose { info!("{}::{} - Webhook {} not found", self.this_class, this_method, id); }
+                    output
+                }
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Disable
+    pub async fn disable(&self, db: &DbConn, id: i32) -> ModelOutput<WebhookModel>
+    {
+        let this_method = "disable";
+        if self.verbose { debug!("{}::{} - Starting disable operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookEntity::find_by_id(id).one(db).await
+        {
+            Ok(Some(existing)) =>
+            {
+                let mut active: WebhookActiveModel = existing.into();
+                active.enable = sea_orm::Set(false);
+
+                match active.update(db).await
+                {
+                    Ok(updated) => ModelOutput::success(updated, "Webhook disabled successfully".to_string()),
+                    Err(e) => {
+                        let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                        error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                        ModelOutput::db_error(error_msg, &e)
+                    }
+                }
+            }
+            Ok(None) => ModelOutput::not_found("Webhook not found".to_string()),
+            Err(e) => {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Enable
+    pub async fn enable(&self, db: &DbConn, id: i32) -> ModelOutput<WebhookModel>
+    {
+        let this_method = "enable";
+        if self.verbose { debug!("{}::{} - Starting enable operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookEntity::find_by_id(id).one(db).await
+        {
+            Ok(Some(existing)) =>
+            {
+                let mut active: WebhookActiveModel = existing.into();
+                active.enable = sea_orm::Set(true);
+
+                match active.update(db).await
+                {
+                    Ok(updated) => ModelOutput::success(updated, "Webhook enabled successfully".to_string()),
+                    Err(e) => {
+                        let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                        error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                        ModelOutput::db_error(error_msg, &e)
+                    }
+                }
+            }
+            Ok(None) => ModelOutput::not_found("Webhook not found".to_string()),
+            Err(e) => {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+
+    //------------------------- Status (Toggle Enable)
+    pub async fn status(&self, db: &DbConn, id: i32) -> ModelOutput<WebhookModel>
+    {
+        let this_method = "status";
+        if self.verbose { debug!("{}::{} - Starting status operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookEntity::find_by_id(id).one(db).await
+        {
+            Ok(Some(existing)) =>
+            {
+                // Get the current enable value before moving existing
+                let current_enable = existing.enable;
+                let mut active: WebhookActiveModel = existing.into();
+                // Toggle the enable field: if true, set to false; if false, set to true
+                active.enable = sea_orm::Set(!current_enable);
+
+                match active.update(db).await
+                {
+                    Ok(updated) => {
+                        let message = if current_enable {
+                            "Webhook disabled successfully".to_string()
+                        } else {
+                            "Webhook enabled successfully".to_string()
+                        };
+                        ModelOutput::success(updated, message)
+                    },
+                    Err(e) => {
+                        let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                        error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                        ModelOutput::db_error(error_msg, &e)
+                    }
+                }
+            }
+            Ok(None) => ModelOutput::not_found("Webhook not found".to_string()),
+            Err(e) => {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                ModelOutput::db_error(error_msg, &e)
+            }
+        }
+    }
+}
\ No newline at end of file
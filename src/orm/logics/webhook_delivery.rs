@@ -0,0 +1,209 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/logics/webhook_delivery.rs
+
+//--------------------------------------------------------------------------------- Description
+// ORM logic for webhook_delivery
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use tracing::{info, error, debug};
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, QueryFilter, ColumnTrait, Condition};
+use crate::orm::models::webhook_delivery::{ActiveModel as WebhookDeliveryActiveModel, Entity as WebhookDeliveryEntity, Model as WebhookDeliveryModel, Column as WebhookDeliveryColumn};
+use crate::logics::general::ModelOutput;
+use super::page::Page;
+
+//--------------------------------------------------------------------------------- Class
+pub struct WebhookDeliveryORM 
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub module: String,
+}
+
+impl WebhookDeliveryORM
+{
+    //------------------------- New
+    pub fn new(verbose: bool, log: bool) -> Self 
+    {
+        Self 
+        {
+            verbose,
+            log,
+            this_class: "WebhookDeliveryORM".to_string(),
+            module: "webhook_delivery".to_string(),
+        }
+    }
+
+    //------------------------- Add
+    pub async fn add(&self, db: &DbConn, item: WebhookDeliveryActiveModel) -> ModelOutput<WebhookDeliveryModel> 
+    {
+        let this_method = "add";
+        if self.verbose { debug!("{}::{} - Starting add operation", self.this_class, this_method); }
+
+        match item.insert(db).await 
+        {
+            Ok(model) => {
+                let output = ModelOutput::success(model, "WebhookDelivery added successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: WebhookDelivery added", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - WebhookDelivery added", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Items
+    pub async fn items(&self, db: &DbConn, filters: HashMap<String, String>) -> ModelOutput<Vec<WebhookDeliveryModel>> 
+    {
+        self.items_in(db, filters, Condition::all()).await
+    }
+
+    //------------------------- Items In
+    /// Items limited to the rows matching the scope condition
+    pub async fn items_in(&self, db: &DbConn, filters: HashMap<String, String>, scope: Condition) -> ModelOutput<Vec<WebhookDeliveryModel>> 
+    {
+        let this_method = "items";
+        if self.verbose { debug!("{}::{} - Starting items operation with filters: {:?}", self.this_class, this_method, filters); }
+
+        let mut query = WebhookDeliveryEntity::find().filter(scope);
+        if !filters.is_empty() {
+            let mut condition = Condition::all();
+
+            if let Some(id_str) = filters.get("id") && let Ok(id) = id_str.parse::<i32>() { condition = condition.add(WebhookDeliveryColumn::Id.eq(id)); }
+            if let Some(webhook_id_str) = filters.get("webhook_id") && let Ok(webhook_id) = webhook_id_str.parse::<i32>() { condition = condition.add(WebhookDeliveryColumn::WebhookId.eq(webhook_id)); }
+            if let Some(event) = filters.get("event") { condition = condition.add(WebhookDeliveryColumn::Event.eq(event)); }
+            if let Some(status) = filters.get("status") { condition = condition.add(WebhookDeliveryColumn::Status.eq(status)); }
+            if let Some(response_code_str) = filters.get("response_code") && let Ok(response_code) = response_code_str.parse::<i32>() { condition = condition.add(WebhookDeliveryColumn::ResponseCode.eq(response_code)); }
+            if let Some(date) = filters.get("date") { condition = condition.add(WebhookDeliveryColumn::Date.contains(date)); }
+
+            query = query.filter(condition);
+        }
+
+        let page = match Page::from_filters(&filters, WebhookDeliveryColumn::Id) 
+        {
+            Ok(page) => page,
+            Err(e) => return ModelOutput::invalid(e),
+        };
+
+        match page.fetch(db, query).await 
+        {
+            Ok((items, total)) => 
+            {
+                let message = if filters.is_empty() { "WebhookDeliveries retrieved successfully".to_string() } else { format!("Filtered webhook deliveries retrieved successfully (found {} items)", items.len()) };
+                let output = ModelOutput::success(items, message).with_total(total);
+                if self.verbose { info!("{}::{} - Success: WebhookDeliveries retrieved", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - WebhookDeliveries retrieved", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Item
+    pub async fn item(&self, db: &DbConn, id: i32) -> ModelOutput<WebhookDeliveryModel> 
+    {
+        let this_method = "item";
+        if self.verbose { debug!("{}::{} - Starting item operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookDeliveryEntity::find_by_id(id).one(db).await 
+        {
+            Ok(Some(model)) => 
+            {
+                let output = ModelOutput::success(model, "WebhookDelivery retrieved successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: WebhookDelivery {} found", self.this_class, this_method, id); }
+                if self.log { info!("LOG: {}::{} - WebhookDelivery {} retrieved", self.this_class, this_method, id); }
+                output
+            }
+            Ok(None) => 
+            {
+                let output = ModelOutput::not_found("WebhookDelivery not found".to_string());
+                if self.verbose { info!("{}::{} - WebhookDelivery {} not found", self.this_class, this_method, id); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Update
+    pub async fn update(&self, db: &DbConn, item: WebhookDeliveryActiveModel) -> ModelOutput<WebhookDeliveryModel> 
+    {
+        let this_method = "update";
+        if self.verbose { debug!("{}::{} - Starting update operation", self.this_class, this_method); }
+
+        match item.update(db).await 
+        {
+            Ok(model) => 
+            {
+                let output = ModelOutput::success(model, "WebhookDelivery updated successfully".to_string());
+                if self.verbose { info!("{}::{} - Success: WebhookDelivery updated", self.this_class, this_method); }
+                if self.log { info!("LOG: {}::{} - WebhookDelivery updated", self.this_class, this_method); }
+                output
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+
+    //------------------------- Delete
+    pub async fn delete(&self, db: &DbConn, id: i32) -> ModelOutput<String> 
+    {
+        let this_method = "delete";
+        if self.verbose { debug!("{}::{} - Starting delete operation for id: {}", self.this_class, this_method, id); }
+
+        match WebhookDeliveryEntity::delete_by_id(id).exec(db).await 
+        {
+            Ok(result) => 
+            {
+                if result.rows_affected > 0 
+                {
+                    let output = ModelOutput::success("deleted".to_string(), "WebhookDelivery deleted successfully".to_string());
+                    if self.verbose { info!("{}::{} - Success: WebhookDelivery {} deleted", self.this_class, this_method, id); }
+                    if self.log { info!("LOG: {}::{} - WebhookDelivery {} deleted", self.this_class, this_method, id); }
+                    output
+                } 
+                else 
+                {
+                    let output = ModelOutput::not_found("WebhookDelivery not found".to_string());
+                    if self.verbose { info!("{}::{} - WebhookDelivery {} not found", self.this_class, this_method, id); }
+                    output
+                }
+            }
+            Err(e) => 
+            {
+                let error_msg = format!("Database error in {}::{}: {}", self.this_class, this_method, e);
+                let output = ModelOutput::db_error(error_msg.clone(), &e);
+                error!("{}::{} - Error: {}", self.this_class, this_method, error_msg);
+                if self.log { error!("LOG: {}::{} - Error: {}", self.this_class, this_method, error_msg); }
+                output
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,282 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250901_000001_create_tables.rs
+
+//--------------------------------------------------------------------------------- Description
+// Initial schema, equivalent to db_postgres.sql; existing tables and indexes are left untouched
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+use super::unique_index;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        //------------------------- Config
+        manager.create_table(Table::create().table(Config::Table).if_not_exists()
+            .col(pk_auto(Config::Id))
+            .col(string(Config::Name))
+            .col(text(Config::TimeZone))
+            .col(text(Config::PathApi))
+            .col(text(Config::PathGui))
+            .col(text(Config::WebapiTitle))
+            .col(text(Config::WebapiDescription))
+            .col(text(Config::WebapiVersion))
+            .col(text(Config::WebapiOpenapiUrl))
+            .col(text(Config::WebapiDocsUrl))
+            .col(text(Config::WebapiRedocUrl))
+            .col(text(Config::WebapiKey))
+            .col(text(Config::WebapiHost))
+            .col(integer_null(Config::WebapiPort).default(0))
+            .col(integer_null(Config::WebapiWorkers).default(0))
+            .col(text(Config::NginxApiHost))
+            .col(integer_null(Config::NginxApiPort).default(0))
+            .col(text(Config::NginxApiKey))
+            .col(text(Config::NginxGuiHost))
+            .col(integer_null(Config::NginxGuiPort).default(0))
+            .col(text(Config::NginxGuiKey))
+            .col(text(Config::GitEmail))
+            .col(text(Config::GitName))
+            .col(text(Config::GitKey))
+            .col(text(Config::HotspodSsid))
+            .col(text(Config::HotspodIp))
+            .col(text(Config::HotspodPass))
+            .col(text(Config::WifiSsid))
+            .col(text(Config::WifiIp))
+            .col(text(Config::WifiPass))
+            .col(boolean(Config::Debug).default(false))
+            .col(boolean(Config::Log).default(false))
+            .col(boolean(Config::Verbose).default(false))
+            .to_owned()).await?;
+        unique_index(manager, Config::Table, "config_unique", vec![Config::Name]).await?;
+
+        //------------------------- User
+        manager.create_table(Table::create().table(User::Table).if_not_exists()
+            .col(pk_auto(User::Id))
+            .col(string(User::Name))
+            .col(text(User::Username))
+            .col(text(User::Password))
+            .col(text(User::Key))
+            .col(text(User::Email))
+            .col(text(User::Phone))
+            .col(text(User::TgId))
+            .col(boolean(User::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, User::Table, "user_unique", vec![User::Name]).await?;
+
+        //------------------------- Port
+        manager.create_table(Table::create().table(Port::Table).if_not_exists()
+            .col(pk_auto(Port::Id))
+            .col(integer(Port::UserId).default(0))
+            .col(string(Port::Name))
+            .col(integer(Port::Pin).default(0))
+            .col(integer(Port::Line).default(0))
+            .col(integer(Port::Value).default(0))
+            .col(text(Port::Description))
+            .col(boolean(Port::Enable).default(false))
+            .col(text(Port::Protocol))
+            .col(text(Port::Type))
+            .to_owned()).await?;
+        unique_index(manager, Port::Table, "port_unique", vec![Port::UserId, Port::Name]).await?;
+
+        //------------------------- Zone
+        manager.create_table(Table::create().table(Zone::Table).if_not_exists()
+            .col(pk_auto(Zone::Id))
+            .col(integer(Zone::UserId).default(0))
+            .col(string(Zone::Name))
+            .col(text(Zone::Description))
+            .col(boolean(Zone::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, Zone::Table, "zone_unique", vec![Zone::UserId, Zone::Name]).await?;
+
+        //------------------------- Device
+        manager.create_table(Table::create().table(Device::Table).if_not_exists()
+            .col(pk_auto(Device::Id))
+            .col(integer(Device::ZoneId).default(0))
+            .col(integer(Device::PortId).default(0))
+            .col(integer(Device::PowerId).default(0))
+            .col(integer(Device::CommandId).default(0))
+            .col(integer(Device::Value).default(0))
+            .col(integer(Device::Tune).default(0))
+            .col(text(Device::Date))
+            .col(text(Device::Address))
+            .col(string(Device::Name))
+            .col(text(Device::Description))
+            .col(boolean(Device::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, Device::Table, "device_unique", vec![Device::ZoneId, Device::Name]).await?;
+
+        //------------------------- Device Command
+        manager.create_table(Table::create().table(DeviceCommand::Table).if_not_exists()
+            .col(pk_auto(DeviceCommand::Id))
+            .col(integer(DeviceCommand::DeviceId).default(0))
+            .col(string(DeviceCommand::Name))
+            .col(integer_null(DeviceCommand::ValueFrom).default(0))
+            .col(integer_null(DeviceCommand::ValueTo).default(0))
+            .col(integer_null(DeviceCommand::Delay).default(0))
+            .col(text(DeviceCommand::Description))
+            .col(boolean(DeviceCommand::Reload).default(false))
+            .col(boolean(DeviceCommand::Enable).default(false))
+            .col(text(DeviceCommand::Type))
+            .to_owned()).await?;
+        unique_index(manager, DeviceCommand::Table, "device_command_unique", vec![DeviceCommand::DeviceId, DeviceCommand::Name]).await?;
+
+        //------------------------- Log
+        manager.create_table(Table::create().table(Log::Table).if_not_exists()
+            .col(pk_auto(Log::Id))
+            .col(text(Log::Date))
+            .col(text(Log::Name))
+            .col(boolean(Log::Status).default(false))
+            .col(text(Log::Data))
+            .to_owned()).await?;
+
+        //------------------------- Timer
+        manager.create_table(Table::create().table(Timer::Table).if_not_exists()
+            .col(pk_auto(Timer::Id))
+            .col(integer(Timer::UserId).default(0))
+            .col(string(Timer::Name))
+            .col(text(Timer::Description))
+            .col(boolean(Timer::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, Timer::Table, "timer_unique", vec![Timer::UserId, Timer::Name]).await?;
+
+        //------------------------- Timer Item
+        manager.create_table(Table::create().table(TimerItem::Table).if_not_exists()
+            .col(pk_auto(TimerItem::Id))
+            .col(integer(TimerItem::TimerId).default(0))
+            .col(string(TimerItem::Name))
+            .col(text(TimerItem::ValueFrom))
+            .col(text(TimerItem::ValueTo))
+            .col(text(TimerItem::Description))
+            .col(boolean(TimerItem::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, TimerItem::Table, "timer_item_unique", vec![TimerItem::TimerId, TimerItem::Name]).await?;
+
+        //------------------------- Timer Device
+        manager.create_table(Table::create().table(TimerDevice::Table).if_not_exists()
+            .col(pk_auto(TimerDevice::Id))
+            .col(integer(TimerDevice::TimerId).default(0))
+            .col(integer(TimerDevice::DeviceId).default(0))
+            .col(integer(TimerDevice::CommandId).default(0))
+            .col(text(TimerDevice::Description))
+            .col(boolean(TimerDevice::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, TimerDevice::Table, "timer_device_unique", vec![TimerDevice::TimerId, TimerDevice::DeviceId, TimerDevice::CommandId]).await?;
+
+        //------------------------- Timer Limit
+        manager.create_table(Table::create().table(TimerLimit::Table).if_not_exists()
+            .col(pk_auto(TimerLimit::Id))
+            .col(integer(TimerLimit::DeviceId).default(0))
+            .col(integer(TimerLimit::CommandFromId).default(0))
+            .col(integer(TimerLimit::CommandToId).default(0))
+            .col(integer(TimerLimit::Value).default(0))
+            .col(text(TimerLimit::Description))
+            .col(boolean(TimerLimit::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, TimerLimit::Table, "timer_limit_unique", vec![TimerLimit::DeviceId, TimerLimit::CommandFromId, TimerLimit::CommandToId]).await?;
+
+        //------------------------- Zone Command
+        manager.create_table(Table::create().table(ZoneCommand::Table).if_not_exists()
+            .col(pk_auto(ZoneCommand::Id))
+            .col(integer(ZoneCommand::ZoneId).default(0))
+            .col(string(ZoneCommand::Name))
+            .col(text(ZoneCommand::Description))
+            .col(boolean(ZoneCommand::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, ZoneCommand::Table, "zone_command_unique", vec![ZoneCommand::ZoneId, ZoneCommand::Name]).await?;
+
+        //------------------------- Zone Command If
+        manager.create_table(Table::create().table(ZoneCommandIf::Table).if_not_exists()
+            .col(pk_auto(ZoneCommandIf::Id))
+            .col(string(ZoneCommandIf::Name))
+            .col(integer(ZoneCommandIf::ZoneCommandId).default(0))
+            .col(integer(ZoneCommandIf::DeviceId).default(0))
+            .col(integer(ZoneCommandIf::CommandId).default(0))
+            .col(text(ZoneCommandIf::Description))
+            .col(boolean(ZoneCommandIf::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, ZoneCommandIf::Table, "zone_command_if_unique", vec![ZoneCommandIf::ZoneCommandId, ZoneCommandIf::Name]).await?;
+
+        //------------------------- Zone Command Action
+        manager.create_table(Table::create().table(ZoneCommandAction::Table).if_not_exists()
+            .col(pk_auto(ZoneCommandAction::Id))
+            .col(string(ZoneCommandAction::Name))
+            .col(integer(ZoneCommandAction::ZoneCommandId).default(0))
+            .col(integer(ZoneCommandAction::DeviceId).default(0))
+            .col(integer_null(ZoneCommandAction::CommandId).default(0))
+            .col(text(ZoneCommandAction::Description))
+            .col(boolean(ZoneCommandAction::Enable).default(false))
+            .to_owned()).await?;
+        unique_index(manager, ZoneCommandAction::Table, "zone_command_action_unique", vec![ZoneCommandAction::ZoneCommandId, ZoneCommandAction::Name]).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        manager.drop_table(Table::drop().table(ZoneCommandAction::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(ZoneCommandIf::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(ZoneCommand::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(TimerLimit::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(TimerDevice::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(TimerItem::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Timer::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Log::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(DeviceCommand::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Device::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Zone::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Port::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(User::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Config::Table).if_exists().to_owned()).await?;
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum Config { Table, Id, Name, TimeZone, PathApi, PathGui, WebapiTitle, WebapiDescription, WebapiVersion, WebapiOpenapiUrl, WebapiDocsUrl, WebapiRedocUrl, WebapiKey, WebapiHost, WebapiPort, WebapiWorkers, NginxApiHost, NginxApiPort, NginxApiKey, NginxGuiHost, NginxGuiPort, NginxGuiKey, GitEmail, GitName, GitKey, HotspodSsid, HotspodIp, HotspodPass, WifiSsid, WifiIp, WifiPass, Debug, Log, Verbose }
+
+#[derive(DeriveIden)]
+enum User { Table, Id, Name, Username, Password, Key, Email, Phone, TgId, Enable }
+
+#[derive(DeriveIden)]
+enum Port { Table, Id, UserId, Name, Pin, #[sea_orm(iden = "port")] Line, Value, Description, Enable, Protocol, Type }
+
+#[derive(DeriveIden)]
+enum Zone { Table, Id, UserId, Name, Description, Enable }
+
+#[derive(DeriveIden)]
+enum Device { Table, Id, ZoneId, PortId, PowerId, CommandId, Value, Tune, Date, Address, Name, Description, Enable }
+
+#[derive(DeriveIden)]
+enum DeviceCommand { Table, Id, DeviceId, Name, ValueFrom, ValueTo, Delay, Description, Reload, Enable, Type }
+
+#[derive(DeriveIden)]
+enum Log { Table, Id, Date, Name, Status, Data }
+
+#[derive(DeriveIden)]
+enum Timer { Table, Id, UserId, Name, Description, Enable }
+
+#[derive(DeriveIden)]
+enum TimerItem { Table, Id, TimerId, Name, ValueFrom, ValueTo, Description, Enable }
+
+#[derive(DeriveIden)]
+enum TimerDevice { Table, Id, TimerId, DeviceId, CommandId, Description, Enable }
+
+#[derive(DeriveIden)]
+enum TimerLimit { Table, Id, DeviceId, CommandFromId, CommandToId, Value, Description, Enable }
+
+#[derive(DeriveIden)]
+enum ZoneCommand { Table, Id, ZoneId, Name, Description, Enable }
+
+#[derive(DeriveIden)]
+enum ZoneCommandIf { Table, Id, Name, ZoneCommandId, DeviceId, CommandId, Description, Enable }
+
+#[derive(DeriveIden)]
+enum ZoneCommandAction { Table, Id, Name, ZoneCommandId, DeviceId, CommandId, Description, Enable }
@@ -0,0 +1,37 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250902_000001_zone_command_if_type.rs
+
+//--------------------------------------------------------------------------------- Description
+// Condition type (None, Equal, Unequal, Dtu, Utd) on zone_command_if
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        if manager.has_column("zone_command_if", "type").await? { return Ok(()); }
+
+        manager.alter_table(Table::alter().table(ZoneCommandIf::Table)
+            .add_column(string_len(ZoneCommandIf::Type, 16).default("None"))
+            .to_owned()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        manager.alter_table(Table::alter().table(ZoneCommandIf::Table)
+            .drop_column(ZoneCommandIf::Type)
+            .to_owned()).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum ZoneCommandIf { Table, Type }
@@ -0,0 +1,66 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250903_000001_user_access.rs
+
+//--------------------------------------------------------------------------------- Description
+// Roles (user_role, all_zones grants every zone) and zone grants (user_zone); users that exist
+// before this migration become Admin
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+use super::unique_index;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        //------------------------- User Role
+        let seed = !manager.has_table("user_role").await?;
+        manager.create_table(Table::create().table(UserRole::Table).if_not_exists()
+            .col(pk_auto(UserRole::Id))
+            .col(integer(UserRole::UserId).default(0))
+            .col(string_len(UserRole::Role, 16).default("Viewer"))
+            .col(boolean(UserRole::AllZones).default(false))
+            .to_owned()).await?;
+        unique_index(manager, UserRole::Table, "user_role_unique", vec![UserRole::UserId]).await?;
+
+        if seed
+        {
+            let mut insert = Query::insert();
+            insert.into_table(UserRole::Table)
+                .columns([UserRole::UserId, UserRole::Role])
+                .select_from(Query::select().column(User::Id).expr(Expr::val("Admin")).from(User::Table).to_owned())
+                .map_err(|e| DbErr::Migration(e.to_string()))?;
+            manager.exec_stmt(insert).await?;
+        }
+
+        //------------------------- User Zone
+        manager.create_table(Table::create().table(UserZone::Table).if_not_exists()
+            .col(pk_auto(UserZone::Id))
+            .col(integer(UserZone::UserId).default(0))
+            .col(integer(UserZone::ZoneId).default(0))
+            .to_owned()).await?;
+        unique_index(manager, UserZone::Table, "user_zone_unique", vec![UserZone::UserId, UserZone::ZoneId]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        manager.drop_table(Table::drop().table(UserZone::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(UserRole::Table).if_exists().to_owned()).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum User { Table, Id }
+
+#[derive(DeriveIden)]
+enum UserRole { Table, Id, UserId, Role, AllZones }
+
+#[derive(DeriveIden)]
+enum UserZone { Table, Id, UserId, ZoneId }
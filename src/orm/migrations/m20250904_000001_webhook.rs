@@ -0,0 +1,60 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250904_000001_webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// Outgoing webhooks (webhook) and the log of every delivery made to them (webhook_delivery)
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        //------------------------- Webhook
+        manager.create_table(Table::create().table(Webhook::Table).if_not_exists()
+            .col(pk_auto(Webhook::Id))
+            .col(integer(Webhook::UserId).default(0))
+            .col(text(Webhook::Name))
+            .col(text(Webhook::Url))
+            .col(text(Webhook::Secret))
+            .col(text(Webhook::Events))
+            .col(text(Webhook::Description))
+            .col(boolean(Webhook::Enable).default(true))
+            .to_owned()).await?;
+
+        //------------------------- Webhook Delivery
+        manager.create_table(Table::create().table(WebhookDelivery::Table).if_not_exists()
+            .col(pk_auto(WebhookDelivery::Id))
+            .col(integer(WebhookDelivery::WebhookId).default(0))
+            .col(text(WebhookDelivery::Event))
+            .col(text(WebhookDelivery::Payload))
+            .col(string_len(WebhookDelivery::Status, 16).default("pending"))
+            .col(integer(WebhookDelivery::Attempts).default(0))
+            .col(integer(WebhookDelivery::ResponseCode).default(0))
+            .col(text(WebhookDelivery::Error))
+            .col(text(WebhookDelivery::Date))
+            .col(text(WebhookDelivery::Updated))
+            .to_owned()).await?;
+        manager.create_index(Index::create().if_not_exists().name("webhook_delivery_webhook_id").table(WebhookDelivery::Table).col(WebhookDelivery::WebhookId).to_owned()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        manager.drop_table(Table::drop().table(WebhookDelivery::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Webhook::Table).if_exists().to_owned()).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum Webhook { Table, Id, UserId, Name, Url, Secret, Events, Description, Enable }
+
+#[derive(DeriveIden)]
+enum WebhookDelivery { Table, Id, WebhookId, Event, Payload, Status, Attempts, ResponseCode, Error, Date, Updated }
@@ -0,0 +1,54 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250905_000001_notification.rs
+
+//--------------------------------------------------------------------------------- Description
+// Notification subscriptions (notification) and the Telegram chat learned for each user (telegram_chat)
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+use super::unique_index;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        //------------------------- Notification
+        manager.create_table(Table::create().table(Notification::Table).if_not_exists()
+            .col(pk_auto(Notification::Id))
+            .col(integer(Notification::UserId).default(0))
+            .col(integer(Notification::DeviceId).default(0))
+            .col(string_len(Notification::Kind, 16).default("alarm"))
+            .col(text(Notification::Description))
+            .col(boolean(Notification::Enable).default(true))
+            .to_owned()).await?;
+
+        //------------------------- Telegram Chat
+        manager.create_table(Table::create().table(TelegramChat::Table).if_not_exists()
+            .col(pk_auto(TelegramChat::Id))
+            .col(integer(TelegramChat::UserId).default(0))
+            .col(text(TelegramChat::ChatId))
+            .col(text(TelegramChat::Username))
+            .col(text(TelegramChat::Date))
+            .to_owned()).await?;
+        unique_index(manager, TelegramChat::Table, "telegram_chat_unique", vec![TelegramChat::UserId]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        manager.drop_table(Table::drop().table(TelegramChat::Table).if_exists().to_owned()).await?;
+        manager.drop_table(Table::drop().table(Notification::Table).if_exists().to_owned()).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum Notification { Table, Id, UserId, DeviceId, Kind, Description, Enable }
+
+#[derive(DeriveIden)]
+enum TelegramChat { Table, Id, UserId, ChatId, Username, Date }
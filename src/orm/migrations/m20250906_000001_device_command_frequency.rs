@@ -0,0 +1,37 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250906_000001_device_command_frequency.rs
+
+//--------------------------------------------------------------------------------- Description
+// PWM frequency (Hz) set by a device_command on PWM ports, NULL keeps the current one
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        if manager.has_column("device_command", "frequency").await? { return Ok(()); }
+
+        manager.alter_table(Table::alter().table(DeviceCommand::Table)
+            .add_column(integer_null(DeviceCommand::Frequency))
+            .to_owned()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        manager.alter_table(Table::alter().table(DeviceCommand::Table)
+            .drop_column(DeviceCommand::Frequency)
+            .to_owned()).await
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum DeviceCommand { Table, Frequency }
@@ -0,0 +1,53 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250907_000002_hash_passwords.rs
+
+//--------------------------------------------------------------------------------- Description
+// Hash the passwords still stored as plain text, login only accepts argon2 hashes from now on;
+// an empty password stays empty, nobody can log in with it
+
+//--------------------------------------------------------------------------------- Import
+use argon2::{Argon2, PasswordHash, PasswordHasher};
+use argon2::password_hash::{rand_core::OsRng, SaltString};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        let select = Query::select().columns([User::Id, User::Password]).from(User::Table).to_owned();
+        for row in db.query_all(backend.build(&select)).await?
+        {
+            let id: i32 = row.try_get("", "id")?;
+            let password: String = row.try_get("", "password")?;
+            if password.is_empty() || PasswordHash::new(&password).is_ok() { continue; }
+
+            let salt = SaltString::generate(&mut OsRng);
+            let hash = Argon2::default().hash_password(password.as_bytes(), &salt)
+                .map_err(|e| DbErr::Migration(format!("Failed to hash the password of user {}: {}", id, e)))?
+                .to_string();
+
+            let update = Query::update().table(User::Table).value(User::Password, hash).and_where(Expr::col(User::Id).eq(id)).to_owned();
+            db.execute(backend.build(&update)).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        // A hash cannot be turned back into the password
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum User { Table, Id, Password }
@@ -0,0 +1,46 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250907_000003_telegram_link.rs
+
+//--------------------------------------------------------------------------------- Description
+// One-time link codes on telegram_chat: the bot trusts a chat only once its sender has sent the
+// code issued through the API, and then by the numeric Telegram id it stores in sender_id.
+// Chats recorded before this by @username stay untrusted until they are linked
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        for (name, column) in [("sender_id", TelegramChat::SenderId), ("link_code", TelegramChat::LinkCode), ("link_expires", TelegramChat::LinkExpires)]
+        {
+            if manager.has_column("telegram_chat", name).await? { continue; }
+            manager.alter_table(Table::alter().table(TelegramChat::Table)
+                .add_column(text(column).default(""))
+                .to_owned()).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        for column in [TelegramChat::SenderId, TelegramChat::LinkCode, TelegramChat::LinkExpires]
+        {
+            manager.alter_table(Table::alter().table(TelegramChat::Table)
+                .drop_column(column)
+                .to_owned()).await?;
+        }
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum TelegramChat { Table, SenderId, LinkCode, LinkExpires }
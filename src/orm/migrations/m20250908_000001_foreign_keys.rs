@@ -0,0 +1,223 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/m20250908_000001_foreign_keys.rs
+
+//--------------------------------------------------------------------------------- Description
+// The foreign keys of db_postgres.sql, for every database built by the migrations. None where 0
+// means "none" or "every" (device.power_id and command_id, notification.device_id), the sample
+// "none" command with device_id 0 is removed for device_command.device_id. Rows pointing at a
+// missing parent stop the migration with the table and column to fix. SQLite cannot add a
+// foreign key to an existing table, so each table is rebuilt with them, parents first
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+//--------------------------------------------------------------------------------- Foreign Keys
+/// (table, column, referenced table), a table comes after every table it references
+pub(crate) const FOREIGN_KEYS: [(&str, &str, &str); 18] = [
+    ("device", "zone_id", "zone"),
+    ("device", "port_id", "port"),
+    ("device_command", "device_id", "device"),
+    ("timer_device", "timer_id", "timer"),
+    ("timer_device", "device_id", "device"),
+    ("timer_device", "command_id", "device_command"),
+    ("timer_item", "timer_id", "timer"),
+    ("timer_limit", "device_id", "device"),
+    ("timer_limit", "command_from_id", "device_command"),
+    ("timer_limit", "command_to_id", "device_command"),
+    ("webhook_delivery", "webhook_id", "webhook"),
+    ("zone_command", "zone_id", "zone"),
+    ("zone_command_action", "zone_command_id", "zone_command"),
+    ("zone_command_action", "device_id", "device"),
+    ("zone_command_action", "command_id", "device_command"),
+    ("zone_command_if", "zone_command_id", "zone_command"),
+    ("zone_command_if", "device_id", "device"),
+    ("zone_command_if", "command_id", "device_command"),
+];
+
+//--------------------------------------------------------------------------------- Migration
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration
+{
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        // The "none" command of older samples, device.command_id 0 already means no command
+        let none = Query::delete().from_table(DeviceCommand::Table)
+            .and_where(Expr::col(DeviceCommand::Id).eq(0))
+            .and_where(Expr::col(DeviceCommand::DeviceId).eq(0))
+            .to_owned();
+        db.execute(backend.build(&none)).await?;
+
+        for (table, column, parent) in FOREIGN_KEYS { orphans(db, backend, table, column, parent).await?; }
+
+        match backend
+        {
+            DatabaseBackend::Sqlite =>
+            {
+                let mut tables: Vec<&str> = FOREIGN_KEYS.iter().map(|(table, _, _)| *table).collect();
+                tables.dedup();
+                for table in tables { rebuild(db, table).await?; }
+            }
+            _ =>
+            {
+                for (table, column, parent) in FOREIGN_KEYS
+                {
+                    let name = format!("{}_{}_fkey", table, column);
+                    if has_foreign_key(db, backend, table, &name).await? { continue; }
+                    manager.create_foreign_key(ForeignKey::create()
+                        .name(&name)
+                        .from(Alias::new(table), Alias::new(column))
+                        .to(Alias::new(parent), Alias::new("id"))
+                        .to_owned()).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>
+    {
+        // SQLite keeps them, dropping one means rebuilding the table again
+        if manager.get_database_backend() == DatabaseBackend::Sqlite { return Ok(()); }
+
+        for (table, column, _) in FOREIGN_KEYS.iter().rev()
+        {
+            manager.drop_foreign_key(ForeignKey::drop()
+                .name(format!("{}_{}_fkey", table, column))
+                .table(Alias::new(*table))
+                .to_owned()).await?;
+        }
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------- Helpers
+/// Fail with the table and column to fix when rows point at a missing parent
+async fn orphans<C: ConnectionTrait>(db: &C, backend: DatabaseBackend, table: &str, column: &str, parent: &str) -> Result<(), DbErr>
+{
+    let select = Query::select().expr_as(Expr::col(Asterisk).count(), Alias::new("orphans"))
+        .from(Alias::new(table))
+        .and_where(Expr::col(Alias::new(column)).is_not_null())
+        .and_where(Expr::col(Alias::new(column)).not_in_subquery(
+            Query::select().column(Alias::new("id")).from(Alias::new(parent)).to_owned()))
+        .to_owned();
+    let count: i64 = match db.query_one(backend.build(&select)).await?
+    {
+        Some(row) => row.try_get("", "orphans")?,
+        None => 0,
+    };
+    if count > 0
+    {
+        return Err(DbErr::Migration(format!("{} rows of {}.{} point at no {}, fix or delete them before upgrading", count, table, column, parent)));
+    }
+    Ok(())
+}
+
+/// Whether a database built from db_postgres.sql already has the constraint
+async fn has_foreign_key<C: ConnectionTrait>(db: &C, backend: DatabaseBackend, table: &str, name: &str) -> Result<bool, DbErr>
+{
+    let select = Query::select().expr(Expr::val(1))
+        .from((Alias::new("information_schema"), Alias::new("table_constraints")))
+        .and_where(Expr::col(Alias::new("constraint_type")).eq("FOREIGN KEY"))
+        .and_where(Expr::col(Alias::new("table_name")).eq(table))
+        .and_where(Expr::col(Alias::new("constraint_name")).eq(name))
+        .to_owned();
+    Ok(db.query_one(backend.build(&select)).await?.is_some())
+}
+
+/// Rebuild a SQLite table with its foreign keys: same columns, rows, indexes and AUTOINCREMENT counter
+async fn rebuild<C: ConnectionTrait>(db: &C, table: &str) -> Result<(), DbErr>
+{
+    let sql = |sql: String| Statement::from_string(DatabaseBackend::Sqlite, sql);
+
+    let declared = db.query_one(sql(format!("SELECT COUNT(*) AS keys FROM pragma_foreign_key_list('{}')", table))).await?;
+    if let Some(row) = declared && row.try_get::<i64>("", "keys")? > 0 { return Ok(()); }
+
+    let create: String = db.query_one(sql(format!("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = '{}'", table))).await?
+        .ok_or_else(|| DbErr::Migration(format!("Table {} not found", table)))?
+        .try_get("", "sql")?;
+    let (Some(open), Some(close)) = (create.find('('), create.rfind(')'))
+    else { return Err(DbErr::Migration(format!("Unexpected definition of table {}", table))); };
+
+    let keys: Vec<String> = FOREIGN_KEYS.iter()
+        .filter(|(name, _, _)| *name == table)
+        .map(|(_, column, parent)| format!("FOREIGN KEY (\"{}\") REFERENCES \"{}\" (\"id\")", column, parent))
+        .collect();
+    let indexes = db.query_all(sql(format!("SELECT sql FROM sqlite_master WHERE type = 'index' AND tbl_name = '{}' AND sql IS NOT NULL", table))).await?;
+
+    let new = format!("{}_new", table);
+    db.execute(sql(format!("CREATE TABLE \"{}\" ({}, {})", new, &create[open + 1..close], keys.join(", ")))).await?;
+    db.execute(sql(format!("INSERT INTO \"{}\" SELECT * FROM \"{}\"", new, table))).await?;
+    db.execute(sql(format!("UPDATE sqlite_sequence SET seq = (SELECT seq FROM sqlite_sequence WHERE name = '{}') WHERE name = '{}'", table, new))).await?;
+    db.execute(sql(format!("DROP TABLE \"{}\"", table))).await?;
+    db.execute(sql(format!("ALTER TABLE \"{}\" RENAME TO \"{}\"", new, table))).await?;
+    for index in indexes
+    {
+        let index: String = index.try_get("", "sql")?;
+        db.execute(sql(index)).await?;
+    }
+    Ok(())
+}
+
+//--------------------------------------------------------------------------------- Identifiers
+#[derive(DeriveIden)]
+enum DeviceCommand { Table, Id, DeviceId }
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use sea_orm::Database;
+
+    /// db_postgres.sql and the migrations declare the same foreign keys
+    #[test]
+    fn matches_db_postgres_sql()
+    {
+        let mut declared: Vec<String> = include_str!("../../../db_postgres.sql")
+            .split("ALTER TABLE ONLY public.")
+            .skip(1)
+            .filter_map(|block| {
+                let (table, rest) = block.split_once('\n')?;
+                let rest = rest.split(';').next()?;
+                let column = rest.split("FOREIGN KEY (").nth(1)?.split(')').next()?;
+                let parent = rest.split("REFERENCES public.").nth(1)?.split('(').next()?;
+                Some(format!("{}.{} -> {}", table.trim(), column, parent))
+            })
+            .collect();
+        declared.sort();
+
+        let mut migrated: Vec<String> = FOREIGN_KEYS.iter().map(|(table, column, parent)| format!("{}.{} -> {}", table, column, parent)).collect();
+        migrated.sort();
+        assert_eq!(declared, migrated);
+    }
+
+    /// The rebuilt SQLite tables keep their rows and indexes and refuse a missing parent
+    #[tokio::test]
+    async fn sqlite_enforces_them()
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+        let sql = |sql: &str| Statement::from_string(DatabaseBackend::Sqlite, sql.to_string());
+
+        for (table, column, parent) in FOREIGN_KEYS
+        {
+            let found = db.query_all(sql(&format!("SELECT \"from\", \"table\" FROM pragma_foreign_key_list('{}')", table))).await.unwrap()
+                .into_iter()
+                .any(|row| row.try_get::<String>("", "from").unwrap() == column && row.try_get::<String>("", "table").unwrap() == parent);
+            assert!(found, "{}.{} -> {}", table, column, parent);
+        }
+
+        let indexes = db.query_all(sql("SELECT name FROM sqlite_master WHERE type = 'index' AND tbl_name = 'device' AND sql IS NOT NULL")).await.unwrap();
+        assert!(!indexes.is_empty());
+        assert!(db.execute(sql("UPDATE device SET zone_id = 999 WHERE id = 1")).await.is_err());
+        assert!(db.execute(sql("DELETE FROM zone WHERE id = 1")).await.is_err());
+    }
+}
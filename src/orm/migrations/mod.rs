@@ -0,0 +1,72 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/migrations/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// Versioned schema migrations embedded in the binary (SQLite, Postgres and MySQL)
+
+//--------------------------------------------------------------------------------- Import
+pub use sea_orm_migration::prelude::*;
+use sea_orm::DatabaseConnection;
+use tracing::info;
+
+//--------------------------------------------------------------------------------- Modules
+mod m20250901_000001_create_tables;
+mod m20250902_000001_zone_command_if_type;
+mod m20250903_000001_user_access;
+mod m20250904_000001_webhook;
+mod m20250905_000001_notification;
+mod m20250906_000001_device_command_frequency;
+mod m20250907_000002_hash_passwords;
+mod m20250907_000003_telegram_link;
+mod m20250908_000001_foreign_keys;
+
+//--------------------------------------------------------------------------------- Migrator
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator
+{
+    fn migrations() -> Vec<Box<dyn MigrationTrait>>
+    {
+        vec![
+            Box::new(m20250901_000001_create_tables::Migration),
+            Box::new(m20250902_000001_zone_command_if_type::Migration),
+            Box::new(m20250903_000001_user_access::Migration),
+            Box::new(m20250904_000001_webhook::Migration),
+            Box::new(m20250905_000001_notification::Migration),
+            Box::new(m20250906_000001_device_command_frequency::Migration),
+            Box::new(m20250907_000002_hash_passwords::Migration),
+            Box::new(m20250907_000003_telegram_link::Migration),
+            Box::new(m20250908_000001_foreign_keys::Migration),
+        ]
+    }
+}
+
+//--------------------------------------------------------------------------------- Migrate
+/// Apply every pending migration, returns how many were applied
+pub async fn migrate(db: &DatabaseConnection) -> Result<usize, DbErr>
+{
+    let pending = Migrator::get_pending_migrations(db).await?.len();
+    if pending > 0
+    {
+        Migrator::up(db, None).await?;
+        info!("Database migrations: {} applied", pending);
+    }
+    Ok(pending)
+}
+
+//--------------------------------------------------------------------------------- Helpers
+/// Create a unique index unless it already exists (databases built from db_postgres.sql have them)
+pub(crate) async fn unique_index<T, C>(manager: &SchemaManager<'_>, table: T, name: &str, columns: Vec<C>) -> Result<(), DbErr>
+where
+    T: IntoIden,
+    C: IntoIden,
+{
+    let table = table.into_iden();
+    if manager.has_index(table.to_string(), name).await? { return Ok(()); }
+
+    let mut index = Index::create();
+    index.name(name).table(table).unique();
+    for column in columns { index.col(column); }
+    manager.create_index(index).await
+}
@@ -1,2 +1,3 @@
 pub mod logics;
+pub mod migrations;
 pub mod models;
\ No newline at end of file
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "device")]
 #[schema(description = "IoT device model representing physical devices in the system")]
+#[graphql(complex, name = "Device")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -36,6 +38,82 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::zone::Entity", from = "Column::ZoneId", to = "super::zone::Column::Id")]
+    Zone,
+    #[sea_orm(belongs_to = "super::port::Entity", from = "Column::PortId", to = "super::port::Column::Id")]
+    Port,
+    #[sea_orm(belongs_to = "super::port::Entity", from = "Column::PowerId", to = "super::port::Column::Id")]
+    Power,
+    #[sea_orm(belongs_to = "super::device_command::Entity", from = "Column::CommandId", to = "super::device_command::Column::Id")]
+    Command,
+    #[sea_orm(has_many = "super::device_command::Entity")]
+    DeviceCommand,
+    #[sea_orm(has_many = "super::timer_device::Entity")]
+    TimerDevice,
+    #[sea_orm(has_many = "super::timer_limit::Entity")]
+    TimerLimit,
+    #[sea_orm(has_many = "super::zone_command_if::Entity")]
+    ZoneCommandIf,
+    #[sea_orm(has_many = "super::zone_command_action::Entity")]
+    ZoneCommandAction,
+}
+
+impl Related<super::zone::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Zone.def()
+    }
+}
+
+impl Related<super::port::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Port.def()
+    }
+}
+
+impl Related<super::device_command::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::DeviceCommand.def()
+    }
+}
+
+impl Related<super::timer_device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::TimerDevice.def()
+    }
+}
+
+impl Related<super::timer_limit::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::TimerLimit.def()
+    }
+}
+
+impl Related<super::zone_command_if::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommandIf.def()
+    }
+}
+
+impl Related<super::zone_command_action::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommandAction.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
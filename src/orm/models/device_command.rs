@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "device_command")]
 #[schema(description = "Device command model representing actions that can be sent to a device")]
+#[graphql(complex, name = "DeviceCommand")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -24,6 +26,7 @@ pub struct Model {
     pub value_from: Option<i32>,
     pub value_to: Option<i32>,
     pub delay: Option<i32>,
+    pub frequency: Option<i32>,
     #[sea_orm(column_type = "Text")]
     pub description: String,
     pub reload: bool,
@@ -33,6 +36,18 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::device::Entity", from = "Column::DeviceId", to = "super::device::Column::Id")]
+    Device,
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "log")]
 #[schema(description = "Log model representing system log entries")]
+#[graphql(name = "Log")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
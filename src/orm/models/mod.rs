@@ -9,7 +9,13 @@ pub mod timer_device;
 pub mod timer_item;
 pub mod timer_limit;
 pub mod user;
+pub mod user_role;
+pub mod user_zone;
 pub mod zone;
 pub mod zone_command;
 pub mod zone_command_action;
 pub mod zone_command_if;
+pub mod webhook;
+pub mod webhook_delivery;
+pub mod notification;
+pub mod telegram_chat;
@@ -0,0 +1,47 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/models/notification.rs
+
+//--------------------------------------------------------------------------------- Description
+// Notification model
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use async_graphql::SimpleObject;
+
+//--------------------------------------------------------------------------------- Attribute
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[sea_orm(table_name = "notification")]
+#[schema(description = "Notification model: a user's Telegram subscription to a device alarm, fuse trip or timer limit cutoff")]
+#[graphql(name = "Notification")]
+//--------------------------------------------------------------------------------- Model
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    /// Watched device; 0 means every device of the user
+    pub device_id: i32,
+    /// alarm (value leaves 0), fuse (value drops to 0) or limit (timer limit cutoff)
+    pub kind: String,
+    #[sea_orm(column_type = "Text")]
+    pub description: String,
+    pub enable: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::device::Entity", from = "Column::DeviceId", to = "super::device::Column::Id")]
+    Device,
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "port")]
 #[schema(description = "Port model representing communication ports on a device")]
+#[graphql(complex, name = "Port")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -34,6 +36,18 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(has_many = "super::device::Entity")]
+    Device,
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
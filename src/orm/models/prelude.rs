@@ -8,7 +8,13 @@ pub use super::timer_device::Entity as TimerDevice;
 pub use super::timer_item::Entity as TimerItem;
 pub use super::timer_limit::Entity as TimerLimit;
 pub use super::user::Entity as User;
+pub use super::user_role::Entity as UserRole;
+pub use super::user_zone::Entity as UserZone;
 pub use super::zone::Entity as Zone;
 pub use super::zone_command::Entity as ZoneCommand;
 pub use super::zone_command_action::Entity as ZoneCommandAction;
 pub use super::zone_command_if::Entity as ZoneCommandIf;
+pub use super::webhook::Entity as Webhook;
+pub use super::webhook_delivery::Entity as WebhookDelivery;
+pub use super::notification::Entity as Notification;
+pub use super::telegram_chat::Entity as TelegramChat;
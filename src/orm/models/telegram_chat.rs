@@ -0,0 +1,43 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/models/telegram_chat.rs
+
+//--------------------------------------------------------------------------------- Description
+// Telegram chat model
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+//--------------------------------------------------------------------------------- Attribute
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "telegram_chat")]
+#[schema(description = "Private chat a user linked to the bot with a one-time code; commands are accepted from its sender_id only")]
+//--------------------------------------------------------------------------------- Model
+pub struct Model 
+{
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub user_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub chat_id: String,
+    #[sea_orm(column_type = "Text")]
+    pub username: String,
+    #[sea_orm(column_type = "Text")]
+    pub date: String,
+    /// Numeric Telegram user id that sent the link code, empty until linked
+    #[sea_orm(column_type = "Text", default_value = "")]
+    pub sender_id: String,
+    /// Pending one-time code from /user/telegram, cleared once used
+    #[sea_orm(column_type = "Text", default_value = "")]
+    #[serde(skip_serializing)]
+    pub link_code: String,
+    #[sea_orm(column_type = "Text", default_value = "")]
+    pub link_expires: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "timer")]
 #[schema(description = "Timer model representing scheduled events or actions")]
+#[graphql(complex, name = "Timer")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -27,6 +29,28 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(has_many = "super::timer_device::Entity")]
+    TimerDevice,
+    #[sea_orm(has_many = "super::timer_item::Entity")]
+    TimerItem,
+}
+
+impl Related<super::timer_device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::TimerDevice.def()
+    }
+}
+
+impl Related<super::timer_item::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::TimerItem.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "timer_device")]
 #[schema(description = "Timer device model linking timers to specific devices and commands")]
+#[graphql(complex, name = "TimerDevice")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -27,6 +29,30 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::timer::Entity", from = "Column::TimerId", to = "super::timer::Column::Id")]
+    Timer,
+    #[sea_orm(belongs_to = "super::device::Entity", from = "Column::DeviceId", to = "super::device::Column::Id")]
+    Device,
+    #[sea_orm(belongs_to = "super::device_command::Entity", from = "Column::CommandId", to = "super::device_command::Column::Id")]
+    Command,
+}
+
+impl Related<super::timer::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Timer.def()
+    }
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "timer_item")]
 #[schema(description = "Timer item model representing individual components or actions within a timer")]
+#[graphql(complex, name = "TimerItem")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -31,6 +33,18 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::timer::Entity", from = "Column::TimerId", to = "super::timer::Column::Id")]
+    Timer,
+}
+
+impl Related<super::timer::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Timer.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
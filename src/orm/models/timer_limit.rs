@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "timer_limit")]
 #[schema(description = "Timer limit model defining thresholds and associated commands for devices")]
+#[graphql(complex, name = "TimerLimit")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -28,6 +30,22 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::device::Entity", from = "Column::DeviceId", to = "super::device::Column::Id")]
+    Device,
+    #[sea_orm(belongs_to = "super::device_command::Entity", from = "Column::CommandFromId", to = "super::device_command::Column::Id")]
+    CommandFrom,
+    #[sea_orm(belongs_to = "super::device_command::Entity", from = "Column::CommandToId", to = "super::device_command::Column::Id")]
+    CommandTo,
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
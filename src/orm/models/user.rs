@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "user")]
 #[schema(description = "User model representing system users with authentication and contact information")]
+#[graphql(name = "User")]
 //--------------------------------------------------------------------------------- Model
 pub struct Model 
 {
@@ -22,9 +24,17 @@ pub struct Model
     pub name: String,
     #[sea_orm(column_type = "Text")]
     pub username: String,
+    /// Argon2 hash, never serialized in responses
     #[sea_orm(column_type = "Text")]
+    #[serde(skip_serializing)]
+    #[schema(write_only)]
+    #[graphql(skip)]
     pub password: String,
+    /// API key for X-API-Key, only returned when it is created or rotated
     #[sea_orm(column_type = "Text")]
+    #[serde(skip_serializing)]
+    #[schema(write_only)]
+    #[graphql(skip)]
     pub key: String,
     #[sea_orm(column_type = "Text")]
     pub email: String,
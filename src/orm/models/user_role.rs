@@ -0,0 +1,33 @@
+//--------------------------------------------------------------------------------- Location
+// src/models/user_role.rs
+
+//--------------------------------------------------------------------------------- Description
+// user_role model
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+//--------------------------------------------------------------------------------- Attribute
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "user_role")]
+#[schema(description = "Role of a user: Admin, Operator (can execute commands) or Viewer (read-only), and whether every zone is granted")]
+//--------------------------------------------------------------------------------- Model
+pub struct Model 
+{
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub user_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub role: String,
+    /// Every zone, else only the zones granted in user_zone
+    #[sea_orm(default_value = false)]
+    pub all_zones: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,28 @@
+//--------------------------------------------------------------------------------- Location
+// src/models/user_zone.rs
+
+//--------------------------------------------------------------------------------- Description
+// user_zone model
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+//--------------------------------------------------------------------------------- Attribute
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[sea_orm(table_name = "user_zone")]
+#[schema(description = "Zone grant restricting an operator to the zones it lists")]
+//--------------------------------------------------------------------------------- Model
+pub struct Model 
+{
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub zone_id: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
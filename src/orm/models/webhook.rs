@@ -0,0 +1,56 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/models/webhook.rs
+
+//--------------------------------------------------------------------------------- Description
+// Webhook model
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use async_graphql::SimpleObject;
+
+//--------------------------------------------------------------------------------- Attribute
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[sea_orm(table_name = "webhook")]
+#[schema(description = "Webhook model: URL that receives the selected engine events as signed JSON")]
+#[graphql(name = "Webhook")]
+//--------------------------------------------------------------------------------- Model
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub name: String,
+    #[sea_orm(column_type = "Text")]
+    pub url: String,
+    /// HMAC-SHA256 key of the X-Webhook-Signature header, never serialized in responses
+    #[sea_orm(column_type = "Text")]
+    #[serde(skip_serializing)]
+    #[schema(write_only)]
+    #[graphql(skip)]
+    pub secret: String,
+    /// Comma separated event names, * for every event; empty is device_changed, rule_fired, limit_reached and log_error
+    #[sea_orm(column_type = "Text")]
+    pub events: String,
+    #[sea_orm(column_type = "Text")]
+    pub description: String,
+    pub enable: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation 
+{
+    #[sea_orm(has_many = "super::webhook_delivery::Entity")]
+    WebhookDelivery,
+}
+
+impl Related<super::webhook_delivery::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::WebhookDelivery.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,56 @@
+//--------------------------------------------------------------------------------- Location
+// src/orm/models/webhook_delivery.rs
+
+//--------------------------------------------------------------------------------- Description
+// Webhook delivery model
+
+//--------------------------------------------------------------------------------- Import
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use async_graphql::SimpleObject;
+
+//--------------------------------------------------------------------------------- Attribute
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[sea_orm(table_name = "webhook_delivery")]
+#[schema(description = "Webhook delivery model: one event sent to a webhook, with the outcome of its last attempt")]
+#[graphql(name = "WebhookDelivery")]
+//--------------------------------------------------------------------------------- Model
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub webhook_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub event: String,
+    /// Exact JSON body that was signed and sent
+    #[sea_orm(column_type = "Text")]
+    pub payload: String,
+    /// pending, delivered or failed
+    pub status: String,
+    pub attempts: i32,
+    /// HTTP status of the last attempt, 0 when no response came back
+    pub response_code: i32,
+    #[sea_orm(column_type = "Text")]
+    pub error: String,
+    #[sea_orm(column_type = "Text")]
+    pub date: String,
+    #[sea_orm(column_type = "Text")]
+    pub updated: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::webhook::Entity", from = "Column::WebhookId", to = "super::webhook::Column::Id")]
+    Webhook,
+}
+
+impl Related<super::webhook::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Webhook.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
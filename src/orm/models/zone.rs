@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "zone")]
 #[schema(description = "Zone model representing logical groupings of devices or areas")]
+#[graphql(complex, name = "Zone")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -27,6 +29,28 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(has_many = "super::device::Entity")]
+    Device,
+    #[sea_orm(has_many = "super::zone_command::Entity")]
+    ZoneCommand,
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
+
+impl Related<super::zone_command::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommand.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
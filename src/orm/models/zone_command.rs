@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "zone_command")]
 #[schema(description = "Zone command model defining commands associated with a specific zone")]
+#[graphql(complex, name = "ZoneCommand")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -27,6 +29,38 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::zone::Entity", from = "Column::ZoneId", to = "super::zone::Column::Id")]
+    Zone,
+    #[sea_orm(has_many = "super::zone_command_if::Entity")]
+    ZoneCommandIf,
+    #[sea_orm(has_many = "super::zone_command_action::Entity")]
+    ZoneCommandAction,
+}
+
+impl Related<super::zone::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Zone.def()
+    }
+}
+
+impl Related<super::zone_command_if::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommandIf.def()
+    }
+}
+
+impl Related<super::zone_command_action::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommandAction.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "zone_command_action")]
 #[schema(description = "Zone command action model defining a specific action within a zone command")]
+#[graphql(complex, name = "ZoneCommandAction")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -29,6 +31,30 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::zone_command::Entity", from = "Column::ZoneCommandId", to = "super::zone_command::Column::Id")]
+    ZoneCommand,
+    #[sea_orm(belongs_to = "super::device::Entity", from = "Column::DeviceId", to = "super::device::Column::Id")]
+    Device,
+    #[sea_orm(belongs_to = "super::device_command::Entity", from = "Column::CommandId", to = "super::device_command::Column::Id")]
+    Command,
+}
+
+impl Related<super::zone_command::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommand.def()
+    }
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -8,11 +8,13 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use async_graphql::SimpleObject;
 
 //--------------------------------------------------------------------------------- Attribute
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[sea_orm(table_name = "zone_command_if")]
 #[schema(description = "Zone command condition model for defining conditions under which a command executes")]
+#[graphql(complex, name = "ZoneCommandIf")]
 
 //--------------------------------------------------------------------------------- Model
 pub struct Model {
@@ -24,11 +26,37 @@ pub struct Model {
     pub device_id: i32,
     pub command_id: i32,
     #[sea_orm(column_type = "Text")]
+    pub r#type: String,
+    #[sea_orm(column_type = "Text")]
     pub description: String,
     pub enable: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation 
+{
+    #[sea_orm(belongs_to = "super::zone_command::Entity", from = "Column::ZoneCommandId", to = "super::zone_command::Column::Id")]
+    ZoneCommand,
+    #[sea_orm(belongs_to = "super::device::Entity", from = "Column::DeviceId", to = "super::device::Column::Id")]
+    Device,
+    #[sea_orm(belongs_to = "super::device_command::Entity", from = "Column::CommandId", to = "super::device_command::Column::Id")]
+    Command,
+}
+
+impl Related<super::zone_command::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::ZoneCommand.def()
+    }
+}
+
+impl Related<super::device::Entity> for Entity 
+{
+    fn to() -> RelationDef 
+    {
+        Relation::Device.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -5,7 +5,7 @@
 // Application router configuration with all routes and middleware setup
 
 //--------------------------------------------------------------------------------- Import
-use axum::{middleware::from_fn, Router};
+use axum::{middleware::{from_fn, from_fn_with_state}, routing::{get, post}, Router};
 use tower::ServiceBuilder;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use utoipa::OpenApi;
@@ -18,7 +18,8 @@ pub fn create_app(state: AppState) -> Router {
     let middleware_stack = ServiceBuilder::new()
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
-        .layer(from_fn(api::middleware::logging_middleware));
+        .layer(from_fn(api::middleware::logging_middleware))
+        .layer(from_fn_with_state(state.clone(), api::middleware::auth_middleware));
 
     // Routes configuration
     Router::new()
@@ -36,7 +37,231 @@ pub fn create_app(state: AppState) -> Router {
         .nest("/zone_command", api::routes::zone_command::router())
         .nest("/zone_command_action", api::routes::zone_command_action::router())
         .nest("/zone_command_if", api::routes::zone_command_if::router())
+        .nest("/webhook", api::routes::webhook::router())
+        .nest("/webhook_delivery", api::routes::webhook_delivery::router())
+        .nest("/notification", api::routes::notification::router())
+        .route("/graphql", post(api::graphql::graphql_handler))
+        .route("/graphql/playground", get(api::graphql::playground))
+        .route("/ws", get(api::socket::ws_handler))
         .merge(SwaggerUi::new("/doc").url("/api-doc/openapi.json", doc::ApiDoc::openapi()))
         .layer(middleware_stack)
         .with_state(state)
 }
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use sea_orm::{ActiveModelTrait, ColumnTrait, Database, DatabaseConnection, EntityTrait, QueryFilter, Set};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use crate::api::auth::generate_key;
+    use crate::api::middleware::API_KEY_HEADER;
+    use crate::engine::{events::EventBus, executor::Executor, limit::LimitWatcher, rule::RuleEngine, webhook::WebhookDispatcher};
+    use crate::hardware::Buses;
+    use crate::hardware::gpio::{MockPinDriver, PinDriver};
+    use crate::logics::general::Roles;
+    use crate::orm::logics::{user_role::UserRoleORM, user_zone::UserZoneORM};
+    use crate::orm::models::{device, device_command, port, user};
+    use super::*;
+
+    /// The app over the samples, whose user 1 owns every row
+    async fn app() -> (Router, DatabaseConnection)
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+
+        let pins: Arc<dyn PinDriver> = Arc::new(MockPinDriver::new());
+        let executor = Executor::new(db.clone(), pins.clone(), Buses::mock(pins.clone()), EventBus::new(64));
+        let state = AppState
+        {
+            db: db.clone(),
+            pins,
+            rules: RuleEngine::new(executor.clone()),
+            limits: LimitWatcher::new(executor.clone()),
+            webhooks: WebhookDispatcher::new(executor.clone()),
+            executor,
+            auth: api::auth::Auth::new().unwrap(),
+            graphql: api::graphql::schema(),
+        };
+        (create_app(state), db)
+    }
+
+    /// API key of the user, created when missing, with this role and these zone grants
+    async fn user(db: &DatabaseConnection, id: i32, role: Roles, all_zones: bool, zones: &[i32]) -> String
+    {
+        let key = match user::Entity::find_by_id(id).one(db).await.unwrap()
+        {
+            Some(user) => user.key,
+            None =>
+            {
+                let name = format!("tenant-{}", id);
+                let user = user::ActiveModel { id: Set(id), name: Set(name.clone()), username: Set(name), password: Set(String::new()), key: Set(generate_key()), email: Set(String::new()), phone: Set(String::new()), tg_id: Set(String::new()), enable: Set(true) };
+                user.insert(db).await.unwrap().key
+            }
+        };
+        UserRoleORM::new(false, false).set(db, id, role.name(), Some(all_zones)).await.into_result().unwrap();
+        for zone_id in zones { UserZoneORM::new(false, false).add(db, id, *zone_id).await.into_result().unwrap(); }
+        key
+    }
+
+    async fn send(app: &Router, key: &str, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value)
+    {
+        let request = Request::builder().method(method).uri(uri).header(API_KEY_HEADER, key).header(header::CONTENT_TYPE, "application/json");
+        let request = request.body(body.map(|body| Body::from(body.to_string())).unwrap_or_default()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    async fn get(app: &Router, key: &str, uri: &str) -> StatusCode
+    {
+        send(app, key, "GET", uri, None).await.0
+    }
+
+    /// Toggling a status is a write: Viewer is refused, Operator needs the zone grant
+    #[tokio::test]
+    async fn status_needs_the_role_of_enable_and_disable()
+    {
+        let tables = ["port", "timer_device", "timer_item", "timer_limit", "zone_command", "zone_command_action", "zone_command_if"];
+        let (app, db) = app().await;
+
+        let key = user(&db, 1, Roles::Viewer, true, &[]).await;
+        for table in tables
+        {
+            assert_eq!(get(&app, &key, &format!("/{}/status/1", table)).await, StatusCode::FORBIDDEN, "{}", table);
+            assert_eq!(get(&app, &key, &format!("/{}/item/1", table)).await, StatusCode::OK, "{}", table);
+        }
+
+        let key = user(&db, 1, Roles::Operator, true, &[]).await;
+        for table in tables
+        {
+            assert_eq!(get(&app, &key, &format!("/{}/status/1", table)).await, StatusCode::OK, "{}", table);
+        }
+    }
+
+    /// An Operator manages the rows of its granted zones in its own installation, not another tenant's
+    #[tokio::test]
+    async fn an_operator_manages_its_own_granted_zones()
+    {
+        let (app, db) = app().await;
+        let other_zone = device::Entity::find().filter(device::Column::ZoneId.eq(2)).one(&db).await.unwrap().unwrap().id;
+        let command = |device_id: i32| json!({ "device_id": device_id, "name": "T-Pulse", "value_from": 1, "value_to": 0, "delay": 0, "frequency": null, "description": "", "reload": false, "enable": true, "type": "NONE" });
+        let zone = json!({ "user_id": 1, "name": "T-Garden", "description": "", "enable": true });
+
+        let key = user(&db, 1, Roles::Operator, false, &[1]).await;
+        let (status, body) = send(&app, &key, "POST", "/device_command/add", Some(command(1))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = body["data"]["id"].as_i64().unwrap();
+        assert_eq!(send(&app, &key, "DELETE", &format!("/device_command/delete/{}", id), None).await.0, StatusCode::OK);
+        assert_eq!(send(&app, &key, "POST", "/device_command/add", Some(command(other_zone))).await.0, StatusCode::FORBIDDEN, "zone 2 is not granted");
+        assert_eq!(send(&app, &key, "POST", "/zone/add", Some(zone.clone())).await.0, StatusCode::FORBIDDEN, "a new zone takes every zone");
+
+        let key = user(&db, 1, Roles::Operator, true, &[]).await;
+        let (status, body) = send(&app, &key, "POST", "/zone/add", Some(zone)).await;
+        assert_eq!((status, body["data"]["user_id"].as_i64()), (StatusCode::CREATED, Some(1)));
+
+        // Another tenant granted every zone still does not reach user 1's rows
+        let key = user(&db, 2, Roles::Operator, true, &[]).await;
+        assert_eq!(send(&app, &key, "DELETE", "/device_command/delete/1", None).await.0, StatusCode::NOT_FOUND);
+        assert_eq!(send(&app, &key, "POST", "/device_command/add", Some(command(1))).await.0, StatusCode::NOT_FOUND);
+
+        let key = user(&db, 2, Roles::Viewer, true, &[]).await;
+        assert_eq!(send(&app, &key, "POST", "/zone/add", Some(json!({ "user_id": 2, "name": "T-Shed", "description": "", "enable": true }))).await.0, StatusCode::FORBIDDEN);
+
+        // The power port and the current command of user 1's device stay within user 1 and the device
+        let foreign = port::ActiveModel { user_id: Set(2), name: Set("T-Relay".into()), pin: Set(7), port: Set(0), value: Set(0), description: Set(String::new()), enable: Set(true), protocol: Set("GPIO".into()), r#type: Set("OUTPUT".into()), ..Default::default() }
+            .insert(&db).await.unwrap().id;
+        let own = device::Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        let stray = device_command::Entity::find().filter(device_command::Column::DeviceId.ne(own.id)).one(&db).await.unwrap().unwrap().id;
+        let key = user(&db, 1, Roles::Operator, true, &[]).await;
+        let device = |power_id: i32, command_id: i32| json!({ "zone_id": own.zone_id, "port_id": own.port_id, "power_id": power_id, "command_id": command_id, "value": 0, "tune": 0, "date": "", "address": "", "name": "T-Pump", "description": "", "enable": true });
+        assert_eq!(send(&app, &key, "PUT", "/device/update/1", Some(device(foreign, 0))).await.0, StatusCode::NOT_FOUND, "user 2's port");
+        assert_eq!(send(&app, &key, "POST", "/device/add", Some(device(foreign, 0))).await.0, StatusCode::NOT_FOUND);
+        assert_eq!(send(&app, &key, "PUT", "/device/update/1", Some(device(0, stray))).await.0, StatusCode::UNPROCESSABLE_ENTITY, "another device's command");
+        assert_eq!(send(&app, &key, "POST", "/device/add", Some(device(0, stray))).await.0, StatusCode::UNPROCESSABLE_ENTITY, "a new device has no commands");
+        let (status, body) = send(&app, &key, "POST", "/device_command/add", Some(command(own.id))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let mine = body["data"]["id"].as_i64().unwrap() as i32;
+        assert_eq!(send(&app, &key, "PUT", "/device/update/1", Some(device(own.power_id, mine))).await.0, StatusCode::OK);
+    }
+
+    /// A new user always gets a server generated key, never the one in the body
+    #[tokio::test]
+    async fn user_add_generates_the_key()
+    {
+        let (app, db) = app().await;
+        let key = user(&db, 1, Roles::Admin, true, &[]).await;
+        let body = json!({ "name": "T-User", "username": "t-user", "password": "secret", "key": key, "email": "", "phone": "", "tg_id": "", "enable": true });
+
+        let (status, body) = send(&app, &key, "POST", "/user/add", Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let new_key = body["data"]["key"].as_str().unwrap();
+        assert_eq!(new_key.len(), 43);
+        assert_ne!(new_key, key, "a duplicate key is never stored");
+    }
+
+    /// Every user can see which hardware backend is behind the ports
+    #[tokio::test]
+    async fn port_drivers_reports_the_backends()
+    {
+        let (app, db) = app().await;
+        let key = user(&db, 1, Roles::Viewer, false, &[]).await;
+
+        let (status, body) = send(&app, &key, "GET", "/port/drivers", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((body["data"]["gpio"].as_str(), body["data"]["i2c"].as_str(), body["data"]["uart"].as_str()), (Some("mock"), Some("mock"), Some("mock")));
+    }
+
+    /// A viewer's read reports the level without saving it, a write drives PWM ports like a device command
+    #[tokio::test]
+    async fn port_read_leaves_the_row_and_write_drives_pwm()
+    {
+        let (app, db) = app().await;
+        let input = port::Entity::find().filter(port::Column::Protocol.eq("GPIO")).filter(port::Column::Type.eq("IN")).filter(port::Column::Enable.eq(true)).one(&db).await.unwrap().unwrap();
+        let mut active: port::ActiveModel = input.clone().into();
+        active.value = Set(5);
+        active.update(&db).await.unwrap();
+
+        let key = user(&db, 1, Roles::Viewer, true, &[]).await;
+        let (status, body) = send(&app, &key, "GET", &format!("/port/read/{}", input.id), None).await;
+        assert_eq!((status, body["data"]["value"].as_i64()), (StatusCode::OK, Some(0)));
+        assert_eq!(port::Entity::find_by_id(input.id).one(&db).await.unwrap().unwrap().value, 5, "a read is not saved");
+
+        let light = port::Entity::find().filter(port::Column::Name.eq("P-Light")).one(&db).await.unwrap().unwrap();
+        assert_eq!(send(&app, &key, "GET", &format!("/port/write/{}/40", light.id), None).await.0, StatusCode::FORBIDDEN);
+        let key = user(&db, 1, Roles::Operator, true, &[]).await;
+        let (status, body) = send(&app, &key, "GET", &format!("/port/write/{}/40?frequency=1000", light.id), None).await;
+        assert_eq!((status, body["data"]["value"].as_i64()), (StatusCode::OK, Some(40)));
+        assert_eq!(port::Entity::find_by_id(light.id).one(&db).await.unwrap().unwrap().value, 40);
+    }
+
+    /// A body that is not JSON and an id that is not a number still get the ModelOutput body
+    #[tokio::test]
+    async fn rejections_keep_the_model_output_body()
+    {
+        let (app, db) = app().await;
+        let key = user(&db, 1, Roles::Admin, true, &[]).await;
+
+        let request = Request::builder().method("POST").uri("/zone/add").header(API_KEY_HEADER, &key).header(header::CONTENT_TYPE, "application/json");
+        let response = app.clone().oneshot(request.body(Body::from("{\"name\": ")).unwrap()).await.unwrap();
+        let status = response.status();
+        let body: Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!((status, body["status"].as_bool()), (StatusCode::UNPROCESSABLE_ENTITY, Some(false)));
+
+        let (status, body) = send(&app, &key, "POST", "/zone/add", Some(json!({ "name": "T-Garden" }))).await;
+        assert_eq!((status, body["status"].as_bool()), (StatusCode::UNPROCESSABLE_ENTITY, Some(false)), "missing fields");
+
+        for uri in ["/zone/item/abc", "/port/write/1/on", "/zone/item/99999999999"]
+        {
+            let (status, body) = send(&app, &key, "GET", uri, None).await;
+            assert_eq!((status, body["status"].as_bool()), (StatusCode::UNPROCESSABLE_ENTITY, Some(false)), "{}", uri);
+            assert!(body["error"].is_string() || body["message"].is_string(), "{}: {}", uri, body);
+        }
+    }
+}
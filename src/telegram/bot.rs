@@ -0,0 +1,248 @@
+//--------------------------------------------------------------------------------- Location
+// src/telegram/bot.rs
+
+//--------------------------------------------------------------------------------- Description
+// Long-polls the Bot API for commands. A sender is accepted when its numeric id is the tg_id of an
+// enabled user or was linked to one, and then acts with that user's roles and zones:
+//   /link <code>             link this private chat to the user that requested the code
+//   /status                  zones, devices and their current command
+//   /run <id | name>         run a zone command
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+use crate::api::access::Access;
+use crate::logics::error::ApiError;
+use crate::api::services::zone_command::ZoneCommandService;
+use crate::engine::executor::Executor;
+use crate::engine::rule::RuleEngine;
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, telegram_chat::TelegramChatORM, user::UserORM, zone::ZoneORM, zone_command::ZoneCommandORM};
+use crate::orm::models::device::Entity as DeviceEntity;
+use crate::orm::models::user::Model as UserModel;
+use crate::orm::models::zone::Entity as ZoneEntity;
+use crate::orm::models::zone_command::Entity as ZoneCommandEntity;
+use super::client::{Message, TelegramClient};
+
+//--------------------------------------------------------------------------------- Constants
+/// Wait before polling again after an error
+const RETRY: Duration = Duration::from_secs(5);
+
+/// Telegram rejects messages over 4096 characters
+const MAX_TEXT: usize = 4000;
+
+const HELP: &str = "/status - zones and devices\n/run <id or name> - run a zone command\n/link <code> - link this chat to your account";
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct Bot
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub client: TelegramClient,
+    pub executor: Executor,
+    pub rules: RuleEngine,
+    pub poll_timeout: Duration,
+}
+
+impl Bot
+{
+    //------------------------- New
+    pub fn new(client: TelegramClient, executor: Executor, rules: RuleEngine, poll_timeout: Duration) -> Self
+    {
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "Bot".to_string(),
+            client,
+            executor,
+            rules,
+            poll_timeout,
+        }
+    }
+
+    //------------------------- Spawn
+    pub fn spawn(&self)
+    {
+        let bot = self.clone();
+        tokio::spawn(async move { bot.poll().await; });
+    }
+
+    //------------------------- Poll
+    /// Commands sent while the service was down are dropped rather than run late
+    async fn poll(self)
+    {
+        let this_method = "poll";
+        let mut offset = match self.client.get_updates(-1, Duration::ZERO).await
+        {
+            Ok(updates) => updates.last().map_or(0, |update| update.update_id + 1),
+            Err(e) => { warn!("{}::{} - {}", self.this_class, this_method, e); 0 }
+        };
+
+        loop
+        {
+            let updates = match self.client.get_updates(offset, self.poll_timeout).await
+            {
+                Ok(updates) => updates,
+                Err(e) =>
+                {
+                    warn!("{}::{} - {}", self.this_class, this_method, e);
+                    tokio::time::sleep(RETRY).await;
+                    continue;
+                }
+            };
+
+            for update in updates
+            {
+                offset = offset.max(update.update_id + 1);
+                let Some(message) = update.message else { continue; };
+                if let Err(e) = self.on_message(message).await { error!("{}::{} - Error: {}", self.this_class, this_method, e); }
+            }
+        }
+    }
+
+    //------------------------- On Message
+    async fn on_message(&self, message: Message) -> Result<(), ApiError>
+    {
+        let this_method = "on_message";
+        let (Some(sender), Some(text)) = (&message.from, &message.text) else { return Ok(()); };
+        let mut words = text.split_whitespace();
+        let Some(command) = words.next().filter(|word| word.starts_with('/')) else { return Ok(()); };
+        let command = command.split('@').next().unwrap_or_default().to_lowercase();
+        let argument = words.collect::<Vec<_>>().join(" ");
+        let chat_id = message.chat.id.to_string();
+
+        if command == "/link"
+        {
+            let answer = self.link(message.chat.id, sender.id, sender.username.as_deref(), &argument).await?;
+            return self.reply(&chat_id, &answer).await;
+        }
+
+        let Some(user) = self.user_of(sender.id).await? else
+        {
+            warn!("{}::{} - Rejected {} from Telegram user {}", self.this_class, this_method, command, sender.id);
+            return self.reply(&chat_id, "You are not authorised: send /link <code> with a code from /user/telegram, or ask an administrator to set your numeric Telegram id").await;
+        };
+        if self.verbose { debug!("{}::{} - {} {} from user {}", self.this_class, this_method, command, argument, user.id); }
+
+        let access = Access::load(&self.executor.db, &user).await?;
+        let answer = match command.as_str()
+        {
+            "/start" | "/help" => format!("Hello {}\n{}", user.name, HELP),
+            "/status" => self.status(&access).await?,
+            "/run" => self.run(&access, &argument).await,
+            _ => format!("Unknown command {}\n{}", command, HELP),
+        };
+        self.reply(&chat_id, &answer).await
+    }
+
+    /// Enabled user whose tg_id is the numeric sender id, else the one that linked it with a code
+    async fn user_of(&self, sender_id: i64) -> Result<Option<UserModel>, ApiError>
+    {
+        let db = &self.executor.db;
+        let users = UserORM::new(false, false).items(db, HashMap::new()).await.into_result()?;
+        let linked = match TelegramChatORM::new(false, false).item_by_sender(db, sender_id).await.into_result()
+        {
+            Ok(chat) => Some(chat.user_id),
+            Err(ApiError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let sender_id = sender_id.to_string();
+        Ok(users.into_iter().filter(|user| user.enable).find(|user| user.tg_id.trim() == sender_id || linked == Some(user.id)))
+    }
+
+    //------------------------- Link
+    /// Only from a private chat, so the chat id is the sender's own and group members cannot use a code
+    async fn link(&self, chat_id: i64, sender_id: i64, username: Option<&str>, code: &str) -> Result<String, ApiError>
+    {
+        let this_method = "link";
+        if chat_id != sender_id { return Ok("Send /link in a private chat with the bot".to_string()); }
+        if code.is_empty() { return Ok("Usage: /link <code>, get a code from /user/telegram".to_string()); }
+
+        let code = code.trim().to_uppercase();
+        match TelegramChatORM::new(false, self.log).link(&self.executor.db, &code, &chat_id.to_string(), sender_id, username.unwrap_or_default()).await.into_result()
+        {
+            Ok(chat) => Ok(format!("✅ Linked, you act as user {} now\n{}", chat.user_id, HELP)),
+            Err(ApiError::NotFound(message)) =>
+            {
+                warn!("{}::{} - Rejected link code from Telegram user {}", self.this_class, this_method, sender_id);
+                Ok(format!("❌ {}", message))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    //------------------------- Status
+    async fn status(&self, access: &Access) -> Result<String, ApiError>
+    {
+        let db = &self.executor.db;
+        let zones = ZoneORM::new(false, false).items_in(db, HashMap::new(), access.scope::<ZoneEntity>()).await.into_result()?;
+        let devices = DeviceORM::new(false, false).items_in(db, HashMap::new(), access.scope::<DeviceEntity>()).await.into_result()?;
+        let commands = DeviceCommandORM::new(false, false).items(db, HashMap::new()).await.into_result()?
+            .into_iter().map(|command| (command.id, command.name)).collect::<HashMap<_, _>>();
+
+        if zones.is_empty() { return Ok("No zones".to_string()); }
+        let mut lines = Vec::new();
+        for zone in zones.iter().filter(|zone| zone.enable)
+        {
+            lines.push(format!("🏠 {} ({})", zone.name, zone.id));
+            for device in devices.iter().filter(|device| device.zone_id == zone.id && device.enable)
+            {
+                let command = commands.get(&device.command_id).map_or("-", String::as_str);
+                lines.push(format!("  • {} ({}): {} = {}", device.name, device.id, command, device.value));
+            }
+        }
+
+        let mut text = lines.join("\n");
+        if text.len() > MAX_TEXT
+        {
+            let end = (0..=MAX_TEXT).rev().find(|&index| text.is_char_boundary(index)).unwrap_or(0);
+            text.truncate(end);
+            text.push_str("\n…");
+        }
+        Ok(text)
+    }
+
+    //------------------------- Run
+    /// Zone command by id, or by name among the user's zones
+    async fn run(&self, access: &Access, argument: &str) -> String
+    {
+        let db = &self.executor.db;
+        if argument.is_empty() { return "Usage: /run <id or name>".to_string(); }
+
+        let id = match argument.parse::<i32>()
+        {
+            Ok(id) => id,
+            Err(_) =>
+            {
+                let zone_commands = match ZoneCommandORM::new(false, false).items_in(db, HashMap::new(), access.scope::<ZoneCommandEntity>()).await.into_result()
+                {
+                    Ok(zone_commands) => zone_commands,
+                    Err(e) => return e.to_string(),
+                };
+                let found = zone_commands.into_iter().filter(|zone_command| zone_command.name.eq_ignore_ascii_case(argument)).collect::<Vec<_>>();
+                match found.as_slice()
+                {
+                    [] => return format!("No zone command named '{}'", argument),
+                    [zone_command] => zone_command.id,
+                    _ => return format!("'{}' is ambiguous, use an id: {}", argument, found.iter().map(|zone_command| zone_command.id.to_string()).collect::<Vec<_>>().join(", ")),
+                }
+            }
+        };
+
+        match ZoneCommandService::new().run(db, access, &self.rules, id).await.into_result()
+        {
+            Ok(zone_command) => format!("✅ {} ({}) executed", zone_command.name, zone_command.id),
+            Err(e) => format!("❌ {}", e),
+        }
+    }
+
+    //------------------------- Reply
+    async fn reply(&self, chat_id: &str, text: &str) -> Result<(), ApiError>
+    {
+        self.client.send_message(chat_id, text).await.map_err(ApiError::Internal)
+    }
+}
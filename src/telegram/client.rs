@@ -0,0 +1,106 @@
+//--------------------------------------------------------------------------------- Location
+// src/telegram/client.rs
+
+//--------------------------------------------------------------------------------- Description
+// Minimal Telegram Bot API client: long-polled getUpdates and sendMessage, against a configurable
+// base URL so a local stub can stand in for api.telegram.org
+
+//--------------------------------------------------------------------------------- Import
+use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+//--------------------------------------------------------------------------------- Types
+/// Bot API envelope: the result when ok, the description otherwise
+#[derive(Debug, Deserialize)]
+struct Response<T>
+{
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Update
+{
+    pub update_id: i64,
+    pub message: Option<Message>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Message
+{
+    pub chat: Chat,
+    pub from: Option<Sender>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Chat
+{
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sender
+{
+    pub id: i64,
+    pub username: Option<String>,
+}
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct TelegramClient
+{
+    base_url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+impl TelegramClient
+{
+    //------------------------- New
+    /// The HTTP timeout leaves room for the long poll of getUpdates
+    pub fn new(base_url: &str, token: &str, poll_timeout: Duration) -> Self
+    {
+        let http = reqwest::Client::builder()
+            .timeout(poll_timeout + Duration::from_secs(10))
+            .build()
+            .expect("Failed to build the Telegram HTTP client");
+
+        Self { base_url: base_url.trim_end_matches('/').to_string(), token: token.to_string(), http }
+    }
+
+    //------------------------- Get Updates
+    /// Messages after the offset, waiting up to the timeout for new ones
+    pub async fn get_updates(&self, offset: i64, timeout: Duration) -> Result<Vec<Update>, String>
+    {
+        self.call("getUpdates", json!({ "offset": offset, "timeout": timeout.as_secs(), "allowed_updates": ["message"] })).await
+    }
+
+    //------------------------- Send Message
+    pub async fn send_message(&self, chat_id: &str, text: &str) -> Result<(), String>
+    {
+        // Numeric ids go out as numbers, @channel names as strings
+        let chat_id = chat_id.parse::<i64>().map(Value::from).unwrap_or_else(|_| Value::from(chat_id));
+        self.call::<Value>("sendMessage", json!({ "chat_id": chat_id, "text": text })).await.map(|_| ())
+    }
+
+    //------------------------- Call
+    async fn call<T: DeserializeOwned>(&self, method: &str, body: Value) -> Result<T, String>
+    {
+        let url = format!("{}/bot{}/{}", self.base_url, self.token, method);
+        let response = self.http.post(&url).json(&body).send().await
+            .map_err(|e| format!("{} failed: {}", method, e.without_url()))?;
+        let status = response.status();
+        let envelope: Response<T> = response.json().await
+            .map_err(|e| format!("{} answered {} with an unreadable body: {}", method, status, e.without_url()))?;
+
+        match (envelope.ok, envelope.result)
+        {
+            (true, Some(result)) => Ok(result),
+            _ => Err(format!("{} answered {}: {}", method, status, envelope.description.unwrap_or_default())),
+        }
+    }
+}
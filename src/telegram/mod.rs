@@ -0,0 +1,225 @@
+//--------------------------------------------------------------------------------- Location
+// src/telegram/mod.rs
+
+//--------------------------------------------------------------------------------- Description
+// Telegram notifications: users subscribed through /notification get a message on their tg_id when
+// a device alarm goes off, a fuse trips or a timer limit cuts a device off. The bot also answers
+// /status and /run <zone_command> from known users, with the same roles as the API. A sender is
+// known by its numeric Telegram id only: the user's tg_id, or the id that sent a one-time code from
+// /user/telegram as "/link <code>" (recorded in telegram_chat). Usernames can be changed or taken
+// over, so they are never trusted
+
+//--------------------------------------------------------------------------------- Import
+use std::time::Duration;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use tracing::info;
+use utoipa::ToSchema;
+use crate::engine::executor::Executor;
+use crate::engine::rule::RuleEngine;
+use crate::orm::logics::telegram_chat::TelegramChatORM;
+use crate::orm::models::user::Model as UserModel;
+use self::bot::Bot;
+use self::client::TelegramClient;
+use self::notifier::Notifier;
+
+//--------------------------------------------------------------------------------- Modules
+pub mod bot;
+pub mod client;
+pub mod notifier;
+
+//--------------------------------------------------------------------------------- Config
+#[derive(Debug, Clone)]
+pub struct TelegramConfig
+{
+    pub token: Option<String>,
+    pub api_url: String,
+    pub commands: bool,
+    pub poll_timeout: Duration,
+}
+
+impl TelegramConfig
+{
+    //------------------------- From Env
+    pub fn from_env() -> Self
+    {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        Self
+        {
+            token: var("TELEGRAM_BOT_TOKEN"),
+            api_url: var("TELEGRAM_API_URL").unwrap_or_else(|| "https://api.telegram.org".to_string()),
+            commands: var("TELEGRAM_COMMANDS").is_none_or(|value| value != "false"),
+            poll_timeout: Duration::from_secs(var("TELEGRAM_POLL_TIMEOUT").and_then(|value| value.parse().ok()).unwrap_or(25u64)),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------- Start
+/// Start the notifier and, unless turned off, the command bot; nothing runs without a bot token
+pub fn start(config: TelegramConfig, executor: Executor, rules: RuleEngine)
+{
+    let Some(token) = &config.token else { return; };
+    let client = TelegramClient::new(&config.api_url, token, config.poll_timeout);
+
+    Notifier::new(client.clone(), executor.clone()).spawn();
+    if config.commands { Bot::new(client, executor, rules, config.poll_timeout).spawn(); }
+    info!("Telegram: notifications on, commands {}", if config.commands { "on" } else { "off" });
+}
+
+//--------------------------------------------------------------------------------- Chat
+/// Where to message the user: its numeric tg_id, else the chat it linked with a code
+pub async fn chat_of(db: &DatabaseConnection, user: &UserModel) -> Option<String>
+{
+    let tg_id = user.tg_id.trim();
+    if tg_id.parse::<i64>().is_ok() { return Some(tg_id.to_string()); }
+
+    let chat = TelegramChatORM::new(false, false).item_by_user(db, user.id).await.data?;
+    (!chat.sender_id.is_empty() && !chat.chat_id.is_empty()).then_some(chat.chat_id)
+}
+
+//--------------------------------------------------------------------------------- Link Code
+/// How long a code from /user/telegram can be sent to the bot
+pub const LINK_TTL: Duration = Duration::from_secs(600);
+
+/// No 0/O or 1/I, the code is typed by hand
+const LINK_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Code a user sends to the bot to link its Telegram account
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(description = "One-time code to send to the bot as /link <code> from a private chat")]
+pub struct TelegramLink
+{
+    #[schema(example = "K7QF3MZP")]
+    pub code: String,
+    #[schema(example = "2025-09-01 12:10:00")]
+    pub expires: String,
+    #[schema(example = "/link K7QF3MZP")]
+    pub command: String,
+}
+
+/// Eight random characters, about 40 bits
+pub fn link_code() -> String
+{
+    let mut bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| LINK_ALPHABET[usize::from(*byte) % LINK_ALPHABET.len()] as char).collect()
+}
+
+//--------------------------------------------------------------------------------- Tests
+#[cfg(test)]
+mod tests
+{
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use axum::{extract::{Path, State}, routing::post, Json, Router};
+    use sea_orm::{ActiveModelTrait, Database, EntityTrait, IntoActiveModel, Set};
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc;
+    use crate::engine::events::{Event, EventBus};
+    use crate::hardware::{gpio::{MockPinDriver, PinDriver}, Buses};
+    use crate::orm::models::{device, notification, user};
+    use super::*;
+
+    /// Bot API stand-in: getUpdates hands out the queued updates, sendMessage reports (chat, text)
+    #[derive(Clone)]
+    struct Stub
+    {
+        updates: Arc<Mutex<Vec<Value>>>,
+        sent: mpsc::UnboundedSender<(i64, String)>,
+    }
+
+    async fn bot_api(State(stub): State<Stub>, Path((_, method)): Path<(String, String)>, Json(body): Json<Value>) -> Json<Value>
+    {
+        let result = match method.as_str()
+        {
+            // The bot drops what is pending at start (offset -1), so queued updates wait for the next call
+            "getUpdates" if body["offset"].as_i64().unwrap_or(0) < 0 => json!([]),
+            "getUpdates" =>
+            {
+                let updates = std::mem::take(&mut *stub.updates.lock().unwrap());
+                if updates.is_empty() { tokio::time::sleep(Duration::from_millis(50)).await; }
+                json!(updates)
+            }
+            _ =>
+            {
+                stub.sent.send((body["chat_id"].as_i64().unwrap(), body["text"].as_str().unwrap().to_string())).unwrap();
+                json!({})
+            }
+        };
+        Json(json!({ "ok": true, "result": result }))
+    }
+
+    async fn start_stub() -> (TelegramClient, Arc<Mutex<Vec<Value>>>, mpsc::UnboundedReceiver<(i64, String)>)
+    {
+        let (sent, receiver) = mpsc::unbounded_channel();
+        let stub = Stub { updates: Arc::new(Mutex::new(Vec::new())), sent };
+        let updates = stub.updates.clone();
+        let app = Router::new().route("/{bot}/{method}", post(bot_api)).with_state(stub);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap(); });
+        (TelegramClient::new(&format!("http://{}", address), "token", Duration::from_secs(1)), updates, receiver)
+    }
+
+    /// Sample data with the admin (user 1) on Telegram id 333 and a viewer owning no rows on 222
+    async fn start_executor() -> Executor
+    {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        crate::orm::migrations::migrate(&db).await.unwrap();
+        crate::logics::admin::Admin::new().add_samples(&db).await.unwrap();
+
+        let mut admin = user::Entity::find_by_id(1).one(&db).await.unwrap().unwrap().into_active_model();
+        admin.tg_id = Set("333".to_string());
+        admin.update(&db).await.unwrap();
+        let viewer = user::ActiveModel { name: Set("viewer".to_string()), username: Set("viewer".to_string()), password: Set(String::new()), key: Set("viewer_key".to_string()),
+            email: Set(String::new()), phone: Set(String::new()), tg_id: Set("222".to_string()), enable: Set(true), ..Default::default() };
+        viewer.insert(&db).await.unwrap();
+
+        let pins: Arc<dyn PinDriver> = Arc::new(MockPinDriver::new());
+        Executor::new(db, pins.clone(), Buses::mock(pins), EventBus::new(64))
+    }
+
+    async fn next(receiver: &mut mpsc::UnboundedReceiver<(i64, String)>) -> (i64, String)
+    {
+        tokio::time::timeout(Duration::from_secs(10), receiver.recv()).await.expect("no message sent").expect("stub stopped")
+    }
+
+    #[tokio::test]
+    async fn bot_runs_zone_commands_for_authorised_senders_only()
+    {
+        let (client, updates, mut sent) = start_stub().await;
+        let executor = start_executor().await;
+        Bot::new(client, executor.clone(), RuleEngine::new(executor), Duration::from_secs(1)).spawn();
+
+        let messages = [(1, 999), (2, 222), (3, 333)].map(|(update_id, sender)| json!({ "update_id": update_id, "message": { "chat": { "id": sender }, "from": { "id": sender }, "text": "/run 1" } }));
+        updates.lock().unwrap().extend(messages);
+
+        let mut replies = std::collections::HashMap::new();
+        while replies.len() < 3
+        {
+            let (chat_id, text) = next(&mut sent).await;
+            replies.insert(chat_id, text);
+        }
+        assert!(replies[&999].starts_with("You are not authorised"), "unknown sender: {}", replies[&999]);
+        assert!(replies[&222].starts_with("❌"), "viewer outside the tenant: {}", replies[&222]);
+        assert!(replies[&333].starts_with("✅"), "admin: {}", replies[&333]);
+    }
+
+    #[tokio::test]
+    async fn fuse_subscription_on_every_device_is_notified()
+    {
+        let (client, _, mut sent) = start_stub().await;
+        let executor = start_executor().await;
+        let subscription = notification::ActiveModel { user_id: Set(1), device_id: Set(0), kind: Set("fuse".to_string()), description: Set(String::new()), enable: Set(true), ..Default::default() };
+        subscription.insert(&executor.db).await.unwrap();
+        let fuse = device::Entity::find_by_id(8).one(&executor.db).await.unwrap().unwrap();
+        Notifier::new(client, executor.clone()).spawn();
+
+        executor.events.publish(Event::DeviceChanged { device_id: fuse.id, zone_id: fuse.zone_id, command_id: fuse.command_id, previous: 1, value: 0 });
+        let (chat_id, text) = next(&mut sent).await;
+        assert_eq!(chat_id, 333);
+        assert!(text.contains("Fuse tripped") && text.contains(&fuse.name), "{}", text);
+    }
+}
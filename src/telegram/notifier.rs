@@ -0,0 +1,167 @@
+//--------------------------------------------------------------------------------- Location
+// src/telegram/notifier.rs
+
+//--------------------------------------------------------------------------------- Description
+// Turns engine events into Telegram messages for the subscribed users: alarm when a watched device
+// leaves 0, fuse when it drops to 0, limit when a timer limit cuts a device off. Subscriptions are
+// read on every event so CRUD changes apply without a restart
+
+//--------------------------------------------------------------------------------- Import
+use std::collections::HashMap;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+use crate::api::access::Access;
+use crate::logics::error::ApiError;
+use crate::engine::events::Event;
+use crate::engine::executor::Executor;
+use crate::orm::logics::{device::DeviceORM, device_command::DeviceCommandORM, notification::NotificationORM, user::UserORM, zone::ZoneORM};
+use crate::orm::models::notification::Model as NotificationModel;
+use crate::orm::models::zone::Entity as ZoneEntity;
+use super::client::TelegramClient;
+
+//--------------------------------------------------------------------------------- Class
+#[derive(Clone)]
+pub struct Notifier
+{
+    pub verbose: bool,
+    pub log: bool,
+    pub this_class: String,
+    pub client: TelegramClient,
+    pub executor: Executor,
+}
+
+impl Notifier
+{
+    //------------------------- New
+    pub fn new(client: TelegramClient, executor: Executor) -> Self
+    {
+        Self
+        {
+            verbose: true,
+            log: true,
+            this_class: "Notifier".to_string(),
+            client,
+            executor,
+        }
+    }
+
+    //------------------------- Spawn
+    pub fn spawn(&self)
+    {
+        let notifier = self.clone();
+        let mut receiver = notifier.executor.events.subscribe();
+
+        tokio::spawn(async move
+        {
+            loop
+            {
+                let result = match receiver.recv().await
+                {
+                    Ok(Event::DeviceChanged { device_id, zone_id, previous, value, .. }) => notifier.on_device_changed(device_id, zone_id, previous, value).await,
+                    Ok(Event::LimitReached { timer_limit_id, device_id, zone_id, command_to_id, status }) => notifier.on_limit_reached(timer_limit_id, device_id, zone_id, command_to_id, status).await,
+                    Ok(_) => Ok(()),
+                    Err(RecvError::Lagged(skipped)) => { warn!("{}::spawn - Skipped {} events", notifier.this_class, skipped); Ok(()) }
+                    Err(RecvError::Closed) => break,
+                };
+                if let Err(e) = result { error!("{}::spawn - Error: {}", notifier.this_class, e); }
+            }
+        });
+    }
+
+    //------------------------- On Device Changed
+    async fn on_device_changed(&self, device_id: i32, zone_id: i32, previous: i32, value: i32) -> Result<(), ApiError>
+    {
+        let kind = match (previous, value)
+        {
+            (0, value) if value != 0 => "alarm",
+            (previous, 0) if previous != 0 => "fuse",
+            _ => return Ok(()),
+        };
+        let subscriptions = self.subscriptions(kind, device_id).await?;
+        if subscriptions.is_empty() { return Ok(()); }
+
+        let (device, zone) = self.names(device_id, zone_id).await;
+        let text = match kind
+        {
+            "alarm" => format!("🚨 Alarm: {} in {} is on (value {})", device, zone, value),
+            _ => format!("⚡ Fuse tripped: {} in {} went off (was {})", device, zone, previous),
+        };
+        self.notify(&subscriptions, zone_id, &text).await;
+        Ok(())
+    }
+
+    //------------------------- On Limit Reached
+    async fn on_limit_reached(&self, timer_limit_id: i32, device_id: i32, zone_id: i32, command_to_id: i32, status: bool) -> Result<(), ApiError>
+    {
+        let subscriptions = self.subscriptions("limit", device_id).await?;
+        if subscriptions.is_empty() { return Ok(()); }
+
+        let (device, zone) = self.names(device_id, zone_id).await;
+        let command = DeviceCommandORM::new(false, false).item(&self.executor.db, command_to_id).await.data.map(|command| command.name).unwrap_or_else(|| command_to_id.to_string());
+        let text = match status
+        {
+            true => format!("⏱️ Timer limit {}: {} in {} switched to '{}'", timer_limit_id, device, zone, command),
+            false => format!("⏱️ Timer limit {}: {} in {} could not be switched to '{}', see the log", timer_limit_id, device, zone, command),
+        };
+        self.notify(&subscriptions, zone_id, &text).await;
+        Ok(())
+    }
+
+    /// Enabled subscriptions of this kind on the device or on every device (device_id 0)
+    async fn subscriptions(&self, kind: &str, device_id: i32) -> Result<Vec<NotificationModel>, ApiError>
+    {
+        let filters = HashMap::from([("kind".to_string(), kind.to_string()), ("enable".to_string(), "true".to_string())]);
+        Ok(NotificationORM::new(false, self.log).items(&self.executor.db, filters).await.into_result()?
+            .into_iter().filter(|subscription| subscription.device_id == 0 || subscription.device_id == device_id).collect())
+    }
+
+    /// Device and zone names for the message, their ids when the rows are gone
+    async fn names(&self, device_id: i32, zone_id: i32) -> (String, String)
+    {
+        let db = &self.executor.db;
+        let device = DeviceORM::new(false, false).item(db, device_id).await.data.map(|device| device.name).unwrap_or_else(|| format!("device {}", device_id));
+        let zone = ZoneORM::new(false, false).item(db, zone_id).await.data.map(|zone| zone.name).unwrap_or_else(|| format!("zone {}", zone_id));
+        (device, zone)
+    }
+
+    //------------------------- Notify
+    /// One message per user, to enabled users that still see the zone
+    async fn notify(&self, subscriptions: &[NotificationModel], zone_id: i32, text: &str)
+    {
+        let this_method = "notify";
+        let db = &self.executor.db;
+
+        let mut user_ids = subscriptions.iter().map(|subscription| subscription.user_id).collect::<Vec<_>>();
+        user_ids.sort_unstable();
+        user_ids.dedup();
+
+        for user_id in user_ids
+        {
+            let Some(user) = UserORM::new(false, false).item(db, user_id).await.data.filter(|user| user.enable) else { continue; };
+            let visible = match Access::load(db, &user).await
+            {
+                Ok(access) => access.require_owned::<ZoneEntity>(db, zone_id).await.is_ok(),
+                Err(e) => { error!("{}::{} - User {}: {}", self.this_class, this_method, user_id, e); false }
+            };
+            if !visible { continue; }
+
+            let Some(chat_id) = super::chat_of(db, &user).await else
+            {
+                warn!("{}::{} - User {} ({}) has no reachable chat, they must write to the bot first", self.this_class, this_method, user.id, user.tg_id);
+                continue;
+            };
+
+            match self.client.send_message(&chat_id, text).await
+            {
+                Ok(()) => if self.verbose { debug!("{}::{} - Sent to user {}: {}", self.this_class, this_method, user.id, text); },
+                Err(e) =>
+                {
+                    let error_msg = format!("Cannot notify user {} ({}): {}", user.id, user.tg_id, e);
+                    warn!("{}::{} - {}", self.this_class, this_method, error_msg);
+                    crate::engine::record(&self.executor, "Telegram Notification", false, error_msg).await;
+                }
+            }
+        }
+        if self.verbose { info!("{}::{} - {}", self.this_class, this_method, text); }
+    }
+}